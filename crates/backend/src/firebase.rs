@@ -446,6 +446,78 @@ impl FirestoreClient {
         Ok(())
     }
 
+    /// Atomically add `delta` to an integer field (created at 0 with its
+    /// document when missing) and return the new value
+    pub async fn increment_field(
+        &self,
+        collection: &str,
+        doc_id: &str,
+        field: &str,
+        delta: i64,
+    ) -> Result<i64, FirestoreError> {
+        {
+            let mut limiter = RATE_LIMITER
+                .write()
+                .map_err(|e| FirestoreError::LockError(e.to_string()))?;
+            if !limiter.can_write() {
+                return Err(FirestoreError::RateLimited);
+            }
+        }
+
+        let url = format!("{}:commit", self.base_url());
+        let document = format!(
+            "projects/{}/databases/(default)/documents/{}/{}",
+            self.config.project_id, collection, doc_id
+        );
+        let body = serde_json::json!({
+            "writes": [{
+                "transform": {
+                    "document": document,
+                    "fieldTransforms": [{
+                        "fieldPath": field,
+                        "increment": { "integerValue": delta.to_string() }
+                    }]
+                }
+            }]
+        });
+
+        // Get token
+        let token = self.get_token().await?;
+
+        let mut req = self.http.post(&url);
+
+        if let Some(t) = token {
+            req = req.bearer_auth(t);
+        }
+
+        let res = req
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| FirestoreError::NetworkError(e.to_string()))?;
+
+        if !res.status().is_success() {
+            return Err(FirestoreError::ApiError(format!(
+                "Status: {}",
+                res.status()
+            )));
+        }
+
+        if let Ok(mut cache) = CACHE.write() {
+            cache.invalidate(&format!("{}/{}", collection, doc_id));
+            cache.invalidate_prefix(&format!("list:{}", collection));
+        }
+
+        let json: serde_json::Value = res
+            .json()
+            .await
+            .map_err(|e| FirestoreError::ParseError(e.to_string()))?;
+        json.pointer("/writeResults/0/transformResults/0/integerValue")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| FirestoreError::ParseError("Missing increment result".into()))
+    }
+
     /// Update a document (partial update)
     pub async fn update_doc<T: Serialize>(
         &self,
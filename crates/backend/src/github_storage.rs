@@ -355,6 +355,164 @@ impl GitHubStorage {
             .await
     }
 
+    // =========== Report Snapshot Operations ===========
+
+    /// Save report data snapshot (PocReportData JSON) for later re-rendering
+    pub async fn save_report_snapshot(
+        &self,
+        user_id: &str,
+        snapshot_id: &str,
+        snapshot_json: &str,
+    ) -> Result<(), String> {
+        let user_hash = Self::hash_user_id(user_id);
+        let path = self.user_path(&user_hash, &format!("snapshots/{}.json", snapshot_id));
+        self.save(
+            &path,
+            snapshot_json,
+            &format!("Save report snapshot: {}", snapshot_id),
+        )
+        .await
+    }
+
+    /// Load report data snapshot
+    pub async fn load_report_snapshot(
+        &self,
+        user_id: &str,
+        snapshot_id: &str,
+    ) -> Result<String, String> {
+        let user_hash = Self::hash_user_id(user_id);
+        let path = self.user_path(&user_hash, &format!("snapshots/{}.json", snapshot_id));
+        self.load(&path).await
+    }
+
+    /// List report data snapshots
+    pub async fn list_report_snapshots(&self, user_id: &str) -> Result<Vec<String>, String> {
+        let user_hash = Self::hash_user_id(user_id);
+        let path = self.user_path(&user_hash, "snapshots");
+        self.list(&path).await
+    }
+
+    /// Save a report generated by a schedule, returns the stored path
+    pub async fn save_scheduled_report(
+        &self,
+        user_id: &str,
+        schedule_id: &str,
+        run_id: &str,
+        html: &str,
+    ) -> Result<String, String> {
+        let user_hash = Self::hash_user_id(user_id);
+        let path = self.user_path(
+            &user_hash,
+            &format!("reports/{}/{}.html", schedule_id, run_id),
+        );
+        self.save(
+            &path,
+            html,
+            &format!("Save scheduled report: {}/{}", schedule_id, run_id),
+        )
+        .await?;
+        Ok(path)
+    }
+
+    // =========== Schedule Operations ===========
+    // Schedules are stored globally so the scheduler can enumerate them across users
+
+    /// Save report schedule definition
+    pub async fn save_schedule(
+        &self,
+        schedule_id: &str,
+        schedule_json: &str,
+    ) -> Result<(), String> {
+        self.save(
+            &format!("schedules/{}.json", schedule_id),
+            schedule_json,
+            &format!("Save schedule: {}", schedule_id),
+        )
+        .await
+    }
+
+    /// Load report schedule definition
+    pub async fn load_schedule(&self, schedule_id: &str) -> Result<String, String> {
+        self.load(&format!("schedules/{}.json", schedule_id)).await
+    }
+
+    /// List report schedule files
+    pub async fn list_schedules(&self) -> Result<Vec<String>, String> {
+        self.list("schedules").await
+    }
+
+    /// Delete report schedule definition
+    pub async fn delete_schedule(&self, schedule_id: &str) -> Result<(), String> {
+        self.delete(
+            &format!("schedules/{}.json", schedule_id),
+            &format!("Delete schedule: {}", schedule_id),
+        )
+        .await
+    }
+
+    /// Save the encrypted session a user's scheduled runs act with
+    pub async fn save_schedule_credential(
+        &self,
+        user_id: &str,
+        credential_json: &str,
+    ) -> Result<(), String> {
+        let user_hash = Self::hash_user_id(user_id);
+        self.save(
+            &format!("schedule_credentials/{}.json", user_hash),
+            credential_json,
+            &format!("Save schedule credential: {}", user_hash),
+        )
+        .await
+    }
+
+    /// Load the encrypted session a user's scheduled runs act with
+    pub async fn load_schedule_credential(&self, user_id: &str) -> Result<String, String> {
+        let user_hash = Self::hash_user_id(user_id);
+        self.load(&format!("schedule_credentials/{}.json", user_hash))
+            .await
+    }
+
+    /// Delete a user's stored schedule credential
+    pub async fn delete_schedule_credential(&self, user_id: &str) -> Result<(), String> {
+        let user_hash = Self::hash_user_id(user_id);
+        self.delete(
+            &format!("schedule_credentials/{}.json", user_hash),
+            &format!("Delete schedule credential: {}", user_hash),
+        )
+        .await
+    }
+
+    // =========== Request Queue Operations ===========
+
+    /// Save queued/finished job state (session tokens are never included)
+    pub async fn save_queue_job(&self, job_id: &str, job_json: &str) -> Result<(), String> {
+        self.save(
+            &format!("queue/{}.json", job_id),
+            job_json,
+            &format!("Save queue job: {}", job_id),
+        )
+        .await
+    }
+
+    /// Load queue job state
+    pub async fn load_queue_job(&self, job_id: &str) -> Result<String, String> {
+        self.load(&format!("queue/{}.json", job_id)).await
+    }
+
+    /// List persisted queue job files
+    pub async fn list_queue_jobs(&self) -> Result<Vec<String>, String> {
+        self.list("queue").await
+    }
+
+    /// Delete queue job state
+    pub async fn delete_queue_job(&self, job_id: &str) -> Result<(), String> {
+        self.delete(
+            &format!("queue/{}.json", job_id),
+            &format!("Delete queue job: {}", job_id),
+        )
+        .await
+    }
+
     // =========== Permission Operations (always fresh, 0 TTL) ===========
 
     /// Check if user is allowed (beta tester or admin)
@@ -10,6 +10,7 @@ pub mod injector;
 pub mod middleware;
 pub mod queue;
 pub mod routes;
+pub mod scheduler;
 pub mod services;
 pub mod utils;
 
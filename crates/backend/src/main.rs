@@ -25,6 +25,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     axur_backend::queue::start_worker();
     tracing::info!("Queue worker started");
 
+    // Start scheduled report runner
+    axur_backend::scheduler::start_scheduler();
+    tracing::info!("Report scheduler started");
+
+    // Load declarative slide plugins (JSON/TOML definitions)
+    if let Ok(dir) = std::env::var("SLIDE_PLUGINS_DIR") {
+        match axur_core::plugins::declarative::load_dir(std::path::Path::new(&dir)) {
+            Ok(plugins) => {
+                tracing::info!(
+                    "Loaded {} declarative slide plugins from {}",
+                    plugins.len(),
+                    dir
+                );
+                axur_core::plugins::declarative::install(plugins);
+            }
+            Err(e) => tracing::error!("Failed to load slide plugins from {}: {}", dir, e),
+        }
+    }
+
     // Initialize Firestore (Auth)
     axur_backend::firebase::init_global().await;
     tracing::info!("Firestore initialized");
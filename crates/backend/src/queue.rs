@@ -1,13 +1,23 @@
 //! Request Queue with Token Bucket Rate Limiting
 //!
 //! Manages API request rates to prevent 429 errors and provides
-//! real-time queue position updates to users.
-
+//! real-time queue position updates to users. Report and Threat Hunting
+//! jobs are executed by the background worker. Requests that do their own
+//! work (SSE streams, exports) hold a [`QueueSlot`] instead, so they wait
+//! their turn under the same limits. Job requests and statuses (not results)
+//! are persisted in GitHub storage in the background so queued work
+//! survives a restart.
+
+use crate::services::report_service::{
+    GenerateReportRequest, ReportService, ThreatHuntingPreviewRequest,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::task::AbortHandle;
 use uuid::Uuid;
 
 /// Token Bucket Rate Limiter
@@ -136,10 +146,39 @@ impl ApiType {
 /// Job type for the queue
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum JobType {
-    GenerateReport { tenant_id: String },
-    SaveTemplate { template_name: String },
-    LoadTemplate { template_id: String },
-    ThreatHuntingSearch { query: String },
+    GenerateReport {
+        request: Box<GenerateReportRequest>,
+    },
+    SaveTemplate {
+        template_name: String,
+    },
+    LoadTemplate {
+        template_id: String,
+    },
+    ThreatHuntingPreview {
+        request: ThreatHuntingPreviewRequest,
+    },
+    /// A request running on its own while it holds a [`QueueSlot`]
+    /// (e.g. "report_stream", "export_pdf")
+    Interactive {
+        operation: String,
+    },
+}
+
+impl JobType {
+    /// Jobs that call the Axur API on behalf of the user need their session token
+    pub fn requires_token(&self) -> bool {
+        matches!(
+            self,
+            JobType::GenerateReport { .. } | JobType::ThreatHuntingPreview { .. }
+        )
+    }
+
+    /// Interactive requests are not persisted: their connection cannot be
+    /// resumed after a restart
+    pub fn is_persisted(&self) -> bool {
+        !matches!(self, JobType::Interactive { .. })
+    }
 }
 
 /// Job status
@@ -150,6 +189,17 @@ pub enum JobStatus {
     Processing { started_at: u64 },
     Completed { result: serde_json::Value },
     Failed { error: String },
+    Cancelled { cancelled_at: u64 },
+}
+
+impl JobStatus {
+    /// Completed, failed or cancelled
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Completed { .. } | JobStatus::Failed { .. } | JobStatus::Cancelled { .. }
+        )
+    }
 }
 
 /// A job in the queue
@@ -162,6 +212,10 @@ pub struct QueueJob {
     pub created_at: u64,
     pub status: JobStatus,
     pub priority: u8, // Higher = more priority
+    /// Session token used to run the job. Never persisted: jobs restored after a
+    /// restart wait until their owner calls the queue API again.
+    #[serde(skip)]
+    pub token: Option<String>,
 }
 
 impl QueueJob {
@@ -174,15 +228,66 @@ impl QueueJob {
             created_at: TokenBucket::now_ms(),
             status: JobStatus::Queued { position: 0 },
             priority: 0,
+            token: None,
         }
     }
+
+    /// Attach the session token the job runs with
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Queued but cannot run until a token is attached
+    fn awaiting_token(&self) -> bool {
+        self.job_type.requires_token() && self.token.is_none()
+    }
+}
+
+/// Maximum jobs processed at the same time
+const MAX_CONCURRENT_JOBS: usize = 4;
+
+/// Finished jobs kept for status and result lookup
+const MAX_COMPLETED_JOBS: usize = 100;
+
+/// Restored jobs waiting for a token longer than this are failed (30 min)
+const ORPHANED_JOB_TTL_MS: u64 = 30 * 60 * 1000;
+
+/// A job currently being processed by the worker
+struct RunningJob {
+    job: QueueJob,
+    handle: AbortHandle,
+}
+
+/// Channels between a request waiting for its slot and the worker task that
+/// stands for it while it runs
+struct SlotChannels {
+    started: oneshot::Sender<()>,
+    released: oneshot::Receiver<()>,
+}
+
+/// A place in the queue held by a request that does its own work. It counts
+/// as a running job until dropped.
+pub struct QueueSlot {
+    pub job_id: String,
+    _release: oneshot::Sender<()>,
+}
+
+/// Result of a cancellation request
+#[derive(Debug)]
+pub enum CancelOutcome {
+    Cancelled(QueueJob),
+    AlreadyFinished(QueueJob),
+    NotFound,
 }
 
 /// Request Queue Manager
 pub struct RequestQueue {
     jobs: RwLock<VecDeque<QueueJob>>,
     rate_limiters: HashMap<ApiType, TokenBucket>,
+    running: RwLock<HashMap<String, RunningJob>>,
     completed_jobs: RwLock<HashMap<String, QueueJob>>,
+    slots: Mutex<HashMap<String, SlotChannels>>,
 }
 
 impl RequestQueue {
@@ -198,20 +303,83 @@ impl RequestQueue {
         Self {
             jobs: RwLock::new(VecDeque::new()),
             rate_limiters,
+            running: RwLock::new(HashMap::new()),
             completed_jobs: RwLock::new(HashMap::new()),
+            slots: Mutex::new(HashMap::new()),
         }
     }
 
     /// Submit a job to the queue
     pub async fn submit(&self, mut job: QueueJob) -> String {
-        let mut jobs = self.jobs.write().await;
-        let position = jobs.len();
-        job.status = JobStatus::Queued { position };
+        job.status = JobStatus::Queued {
+            position: self.queue_length().await,
+        };
+        persist_job(&job);
+
         let job_id = job.id.clone();
-        jobs.push_back(job);
+        self.jobs.write().await.push_back(job);
         job_id
     }
 
+    /// Submit a job and wait until it finishes, returning its result
+    pub async fn run_and_wait(&self, job: QueueJob) -> Result<serde_json::Value, String> {
+        let job_id = self.submit(job).await;
+
+        loop {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+
+            match self.get_job(&job_id).await.map(|j| j.status) {
+                Some(JobStatus::Completed { result }) => return Ok(result),
+                Some(JobStatus::Failed { error }) => return Err(error),
+                Some(JobStatus::Cancelled { .. }) => return Err("Job was cancelled".to_string()),
+                Some(_) => continue,
+                None => return Err(format!("Job {} is no longer tracked", job_id)),
+            }
+        }
+    }
+
+    /// Wait for a turn to run `operation` for a user, under the same rate
+    /// limits and concurrency as queued jobs. The slot is freed when the
+    /// returned guard is dropped.
+    pub async fn acquire(
+        &self,
+        user_id: &str,
+        operation: &str,
+        api_type: ApiType,
+    ) -> Result<QueueSlot, String> {
+        let job = QueueJob::new(
+            user_id.to_string(),
+            JobType::Interactive {
+                operation: operation.to_string(),
+            },
+            api_type,
+        );
+        let (started, started_rx) = oneshot::channel();
+        let (release, released) = oneshot::channel();
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(job.id.clone(), SlotChannels { started, released });
+
+        let job_id = self.submit(job).await;
+        started_rx
+            .await
+            .map_err(|_| "Job was cancelled".to_string())?;
+        Ok(QueueSlot {
+            job_id,
+            _release: release,
+        })
+    }
+
+    /// Channels of a slot, taken by the worker when its job starts (or
+    /// dropped on cancellation, which wakes the waiting request)
+    fn take_slot(&self, job_id: &str) -> Option<SlotChannels> {
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(job_id)
+    }
+
     /// Get job status by ID
     pub async fn get_job(&self, job_id: &str) -> Option<QueueJob> {
         // Check active queue first
@@ -226,6 +394,14 @@ impl RequestQueue {
             }
         }
 
+        // Then jobs being processed
+        {
+            let running = self.running.read().await;
+            if let Some(running) = running.get(job_id) {
+                return Some(running.job.clone());
+            }
+        }
+
         // Check completed jobs
         {
             let completed = self.completed_jobs.read().await;
@@ -242,6 +418,11 @@ impl RequestQueue {
         self.jobs.read().await.len()
     }
 
+    /// Number of jobs being processed
+    pub async fn running_count(&self) -> usize {
+        self.running.read().await.len()
+    }
+
     /// Get estimated wait time for a position
     pub fn estimate_wait_time(&self, position: usize, api_type: ApiType) -> Duration {
         if let Some(bucket) = self.rate_limiters.get(&api_type) {
@@ -272,8 +453,11 @@ impl RequestQueue {
     pub async fn pop_ready(&self) -> Option<QueueJob> {
         let mut jobs = self.jobs.write().await;
 
-        // Find first job whose rate limit allows processing
+        // Find first job with a token whose rate limit allows processing
         for i in 0..jobs.len() {
+            if jobs[i].awaiting_token() {
+                continue;
+            }
             if self
                 .rate_limiters
                 .get(&jobs[i].api_type)
@@ -287,39 +471,174 @@ impl RequestQueue {
         None
     }
 
+    /// Give restored jobs of a user their session token back so they can run
+    pub async fn attach_token(&self, user_id: &str, token: &str) {
+        let mut jobs = self.jobs.write().await;
+        for job in jobs
+            .iter_mut()
+            .filter(|j| j.user_id == user_id && j.token.is_none())
+        {
+            job.token = Some(token.to_string());
+        }
+    }
+
+    /// Fail restored jobs whose owner did not come back to resume them
+    pub async fn expire_orphaned(&self) {
+        let now = TokenBucket::now_ms();
+        let expired: Vec<QueueJob> = {
+            let mut jobs = self.jobs.write().await;
+            let (expired, keep): (VecDeque<_>, VecDeque<_>) = jobs.drain(..).partition(|j| {
+                j.awaiting_token() && now.saturating_sub(j.created_at) > ORPHANED_JOB_TTL_MS
+            });
+            *jobs = keep;
+            expired.into()
+        };
+
+        for job in expired {
+            tracing::warn!("[Queue] Job {} expired waiting for its session", job.id);
+            self.fail(
+                job,
+                "Session expired before the job could resume, please submit it again".to_string(),
+            )
+            .await;
+        }
+    }
+
+    /// Cancel a queued or running job owned by `user_id`
+    pub async fn cancel(&self, job_id: &str, user_id: &str) -> CancelOutcome {
+        // Still waiting: drop it from the queue
+        let queued = {
+            let mut jobs = self.jobs.write().await;
+            jobs.iter()
+                .position(|j| j.id == job_id && j.user_id == user_id)
+                .and_then(|idx| jobs.remove(idx))
+        };
+
+        // Being processed: abort its task
+        let job = match queued {
+            Some(job) => Some(job),
+            None => {
+                let mut running = self.running.write().await;
+                let owned = running
+                    .get(job_id)
+                    .is_some_and(|r| r.job.user_id == user_id);
+                if owned {
+                    running.remove(job_id).map(|r| {
+                        r.handle.abort();
+                        r.job
+                    })
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(mut job) = job {
+            self.take_slot(&job.id);
+            job.status = JobStatus::Cancelled {
+                cancelled_at: TokenBucket::now_ms(),
+            };
+            self.store_completed(job.clone()).await;
+            tracing::info!("[Queue] Cancelled job {}", job.id);
+            return CancelOutcome::Cancelled(job);
+        }
+
+        match self.completed_jobs.read().await.get(job_id) {
+            Some(job) if job.user_id == user_id => CancelOutcome::AlreadyFinished(job.clone()),
+            _ => CancelOutcome::NotFound,
+        }
+    }
+
     /// Mark a job as completed
     pub async fn complete(&self, mut job: QueueJob, result: serde_json::Value) {
         job.status = JobStatus::Completed { result };
-        self.completed_jobs
-            .write()
-            .await
-            .insert(job.id.clone(), job);
+        self.store_completed(job).await;
     }
 
     /// Mark a job as failed
     pub async fn fail(&self, mut job: QueueJob, error: String) {
         job.status = JobStatus::Failed { error };
-        self.completed_jobs
-            .write()
-            .await
-            .insert(job.id.clone(), job);
+        self.store_completed(job).await;
     }
 
     /// Get all jobs for a user
     pub async fn user_jobs(&self, user_id: &str) -> Vec<QueueJob> {
         let jobs = self.jobs.read().await;
+        let running = self.running.read().await;
         let completed = self.completed_jobs.read().await;
 
         let mut result: Vec<_> = jobs
             .iter()
-            .filter(|j| j.user_id == user_id)
-            .cloned()
+            .enumerate()
+            .filter(|(_, j)| j.user_id == user_id)
+            .map(|(idx, j)| {
+                let mut job = j.clone();
+                job.status = JobStatus::Queued { position: idx };
+                job
+            })
             .collect();
 
+        result.extend(
+            running
+                .values()
+                .map(|r| &r.job)
+                .filter(|j| j.user_id == user_id)
+                .cloned(),
+        );
         result.extend(completed.values().filter(|j| j.user_id == user_id).cloned());
 
         result
     }
+
+    /// Reload persisted jobs after a restart: unfinished jobs are queued again,
+    /// finished ones keep their results available
+    pub async fn restore(&self) {
+        let Some(storage) = crate::github_storage::get_github_storage() else {
+            return;
+        };
+
+        let files = match storage.list_queue_jobs().await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!("[Queue] Could not list persisted jobs: {}", e);
+                return;
+            }
+        };
+
+        let mut restored = Vec::new();
+        for file in files {
+            let Some(id) = file.strip_suffix(".json") else {
+                continue;
+            };
+            match storage.load_queue_job(id).await {
+                Ok(json) => match serde_json::from_str::<QueueJob>(&json) {
+                    Ok(job) => restored.push(job),
+                    Err(e) => tracing::warn!("[Queue] Corrupted job {}: {}", id, e),
+                },
+                Err(e) => tracing::warn!("[Queue] Failed to load job {}: {}", id, e),
+            }
+        }
+        restored.sort_by_key(|j| j.created_at);
+
+        let mut jobs = self.jobs.write().await;
+        let mut completed = self.completed_jobs.write().await;
+        for mut job in restored {
+            if job.status.is_finished() {
+                completed.insert(job.id.clone(), job);
+            } else {
+                job.status = JobStatus::Queued {
+                    position: jobs.len(),
+                };
+                jobs.push_back(job);
+            }
+        }
+
+        tracing::info!(
+            "[Queue] Restored {} queued and {} finished jobs",
+            jobs.len(),
+            completed.len()
+        );
+    }
 }
 
 impl Default for RequestQueue {
@@ -383,7 +702,92 @@ impl From<&QueueJob> for QueueStatusResponse {
                 result: None,
                 error: Some(error.clone()),
             },
+            JobStatus::Cancelled { .. } => Self {
+                job_id: job.id.clone(),
+                status: "cancelled".to_string(),
+                position: None,
+                eta_seconds: None,
+                result: None,
+                error: None,
+            },
+        }
+    }
+}
+
+// =========== Persistence ===========
+
+/// A write to the persisted job files
+enum StorageOp {
+    Save { job_id: String, json: String },
+    Delete { job_id: String },
+}
+
+static STORAGE_WRITER: OnceLock<Option<mpsc::UnboundedSender<StorageOp>>> = OnceLock::new();
+
+/// Background task applying job writes in order, so a job's final status is
+/// never overwritten by an earlier one (None when storage is not configured)
+fn storage_writer() -> Option<&'static mpsc::UnboundedSender<StorageOp>> {
+    STORAGE_WRITER
+        .get_or_init(|| {
+            let storage = crate::github_storage::get_github_storage()?;
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                while let Some(op) = rx.recv().await {
+                    let (job_id, result) = match op {
+                        StorageOp::Save { job_id, json } => {
+                            let result = storage.save_queue_job(&job_id, &json).await;
+                            (job_id, result)
+                        }
+                        StorageOp::Delete { job_id } => {
+                            let result = storage.delete_queue_job(&job_id).await;
+                            (job_id, result)
+                        }
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("[Queue] Failed to write job {}: {}", job_id, e);
+                    }
+                }
+            });
+            Some(tx)
+        })
+        .as_ref()
+}
+
+/// Stored form of a job: its request and status. Results (report HTML,
+/// previews) stay in memory, so a job restored after a restart keeps its
+/// status but not its result.
+fn stored_job(job: &QueueJob) -> QueueJob {
+    let mut job = job.clone();
+    if let JobStatus::Completed { result } = &mut job.status {
+        *result = serde_json::Value::Null;
+    }
+    job
+}
+
+/// Queue a job write to GitHub storage (no-op when storage is not configured)
+fn persist_job(job: &QueueJob) {
+    if !job.job_type.is_persisted() {
+        return;
+    }
+    let Some(writer) = storage_writer() else {
+        return;
+    };
+
+    match serde_json::to_string(&stored_job(job)) {
+        Ok(json) => {
+            let _ = writer.send(StorageOp::Save {
+                job_id: job.id.clone(),
+                json,
+            });
         }
+        Err(e) => tracing::warn!("[Queue] Failed to serialize job {}: {}", job.id, e),
+    }
+}
+
+/// Remove an evicted job from GitHub storage in the background
+fn forget_job(job_id: String) {
+    if let Some(writer) = storage_writer() {
+        let _ = writer.send(StorageOp::Delete { job_id });
     }
 }
 
@@ -404,59 +808,92 @@ pub fn start_worker() {
     tokio::spawn(async move {
         tracing::info!("[Queue Worker] Started background job processor");
 
+        let queue = get_queue();
+        queue.restore().await;
+
         loop {
             // Check queue every 500ms
             tokio::time::sleep(Duration::from_millis(500)).await;
 
-            let queue = get_queue();
-
-            // Try to pop a ready job
-            if let Some(mut job) = queue.pop_ready().await {
-                tracing::info!(
-                    "[Queue Worker] Processing job {} ({:?})",
-                    job.id,
-                    job.job_type
-                );
+            queue.expire_orphaned().await;
 
-                // Mark as processing
-                job.status = JobStatus::Processing {
-                    started_at: TokenBucket::now_ms(),
-                };
+            // Start ready jobs while there is capacity
+            while queue.running_count().await < MAX_CONCURRENT_JOBS {
+                match queue.pop_ready().await {
+                    Some(job) => queue.spawn_job(job).await,
+                    None => break,
+                }
+            }
+        }
+    });
+}
 
-                // Process based on job type
-                let result = process_job(&job).await;
+impl RequestQueue {
+    /// Run a job in its own task so it can be cancelled
+    async fn spawn_job(&'static self, mut job: QueueJob) {
+        tracing::info!(
+            "[Queue Worker] Processing job {} ({:?})",
+            job.id,
+            job.job_type
+        );
 
-                // Update job status
-                match result {
-                    Ok(value) => {
-                        job.status = JobStatus::Completed { result: value };
-                        tracing::info!("[Queue Worker] Completed job {}", job.id);
-                    }
-                    Err(e) => {
-                        job.status = JobStatus::Failed { error: e };
-                        tracing::error!("[Queue Worker] Failed job {}", job.id);
-                    }
-                }
+        // Mark as processing
+        job.status = JobStatus::Processing {
+            started_at: TokenBucket::now_ms(),
+        };
+
+        // Hold the lock until the handle is registered so a fast job cannot finish first
+        let mut running = self.running.write().await;
+        let task_job = job.clone();
+        let handle = tokio::spawn(async move {
+            let result = process_job(&task_job).await;
+            self.finish(&task_job.id, result).await;
+        });
+        running.insert(
+            job.id.clone(),
+            RunningJob {
+                job,
+                handle: handle.abort_handle(),
+            },
+        );
+    }
 
-                // Store in completed jobs
-                queue.store_completed(job).await;
+    /// Record the outcome of a processed job
+    async fn finish(&self, job_id: &str, result: Result<serde_json::Value, String>) {
+        let Some(RunningJob { mut job, .. }) = self.running.write().await.remove(job_id) else {
+            // Cancelled while running
+            return;
+        };
+
+        // Update job status
+        match result {
+            Ok(value) => {
+                job.status = JobStatus::Completed { result: value };
+                tracing::info!("[Queue Worker] Completed job {}", job.id);
+            }
+            Err(e) => {
+                tracing::error!("[Queue Worker] Failed job {}: {}", job.id, e);
+                job.status = JobStatus::Failed { error: e };
             }
         }
-    });
+
+        // Store in completed jobs
+        self.store_completed(job).await;
+    }
 }
 
 /// Process a job based on its type
 async fn process_job(job: &QueueJob) -> Result<serde_json::Value, String> {
     match &job.job_type {
-        JobType::GenerateReport { tenant_id } => {
-            // Placeholder - actual report generation would happen here
-            // For now, return success with tenant info
-            tokio::time::sleep(Duration::from_secs(1)).await; // Simulate work
-            Ok(serde_json::json!({
-                "status": "report_generation_queued",
-                "tenant_id": tenant_id,
-                "message": "Use /api/reports/generate directly with the cookie for full report"
-            }))
+        JobType::GenerateReport { request } => {
+            let token = job
+                .token
+                .as_deref()
+                .ok_or_else(|| "Missing session token".to_string())?;
+            let response = ReportService::generate_report(request, token, &job.user_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            serde_json::to_value(response).map_err(|e| format!("Failed to encode report: {}", e))
         }
         JobType::SaveTemplate { template_name } => {
             // Template saving via GitHub storage
@@ -474,14 +911,25 @@ async fn process_job(job: &QueueJob) -> Result<serde_json::Value, String> {
                 "id": template_id
             }))
         }
-        JobType::ThreatHuntingSearch { query } => {
-            // TH search placeholder
-            tokio::time::sleep(Duration::from_secs(2)).await;
-            Ok(serde_json::json!({
-                "status": "search_complete",
-                "query": query,
-                "results_count": 0
-            }))
+        JobType::ThreatHuntingPreview { request } => {
+            let token = job
+                .token
+                .as_deref()
+                .ok_or_else(|| "Missing session token".to_string())?;
+            let response =
+                ReportService::threat_hunting_preview(request, token, &job.user_id).await;
+            serde_json::to_value(response).map_err(|e| format!("Failed to encode preview: {}", e))
+        }
+        JobType::Interactive { .. } => {
+            let slot = get_queue()
+                .take_slot(&job.id)
+                .ok_or_else(|| "Slot is no longer held".to_string())?;
+            slot.started
+                .send(())
+                .map_err(|_| "Request closed before its turn".to_string())?;
+            // Occupies a worker slot until the request drops its guard
+            let _ = slot.released.await;
+            Ok(serde_json::json!({ "status": "released" }))
         }
     }
 }
@@ -489,18 +937,23 @@ async fn process_job(job: &QueueJob) -> Result<serde_json::Value, String> {
 /// Store a completed job for status lookup
 impl RequestQueue {
     pub async fn store_completed(&self, job: QueueJob) {
+        persist_job(&job);
+
         let mut completed = self.completed_jobs.write().await;
         completed.insert(job.id.clone(), job);
 
-        // Keep only last 100 completed jobs
-        if completed.len() > 100 {
-            let oldest: Vec<String> = completed
-                .iter()
-                .take(completed.len() - 100)
-                .map(|(k, _)| k.clone())
+        // Keep only the most recent completed jobs
+        if completed.len() > MAX_COMPLETED_JOBS {
+            let mut by_age: Vec<(u64, String)> = completed
+                .values()
+                .map(|j| (j.created_at, j.id.clone()))
                 .collect();
-            for key in oldest {
+            by_age.sort();
+
+            let excess = completed.len() - MAX_COMPLETED_JOBS;
+            for (_, key) in by_age.into_iter().take(excess) {
                 completed.remove(&key);
+                forget_job(key);
             }
         }
     }
@@ -510,6 +963,25 @@ impl RequestQueue {
 mod tests {
     use super::*;
 
+    fn report_job(user_id: &str) -> QueueJob {
+        let request: GenerateReportRequest = serde_json::from_value(serde_json::json!({
+            "tenant_id": "t1",
+            "from_date": "2025-01-01",
+            "to_date": "2025-01-31",
+            "story_tag": null,
+            "template_id": null
+        }))
+        .unwrap();
+
+        QueueJob::new(
+            user_id.to_string(),
+            JobType::GenerateReport {
+                request: Box::new(request),
+            },
+            ApiType::Axur,
+        )
+    }
+
     #[test]
     fn test_token_bucket_acquire() {
         let bucket = TokenBucket::new(5, 1, Duration::from_secs(1));
@@ -537,16 +1009,126 @@ mod tests {
     async fn test_queue_submit() {
         let queue = RequestQueue::new();
 
-        let job = QueueJob::new(
-            "user-1".to_string(),
-            JobType::GenerateReport {
-                tenant_id: "t1".to_string(),
-            },
-            ApiType::Axur,
-        );
+        let job = report_job("user-1");
 
         let job_id = queue.submit(job).await;
         assert!(!job_id.is_empty());
         assert_eq!(queue.queue_length().await, 1);
     }
+
+    #[tokio::test]
+    async fn test_jobs_wait_for_token() {
+        let queue = RequestQueue::new();
+        queue.submit(report_job("user-1")).await;
+
+        // A restored job has no token and must not be picked up
+        assert!(queue.pop_ready().await.is_none());
+
+        queue.attach_token("user-2", "other").await;
+        assert!(queue.pop_ready().await.is_none());
+
+        queue.attach_token("user-1", "secret").await;
+        let job = queue.pop_ready().await.expect("job ready");
+        assert_eq!(job.token.as_deref(), Some("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job() {
+        let queue = RequestQueue::new();
+        let job_id = queue.submit(report_job("user-1")).await;
+
+        assert!(matches!(
+            queue.cancel(&job_id, "user-2").await,
+            CancelOutcome::NotFound
+        ));
+        assert!(matches!(
+            queue.cancel(&job_id, "user-1").await,
+            CancelOutcome::Cancelled(_)
+        ));
+        assert_eq!(queue.queue_length().await, 0);
+
+        let job = queue.get_job(&job_id).await.expect("job tracked");
+        assert!(matches!(job.status, JobStatus::Cancelled { .. }));
+        assert_eq!(QueueStatusResponse::from(&job).status, "cancelled");
+
+        assert!(matches!(
+            queue.cancel(&job_id, "user-1").await,
+            CancelOutcome::AlreadyFinished(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_jobs_expire() {
+        let queue = RequestQueue::new();
+        let mut stale = report_job("user-1");
+        stale.created_at -= ORPHANED_JOB_TTL_MS + 1;
+        let stale_id = queue.submit(stale).await;
+        let fresh_id = queue.submit(report_job("user-1")).await;
+
+        queue.expire_orphaned().await;
+
+        assert_eq!(queue.queue_length().await, 1);
+        let stale = queue.get_job(&stale_id).await.unwrap();
+        assert!(matches!(stale.status, JobStatus::Failed { .. }));
+        let fresh = queue.get_job(&fresh_id).await.unwrap();
+        assert!(matches!(fresh.status, JobStatus::Queued { position: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_slot_waits_for_its_turn() {
+        let queue: &'static RequestQueue = Box::leak(Box::new(RequestQueue::new()));
+        let waiting = tokio::spawn(queue.acquire("user-1", "export_pdf", ApiType::Axur));
+        while queue.queue_length().await == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // Interactive jobs need no token and start when the worker picks them up
+        let job = queue.pop_ready().await.expect("slot job ready");
+        assert!(!job.job_type.is_persisted());
+        let channels = queue.take_slot(&job.id).expect("slot channels");
+        channels.started.send(()).unwrap();
+        let slot = waiting.await.unwrap().expect("slot granted");
+        assert_eq!(slot.job_id, job.id);
+
+        // Dropping the guard releases the worker
+        drop(slot);
+        assert!(channels.released.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_slot_is_refused() {
+        let queue: &'static RequestQueue = Box::leak(Box::new(RequestQueue::new()));
+        let waiting = tokio::spawn(queue.acquire("user-1", "report_stream", ApiType::Axur));
+        while queue.queue_length().await == 0 {
+            tokio::task::yield_now().await;
+        }
+        let job_id = queue.user_jobs("user-1").await[0].id.clone();
+        assert!(matches!(
+            queue.cancel(&job_id, "user-1").await,
+            CancelOutcome::Cancelled(_)
+        ));
+        assert!(waiting.await.unwrap().is_err());
+    }
+
+    #[test]
+    fn test_results_not_persisted() {
+        let mut job = report_job("user-1");
+        job.status = JobStatus::Completed {
+            result: serde_json::json!({ "html": "<html>tenant data</html>" }),
+        };
+        let json = serde_json::to_string(&stored_job(&job)).unwrap();
+        assert!(!json.contains("tenant data"));
+        assert!(json.contains("\"tenant_id\":\"t1\""));
+    }
+
+    #[test]
+    fn test_token_not_persisted() {
+        let job = report_job("user-1").with_token("secret".to_string());
+        let json = serde_json::to_string(&job).unwrap();
+        assert!(!json.contains("secret"));
+
+        let restored: QueueJob = serde_json::from_str(&json).unwrap();
+        assert!(restored.token.is_none());
+        assert!(restored.awaiting_token());
+    }
 }
@@ -78,7 +78,7 @@ pub fn admin_routes() -> Router<AppState> {
 
 /// Check if the caller is an admin
 /// Check if the caller is an admin
-async fn require_admin(jar: &CookieJar) -> Result<String, ApiError> {
+pub(crate) async fn require_admin(jar: &CookieJar) -> Result<String, ApiError> {
     let user_email = jar
         .get(AUTH_USER_COOKIE_NAME)
         .map(|c| c.value().to_string())
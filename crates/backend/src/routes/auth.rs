@@ -342,6 +342,9 @@ pub async fn finalize(
         .token
         .ok_or_else(|| ApiError::Internal("No master token received".into()))?;
 
+    // Scheduled reports run with the owner's latest session
+    crate::scheduler::remember_owner_token(&payload.email, &master_token).await;
+
     // Create httpOnly secure cookie (OWASP compliant)
     let cookie = Cookie::build((AUTH_COOKIE_NAME, master_token))
         .http_only(true)
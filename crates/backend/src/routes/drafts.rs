@@ -0,0 +1,510 @@
+//! Report draft API routes
+//!
+//! A draft holds analyst annotations (executive summary, highlighted tickets,
+//! recommendations and per-slide callouts) against a stored report snapshot.
+//! The author submits the draft for review and another user approves it or
+//! requests changes; editing an approved draft sends it back to draft.
+//! Reviewers only see, review and render drafts of tenants their session
+//! can access.
+//! Snapshot PDF/PPTX exports of a drafted report require the approval and
+//! include the annotations. Approval gates the annotations, not the data:
+//! live generation and exports (`/api/report/generate`, `/api/export/*`
+//! and the SSE stream) render freshly fetched data with no draft, so they
+//! never carry annotations and need no approval. Drafts are stored in
+//! Firestore (`report_drafts/{snapshot_id}`) so reviewers can open them.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use axum_extra::extract::CookieJar;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::firebase::{get_firestore, FirestoreClient};
+use crate::middleware::get_token_from_cookies;
+use crate::services::report_service::{
+    GenerateReportResponse, RenderSnapshotRequest, ReportService,
+};
+use axur_core::plugins::ReportAnnotations;
+
+const COLLECTION: &str = "report_drafts";
+
+/// Review state of a draft
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftStatus {
+    #[default]
+    Draft,
+    InReview,
+    ChangesRequested,
+    Approved,
+}
+
+/// One entry of the review history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftEvent {
+    pub at: String,
+    pub by: String,
+    pub status: DraftStatus,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Stored draft of a report snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDraft {
+    pub snapshot_id: String,
+    /// Author; the snapshot is stored under this user
+    pub owner: String,
+    /// Tenant of the snapshot; reviewers need access to it
+    #[serde(default)]
+    pub tenant_id: String,
+    #[serde(default)]
+    pub company_name: String,
+    #[serde(default)]
+    pub annotations: ReportAnnotations,
+    #[serde(default)]
+    pub status: DraftStatus,
+    /// Last user who approved or requested changes
+    #[serde(default)]
+    pub reviewer: Option<String>,
+    #[serde(default)]
+    pub history: Vec<DraftEvent>,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewAction {
+    Approve,
+    RequestChanges,
+}
+
+/// POST /api/reports/snapshots/:id/draft/review body
+#[derive(Debug, Deserialize)]
+pub struct ReviewRequest {
+    pub action: ReviewAction,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl ReportDraft {
+    fn new(snapshot_id: &str, owner: &str, tenant_id: &str, company_name: &str) -> Self {
+        Self {
+            snapshot_id: snapshot_id.to_string(),
+            owner: owner.to_string(),
+            tenant_id: tenant_id.to_string(),
+            company_name: company_name.to_string(),
+            annotations: ReportAnnotations::default(),
+            status: DraftStatus::Draft,
+            reviewer: None,
+            history: Vec::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn record(&mut self, by: &str, status: DraftStatus, comment: Option<String>) {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.status = status;
+        self.history.push(DraftEvent {
+            at: now.clone(),
+            by: by.to_string(),
+            status,
+            comment,
+        });
+        self.updated_at = now;
+    }
+
+    /// Author, or any reviewer once the draft has been submitted
+    fn can_view(&self, user_id: &str) -> bool {
+        self.owner == user_id || self.status != DraftStatus::Draft
+    }
+
+    /// Replace the annotations (author only). A draft in review or approved
+    /// goes back to draft so the new text is reviewed again.
+    fn edit(&mut self, user_id: &str, annotations: ReportAnnotations) -> Result<(), ApiError> {
+        if self.owner != user_id {
+            return Err(ApiError::Forbidden(
+                "Only the author can edit a report draft".into(),
+            ));
+        }
+        annotations.validate().map_err(ApiError::BadRequest)?;
+        self.annotations = annotations;
+        let status = match self.status {
+            DraftStatus::ChangesRequested => DraftStatus::ChangesRequested,
+            _ => DraftStatus::Draft,
+        };
+        self.record(user_id, status, None);
+        Ok(())
+    }
+
+    /// Submit for review (author only)
+    fn submit(&mut self, user_id: &str) -> Result<(), ApiError> {
+        if self.owner != user_id {
+            return Err(ApiError::Forbidden(
+                "Only the author can submit a report draft".into(),
+            ));
+        }
+        match self.status {
+            DraftStatus::Draft | DraftStatus::ChangesRequested => {
+                self.record(user_id, DraftStatus::InReview, None);
+                Ok(())
+            }
+            DraftStatus::InReview => Err(ApiError::BadRequest("Draft is already in review".into())),
+            DraftStatus::Approved => Err(ApiError::BadRequest("Draft is already approved".into())),
+        }
+    }
+
+    /// Approve or request changes; the author cannot review their own draft
+    fn review(
+        &mut self,
+        user_id: &str,
+        action: ReviewAction,
+        comment: Option<String>,
+    ) -> Result<(), ApiError> {
+        if self.owner == user_id {
+            return Err(ApiError::Forbidden(
+                "A report draft must be reviewed by someone other than its author".into(),
+            ));
+        }
+        if self.status != DraftStatus::InReview {
+            return Err(ApiError::BadRequest("Draft is not in review".into()));
+        }
+        let comment = comment.filter(|c| !c.trim().is_empty());
+        let status = match action {
+            ReviewAction::Approve => DraftStatus::Approved,
+            ReviewAction::RequestChanges if comment.is_none() => {
+                return Err(ApiError::BadRequest(
+                    "A comment is required when requesting changes".into(),
+                ))
+            }
+            ReviewAction::RequestChanges => DraftStatus::ChangesRequested,
+        };
+        self.reviewer = Some(user_id.to_string());
+        self.record(user_id, status, comment);
+        Ok(())
+    }
+}
+
+fn firestore() -> Result<&'static FirestoreClient, ApiError> {
+    get_firestore().ok_or_else(|| ApiError::Internal("Storage not available".into()))
+}
+
+/// Load the draft of a snapshot, if any
+async fn load_draft(snapshot_id: &str) -> Result<Option<ReportDraft>, ApiError> {
+    if Uuid::parse_str(snapshot_id).is_err() {
+        return Err(ApiError::BadRequest("Invalid snapshot id".into()));
+    }
+    let Some(firestore) = get_firestore() else {
+        return Ok(None);
+    };
+    firestore
+        .get_doc::<ReportDraft>(COLLECTION, snapshot_id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to load report draft: {}", e)))
+}
+
+/// Tenant of a draft's snapshot (read from the snapshot for drafts saved
+/// before the tenant was stored)
+async fn draft_tenant(draft: &ReportDraft) -> Result<String, ApiError> {
+    if !draft.tenant_id.is_empty() {
+        return Ok(draft.tenant_id.clone());
+    }
+    let snapshot = ReportService::load_snapshot(&draft.owner, &draft.snapshot_id).await?;
+    Ok(snapshot.tenant_id)
+}
+
+fn session_token(jar: &CookieJar) -> Result<String, ApiError> {
+    get_token_from_cookies(jar).ok_or_else(|| ApiError::Unauthorized("No session found".into()))
+}
+
+/// Load a draft visible to the current user: their own, or a submitted one
+/// of a tenant their session can access
+async fn visible_draft(
+    user_id: &str,
+    jar: &CookieJar,
+    snapshot_id: &str,
+) -> Result<ReportDraft, ApiError> {
+    let draft = match load_draft(snapshot_id).await? {
+        Some(draft) if draft.can_view(user_id) => draft,
+        _ => {
+            return Err(ApiError::NotFound(format!(
+                "Draft for snapshot {} not found",
+                snapshot_id
+            )))
+        }
+    };
+    if draft.owner != user_id {
+        let tenant_id = draft_tenant(&draft).await?;
+        ReportService::ensure_tenant_access(&session_token(jar)?, &tenant_id).await?;
+    }
+    Ok(draft)
+}
+
+async fn save(draft: &ReportDraft) -> Result<(), ApiError> {
+    firestore()?
+        .set_doc(COLLECTION, &draft.snapshot_id, draft)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to save report draft: {}", e)))
+}
+
+/// Annotations for the final export of a snapshot: none without a draft,
+/// an error unless the draft is approved
+pub async fn export_annotations(snapshot_id: &str) -> Result<Option<ReportAnnotations>, ApiError> {
+    match load_draft(snapshot_id).await? {
+        None => Ok(None),
+        Some(draft) if draft.status == DraftStatus::Approved => Ok(Some(draft.annotations)),
+        Some(_) => Err(ApiError::Forbidden(
+            "The report draft must be approved before export".into(),
+        )),
+    }
+}
+
+/// Annotations for a preview render, whatever the review state
+pub async fn preview_annotations(snapshot_id: &str) -> Option<ReportAnnotations> {
+    match load_draft(snapshot_id).await {
+        Ok(draft) => draft.map(|d| d.annotations),
+        Err(e) => {
+            tracing::warn!("{}", e);
+            None
+        }
+    }
+}
+
+/// GET /api/reports/drafts - own drafts and drafts waiting for review
+pub async fn list_drafts(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+) -> Result<Json<Vec<ReportDraft>>, ApiError> {
+    let drafts = firestore()?
+        .list_docs::<ReportDraft>(COLLECTION)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to list report drafts: {}", e)))?;
+
+    let mut visible = Vec::new();
+    let mut tenants: Option<Vec<String>> = None;
+    for draft in drafts {
+        if draft.owner == user_id {
+            visible.push(draft);
+            continue;
+        }
+        if draft.status != DraftStatus::InReview {
+            continue;
+        }
+        // Reviewers only see drafts of tenants they can access
+        let tenants = match &tenants {
+            Some(tenants) => tenants,
+            None => tenants.insert(ReportService::accessible_tenants(&session_token(&jar)?).await?),
+        };
+        match draft_tenant(&draft).await {
+            Ok(tenant_id) if tenants.contains(&tenant_id) => visible.push(draft),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(snapshot = %draft.snapshot_id, "{}", e),
+        }
+    }
+    Ok(Json(visible))
+}
+
+/// GET /api/reports/snapshots/:id/draft
+pub async fn get_draft(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(snapshot_id): Path<String>,
+) -> Result<Json<ReportDraft>, ApiError> {
+    visible_draft(&user_id, &jar, &snapshot_id).await.map(Json)
+}
+
+/// PUT /api/reports/snapshots/:id/draft - create or update the annotations
+pub async fn save_draft(
+    Extension(user_id): Extension<String>,
+    Path(snapshot_id): Path<String>,
+    Json(annotations): Json<ReportAnnotations>,
+) -> Result<Json<ReportDraft>, ApiError> {
+    let mut draft = match load_draft(&snapshot_id).await? {
+        Some(draft) => draft,
+        None => {
+            // Only the snapshot's owner can start a draft
+            let snapshot = ReportService::load_snapshot(&user_id, &snapshot_id).await?;
+            ReportDraft::new(
+                &snapshot_id,
+                &user_id,
+                &snapshot.tenant_id,
+                &snapshot.data.company_name,
+            )
+        }
+    };
+    draft.edit(&user_id, annotations)?;
+    save(&draft).await?;
+    Ok(Json(draft))
+}
+
+/// DELETE /api/reports/snapshots/:id/draft
+pub async fn delete_draft(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(snapshot_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let draft = visible_draft(&user_id, &jar, &snapshot_id).await?;
+    if draft.owner != user_id {
+        return Err(ApiError::Forbidden(
+            "Only the author can delete a report draft".into(),
+        ));
+    }
+    firestore()?
+        .delete_doc(COLLECTION, &snapshot_id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to delete report draft: {}", e)))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/reports/snapshots/:id/draft/submit
+pub async fn submit_draft(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(snapshot_id): Path<String>,
+) -> Result<Json<ReportDraft>, ApiError> {
+    let mut draft = visible_draft(&user_id, &jar, &snapshot_id).await?;
+    draft.submit(&user_id)?;
+    save(&draft).await?;
+    tracing::info!(snapshot = %snapshot_id, "Report draft submitted for review");
+    Ok(Json(draft))
+}
+
+/// POST /api/reports/snapshots/:id/draft/review { "action": "approve"|"request_changes" }
+pub async fn review_draft(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(snapshot_id): Path<String>,
+    Json(request): Json<ReviewRequest>,
+) -> Result<Json<ReportDraft>, ApiError> {
+    let mut draft = visible_draft(&user_id, &jar, &snapshot_id).await?;
+    draft.review(&user_id, request.action, request.comment)?;
+    save(&draft).await?;
+    tracing::info!(snapshot = %snapshot_id, status = ?draft.status, "Report draft reviewed");
+    Ok(Json(draft))
+}
+
+/// POST /api/reports/snapshots/:id/draft/render - render the snapshot with the
+/// draft annotations (author and reviewers)
+pub async fn render_draft(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(snapshot_id): Path<String>,
+    Json(mut payload): Json<RenderSnapshotRequest>,
+) -> Result<Json<GenerateReportResponse>, ApiError> {
+    let draft = visible_draft(&user_id, &jar, &snapshot_id).await?;
+    let snapshot = ReportService::load_snapshot(&draft.owner, &snapshot_id).await?;
+    payload.annotations = draft.annotations;
+    let response = ReportService::render_snapshot(&snapshot, &payload, &user_id).await?;
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::report_service::GenerateReportRequest;
+    use axur_core::plugins::HighlightedTicket;
+
+    fn annotations() -> ReportAnnotations {
+        ReportAnnotations {
+            executive_summary: Some("Phishing doubled this quarter.".into()),
+            highlighted_tickets: vec![HighlightedTicket {
+                ticket_key: "TK-1234".into(),
+                note: "Kit reused across brands".into(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_review_workflow() {
+        let mut draft = ReportDraft::new("snap", "author@axur.com", "acme", "Acme");
+        assert!(!draft.can_view("reviewer@axur.com"));
+        assert!(draft.edit("reviewer@axur.com", annotations()).is_err());
+        draft.edit("author@axur.com", annotations()).unwrap();
+
+        // Nothing to review until submitted; the author cannot self-approve
+        assert!(draft
+            .review("reviewer@axur.com", ReviewAction::Approve, None)
+            .is_err());
+        draft.submit("author@axur.com").unwrap();
+        assert!(draft.can_view("reviewer@axur.com"));
+        assert!(draft
+            .review("author@axur.com", ReviewAction::Approve, None)
+            .is_err());
+
+        // Requesting changes needs a comment
+        assert!(draft
+            .review("reviewer@axur.com", ReviewAction::RequestChanges, None)
+            .is_err());
+        draft
+            .review(
+                "reviewer@axur.com",
+                ReviewAction::RequestChanges,
+                Some("Add the takedown numbers".into()),
+            )
+            .unwrap();
+        assert_eq!(draft.status, DraftStatus::ChangesRequested);
+
+        draft.edit("author@axur.com", annotations()).unwrap();
+        draft.submit("author@axur.com").unwrap();
+        draft
+            .review("reviewer@axur.com", ReviewAction::Approve, None)
+            .unwrap();
+        assert_eq!(draft.status, DraftStatus::Approved);
+        assert_eq!(draft.reviewer.as_deref(), Some("reviewer@axur.com"));
+
+        // Editing an approved draft needs a new review
+        draft.edit("author@axur.com", annotations()).unwrap();
+        assert_eq!(draft.status, DraftStatus::Draft);
+        assert_eq!(draft.history.len(), 7);
+    }
+
+    #[test]
+    fn test_draft_roundtrip() {
+        let mut draft = ReportDraft::new("snap", "author@axur.com", "acme", "Acme");
+        draft.edit("author@axur.com", annotations()).unwrap();
+        draft.submit("author@axur.com").unwrap();
+
+        let json = serde_json::to_value(&draft).unwrap();
+        assert_eq!(json["status"], "in_review");
+        assert_eq!(
+            json["annotations"]["highlighted_tickets"][0]["ticket_key"],
+            "TK-1234"
+        );
+        let restored: ReportDraft = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.annotations, draft.annotations);
+        assert_eq!(restored.status, DraftStatus::InReview);
+        assert_eq!(restored.tenant_id, "acme");
+
+        let review: ReviewRequest =
+            serde_json::from_value(serde_json::json!({ "action": "request_changes" })).unwrap();
+        assert!(matches!(review.action, ReviewAction::RequestChanges));
+    }
+
+    #[test]
+    fn test_live_requests_carry_no_annotations() {
+        // Annotations only come from an approved draft, never from a request body
+        let request: GenerateReportRequest = serde_json::from_value(serde_json::json!({
+            "tenant_id": "acme",
+            "from_date": "2025-01-01",
+            "to_date": "2025-01-31",
+            "story_tag": null,
+            "template_id": null,
+            "annotations": { "executive_summary": "Unreviewed" }
+        }))
+        .unwrap();
+        assert_eq!(
+            request.render_options().annotations,
+            ReportAnnotations::default()
+        );
+
+        let render: RenderSnapshotRequest = serde_json::from_value(serde_json::json!({
+            "annotations": { "executive_summary": "Unreviewed" }
+        }))
+        .unwrap();
+        assert_eq!(render.annotations, ReportAnnotations::default());
+    }
+}
@@ -1,11 +1,13 @@
 //! Import/Export routes for templates
 //! Handles PPTX import and export functionality
 
+use crate::google_services::GoogleServices;
 use crate::injector::{inject_edits, SlideEdit};
 use crate::routes::AppState;
 use axum::extract::State;
 use axum::Json;
 use axum_extra::extract::Multipart;
+use axur_core::editor::{import_pptx as import_pptx_template, PresentationTemplate};
 use serde::Serialize;
 use tracing;
 use uuid;
@@ -13,19 +15,26 @@ use uuid;
 #[derive(Serialize)]
 pub struct ImportResponse {
     pub success: bool,
+    /// Editable template parsed from the deck (text, images, shapes, positions)
+    pub template: PresentationTemplate,
+    /// Slide thumbnails as data URLs (only when `previews=true` and Google is configured)
     pub slides: Vec<String>,
     pub message: String,
 }
 
-/// Upload PPTX to Google Drive, generate thumbnails via Slides API, and return data URLs.
-/// Uses strict Rate Limiting (4 req/sec) to stay within Free Tier.
-/// Images are downloaded to base64 data URLs to avoid 429 errors on frontend.
+/// Parse an uploaded PPTX locally into an editable template.
+///
+/// Slides are read straight from the package, so no external service is
+/// needed. Rendered thumbnails are optional: send `previews=true` to also
+/// render them through Google Slides (rate limited, requires credentials);
+/// a thumbnail failure does not fail the import.
 pub async fn import_pptx(
     State(state): State<AppState>,
     mut multipart: Multipart,
 ) -> Result<Json<ImportResponse>, (axum::http::StatusCode, String)> {
     let mut file_data = None;
     let mut file_name = String::from("presentation.pptx");
+    let mut want_previews = false;
 
     while let Ok(Some(field)) = multipart.next_field().await {
         let name = field.name().unwrap_or("").to_string();
@@ -34,6 +43,8 @@ pub async fn import_pptx(
             if let Ok(bytes) = field.bytes().await {
                 file_data = Some(bytes.to_vec());
             }
+        } else if name == "previews" {
+            want_previews = field.text().await.map(|v| v == "true").unwrap_or(false);
         }
     }
 
@@ -44,53 +55,83 @@ pub async fn import_pptx(
         ));
     };
 
-    let services = state.google_services.ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "Google Services not configured (missing credentials)".to_string(),
-    ))?;
+    let template_name = file_name
+        .strip_suffix(".pptx")
+        .unwrap_or(&file_name)
+        .to_string();
+    let parse_data = data.clone();
+    let template =
+        tokio::task::spawn_blocking(move || import_pptx_template(&parse_data, &template_name))
+            .await
+            .map_err(|e| {
+                (
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Import task failed: {}", e),
+                )
+            })?
+            .map_err(|e| (axum::http::StatusCode::BAD_REQUEST, e))?;
 
+    tracing::info!(
+        "Imported {} locally: {} slides",
+        file_name,
+        template.slides.len()
+    );
+
+    let slides = match (want_previews, state.google_services) {
+        (true, Some(services)) => match render_previews(&services, &file_name, data).await {
+            Ok(urls) => urls,
+            Err(e) => {
+                tracing::warn!("Slide previews unavailable: {}", e);
+                Vec::new()
+            }
+        },
+        (true, None) => {
+            tracing::warn!("Slide previews requested but Google Services are not configured");
+            Vec::new()
+        }
+        (false, _) => Vec::new(),
+    };
+
+    Ok(Json(ImportResponse {
+        success: true,
+        message: format!("Imported {} slides", template.slides.len()),
+        template,
+        slides,
+    }))
+}
+
+/// Upload PPTX to Google Drive, generate thumbnails via Slides API, and return data URLs.
+/// Uses strict Rate Limiting (4 req/sec) to stay within Free Tier.
+/// Images are downloaded to base64 data URLs to avoid 429 errors on frontend.
+async fn render_previews(
+    services: &GoogleServices,
+    file_name: &str,
+    data: Vec<u8>,
+) -> Result<Vec<String>, String> {
     let uuid = uuid::Uuid::new_v4();
     let temp_name = format!("preview_{}_{}", uuid, file_name);
 
     // 1. Upload to Drive
     tracing::info!("Uploading to Google Drive: {}", temp_name);
-    let file_id = services
-        .upload_pptx(&temp_name, data)
-        .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let file_id = services.upload_pptx(&temp_name, data).await?;
 
     // 2. Generate Previews (Rate Limited) - get Google content URLs
     tracing::info!("Generating previews for file ID: {}", file_id);
-    let google_urls = services
-        .generate_previews(&file_id)
-        .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e))?;
-
-    // 3. Download images as base64 to avoid 429 on frontend
-    // Uses Retry-After header handling for proper rate limiting
-    tracing::info!("Downloading {} images as base64...", google_urls.len());
-    let data_urls = services
-        .fetch_images_as_base64(google_urls)
-        .await
-        .map_err(|e| {
-            // Attempt cleanup on error
-            let services_clone = services.clone();
-            let file_id_clone = file_id.clone();
-            tokio::spawn(async move {
-                let _ = services_clone.delete_file(&file_id_clone).await;
-            });
-            (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e)
-        })?;
+    let result = match services.generate_previews(&file_id).await {
+        // 3. Download images as base64 to avoid 429 on frontend
+        // Uses Retry-After header handling for proper rate limiting
+        Ok(google_urls) => {
+            tracing::info!("Downloading {} images as base64...", google_urls.len());
+            services.fetch_images_as_base64(google_urls).await
+        }
+        Err(e) => Err(e),
+    };
 
     // 4. Cleanup
     tracing::info!("Cleaning up file from Drive: {}", file_id);
     let _ = services.delete_file(&file_id).await;
 
-    Ok(Json(ImportResponse {
-        success: true,
-        slides: data_urls, // Now returns base64 data URLs instead of Google URLs
-        message: "Successfully generated previews with embedded images".to_string(),
-    }))
+    result
 }
 
 /// Inject placeholders into PPTX and download
@@ -4,16 +4,21 @@ pub mod admin; // Admin user management
 pub mod admin_config; // Admin access control
 pub mod auth;
 pub mod beta;
+pub mod drafts; // Report drafts: analyst annotations and review
 pub mod feedback;
 pub mod import_export;
 pub mod logs_api; // Log viewing API
 pub mod marketplace; // Template marketplace
+pub mod presets; // Report presets (industry/customer)
 pub mod queue; // Request queue with rate limiting
+pub mod redaction; // Credential redaction overrides
 pub mod remote_log; // Private GitHub log uploads
 pub mod report;
+pub mod schedules; // Scheduled recurring reports
 pub mod status; // Production health checks
 pub mod storage; // GitHub storage for user data
 pub mod templates; // Template CRUD // Beta registration
+pub mod th_credits; // Threat Hunting credit budgets and ledger
 
 use axum::{
     extract::DefaultBodyLimit,
@@ -97,6 +102,10 @@ pub fn create_router(state: AppState) -> Router {
         // Marketplace (browse is public)
         .route("/api/marketplace", get(marketplace::list_marketplace))
         // Template GET is public (mock templates don't need auth)
+        .route(
+            "/api/templates/chart-series",
+            get(templates::list_chart_series),
+        )
         .route("/api/templates/:id", get(templates::get_template));
 
     // Protected routes (Require Authentication)
@@ -122,6 +131,80 @@ pub fn create_router(state: AppState) -> Router {
             "/api/reports/generate-stream",
             get(report::generate_report_stream),
         )
+        .route("/api/reports/snapshots", get(report::list_snapshots))
+        .route(
+            "/api/reports/snapshots/:id/render",
+            post(report::render_snapshot),
+        )
+        .route(
+            "/api/reports/snapshots/:id/pdf",
+            post(report::export_snapshot_pdf),
+        )
+        .route("/api/export/pdf", post(report::export_pdf))
+        .route(
+            "/api/reports/snapshots/:id/pptx",
+            post(report::export_snapshot_pptx),
+        )
+        .route("/api/export/pptx", post(report::export_pptx))
+        // Report drafts (analyst annotations, review/approve before export)
+        .route("/api/reports/drafts", get(drafts::list_drafts))
+        .route("/api/reports/snapshots/:id/draft", get(drafts::get_draft))
+        .route("/api/reports/snapshots/:id/draft", put(drafts::save_draft))
+        .route(
+            "/api/reports/snapshots/:id/draft",
+            delete(drafts::delete_draft),
+        )
+        .route(
+            "/api/reports/snapshots/:id/draft/submit",
+            post(drafts::submit_draft),
+        )
+        .route(
+            "/api/reports/snapshots/:id/draft/review",
+            post(drafts::review_draft),
+        )
+        .route(
+            "/api/reports/snapshots/:id/draft/render",
+            post(drafts::render_draft),
+        )
+        // Credential redaction (per-report overrides need admin approval)
+        .route("/api/redaction/policy", get(redaction::get_policy))
+        .route("/api/redaction/overrides", get(redaction::list_overrides))
+        .route(
+            "/api/redaction/overrides",
+            post(redaction::request_override),
+        )
+        .route(
+            "/api/admin/redaction/overrides",
+            get(redaction::list_pending_overrides),
+        )
+        .route(
+            "/api/admin/redaction/overrides/:id/review",
+            post(redaction::review_override),
+        )
+        // Threat Hunting credit budgets and ledger
+        .route("/api/th-credits/:tenant_id", get(th_credits::get_summary))
+        .route(
+            "/api/th-credits/:tenant_id/ledger",
+            get(th_credits::get_ledger),
+        )
+        .route(
+            "/api/admin/th-credits/:tenant_id/budget",
+            put(th_credits::set_budget),
+        )
+        // Scheduled recurring reports
+        .route("/api/schedules", get(schedules::list_schedules))
+        .route("/api/schedules", post(schedules::create_schedule))
+        .route("/api/schedules/:id", get(schedules::get_schedule))
+        .route("/api/schedules/:id", put(schedules::update_schedule))
+        .route("/api/schedules/:id", delete(schedules::delete_schedule))
+        .route("/api/schedules/:id/run", post(schedules::run_schedule_now))
+        .route("/api/schedules/:id/runs", get(schedules::list_runs))
+        // Report presets
+        .route("/api/presets", get(presets::list_presets))
+        .route("/api/presets", post(presets::create_preset))
+        .route("/api/presets/:id", get(presets::get_preset))
+        .route("/api/presets/:id", put(presets::update_preset))
+        .route("/api/presets/:id", delete(presets::delete_preset))
         .route("/api/feedback", post(feedback::submit_feedback))
         .route("/api/logs/sync", post(remote_log::sync_logs))
         // Log viewer API
@@ -177,7 +260,7 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/api/admin", admin::admin_routes())
         .route_layer(axum::middleware::from_fn(crate::middleware::require_auth));
 
-    // Queue routes (public - uses global queue, no AppState needed)
+    // Queue routes (global queue, no AppState needed; auth applied inside except /length)
     let queue_routes: Router<AppState> = queue::queue_routes().with_state(());
 
     // Storage routes (user templates via GitHub, no AppState needed)
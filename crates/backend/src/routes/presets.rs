@@ -0,0 +1,261 @@
+//! Report preset API routes
+//!
+//! Presets (plugin selection and order, theme, accent color, terminology,
+//! default language, risk model) are stored per user in Firestore like
+//! editor templates (`user_presets/{user_id}/items`). Shared presets are
+//! copied to `shared_presets` so every user can apply them. The builtin
+//! industry presets are always listed first and cannot be modified.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::firebase::{get_firestore, FirestoreClient};
+use axur_core::templates::{builtin_preset, builtin_presets, ReportPreset};
+
+const SHARED_COLLECTION: &str = "shared_presets";
+
+fn user_collection(user_id: &str) -> String {
+    format!("user_presets/{}/items", user_id)
+}
+
+/// Stored preset with its ownership
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPreset {
+    #[serde(flatten)]
+    pub preset: ReportPreset,
+    /// Owner user id (empty for builtin presets)
+    #[serde(default)]
+    pub owner: String,
+    /// Visible to every user
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default)]
+    pub builtin: bool,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+impl StoredPreset {
+    fn builtin(preset: ReportPreset) -> Self {
+        Self {
+            preset,
+            owner: String::new(),
+            shared: true,
+            builtin: true,
+            updated_at: String::new(),
+        }
+    }
+}
+
+/// Create/update body
+#[derive(Debug, Deserialize)]
+pub struct SavePresetRequest {
+    #[serde(flatten)]
+    pub preset: ReportPreset,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+fn firestore() -> Result<&'static FirestoreClient, ApiError> {
+    get_firestore().ok_or_else(|| ApiError::Internal("Storage not available".into()))
+}
+
+/// Load a preset by id: builtin, the user's own, then shared ones
+pub async fn load_preset(preset_id: &str, user_id: &str) -> Option<ReportPreset> {
+    if let Some(preset) = builtin_preset(preset_id) {
+        return Some(preset);
+    }
+    Uuid::parse_str(preset_id).ok()?;
+    let firestore = get_firestore()?;
+
+    for collection in [user_collection(user_id), SHARED_COLLECTION.to_string()] {
+        match firestore
+            .get_doc::<StoredPreset>(&collection, preset_id)
+            .await
+        {
+            Ok(Some(stored)) => return Some(stored.preset),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to load preset {}: {}", preset_id, e),
+        }
+    }
+    None
+}
+
+/// Load a preset owned by the current user
+async fn owned_preset(user_id: &str, id: &str) -> Result<StoredPreset, ApiError> {
+    if builtin_preset(id).is_some() {
+        return Err(ApiError::Forbidden(
+            "Builtin presets cannot be modified".into(),
+        ));
+    }
+    match firestore()?
+        .get_doc::<StoredPreset>(&user_collection(user_id), id)
+        .await
+    {
+        Ok(Some(stored)) if stored.owner == user_id => Ok(stored),
+        Ok(_) => Err(ApiError::NotFound(format!("Preset {} not found", id))),
+        Err(e) => Err(ApiError::Internal(format!("Failed to load preset: {}", e))),
+    }
+}
+
+/// Write a preset to the user's collection and keep its shared copy in sync
+async fn save(stored: &StoredPreset, was_shared: bool) -> Result<(), ApiError> {
+    let firestore = firestore()?;
+    let id = &stored.preset.id;
+    firestore
+        .set_doc(&user_collection(&stored.owner), id, stored)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to save preset: {}", e)))?;
+
+    let result = if stored.shared {
+        firestore.set_doc(SHARED_COLLECTION, id, stored).await
+    } else if was_shared {
+        firestore.delete_doc(SHARED_COLLECTION, id).await
+    } else {
+        Ok(())
+    };
+    result.map_err(|e| ApiError::Internal(format!("Failed to share preset: {}", e)))
+}
+
+/// GET /api/presets - builtin, own and shared presets
+pub async fn list_presets(
+    Extension(user_id): Extension<String>,
+) -> Result<Json<Vec<StoredPreset>>, ApiError> {
+    let mut presets: Vec<StoredPreset> = builtin_presets()
+        .into_iter()
+        .map(StoredPreset::builtin)
+        .collect();
+
+    if let Some(firestore) = get_firestore() {
+        for collection in [user_collection(&user_id), SHARED_COLLECTION.to_string()] {
+            match firestore.list_docs::<StoredPreset>(&collection).await {
+                Ok(stored) => {
+                    for preset in stored {
+                        if !presets.iter().any(|p| p.preset.id == preset.preset.id) {
+                            presets.push(preset);
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to list presets in {}: {}", collection, e),
+            }
+        }
+    }
+
+    Ok(Json(presets))
+}
+
+/// GET /api/presets/:id
+pub async fn get_preset(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+) -> Result<Json<ReportPreset>, ApiError> {
+    load_preset(&id, &user_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Preset {} not found", id)))
+}
+
+/// POST /api/presets
+pub async fn create_preset(
+    Extension(user_id): Extension<String>,
+    Json(request): Json<SavePresetRequest>,
+) -> Result<(StatusCode, Json<StoredPreset>), ApiError> {
+    request.preset.validate().map_err(ApiError::BadRequest)?;
+
+    let stored = StoredPreset {
+        preset: ReportPreset {
+            id: Uuid::new_v4().to_string(),
+            ..request.preset
+        },
+        owner: user_id,
+        shared: request.shared,
+        builtin: false,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    save(&stored, false).await?;
+
+    tracing::info!(preset = %stored.preset.id, shared = stored.shared, "Report preset created");
+    Ok((StatusCode::CREATED, Json(stored)))
+}
+
+/// PUT /api/presets/:id
+pub async fn update_preset(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+    Json(request): Json<SavePresetRequest>,
+) -> Result<Json<StoredPreset>, ApiError> {
+    request.preset.validate().map_err(ApiError::BadRequest)?;
+
+    let existing = owned_preset(&user_id, &id).await?;
+    let stored = StoredPreset {
+        preset: ReportPreset {
+            id,
+            ..request.preset
+        },
+        owner: user_id,
+        shared: request.shared,
+        builtin: false,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    save(&stored, existing.shared).await?;
+    Ok(Json(stored))
+}
+
+/// DELETE /api/presets/:id
+pub async fn delete_preset(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let existing = owned_preset(&user_id, &id).await?;
+    let firestore = firestore()?;
+    firestore
+        .delete_doc(&user_collection(&user_id), &id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to delete preset: {}", e)))?;
+    if existing.shared {
+        firestore
+            .delete_doc(SHARED_COLLECTION, &id)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to unshare preset: {}", e)))?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_preset_roundtrip() {
+        let body = serde_json::json!({
+            "name": "Telco LATAM",
+            "plugins": ["builtin.cover", "builtin.threats"],
+            "theme": "light",
+            "accent_color": "#E11D48",
+            "terminology": { "footer_text": "Operadora X" },
+            "language": "pt-br",
+            "shared": true
+        });
+        let request: SavePresetRequest = serde_json::from_value(body).unwrap();
+        assert!(request.shared);
+        assert_eq!(request.preset.plugins.len(), 2);
+        assert!(request.preset.validate().is_ok());
+
+        let stored = StoredPreset {
+            preset: request.preset,
+            owner: "user-1".into(),
+            shared: true,
+            builtin: false,
+            updated_at: String::new(),
+        };
+        let json = serde_json::to_value(&stored).unwrap();
+        assert_eq!(json["name"], "Telco LATAM");
+        assert_eq!(json["owner"], "user-1");
+        let restored: StoredPreset = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.preset, stored.preset);
+    }
+}
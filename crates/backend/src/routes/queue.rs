@@ -1,4 +1,7 @@
 //! Queue Routes - API endpoints for queue status and job submission
+//!
+//! Everything except `/length` requires a session: jobs belong to the
+//! logged-in user and run with their token.
 
 use axum::{
     extract::Path,
@@ -7,13 +10,18 @@ use axum::{
         IntoResponse,
     },
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
+use axum_extra::extract::CookieJar;
 use futures::stream::{self, Stream};
 use serde::Deserialize;
 use std::{convert::Infallible, time::Duration};
 
-use crate::queue::{get_queue, ApiType, JobStatus, JobType, QueueJob, QueueStatusResponse};
+use crate::error::ApiError;
+use crate::middleware::AUTH_COOKIE_NAME;
+use crate::queue::{
+    get_queue, ApiType, CancelOutcome, JobStatus, JobType, QueueJob, QueueStatusResponse,
+};
 
 /// Create queue routes (no state needed - uses global queue)
 pub fn queue_routes() -> Router {
@@ -21,31 +29,59 @@ pub fn queue_routes() -> Router {
         .route("/submit", post(submit_job))
         .route("/status/:job_id", get(get_job_status))
         .route("/stream/:job_id", get(stream_job_status))
+        .route("/result/:job_id", get(get_job_result))
+        .route("/cancel/:job_id", post(cancel_job))
+        .route("/jobs", get(list_jobs))
+        .route_layer(axum::middleware::from_fn(crate::middleware::require_auth))
         .route("/length", get(get_queue_length))
 }
 
 /// Request body for job submission
 #[derive(Debug, Deserialize)]
 pub struct SubmitJobRequest {
-    pub user_id: String,
     pub job_type: String,
     pub params: serde_json::Value,
 }
 
+/// Session token from the auth cookie. Also hands it to the user's restored
+/// jobs so work queued before a restart can resume.
+async fn session_token(jar: &CookieJar, user_id: &str) -> Option<String> {
+    let token = jar.get(AUTH_COOKIE_NAME).map(|c| c.value().to_string())?;
+    get_queue().attach_token(user_id, &token).await;
+    Some(token)
+}
+
+/// Load a job owned by the current user
+async fn owned_job(user_id: &str, job_id: &str) -> Option<QueueJob> {
+    get_queue()
+        .get_job(job_id)
+        .await
+        .filter(|job| job.user_id == user_id)
+}
+
 /// Submit a new job to the queue
-async fn submit_job(Json(req): Json<SubmitJobRequest>) -> impl IntoResponse {
+async fn submit_job(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Json(req): Json<SubmitJobRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
     let queue = get_queue();
+    let token = session_token(&jar, &user_id)
+        .await
+        .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
 
     // Parse job type
     let (job_type, api_type) = match req.job_type.as_str() {
         "generate_report" => {
-            let tenant_id = req
-                .params
-                .get("tenant_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("default")
-                .to_string();
-            (JobType::GenerateReport { tenant_id }, ApiType::Axur)
+            let request = serde_json::from_value(req.params)
+                .map_err(|e| ApiError::BadRequest(format!("Invalid report params: {}", e)))?;
+            (JobType::GenerateReport { request }, ApiType::Axur)
+        }
+        "threat_hunting_preview" => {
+            let request = serde_json::from_value(req.params).map_err(|e| {
+                ApiError::BadRequest(format!("Invalid Threat Hunting params: {}", e))
+            })?;
+            (JobType::ThreatHuntingPreview { request }, ApiType::Axur)
         }
         "save_template" => {
             let template_name = req
@@ -65,30 +101,33 @@ async fn submit_job(Json(req): Json<SubmitJobRequest>) -> impl IntoResponse {
                 .to_string();
             (JobType::LoadTemplate { template_id }, ApiType::GitHub)
         }
-        _ => {
-            return Json(serde_json::json!({
-                "error": "Unknown job type"
-            }));
+        other => {
+            return Err(ApiError::BadRequest(format!("Unknown job type: {}", other)));
         }
     };
 
-    let job = QueueJob::new(req.user_id, job_type, api_type);
+    let job = QueueJob::new(user_id, job_type, api_type).with_token(token);
     let job_id = queue.submit(job).await;
     let position = queue.queue_length().await;
     let eta = queue.estimate_wait_time(position, api_type);
 
-    Json(serde_json::json!({
+    Ok(Json(serde_json::json!({
         "job_id": job_id,
         "position": position,
         "eta_seconds": eta.as_secs()
-    }))
+    })))
 }
 
 /// Get current job status
-async fn get_job_status(Path(job_id): Path<String>) -> impl IntoResponse {
+async fn get_job_status(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Path(job_id): Path<String>,
+) -> impl IntoResponse {
     let queue = get_queue();
+    session_token(&jar, &user_id).await;
 
-    match queue.get_job(&job_id).await {
+    match owned_job(&user_id, &job_id).await {
         Some(job) => {
             let mut response = QueueStatusResponse::from(&job);
 
@@ -106,6 +145,61 @@ async fn get_job_status(Path(job_id): Path<String>) -> impl IntoResponse {
     }
 }
 
+/// Get the result of a finished job
+async fn get_job_result(
+    Extension(user_id): Extension<String>,
+    Path(job_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let job = owned_job(&user_id, &job_id)
+        .await
+        .ok_or_else(|| ApiError::NotFound(format!("Job {} not found", job_id)))?;
+
+    match job.status {
+        JobStatus::Completed { result } => Ok(Json(result)),
+        JobStatus::Failed { error } => Err(ApiError::BadRequest(format!("Job failed: {}", error))),
+        JobStatus::Cancelled { .. } => Err(ApiError::BadRequest("Job was cancelled".into())),
+        JobStatus::Queued { .. } | JobStatus::Processing { .. } => {
+            Err(ApiError::BadRequest("Job has not finished yet".into()))
+        }
+    }
+}
+
+/// Cancel a queued or running job
+async fn cancel_job(
+    Extension(user_id): Extension<String>,
+    Path(job_id): Path<String>,
+) -> Result<Json<QueueStatusResponse>, ApiError> {
+    match get_queue().cancel(&job_id, &user_id).await {
+        CancelOutcome::Cancelled(job) => Ok(Json(QueueStatusResponse::from(&job))),
+        CancelOutcome::AlreadyFinished(_) => {
+            Err(ApiError::BadRequest("Job has already finished".into()))
+        }
+        CancelOutcome::NotFound => Err(ApiError::NotFound(format!("Job {} not found", job_id))),
+    }
+}
+
+/// List the current user's jobs (queued, running and finished)
+async fn list_jobs(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+) -> Json<Vec<QueueStatusResponse>> {
+    session_token(&jar, &user_id).await;
+
+    let mut jobs = get_queue().user_jobs(&user_id).await;
+    jobs.sort_by_key(|j| std::cmp::Reverse(j.created_at));
+
+    // Results can be large; fetch them through /result/:job_id
+    let statuses = jobs
+        .iter()
+        .map(|job| QueueStatusResponse {
+            result: None,
+            ..QueueStatusResponse::from(job)
+        })
+        .collect();
+
+    Json(statuses)
+}
+
 /// Get queue length
 async fn get_queue_length() -> impl IntoResponse {
     let queue = get_queue();
@@ -117,43 +211,44 @@ async fn get_queue_length() -> impl IntoResponse {
 
 /// Server-Sent Events stream for job status
 async fn stream_job_status(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
     Path(job_id): Path<String>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    let stream = stream::unfold((job_id, 0u32), |(job_id, tick)| async move {
-        // Poll every 2 seconds, max 300 ticks (10 minutes)
-        if tick > 300 {
-            return None;
-        }
-
-        tokio::time::sleep(Duration::from_secs(2)).await;
-
-        let queue = get_queue();
-        let event = match queue.get_job(&job_id).await {
-            Some(job) => {
-                let response = QueueStatusResponse::from(&job);
-                let data = serde_json::to_string(&response).unwrap_or_default();
-
-                // If completed or failed, this will be the last event
-                let is_final = matches!(
-                    &job.status,
-                    JobStatus::Completed { .. } | JobStatus::Failed { .. }
-                );
-
-                if is_final {
-                    return Some((
-                        Ok(Event::default().data(data).event("complete")),
-                        (job_id, 301), // Stop after this
-                    ));
-                }
+    session_token(&jar, &user_id).await;
 
-                Event::default().data(data).event("update")
+    let stream = stream::unfold((job_id, 0u32), move |(job_id, tick)| {
+        let user_id = user_id.clone();
+        async move {
+            // Poll every 2 seconds, max 300 ticks (10 minutes)
+            if tick > 300 {
+                return None;
             }
-            None => Event::default()
-                .data(r#"{"error":"Job not found"}"#)
-                .event("error"),
-        };
 
-        Some((Ok(event), (job_id, tick + 1)))
+            tokio::time::sleep(Duration::from_secs(2)).await;
+
+            let event = match owned_job(&user_id, &job_id).await {
+                Some(job) => {
+                    let response = QueueStatusResponse::from(&job);
+                    let data = serde_json::to_string(&response).unwrap_or_default();
+
+                    // If finished, this will be the last event
+                    if job.status.is_finished() {
+                        return Some((
+                            Ok(Event::default().data(data).event("complete")),
+                            (job_id, 301), // Stop after this
+                        ));
+                    }
+
+                    Event::default().data(data).event("update")
+                }
+                None => Event::default()
+                    .data(r#"{"error":"Job not found"}"#)
+                    .event("error"),
+            };
+
+            Some((Ok(event), (job_id, tick + 1)))
+        }
     });
 
     Sse::new(stream).keep_alive(
@@ -0,0 +1,288 @@
+//! Credential redaction override routes
+//!
+//! Reports mask leaked credentials with the server policy
+//! (`AXUR_REDACT_USER` / `AXUR_REDACT_PASSWORD`, see
+//! `axur_core::api::redaction`). An analyst who needs a different masking
+//! for a report requests an override for one tenant; an admin other than
+//! the requester approves or rejects it. Once approved, the requester
+//! references it with `redaction_override_id` when generating or exporting
+//! that tenant's report, until it expires. Overrides are stored in Firestore
+//! (`redaction_overrides/{id}`) so admins can review them.
+
+use axum::extract::Path;
+use axum::{Extension, Json};
+use axum_extra::extract::CookieJar;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::firebase::{get_firestore, FirestoreClient};
+use crate::routes::admin::require_admin;
+use axur_core::api::redaction::RedactionPolicy;
+
+const COLLECTION: &str = "redaction_overrides";
+
+/// How long an approved override can be used
+const OVERRIDE_VALIDITY_HOURS: i64 = 24;
+
+/// Review state of an override request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Stored override request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionOverride {
+    pub id: String,
+    pub tenant_id: String,
+    pub requested_by: String,
+    pub policy: RedactionPolicy,
+    pub reason: String,
+    #[serde(default)]
+    pub status: OverrideStatus,
+    #[serde(default)]
+    pub reviewed_by: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    pub created_at: String,
+    /// Set on approval
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+/// POST /api/redaction/overrides body
+#[derive(Debug, Deserialize)]
+pub struct OverrideRequest {
+    pub tenant_id: String,
+    pub policy: RedactionPolicy,
+    pub reason: String,
+}
+
+/// POST /api/admin/redaction/overrides/:id/review body
+#[derive(Debug, Deserialize)]
+pub struct OverrideReview {
+    pub approve: bool,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl RedactionOverride {
+    fn new(user_id: &str, request: OverrideRequest) -> Result<Self, ApiError> {
+        if request.tenant_id.trim().is_empty() {
+            return Err(ApiError::BadRequest("Tenant ID is required".into()));
+        }
+        if request.reason.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "A reason is required for a redaction override".into(),
+            ));
+        }
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id: request.tenant_id,
+            requested_by: user_id.to_string(),
+            policy: request.policy,
+            reason: request.reason,
+            status: OverrideStatus::Pending,
+            reviewed_by: None,
+            comment: None,
+            created_at: Utc::now().to_rfc3339(),
+            expires_at: None,
+        })
+    }
+
+    /// Approve or reject; an admin cannot review their own request
+    fn review(
+        &mut self,
+        admin: &str,
+        approve: bool,
+        comment: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        if self.requested_by.eq_ignore_ascii_case(admin) {
+            return Err(ApiError::Forbidden(
+                "A redaction override must be approved by another admin".into(),
+            ));
+        }
+        if self.status != OverrideStatus::Pending {
+            return Err(ApiError::BadRequest(
+                "Redaction override was already reviewed".into(),
+            ));
+        }
+        if approve {
+            self.status = OverrideStatus::Approved;
+            self.expires_at = Some((now + Duration::hours(OVERRIDE_VALIDITY_HOURS)).to_rfc3339());
+        } else {
+            self.status = OverrideStatus::Rejected;
+        }
+        self.reviewed_by = Some(admin.to_string());
+        self.comment = comment.filter(|c| !c.trim().is_empty());
+        Ok(())
+    }
+
+    /// Policy for a report of `tenant_id` requested by `user_id`
+    fn policy_for(
+        &self,
+        user_id: &str,
+        tenant_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<RedactionPolicy, ApiError> {
+        if self.requested_by != user_id || self.tenant_id != tenant_id {
+            return Err(ApiError::Forbidden(
+                "Redaction override does not apply to this report".into(),
+            ));
+        }
+        if self.status != OverrideStatus::Approved {
+            return Err(ApiError::Forbidden(
+                "Redaction override has not been approved by an admin".into(),
+            ));
+        }
+        let valid = self
+            .expires_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| at >= now);
+        if !valid {
+            return Err(ApiError::Forbidden("Redaction override has expired".into()));
+        }
+        Ok(self.policy)
+    }
+}
+
+fn firestore() -> Result<&'static FirestoreClient, ApiError> {
+    get_firestore().ok_or_else(|| ApiError::Internal("Storage not available".into()))
+}
+
+async fn load(id: &str) -> Result<RedactionOverride, ApiError> {
+    firestore()?
+        .get_doc::<RedactionOverride>(COLLECTION, id)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to load redaction override: {}", e)))?
+        .ok_or_else(|| ApiError::NotFound(format!("Redaction override {} not found", id)))
+}
+
+async fn save(entry: &RedactionOverride) -> Result<(), ApiError> {
+    firestore()?
+        .set_doc(COLLECTION, &entry.id, entry)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to save redaction override: {}", e)))
+}
+
+async fn list_all() -> Result<Vec<RedactionOverride>, ApiError> {
+    firestore()?
+        .list_docs::<RedactionOverride>(COLLECTION)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to list redaction overrides: {}", e)))
+}
+
+/// Redaction policy for a report: the server policy, or the approved
+/// override referenced by the request
+pub async fn resolve_policy(
+    user_id: &str,
+    tenant_id: &str,
+    override_id: Option<&str>,
+) -> Result<RedactionPolicy, ApiError> {
+    let Some(id) = override_id.filter(|id| !id.is_empty()) else {
+        return Ok(RedactionPolicy::from_env());
+    };
+    let policy = load(id).await?.policy_for(user_id, tenant_id, Utc::now())?;
+    tracing::info!(tenant = %tenant_id, override_id = %id, ?policy, "Using redaction override");
+    Ok(policy)
+}
+
+/// GET /api/redaction/policy - server default policy
+pub async fn get_policy() -> Json<RedactionPolicy> {
+    Json(RedactionPolicy::from_env())
+}
+
+/// GET /api/redaction/overrides - own override requests
+pub async fn list_overrides(
+    Extension(user_id): Extension<String>,
+) -> Result<Json<Vec<RedactionOverride>>, ApiError> {
+    let overrides = list_all().await?;
+    Ok(Json(
+        overrides
+            .into_iter()
+            .filter(|o| o.requested_by == user_id)
+            .collect(),
+    ))
+}
+
+/// POST /api/redaction/overrides - request an override for one tenant
+pub async fn request_override(
+    Extension(user_id): Extension<String>,
+    Json(request): Json<OverrideRequest>,
+) -> Result<Json<RedactionOverride>, ApiError> {
+    let entry = RedactionOverride::new(&user_id, request)?;
+    save(&entry).await?;
+    tracing::info!(tenant = %entry.tenant_id, id = %entry.id, "Redaction override requested");
+    Ok(Json(entry))
+}
+
+/// GET /api/admin/redaction/overrides - requests waiting for review
+pub async fn list_pending_overrides(
+    jar: CookieJar,
+) -> Result<Json<Vec<RedactionOverride>>, ApiError> {
+    require_admin(&jar).await?;
+    let overrides = list_all().await?;
+    Ok(Json(
+        overrides
+            .into_iter()
+            .filter(|o| o.status == OverrideStatus::Pending)
+            .collect(),
+    ))
+}
+
+/// POST /api/admin/redaction/overrides/:id/review { "approve": true|false }
+pub async fn review_override(
+    jar: CookieJar,
+    Path(id): Path<String>,
+    Json(review): Json<OverrideReview>,
+) -> Result<Json<RedactionOverride>, ApiError> {
+    let admin = require_admin(&jar).await?;
+    let mut entry = load(&id).await?;
+    entry.review(&admin, review.approve, review.comment, Utc::now())?;
+    save(&entry).await?;
+    tracing::info!(id = %id, status = ?entry.status, by = %admin, "Redaction override reviewed");
+    Ok(Json(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axur_core::api::redaction::MaskMode;
+
+    #[test]
+    fn test_override_approval() {
+        let request = OverrideRequest {
+            tenant_id: "acme".into(),
+            policy: RedactionPolicy {
+                user: MaskMode::Partial,
+                password: MaskMode::Partial,
+            },
+            reason: "Customer asked to identify the leaked accounts".into(),
+        };
+        let mut entry = RedactionOverride::new("analyst@axur.com", request).unwrap();
+        let now = Utc::now();
+
+        // Not usable until an admin (other than the requester) approves it
+        assert!(entry.policy_for("analyst@axur.com", "acme", now).is_err());
+        assert!(entry.review("analyst@axur.com", true, None, now).is_err());
+        entry.review("admin@axur.com", true, None, now).unwrap();
+        assert!(entry.review("admin@axur.com", false, None, now).is_err());
+
+        let policy = entry.policy_for("analyst@axur.com", "acme", now).unwrap();
+        assert_eq!(policy.password, MaskMode::Partial);
+
+        // Bound to the requester, the tenant and the validity window
+        assert!(entry.policy_for("other@axur.com", "acme", now).is_err());
+        assert!(entry.policy_for("analyst@axur.com", "other", now).is_err());
+        let later = now + Duration::hours(OVERRIDE_VALIDITY_HOURS + 1);
+        assert!(entry.policy_for("analyst@axur.com", "acme", later).is_err());
+    }
+}
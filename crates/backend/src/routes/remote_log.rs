@@ -5,6 +5,7 @@
 //! - Uses daily sharding for Firestore collections to optimize cost/performance.
 
 use axum::http::StatusCode;
+use axur_core::api::redaction::redact_log_text;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde_json::json;
 use uuid::Uuid;
@@ -143,6 +144,8 @@ async fn upload_to_github(
 
 /// Upload a log entry (Hybrid: GitHub + Firestore)
 pub async fn upload_log(category: &str, filename: &str, content: &str) -> Result<String, String> {
+    // Leaked credentials in JSON payloads never leave the server unmasked
+    let content = &redact_log_text(content);
     let now = chrono::Utc::now();
     let message = format!("Log: {} - {}", category, filename);
 
@@ -3,7 +3,7 @@
 //! Endpoints for listing tenants and generating HTML reports.
 //! Includes structured error codes for better debugging.
 
-use axum::extract::State;
+use axum::extract::{Path, State};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::{Extension, Json};
 use axum_extra::extract::CookieJar;
@@ -13,18 +13,27 @@ use std::convert::Infallible;
 
 use crate::error::ApiError;
 use crate::middleware::AUTH_COOKIE_NAME;
-use crate::routes::AppState;
+use crate::queue::{get_queue, ApiType, JobType, QueueJob};
+use crate::routes::{drafts, th_credits, AppState};
 use crate::services::report_service::{
     classify_error, get_user_friendly_message, GenerateReportRequest, GenerateReportResponse,
-    ReportService, TenantResponse,
+    RenderSnapshotRequest, ReportService, ReportSnapshot, TenantResponse,
+    ThreatHuntingPreviewRequest,
 };
+use axur_core::api::context::ReportContext;
 use axur_core::api::report::{
-    fetch_available_tenants, fetch_full_report, fetch_tagged_tickets_for_preview,
-    preview_threat_hunting,
+    fetch_available_tenants, fetch_full_report_with, fetch_tagged_tickets_for_preview,
 };
+use axur_core::api::th_cache::ThCache;
+use axur_core::api::th_progress::{SearchProgress, SearchTimeout, ThProgress};
+use axur_core::api::th_query::{parse_sources, ThSearchConfig};
+use axur_core::api::transport::ApiTransport;
 use axur_core::error_codes;
-use axur_core::i18n::{get_dictionary, Language, Translations};
-use axur_core::report::html::{generate_full_report_html, generate_report_with_plugins};
+use axur_core::i18n::get_dictionary;
+use axur_core::report::html::{
+    enrich_report_data, generate_full_report_html, generate_report_with_plugins,
+};
+use axur_core::report::template_renderer::render_template_report;
 use axur_core::report::OfflineAssets;
 use std::time::Instant;
 
@@ -37,22 +46,6 @@ fn default_language() -> String {
 // ========================
 
 // REQUEST/RESPONSE TYPES MOVED TO services::report_service
-// Kept ThreatHunting types here for now as they are not migrated yet.
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ThreatHuntingPreviewRequest {
-    pub tenant_id: String,
-    pub story_tag: String,
-    #[serde(default)]
-    pub use_user_credits: bool,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ThreatHuntingPreviewResponse {
-    pub success: bool,
-    pub preview: Option<axur_core::api::report::ThreatHuntingPreview>,
-    pub message: String,
-}
 
 // ========================
 // ROUTE HANDLERS
@@ -97,6 +90,9 @@ pub async fn generate_report(
             message: "Tenant ID is required".into(),
             error_code: Some(code.code()),
             error_message: Some(get_user_friendly_message(&code)),
+            snapshot_id: None,
+            insights: Vec::new(),
+            timed_out_sources: Vec::new(),
         }));
     }
     if payload.from_date.is_empty() || payload.to_date.is_empty() {
@@ -108,6 +104,9 @@ pub async fn generate_report(
             message: "Date range is required".into(),
             error_code: Some(code.code()),
             error_message: Some(get_user_friendly_message(&code)),
+            snapshot_id: None,
+            insights: Vec::new(),
+            timed_out_sources: Vec::new(),
         }));
     }
 
@@ -129,8 +128,21 @@ pub async fn generate_report(
 
     let start_time = Instant::now();
 
-    // Delegate to ReportService (Safe & Modular)
-    let response = ReportService::generate_report(&payload, &token, &user_id).await?;
+    // Delegate to ReportService through the request queue (rate limited, cancellable)
+    let job = QueueJob::new(
+        user_id,
+        JobType::GenerateReport {
+            request: Box::new(payload.clone()),
+        },
+        ApiType::Axur,
+    )
+    .with_token(token);
+    let result = get_queue()
+        .run_and_wait(job)
+        .await
+        .map_err(ApiError::Internal)?;
+    let response: GenerateReportResponse = serde_json::from_value(result)
+        .map_err(|e| ApiError::Internal(format!("Invalid report job result: {}", e)))?;
 
     // 📝 Log successful response (Service handles logic, Handler handles HTTP logging)
     crate::routes::remote_log::log_response(
@@ -166,140 +178,246 @@ pub async fn generate_report(
     Ok(Json(response))
 }
 
-/// Preview Threat Hunting results without consuming full credits
-/// Returns counts and estimated credits for user confirmation
-pub async fn threat_hunting_preview(
+/// List stored report data snapshots for the current user
+pub async fn list_snapshots(
+    Extension(user_id): Extension<String>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    let storage = crate::github_storage::get_github_storage()
+        .ok_or_else(|| ApiError::Internal("Snapshot storage not configured".into()))?;
+
+    let files = storage
+        .list_report_snapshots(&user_id)
+        .await
+        .map_err(ApiError::Internal)?;
+
+    let ids = files
+        .into_iter()
+        .filter_map(|f| f.strip_suffix(".json").map(String::from))
+        .collect();
+
+    Ok(Json(ids))
+}
+
+/// Re-render a stored snapshot with a different language, theme or plugin set,
+/// including the annotations of its draft.
+/// Does not call the Axur API (no rate-limit tokens or Threat Hunting credits).
+pub async fn render_snapshot(
+    Extension(user_id): Extension<String>,
+    Path(snapshot_id): Path<String>,
+    Json(mut payload): Json<RenderSnapshotRequest>,
+) -> Result<Json<GenerateReportResponse>, ApiError> {
+    let snapshot = ReportService::load_snapshot(&user_id, &snapshot_id).await?;
+    payload.annotations = drafts::preview_annotations(&snapshot_id)
+        .await
+        .unwrap_or_default();
+
+    tracing::info!(
+        snapshot = %snapshot_id,
+        tenant = %snapshot.tenant_id,
+        language = %payload.language,
+        "Re-rendering report snapshot"
+    );
+
+    let response = ReportService::render_snapshot(&snapshot, &payload, &user_id).await?;
+    Ok(Json(response))
+}
+
+/// Generate the report and return it as a 16:9 PDF (rendered locally, no external service).
+/// Like `generate_report`, it has no draft annotations, so no approval is needed.
+pub async fn export_pdf(
     jar: CookieJar,
-    Json(payload): Json<ThreatHuntingPreviewRequest>,
-) -> Result<Json<ThreatHuntingPreviewResponse>, ApiError> {
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<GenerateReportRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    if !payload.mock && (payload.tenant_id.is_empty() || payload.from_date.is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Tenant ID and date range are required".into(),
+        ));
+    }
+
     let token = jar
         .get(AUTH_COOKIE_NAME)
         .map(|c| c.value().to_string())
         .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
 
-    tracing::info!(
-        tenant = %payload.tenant_id,
-        story_tag = %payload.story_tag,
-        "Starting Threat Hunting preview"
+    // Wait for a turn under the same limits as queued reports
+    let _slot = get_queue()
+        .acquire(&user_id, "export_pdf", ApiType::Axur)
+        .await
+        .map_err(ApiError::Internal)?;
+    let client = ApiTransport::from_env()?;
+    let ctx = ReportService::report_context(
+        &user_id,
+        &payload.tenant_id,
+        payload.redaction_override_id.as_deref(),
+        (payload.include_threat_intel && !payload.mock).then_some(&payload.th_search),
+        payload.force_refresh,
+    )
+    .await?;
+    let fetched = ReportService::fetch_report_data(&payload, &token, &client, &ctx).await;
+    th_credits::record_usage(&payload.tenant_id, &user_id, "export", ctx.credits(), false).await;
+    let data = fetched.map_err(|e| ApiError::ExternalApi(e.to_string()))?;
+
+    let options = payload.render_options();
+    let filename = export_filename(
+        &data.company_name,
+        &payload.from_date,
+        &payload.to_date,
+        "pdf",
     );
+    let pdf = ReportService::export_pdf(data, &options, &user_id).await?;
 
-    // 📝 Log request
-    crate::routes::remote_log::log_request("th_preview", &payload, Some(&payload.tenant_id));
-
-    // ⏱️ Start performance tracking
-    let start_time = std::time::Instant::now();
+    tracing::info!(tenant = %payload.tenant_id, bytes = pdf.len(), "Exported report PDF");
+    Ok(file_response(pdf, &filename, PDF_CONTENT_TYPE))
+}
 
-    // FIXED: Fetch actual tickets with the story_tag instead of using tag as domain
-    let tickets = match axur_core::api::report::fetch_tagged_tickets_for_preview(
-        &token,
-        &payload.tenant_id,
-        &payload.story_tag,
-    )
-    .await
-    {
-        Ok(t) => {
-            tracing::info!(
-                "Fetched {} tickets with tag '{}'",
-                t.len(),
-                payload.story_tag
-            );
-            t
-        }
-        Err(e) => {
-            tracing::warn!("Failed to fetch tagged tickets: {}, using empty list", e);
-            vec![]
-        }
-    };
+/// Export a stored snapshot as PDF (no Axur API calls). A snapshot
+/// with a report draft is only exported once the draft is approved.
+pub async fn export_snapshot_pdf(
+    Extension(user_id): Extension<String>,
+    Path(snapshot_id): Path<String>,
+    Json(mut payload): Json<RenderSnapshotRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    let snapshot = ReportService::load_snapshot(&user_id, &snapshot_id).await?;
+    payload.annotations = drafts::export_annotations(&snapshot_id)
+        .await?
+        .unwrap_or_default();
+    let filename = export_filename(
+        &snapshot.data.company_name,
+        &snapshot.from_date,
+        &snapshot.to_date,
+        "pdf",
+    );
+    let pdf = ReportService::export_pdf(snapshot.data, &payload, &user_id).await?;
+    Ok(file_response(pdf, &filename, PDF_CONTENT_TYPE))
+}
 
-    if tickets.is_empty() {
-        return Ok(Json(ThreatHuntingPreviewResponse {
-            success: false,
-            preview: None,
-            message: format!(
-                "No tickets found with tag '{}' in tenant {}. Please verify the tag exists.",
-                payload.story_tag, payload.tenant_id
-            ),
-        }));
+/// Generate the report and return it as a native, editable PowerPoint file
+/// (without draft annotations, like `export_pdf`)
+pub async fn export_pptx(
+    jar: CookieJar,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<GenerateReportRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    if !payload.mock && (payload.tenant_id.is_empty() || payload.from_date.is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Tenant ID and date range are required".into(),
+        ));
     }
 
-    match preview_threat_hunting(
-        &token,
+    let token = jar
+        .get(AUTH_COOKIE_NAME)
+        .map(|c| c.value().to_string())
+        .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
+
+    // Wait for a turn under the same limits as queued reports
+    let _slot = get_queue()
+        .acquire(&user_id, "export_pptx", ApiType::Axur)
+        .await
+        .map_err(ApiError::Internal)?;
+    let client = ApiTransport::from_env()?;
+    let ctx = ReportService::report_context(
+        &user_id,
         &payload.tenant_id,
-        &tickets,
-        &payload.story_tag,
-        payload.use_user_credits,
+        payload.redaction_override_id.as_deref(),
+        (payload.include_threat_intel && !payload.mock).then_some(&payload.th_search),
+        payload.force_refresh,
     )
-    .await
-    {
-        Ok(preview) => {
-            tracing::info!(
-                total = preview.total_count,
-                estimated_credits = preview.estimated_credits,
-                tickets_used = tickets.len(),
-                "Threat Hunting preview completed"
-            );
+    .await?;
+    let fetched = ReportService::fetch_report_data(&payload, &token, &client, &ctx).await;
+    th_credits::record_usage(&payload.tenant_id, &user_id, "export", ctx.credits(), false).await;
+    let data = fetched.map_err(|e| ApiError::ExternalApi(e.to_string()))?;
+
+    let options = payload.render_options();
+    let filename = export_filename(
+        &data.company_name,
+        &payload.from_date,
+        &payload.to_date,
+        "pptx",
+    );
+    let pptx = ReportService::export_pptx(data, &options, &user_id).await?;
 
-            // ⏱️ Calculate duration
-            let duration_ms = start_time.elapsed().as_millis();
-
-            // 📝 Log successful response (metadata only, not full preview data)
-            crate::routes::remote_log::log_response(
-                "th_preview",
-                &serde_json::json!({
-                    "success": true,
-                    "total_count": preview.total_count,
-                    "estimated_credits": preview.estimated_credits,
-                    "signal_lake_count": preview.signal_lake_count,
-                    "credential_count": preview.credential_count,
-                    "tickets_used": tickets.len()
-                }),
-                duration_ms,
-                Some(&payload.tenant_id),
-                true,
-            );
+    tracing::info!(tenant = %payload.tenant_id, bytes = pptx.len(), "Exported report PPTX");
+    Ok(file_response(pptx, &filename, PPTX_CONTENT_TYPE))
+}
 
-            // 📊 Log feature usage
-            crate::routes::remote_log::log_feature_usage(
-                "preview_generation",
-                Some(&payload.tenant_id),
-                true,
-                Some(serde_json::json!({
-                    "story_tag": &payload.story_tag,
-                    "tickets_count": tickets.len(),
-                    "total_results": preview.total_count,
-                    "estimated_credits": preview.estimated_credits,
-                    "duration_ms": duration_ms
-                })),
-            );
+/// Export a stored snapshot as PPTX (no Axur API calls). A snapshot
+/// with a report draft is only exported once the draft is approved.
+pub async fn export_snapshot_pptx(
+    Extension(user_id): Extension<String>,
+    Path(snapshot_id): Path<String>,
+    Json(mut payload): Json<RenderSnapshotRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    let snapshot = ReportService::load_snapshot(&user_id, &snapshot_id).await?;
+    payload.annotations = drafts::export_annotations(&snapshot_id)
+        .await?
+        .unwrap_or_default();
+    let filename = export_filename(
+        &snapshot.data.company_name,
+        &snapshot.from_date,
+        &snapshot.to_date,
+        "pptx",
+    );
+    let pptx = ReportService::export_pptx(snapshot.data, &payload, &user_id).await?;
+    Ok(file_response(pptx, &filename, PPTX_CONTENT_TYPE))
+}
 
-            Ok(Json(ThreatHuntingPreviewResponse {
-                success: true,
-                preview: Some(preview),
-                message: format!("Preview ready. Found {} tickets with tag.", tickets.len()),
-            }))
-        }
-        Err(e) => {
-            tracing::error!("Threat Hunting preview failed: {}", e);
-
-            // 📝 Log error
-            crate::routes::remote_log::log_error(
-                "th_preview",
-                "TH-ERR",
-                &e.to_string(),
-                Some(&payload.tenant_id),
-                serde_json::json!({
-                    "story_tag": &payload.story_tag,
-                    "tickets_count": tickets.len()
-                }),
-            );
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+const PPTX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation";
 
-            Ok(Json(ThreatHuntingPreviewResponse {
-                success: false,
-                preview: None,
-                message: format!("Preview failed: {}", e),
-            }))
-        }
-    }
+fn export_filename(company_name: &str, from: &str, to: &str, extension: &str) -> String {
+    let company: String = company_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}_{}_{}_report.{}", company, from, to, extension)
+}
+
+fn file_response(
+    bytes: Vec<u8>,
+    filename: &str,
+    content_type: &'static str,
+) -> axum::response::Response {
+    let mut res = axum::response::Response::new(axum::body::Body::from(bytes));
+    res.headers_mut().insert(
+        axum::http::header::CONTENT_TYPE,
+        axum::http::HeaderValue::from_static(content_type),
+    );
+    res.headers_mut().insert(
+        axum::http::header::CONTENT_DISPOSITION,
+        axum::http::HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+            .unwrap_or_else(|_| axum::http::HeaderValue::from_static("attachment")),
+    );
+    res
+}
+
+/// Preview Threat Hunting results without consuming full credits
+/// Returns counts and estimated credits for user confirmation
+/// (runs through the request queue to share the Axur rate limit)
+pub async fn threat_hunting_preview(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Json(payload): Json<ThreatHuntingPreviewRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let token = jar
+        .get(AUTH_COOKIE_NAME)
+        .map(|c| c.value().to_string())
+        .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
+
+    let job = QueueJob::new(
+        user_id,
+        JobType::ThreatHuntingPreview { request: payload },
+        ApiType::Axur,
+    )
+    .with_token(token);
+
+    let result = get_queue()
+        .run_and_wait(job)
+        .await
+        .map_err(ApiError::Internal)?;
+
+    Ok(Json(result))
 }
 
 // Helpers moved to ReportService
@@ -326,6 +444,11 @@ pub enum ThreatHuntingStreamEvent {
         source: String,
         count: u64,
     },
+    /// The search didn't finish in time (its results are not counted)
+    DomainTimedOut {
+        domain: String,
+        source: String,
+    },
     Finished {
         total_count: u64,
         signal_lake_count: u64,
@@ -356,10 +479,16 @@ pub enum ReportStreamEvent {
     },
     /// Stage completed
     StageComplete { stage: String },
+    /// Progress of a Threat Hunting search (started, polled, completed, timed out)
+    ThreatHunting(SearchProgress),
+    /// Non-fatal problem (e.g. incomplete Threat Hunting results)
+    Warning { code: String, message: String },
     /// Report generation finished with HTML
     Finished {
         html: String,
         company_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        snapshot_id: Option<String>,
     },
     /// Error occurred
     Error { code: String, message: String },
@@ -381,11 +510,98 @@ pub struct GenerateReportStreamParams {
     pub use_plugins: bool,
     pub plugin_theme: Option<String>,
     pub disabled_slides: Option<String>, // Comma-separated list
+    /// Report preset (builtin industry or user preset id)
+    pub preset_id: Option<String>,
+    /// Slide plugin IDs in display order, comma-separated
+    pub slide_order: Option<String>,
+    /// White-label: the theme logo/brand replaces Axur's
+    #[serde(default)]
+    pub custom_branding: bool,
+    /// Risk score model as JSON (EventSource cannot send a body)
+    pub risk_model: Option<String>,
+    /// Report theme as JSON
+    pub branding: Option<String>,
+    /// Per-plugin slide overrides as JSON (plugin ID -> override)
+    pub slide_overrides: Option<String>,
+    /// Admin-approved credential redaction override
+    pub redaction_override_id: Option<String>,
+    /// Run Threat Hunting searches again instead of reusing cached results
+    #[serde(default)]
+    pub force_refresh: bool,
+    /// Threat Hunting sources, comma-separated ("credit-card,tokens")
+    pub th_sources: Option<String>,
+    /// Brand aliases searched with the brands, comma-separated
+    pub th_aliases: Option<String>,
+    /// Also search executive names
+    #[serde(default)]
+    pub th_executives: bool,
+}
+
+/// Split a comma-separated query value into trimmed, non-empty items
+fn comma_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse a JSON-encoded query value
+fn json_param<T: serde::de::DeserializeOwned>(
+    name: &str,
+    value: Option<&str>,
+) -> Result<Option<T>, ApiError> {
+    value
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            serde_json::from_str(v)
+                .map_err(|e| ApiError::BadRequest(format!("Invalid {}: {}", name, e)))
+        })
+        .transpose()
+}
+
+impl GenerateReportStreamParams {
+    /// Render options from the query string, the same ones
+    /// [`GenerateReportRequest`] carries in its body
+    fn render_options(&self) -> Result<RenderSnapshotRequest, ApiError> {
+        Ok(RenderSnapshotRequest {
+            language: self.language.clone(),
+            theme: self.plugin_theme.clone(),
+            disabled_plugins: self.disabled_slides.as_deref().map(comma_list),
+            template_id: self.template_id.clone(),
+            risk_model: json_param("risk_model", self.risk_model.as_deref())?,
+            preset_id: self.preset_id.clone(),
+            branding: json_param("branding", self.branding.as_deref())?,
+            custom_branding: self.custom_branding,
+            slide_order: self.slide_order.as_deref().map(comma_list),
+            slide_overrides: json_param("slide_overrides", self.slide_overrides.as_deref())?
+                .unwrap_or_default(),
+            annotations: Default::default(),
+        })
+    }
+
+    /// Threat Hunting selection from the query string
+    fn th_search(&self) -> Result<ThSearchConfig, ApiError> {
+        let mut config = ThSearchConfig {
+            include_executives: self.th_executives,
+            ..Default::default()
+        };
+        if let Some(sources) = self.th_sources.as_deref().filter(|s| !s.is_empty()) {
+            config.sources =
+                parse_sources(sources).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+        }
+        if let Some(aliases) = &self.th_aliases {
+            config.aliases = comma_list(aliases);
+        }
+        Ok(config)
+    }
 }
 
 /// SSE endpoint for streaming Threat Hunting preview progress
 /// Uses GET with query params for EventSource compatibility
 pub async fn threat_hunting_preview_stream(
+    Extension(user_id): Extension<String>,
     jar: CookieJar,
     axum::extract::Query(params): axum::extract::Query<ThreatHuntingPreviewRequest>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
@@ -400,11 +616,25 @@ pub async fn threat_hunting_preview_stream(
         "Starting SSE Threat Hunting preview stream"
     );
 
+    let meter = th_credits::start_meter(&params.tenant_id, params.use_user_credits).await?;
+
     // Clone values for the async stream
     let tenant_id = params.tenant_id.clone();
     let story_tag = params.story_tag.clone();
 
     let stream = async_stream::stream! {
+        // Wait for a turn under the same limits as queued previews
+        let _slot = match get_queue().acquire(&user_id, "th_preview_stream", ApiType::Axur).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                let event = ThreatHuntingStreamEvent::Error { message: e };
+                if let Ok(json) = serde_json::to_string(&event) {
+                    yield Ok(Event::default().data(json));
+                }
+                return;
+            }
+        };
+
         // Fetch tickets first
         let tickets = match fetch_tagged_tickets_for_preview(&token, &tenant_id, &story_tag).await {
             Ok(t) => t,
@@ -450,7 +680,11 @@ pub async fn threat_hunting_preview_stream(
         }
 
         // Process each domain
-        let client = match axur_core::api::create_client() {
+        let ctx = ReportContext::new()
+            .with_user(user_id.as_str())
+            .with_credits(meter)
+            .with_th_cache(ThCache::from_env().with_refresh(params.force_refresh));
+        let client = match ApiTransport::from_env() {
             Ok(c) => c,
             Err(e) => {
                 let event = ThreatHuntingStreamEvent::Error {
@@ -468,9 +702,8 @@ pub async fn threat_hunting_preview_stream(
         let mut total_chatter: u64 = 0;
         let mut total_credentials: u64 = 0;
 
-        // Determine customer for credits (None = User/Admin, Some = Tenant)
+        // Searches are billed to the tenant unless the user pays with their own credits
         let use_user_credits = params.use_user_credits;
-        let customer_opt = if use_user_credits { None } else { Some(tenant_id.as_str()) };
 
         for (idx, domain) in unique_domains.iter().enumerate() {
              // Emit processing event
@@ -485,35 +718,55 @@ pub async fn threat_hunting_preview_stream(
 
             // 1. Infra Search (Signal Lake)
             let query_infra = format!("domain=\"{}\"", domain);
-            if let Ok(count) = axur_core::api::report::start_and_poll_th_search(
-                &client, &auth, customer_opt, &query_infra, "signal-lake"
+            match axur_core::api::report::start_and_poll_th_search(
+                &client, &ctx, &auth, &tenant_id, use_user_credits, &query_infra, "signal-lake"
             ).await {
-                total_signal_lake += count;
-                if count > 0 {
-                    let evt = ThreatHuntingStreamEvent::DomainComplete {
+                Ok(count) => {
+                    total_signal_lake += count;
+                    if count > 0 {
+                        let evt = ThreatHuntingStreamEvent::DomainComplete {
+                            domain: domain.clone(),
+                            source: "signal-lake".to_string(),
+                            count,
+                        };
+                        if let Ok(json) = serde_json::to_string(&evt) { yield Ok(Event::default().data(json)); }
+                    }
+                }
+                Err(e) if e.is::<SearchTimeout>() => {
+                    let evt = ThreatHuntingStreamEvent::DomainTimedOut {
                         domain: domain.clone(),
                         source: "signal-lake".to_string(),
-                        count,
                     };
                     if let Ok(json) = serde_json::to_string(&evt) { yield Ok(Event::default().data(json)); }
                 }
+                Err(_) => {}
             }
 
             // 2. Chatter Search
             let query_chatter = format!("content=\"{}\"", domain);
             for source in ["chat-message", "forum-message"] {
-                if let Ok(count) = axur_core::api::report::start_and_poll_th_search(
-                    &client, &auth, customer_opt, &query_chatter, source
+                match axur_core::api::report::start_and_poll_th_search(
+                    &client, &ctx, &auth, &tenant_id, use_user_credits, &query_chatter, source
                 ).await {
-                    total_chatter += count;
-                    if count > 0 {
-                         let evt = ThreatHuntingStreamEvent::DomainComplete {
+                    Ok(count) => {
+                        total_chatter += count;
+                        if count > 0 {
+                             let evt = ThreatHuntingStreamEvent::DomainComplete {
+                                domain: domain.clone(),
+                                source: source.to_string(),
+                                count,
+                            };
+                            if let Ok(json) = serde_json::to_string(&evt) { yield Ok(Event::default().data(json)); }
+                        }
+                    }
+                    Err(e) if e.is::<SearchTimeout>() => {
+                        let evt = ThreatHuntingStreamEvent::DomainTimedOut {
                             domain: domain.clone(),
                             source: source.to_string(),
-                            count,
                         };
                         if let Ok(json) = serde_json::to_string(&evt) { yield Ok(Event::default().data(json)); }
                     }
+                    Err(_) => {}
                 }
             }
 
@@ -528,7 +781,8 @@ pub async fn threat_hunting_preview_stream(
         // This is done once, not per-domain, since credentials are tagged with story_tag
         if !story_tag.is_empty() {
             let exposure_url = format!(
-                "https://api.axur.com/gateway/1.0/api/exposure-api/credentials?tags=contains:{}&pageSize=100",
+                "{}/exposure-api/credentials?tags=contains:{}&pageSize=100",
+                client.base_url(),
                 story_tag
             );
 
@@ -562,6 +816,8 @@ pub async fn threat_hunting_preview_stream(
             }
         }
 
+        th_credits::record_usage(&tenant_id, &user_id, "th_preview", ctx.credits(), use_user_credits).await;
+
         // Send finished event
         let total_count = total_signal_lake + total_chatter + total_credentials;
         let estimated_credits = (total_count as f64) * 0.01; // Rough estimate
@@ -585,6 +841,7 @@ pub async fn threat_hunting_preview_stream(
 /// Uses GET with query params for EventSource compatibility
 pub async fn generate_report_stream(
     State(_state): State<AppState>,
+    Extension(user_id): Extension<String>,
     jar: CookieJar,
     axum::extract::Query(params): axum::extract::Query<GenerateReportStreamParams>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
@@ -600,20 +857,35 @@ pub async fn generate_report_stream(
         "Starting SSE Report generation stream"
     );
 
+    let th_search = params.th_search()?;
+    // Same preset, theme, slides, risk model and insight rules as the
+    // non-streaming endpoint (invalid options fail before the stream starts)
+    let (language, translations, config) =
+        ReportService::render_config(&params.render_options()?, &user_id).await?;
+    // Threat Hunting progress is reported from the polling tasks and
+    // forwarded while the data is fetched
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let client = ApiTransport::from_env()?;
+    let ctx = ReportService::report_context(
+        &user_id,
+        &params.tenant_id,
+        params.redaction_override_id.as_deref(),
+        params.include_threat_intel.then_some(&th_search),
+        params.force_refresh,
+    )
+    .await?
+    .with_th_progress(ThProgress::new(move |progress| {
+        let _ = progress_tx.send(progress);
+    }));
+
     // Clone all values for the async stream
     let tenant_id = params.tenant_id.clone();
     let from_date = params.from_date.clone();
     let to_date = params.to_date.clone();
-    let language_str = params.language.clone();
     let story_tag = params.story_tag.clone();
     let include_threat_intel = params.include_threat_intel;
     let template_id = params.template_id.clone();
     let use_plugins = params.use_plugins;
-    let _plugin_theme = params.plugin_theme.clone();
-    let _disabled_slides: Option<Vec<String>> = params
-        .disabled_slides
-        .as_ref()
-        .map(|s| s.split(',').map(|x| x.trim().to_string()).collect());
     // let pool = state.pool.clone(); // Removed: No longer using SQL pool
 
     let stream = async_stream::stream! {
@@ -631,6 +903,21 @@ pub async fn generate_report_stream(
             yield Ok(Event::default().data(json));
         }
 
+        // Wait for a turn under the same limits as queued reports
+        let _slot = match get_queue().acquire(&user_id, "report_stream", ApiType::Axur).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                let err = ReportStreamEvent::Error {
+                    code: error_codes::system::resource_exhausted().code(),
+                    message: e,
+                };
+                if let Ok(json) = serde_json::to_string(&err) {
+                    yield Ok(Event::default().data(json));
+                }
+                return;
+            }
+        };
+
         // Stage 1: Validating
         let progress = ReportStreamEvent::StageProgress {
             stage: "validating".into(),
@@ -667,16 +954,35 @@ pub async fn generate_report_stream(
             yield Ok(Event::default().data(json));
         }
 
-        let report_data = match fetch_full_report(
+        let mut fetch = std::pin::pin!(fetch_full_report_with(
+            &client,
+            &ctx,
             &token,
             &tenant_id,
             &from_date,
             &to_date,
             story_tag.clone(),
             include_threat_intel,
-        )
-        .await
-        {
+        ));
+        let fetched = loop {
+            let progress = tokio::select! {
+                fetched = &mut fetch => break fetched,
+                Some(progress) = progress_rx.recv() => progress,
+            };
+            let event = ReportStreamEvent::ThreatHunting(progress);
+            if let Ok(json) = serde_json::to_string(&event) {
+                yield Ok(Event::default().data(json));
+            }
+        };
+        // Events sent right before the fetch finished
+        while let Ok(progress) = progress_rx.try_recv() {
+            let event = ReportStreamEvent::ThreatHunting(progress);
+            if let Ok(json) = serde_json::to_string(&event) {
+                yield Ok(Event::default().data(json));
+            }
+        }
+        th_credits::record_usage(&tenant_id, &user_id, "report", ctx.credits(), false).await;
+        let report_data = match fetched {
             Ok(data) => data,
             Err(e) => {
                 let error_code = classify_error(&e.to_string());
@@ -691,6 +997,33 @@ pub async fn generate_report_stream(
             }
         };
 
+        // Persist fetched data so the report can be re-rendered later
+        let snapshot_id = ReportService::store_snapshot(
+            &user_id,
+            ReportSnapshot::new(
+                &tenant_id,
+                &from_date,
+                &to_date,
+                story_tag.clone(),
+                include_threat_intel,
+                report_data.clone(),
+            ),
+        );
+
+        let timed_out = &report_data.threat_intelligence.timed_out_sources;
+        if !timed_out.is_empty() {
+            let warning = ReportStreamEvent::Warning {
+                code: error_codes::threat_intel::dark_web_timeout().code(),
+                message: format!(
+                    "Threat Hunting searches timed out (results incomplete): {}",
+                    timed_out.join(", ")
+                ),
+            };
+            if let Ok(json) = serde_json::to_string(&warning) {
+                yield Ok(Event::default().data(json));
+            }
+        }
+
         let complete = ReportStreamEvent::StageComplete { stage: "fetching_data".into() };
         if let Ok(json) = serde_json::to_string(&complete) {
             yield Ok(Event::default().data(json));
@@ -707,80 +1040,13 @@ pub async fn generate_report_stream(
         }
 
         // Get dictionary for selected language
-        let language = match language_str.to_lowercase().as_str() {
-            "en" => Language::En,
-            "pt" | "pt-br" => Language::PtBr,
-            _ => Language::Es,
-        };
         let dict = get_dictionary(language);
 
-        // Handle custom template if provided
-        let mut custom_template_slides: Option<Vec<String>> = None;
-        if let Some(ref tid) = template_id {
-            // Try mock templates first
-            if let Some(tmpl) = crate::routes::templates::get_mock_template(tid) {
-                let slides: Vec<String> = tmpl
-                    .slides
-                    .iter()
-                    .filter_map(|s| s.canvas_json.clone())
-                    .collect();
-                if !slides.is_empty() {
-                    custom_template_slides = Some(slides);
-                }
-            }
-            // Try DB templates (Firestore)
-            if custom_template_slides.is_none() {
-                if let Some(firestore) = crate::firebase::get_firestore() {
-                    // Try to finding user_id? We don't have user_id easily here in this context unless we lookup the template globally or search all users?
-                    // The original SQL looked up by template ID without user ID?
-                    // Wait, original SQL was: `SELECT content FROM user_templates WHERE id = $1`
-                    // In Firestore, our schema is `user_templates/{user_id}/items/{template_id}`.
-                    // We CANNOT efficienty get a document by ID if we don't know the parent collection path (the user_id).
-                    // This is a schema mismatch.
-
-                    // HOWEVER, `user_templates` table in Postgres likely had `id` as primary key global.
-                    // In Firestore, we sharded by user.
-                    // If we want to support "public" or "shared" templates by ID without knowing user, we need a global index or a "lookup" collection.
-
-                    // For now, let's assume `template_id` might be a "system" template or we have to search.
-                    // BUT, `marketplace.rs` has `marketplace_templates`.
-
-                    // Workaround: Since we don't have the user_id of the template owner here (we only have `params`),
-                    // we might fail to find private user templates here unless we change the API to pass owner_id.
-                    // But `list_docs` on group collection `items`? No, `items` is subcollection.
-                    // Firestore Group Query: `firestore.collectionGroup('items').where('id', '==', tid)`
-                    // My `firebase.rs` client handles simple paths. It doesn't support collectionGroup queries easily yet.
-
-                    // Quick fix: For now, we only support MOCK templates or we logging a warning that DB template loading is temporarily limited by ID only.
-                    // Or, if the User is logged in (we have `token`), we *could* try to use the *current* user's ID as the owner?
-                    // In `generate_report_stream`, we decode the session cookie to `token`. We can validate it to get `user_id`.
-
-                    // Let's get the user_id from the cookie
-                    let user_id_opt = jar
-                        .get(crate::middleware::AUTH_USER_COOKIE_NAME)
-                        .map(|c| c.value().to_string())
-                        .map(|email| crate::github_storage::GitHubStorage::hash_user_id(&email));
-
-                    if let Some(uid) = user_id_opt {
-                         // Try fetching from THIS user's templates
-                         let path = format!("user_templates/{}/items", uid);
-                         if let Ok(Some(doc)) = firestore.get_doc::<serde_json::Value>(&path, tid).await {
-                              if let Some(content) = doc.get("content").and_then(|c| c.as_array()) {
-                                  let slides: Vec<String> = content
-                                      .iter()
-                                      .filter_map(|s| s.get("canvas_json").and_then(|c| c.as_str()).map(|s| s.to_string()))
-                                      .collect();
-                                   if !slides.is_empty() {
-                                       custom_template_slides = Some(slides);
-                                   }
-                              }
-                         }
-                    } else {
-                        // User not found or invalid session, can't look up private template
-                    }
-                }
-            }
-        }
+        // Editor template (rendered server-side with the report data)
+        let template = match &template_id {
+            Some(tid) => ReportService::load_template(tid, &user_id).await,
+            None => None,
+        };
 
         let complete = ReportStreamEvent::StageComplete { stage: "processing".into() };
         if let Ok(json) = serde_json::to_string(&complete) {
@@ -801,29 +1067,19 @@ pub async fn generate_report_stream(
         let offline_assets = OfflineAssets::load_embedded();
 
         // Generate HTML
-        let html = if use_plugins {
-            let lang_code = match language {
-                Language::En => "en",
-                Language::PtBr => "pt-br",
-                Language::Es => "es",
-            };
-            let translations = match Translations::load(lang_code) {
-                Ok(t) => t,
-                Err(_) => Translations::load("en").expect("CRITICAL: Default English translations missing"),
-            };
+        let html = if let Some(template) = &template {
+            let report_data = enrich_report_data(&report_data, &config);
+            render_template_report(template, &report_data, Some(&offline_assets))
+        } else if use_plugins {
             generate_report_with_plugins(
                 &report_data,
                 &translations,
                 Some(&offline_assets),
-                None, // No custom config
+                Some(config),
             )
         } else {
-            generate_full_report_html(
-                &report_data,
-                custom_template_slides,
-                Some(&offline_assets),
-                &dict,
-            )
+            let report_data = enrich_report_data(&report_data, &config);
+            generate_full_report_html(&report_data, None, Some(&offline_assets), &dict)
         };
 
         let complete = ReportStreamEvent::StageComplete { stage: "generating_html".into() };
@@ -835,6 +1091,7 @@ pub async fn generate_report_stream(
         let finished = ReportStreamEvent::Finished {
             html,
             company_name: Some(report_data.company_name.clone()),
+            snapshot_id,
         };
         if let Ok(json) = serde_json::to_string(&finished) {
             yield Ok(Event::default().data(json));
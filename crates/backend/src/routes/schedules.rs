@@ -0,0 +1,175 @@
+//! Report schedule API routes
+//!
+//! CRUD for recurring report schedules plus manual runs and run history.
+//! Execution lives in `crate::scheduler`. Creating, updating and running a
+//! schedule require access to its tenant and refresh the owner session the
+//! scheduled runs use. Every schedule is returned with the state of that
+//! session (`credential`), which warns when the next run would fail.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use axum_extra::extract::CookieJar;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::error::ApiError;
+use crate::middleware::get_token_from_cookies;
+use crate::scheduler::{
+    credential_status, forget_owner_token, get_store, remember_owner_token, run_schedule,
+    ReportSchedule, ScheduleCredential, ScheduleRun, ScheduleSpec,
+};
+use crate::services::report_service::ReportService;
+
+/// A schedule with the state of the session its runs use
+#[derive(Debug, Serialize)]
+pub struct ScheduleResponse {
+    #[serde(flatten)]
+    pub schedule: ReportSchedule,
+    pub credential: ScheduleCredential,
+}
+
+impl ScheduleResponse {
+    async fn new(schedule: ReportSchedule) -> Self {
+        let credential = credential_status(&schedule).await;
+        Self {
+            schedule,
+            credential,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunAcceptedResponse {
+    pub success: bool,
+    pub schedule_id: String,
+    pub message: String,
+}
+
+/// Load a schedule owned by the current user
+async fn owned_schedule(user_id: &str, id: &str) -> Result<ReportSchedule, ApiError> {
+    match get_store().get(id).await {
+        Some(schedule) if schedule.user_id == user_id => Ok(schedule),
+        _ => Err(ApiError::NotFound(format!("Schedule {} not found", id))),
+    }
+}
+
+/// Check the session can access the tenant, returns the session token
+async fn authorize_tenant(jar: &CookieJar, tenant_id: &str) -> Result<String, ApiError> {
+    let token = get_token_from_cookies(jar)
+        .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
+    ReportService::ensure_tenant_access(&token, tenant_id).await?;
+    Ok(token)
+}
+
+/// List the current user's schedules
+pub async fn list_schedules(
+    Extension(user_id): Extension<String>,
+) -> Result<Json<Vec<ScheduleResponse>>, ApiError> {
+    let mut list = Vec::new();
+    for schedule in get_store().for_user(&user_id).await {
+        list.push(ScheduleResponse::new(schedule).await);
+    }
+    Ok(Json(list))
+}
+
+/// Create a schedule
+pub async fn create_schedule(
+    Extension(user_id): Extension<String>,
+    jar: CookieJar,
+    Json(spec): Json<ScheduleSpec>,
+) -> Result<(StatusCode, Json<ScheduleResponse>), ApiError> {
+    spec.validate().map_err(ApiError::BadRequest)?;
+    let token = authorize_tenant(&jar, &spec.tenant_id).await?;
+
+    let schedule = ReportSchedule::new(&user_id, spec, Utc::now());
+    get_store()
+        .save(schedule.clone())
+        .await
+        .map_err(ApiError::Internal)?;
+    remember_owner_token(&user_id, &token).await;
+
+    tracing::info!(
+        schedule = %schedule.id,
+        tenant = %schedule.spec.tenant_id,
+        next_run = %schedule.next_run,
+        "Report schedule created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ScheduleResponse::new(schedule).await),
+    ))
+}
+
+/// Get a schedule with its run history
+pub async fn get_schedule(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let schedule = owned_schedule(&user_id, &id).await?;
+    Ok(Json(ScheduleResponse::new(schedule).await))
+}
+
+/// Replace a schedule's settings (history is kept)
+pub async fn update_schedule(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+    jar: CookieJar,
+    Json(spec): Json<ScheduleSpec>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    spec.validate().map_err(ApiError::BadRequest)?;
+
+    let mut schedule = owned_schedule(&user_id, &id).await?;
+    let token = authorize_tenant(&jar, &spec.tenant_id).await?;
+    schedule.update(spec, Utc::now());
+    get_store()
+        .save(schedule.clone())
+        .await
+        .map_err(ApiError::Internal)?;
+    remember_owner_token(&user_id, &token).await;
+
+    Ok(Json(ScheduleResponse::new(schedule).await))
+}
+
+/// Delete a schedule
+pub async fn delete_schedule(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    owned_schedule(&user_id, &id).await?;
+    get_store().remove(&id).await.map_err(ApiError::Internal)?;
+    if get_store().for_user(&user_id).await.is_empty() {
+        forget_owner_token(&user_id).await;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Run a schedule now, outside its recurrence. The result shows up in its history.
+pub async fn run_schedule_now(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+    jar: CookieJar,
+) -> Result<(StatusCode, Json<RunAcceptedResponse>), ApiError> {
+    let schedule = owned_schedule(&user_id, &id).await?;
+    let token = authorize_tenant(&jar, &schedule.spec.tenant_id).await?;
+    remember_owner_token(&user_id, &token).await;
+    tokio::spawn(run_schedule(schedule, true));
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RunAcceptedResponse {
+            success: true,
+            schedule_id: id,
+            message: "Run started".to_string(),
+        }),
+    ))
+}
+
+/// Run history of a schedule, most recent first
+pub async fn list_runs(
+    Extension(user_id): Extension<String>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ScheduleRun>>, ApiError> {
+    Ok(Json(owned_schedule(&user_id, &id).await?.history))
+}
@@ -733,6 +733,13 @@ pub fn get_mock_template(id: &str) -> Option<PresentationTemplate> {
     }
 }
 
+/// GET /api/templates/chart-series
+///
+/// Data series a chart element or chart placeholder can bind to
+pub async fn list_chart_series() -> impl IntoResponse {
+    Json(axur_core::report::charts::series_definitions())
+}
+
 /// GET /api/templates/:id
 pub async fn get_template(Path(template_id): Path<String>) -> impl IntoResponse {
     // Check for mock templates first (no auth/db needed for these)
@@ -0,0 +1,358 @@
+//! Threat Hunting credit budget and ledger routes
+//!
+//! Each tenant can have a credit budget (`th_budgets/{tenant_id}`): a
+//! monthly allowance and a per-report cap, in credits (one credit per page
+//! of ten results). Reports with threat intelligence and Threat Hunting
+//! previews get a `CreditMeter` limited to what the budget leaves and are
+//! refused when nothing is left. Every search they run is then written to
+//! the tenant ledger (`th_ledger/{tenant_id}/{YYYY-MM}`) with the user, the
+//! operation and the pages consumed. Searches billed to the user's own
+//! credits (`use_user_credits`) are recorded but don't count against the
+//! tenant budget. Without a stored budget a tenant is unlimited.
+//!
+//! With a monthly allowance, the credits a report may spend are reserved
+//! up front with an atomic increment of `th_reservations/{tenant_id}_{YYYY-MM}`
+//! and given back when its meter is dropped (after the ledger is written),
+//! so concurrent reports cannot together spend more than the allowance.
+//! Spending and the ledger are only visible to users with access to the
+//! tenant.
+
+use axum::extract::{Path, Query};
+use axum::Json;
+use axum_extra::extract::CookieJar;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::firebase::{get_firestore, FirestoreClient};
+use crate::middleware::get_token_from_cookies;
+use crate::routes::admin::require_admin;
+use crate::services::report_service::ReportService;
+use axur_core::api::credits::{CreditBudget, CreditMeter};
+
+const BUDGETS: &str = "th_budgets";
+const LEDGER: &str = "th_ledger";
+const RESERVATIONS: &str = "th_reservations";
+const RESERVED_FIELD: &str = "credits";
+
+/// One search in the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    /// What ran the search ("report", "th_preview", ...)
+    pub operation: String,
+    pub source: String,
+    pub query: String,
+    pub search_id: String,
+    pub pages: u64,
+    #[serde(default)]
+    pub results: Option<u64>,
+    /// Billed to the user's own credits instead of the tenant's
+    #[serde(default)]
+    pub user_credits: bool,
+    pub at: String,
+}
+
+/// GET /api/th-credits/:tenant_id response
+#[derive(Debug, Serialize)]
+pub struct CreditSummary {
+    pub tenant_id: String,
+    pub month: String,
+    pub budget: CreditBudget,
+    /// Tenant credits spent this month
+    pub spent: u64,
+    /// Credits left this month (None = unlimited)
+    pub remaining: Option<u64>,
+    /// Tenant credits spent this month per user
+    pub by_user: BTreeMap<String, u64>,
+}
+
+/// GET /api/th-credits/:tenant_id/ledger query
+#[derive(Debug, Deserialize)]
+pub struct LedgerQuery {
+    /// Month as YYYY-MM (current month by default)
+    pub month: Option<String>,
+    pub user: Option<String>,
+}
+
+fn current_month() -> String {
+    chrono::Utc::now().format("%Y-%m").to_string()
+}
+
+fn ledger_collection(tenant_id: &str, month: &str) -> String {
+    format!("{}/{}/{}", LEDGER, tenant_id, month)
+}
+
+fn firestore() -> Result<&'static FirestoreClient, ApiError> {
+    get_firestore().ok_or_else(|| ApiError::Internal("Storage not available".into()))
+}
+
+/// Tenant credits in a set of ledger entries (user-billed searches excluded)
+fn tenant_spent(entries: &[LedgerEntry]) -> u64 {
+    entries
+        .iter()
+        .filter(|e| !e.user_credits)
+        .map(|e| e.pages)
+        .sum()
+}
+
+/// Stored budget of a tenant (unlimited when none)
+pub async fn load_budget(tenant_id: &str) -> Result<CreditBudget, ApiError> {
+    let Some(firestore) = get_firestore() else {
+        return Ok(CreditBudget::default());
+    };
+    firestore
+        .get_doc::<CreditBudget>(BUDGETS, tenant_id)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ApiError::Internal(format!("Failed to load credit budget: {}", e)))
+}
+
+async fn load_ledger(tenant_id: &str, month: &str) -> Result<Vec<LedgerEntry>, ApiError> {
+    let Some(firestore) = get_firestore() else {
+        return Ok(Vec::new());
+    };
+    firestore
+        .list_docs::<LedgerEntry>(&ledger_collection(tenant_id, month))
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to load credit ledger: {}", e)))
+}
+
+fn budget_exhausted(tenant_id: &str) -> ApiError {
+    ApiError::Forbidden(format!(
+        "Threat Hunting credit budget exhausted for tenant {}",
+        tenant_id
+    ))
+}
+
+/// Credits granted to a report that asked for `requested` and pushed the
+/// tenant's reservations to `total_reserved`: what the budget leaves after
+/// the month's spending and the other reports' reservations
+fn granted_credits(budget: &CreditBudget, spent: u64, total_reserved: i64, requested: u64) -> u64 {
+    let reserved_by_others = (total_reserved - requested as i64).max(0) as u64;
+    budget
+        .report_limit(spent + reserved_by_others)
+        .map_or(requested, |limit| limit.min(requested))
+}
+
+/// Add `delta` credits to the tenant's reservations this month
+async fn reserve(
+    firestore: &FirestoreClient,
+    reservation: &str,
+    delta: i64,
+) -> Result<i64, ApiError> {
+    firestore
+        .increment_field(RESERVATIONS, reservation, RESERVED_FIELD, delta)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to reserve credits: {}", e)))
+}
+
+/// Credit meter for the Threat Hunting searches of one report or preview,
+/// limited by the tenant budget. Fails when the budget has nothing left.
+pub async fn start_meter(tenant_id: &str, user_credits: bool) -> Result<CreditMeter, ApiError> {
+    if user_credits {
+        return Ok(CreditMeter::default());
+    }
+    let budget = load_budget(tenant_id).await?;
+    if budget == CreditBudget::default() {
+        return Ok(CreditMeter::default());
+    }
+    let month = current_month();
+    let spent = tenant_spent(&load_ledger(tenant_id, &month).await?);
+    let requested = budget.report_limit(spent).unwrap_or_default();
+    if requested == 0 {
+        return Err(budget_exhausted(tenant_id));
+    }
+    // A per-report cap alone is not shared between reports
+    let (Some(_), Some(firestore)) = (budget.monthly_allowance, get_firestore()) else {
+        return Ok(CreditMeter::with_limit(Some(requested)));
+    };
+
+    let reservation = format!("{}_{}", tenant_id, month);
+    let total = reserve(firestore, &reservation, requested as i64).await?;
+    let granted = granted_credits(&budget, spent, total, requested);
+    if granted < requested {
+        reserve(firestore, &reservation, -((requested - granted) as i64)).await?;
+    }
+    if granted == 0 {
+        return Err(budget_exhausted(tenant_id));
+    }
+
+    Ok(CreditMeter::with_limit(Some(granted)).on_release(move || {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        runtime.spawn(async move {
+            if let Err(e) = reserve(firestore, &reservation, -(granted as i64)).await {
+                tracing::warn!("Failed to release credits of {}: {}", reservation, e);
+            }
+        });
+    }))
+}
+
+/// Fail unless the session can access the tenant
+async fn authorize_tenant(jar: &CookieJar, tenant_id: &str) -> Result<(), ApiError> {
+    let token = get_token_from_cookies(jar)
+        .ok_or_else(|| ApiError::Unauthorized("No session found".into()))?;
+    ReportService::ensure_tenant_access(&token, tenant_id).await
+}
+
+/// Write the searches recorded by a meter to the tenant ledger
+pub async fn record_usage(
+    tenant_id: &str,
+    user_id: &str,
+    operation: &str,
+    meter: &CreditMeter,
+    user_credits: bool,
+) {
+    let usage = meter.usage();
+    if usage.is_empty() {
+        return;
+    }
+    let Some(firestore) = get_firestore() else {
+        tracing::warn!(tenant = %tenant_id, "Storage not available, credit usage not recorded");
+        return;
+    };
+    let collection = ledger_collection(tenant_id, &current_month());
+    let at = chrono::Utc::now().to_rfc3339();
+    for search in usage {
+        let entry = LedgerEntry {
+            id: Uuid::new_v4().to_string(),
+            tenant_id: tenant_id.to_string(),
+            user_id: user_id.to_string(),
+            operation: operation.to_string(),
+            source: search.source,
+            query: search.query,
+            search_id: search.search_id,
+            pages: search.pages,
+            results: search.results,
+            user_credits,
+            at: at.clone(),
+        };
+        if let Err(e) = firestore.set_doc(&collection, &entry.id, &entry).await {
+            tracing::warn!("Failed to record credit usage for {}: {}", tenant_id, e);
+        }
+    }
+    tracing::info!(
+        tenant = %tenant_id,
+        operation = %operation,
+        credits = meter.spent(),
+        "Threat Hunting credits recorded"
+    );
+}
+
+/// GET /api/th-credits/:tenant_id - budget and spending this month
+pub async fn get_summary(
+    jar: CookieJar,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<CreditSummary>, ApiError> {
+    authorize_tenant(&jar, &tenant_id).await?;
+    let month = current_month();
+    let budget = load_budget(&tenant_id).await?;
+    let entries = load_ledger(&tenant_id, &month).await?;
+    let spent = tenant_spent(&entries);
+
+    let mut by_user = BTreeMap::new();
+    for entry in entries.iter().filter(|e| !e.user_credits) {
+        *by_user.entry(entry.user_id.clone()).or_insert(0) += entry.pages;
+    }
+
+    Ok(Json(CreditSummary {
+        remaining: budget
+            .monthly_allowance
+            .map(|allowance| allowance.saturating_sub(spent)),
+        tenant_id,
+        month,
+        budget,
+        spent,
+        by_user,
+    }))
+}
+
+/// GET /api/th-credits/:tenant_id/ledger?month=YYYY-MM&user= - searches, newest first
+pub async fn get_ledger(
+    jar: CookieJar,
+    Path(tenant_id): Path<String>,
+    Query(query): Query<LedgerQuery>,
+) -> Result<Json<Vec<LedgerEntry>>, ApiError> {
+    authorize_tenant(&jar, &tenant_id).await?;
+    let month = query.month.unwrap_or_else(current_month);
+    if chrono::NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").is_err() {
+        return Err(ApiError::BadRequest("Month must be YYYY-MM".into()));
+    }
+    let mut entries = load_ledger(&tenant_id, &month).await?;
+    if let Some(user) = &query.user {
+        entries.retain(|e| &e.user_id == user);
+    }
+    entries.sort_by(|a, b| b.at.cmp(&a.at));
+    Ok(Json(entries))
+}
+
+/// PUT /api/admin/th-credits/:tenant_id/budget - set the tenant budget (admin)
+pub async fn set_budget(
+    jar: CookieJar,
+    Path(tenant_id): Path<String>,
+    Json(budget): Json<CreditBudget>,
+) -> Result<Json<CreditBudget>, ApiError> {
+    let admin = require_admin(&jar).await?;
+    firestore()?
+        .set_doc(BUDGETS, &tenant_id, &budget)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to save credit budget: {}", e)))?;
+    tracing::info!(tenant = %tenant_id, by = %admin, ?budget, "Threat Hunting credit budget set");
+    Ok(Json(budget))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user: &str, pages: u64, user_credits: bool) -> LedgerEntry {
+        LedgerEntry {
+            id: Uuid::new_v4().to_string(),
+            tenant_id: "acme".into(),
+            user_id: user.into(),
+            operation: "report".into(),
+            source: "signal-lake".into(),
+            query: "domain=\"acme.com\"".into(),
+            search_id: "s".into(),
+            pages,
+            results: Some(pages * 10),
+            user_credits,
+            at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_tenant_spent() {
+        let entries = vec![
+            entry("a@axur.com", 3, false),
+            entry("b@axur.com", 2, false),
+            entry("a@axur.com", 5, true),
+        ];
+        assert_eq!(tenant_spent(&entries), 5);
+        assert_eq!(
+            ledger_collection("acme", "2026-10"),
+            "th_ledger/acme/2026-10"
+        );
+    }
+
+    #[test]
+    fn test_granted_credits() {
+        let budget = CreditBudget {
+            monthly_allowance: Some(100),
+            per_report_cap: Some(30),
+        };
+        // Alone: the full request
+        assert_eq!(granted_credits(&budget, 40, 30, 30), 30);
+        // Others hold 50 of the 60 left: only 10
+        assert_eq!(granted_credits(&budget, 40, 80, 30), 10);
+        // Nothing left once others reserved the rest
+        assert_eq!(granted_credits(&budget, 40, 90, 30), 0);
+        // Stale negative counters never grant more than requested
+        assert_eq!(granted_credits(&budget, 0, -50, 30), 30);
+    }
+}
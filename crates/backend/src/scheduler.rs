@@ -0,0 +1,1223 @@
+//! Scheduled Recurring Reports
+//!
+//! Schedule definitions (tenant, relative date window, language, plugin
+//! settings, template) are persisted in GitHub storage and cached in
+//! memory. A background loop checks due schedules every minute, generates
+//! the report through the request queue (`ReportService::generate_report`)
+//! and delivers it to storage or a webhook. Each run is recorded in the
+//! schedule's history.
+//!
+//! Runs act for the schedule owner: they use the owner's Axur session
+//! (remembered when the owner signs in, saves or runs a schedule) and check
+//! that it still has access to the tenant. Axur sessions cannot be refreshed,
+//! so runs stop once the session is older than `OWNER_SESSION_TTL`; with
+//! `SCHEDULE_CREDENTIALS_KEY` set the session is stored AES-256-GCM encrypted
+//! and survives restarts. The API reports the session state with each
+//! schedule so a run that would fail is flagged ahead of time. Webhooks must
+//! be https and may not point at private or loopback addresses.
+
+use crate::queue::{get_queue, ApiType, JobType, QueueJob};
+use crate::services::report_service::{
+    GenerateReportRequest, GenerateReportResponse, ReportService,
+};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use axur_core::api::risk::RiskModel;
+use axur_core::api::th_query::ThSearchConfig;
+use axur_core::plugins::ReportTheme;
+use base64::Engine;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Runs kept in each schedule's history
+pub const MAX_RUN_HISTORY: usize = 20;
+
+/// How often the scheduler looks for due schedules
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// When the schedule fires (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily {
+        hour: u32,
+    },
+    /// `weekday`: 0 = Monday ... 6 = Sunday
+    Weekly {
+        weekday: u32,
+        hour: u32,
+    },
+    /// `day` is clamped to 28 so every month has a run
+    Monthly {
+        day: u32,
+        hour: u32,
+    },
+}
+
+impl Recurrence {
+    pub fn validate(&self) -> Result<(), String> {
+        let (hour, extra) = match self {
+            Recurrence::Daily { hour } => (*hour, Ok(())),
+            Recurrence::Weekly { weekday, hour } => (
+                *hour,
+                if *weekday <= 6 {
+                    Ok(())
+                } else {
+                    Err("weekday must be 0 (Monday) to 6 (Sunday)".to_string())
+                },
+            ),
+            Recurrence::Monthly { day, hour } => (
+                *hour,
+                if (1..=28).contains(day) {
+                    Ok(())
+                } else {
+                    Err("day must be between 1 and 28".to_string())
+                },
+            ),
+        };
+        if hour > 23 {
+            return Err("hour must be between 0 and 23".to_string());
+        }
+        extra
+    }
+
+    /// First fire time strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        let at = |date: NaiveDate, hour: u32| {
+            Utc.from_utc_datetime(&date.and_hms_opt(hour, 0, 0).unwrap_or_default())
+        };
+        let today = after.date_naive();
+
+        match *self {
+            Recurrence::Daily { hour } => {
+                let candidate = at(today, hour);
+                if candidate > after {
+                    candidate
+                } else {
+                    at(today + ChronoDuration::days(1), hour)
+                }
+            }
+            Recurrence::Weekly { weekday, hour } => {
+                let current = today.weekday().num_days_from_monday() as i64;
+                let offset = (weekday as i64 - current).rem_euclid(7);
+                let candidate = at(today + ChronoDuration::days(offset), hour);
+                if candidate > after {
+                    candidate
+                } else {
+                    candidate + ChronoDuration::days(7)
+                }
+            }
+            Recurrence::Monthly { day, hour } => {
+                let day = day.clamp(1, 28);
+                let this_month =
+                    NaiveDate::from_ymd_opt(today.year(), today.month(), day).map(|d| at(d, hour));
+                match this_month {
+                    Some(candidate) if candidate > after => candidate,
+                    _ => {
+                        let (year, month) = next_month(today.year(), today.month());
+                        NaiveDate::from_ymd_opt(year, month, day)
+                            .map(|d| at(d, hour))
+                            .unwrap_or(after + ChronoDuration::days(30))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Report period relative to the run date
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DateWindow {
+    /// Full previous calendar month
+    PreviousMonth,
+    /// Previous Monday-Sunday week
+    PreviousWeek,
+    /// From the 1st of the current month until yesterday
+    MonthToDate,
+    /// The last N days, ending yesterday
+    LastDays { days: u32 },
+}
+
+impl DateWindow {
+    /// Resolve to (from, to) dates for a run on `today`
+    pub fn resolve(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let yesterday = today - ChronoDuration::days(1);
+        match self {
+            DateWindow::PreviousMonth => {
+                let first_this_month = today.with_day(1).unwrap_or(today);
+                let last_prev = first_this_month - ChronoDuration::days(1);
+                (last_prev.with_day(1).unwrap_or(last_prev), last_prev)
+            }
+            DateWindow::PreviousWeek => {
+                let this_monday =
+                    today - ChronoDuration::days(today.weekday().num_days_from_monday() as i64);
+                let prev_monday = this_monday - ChronoDuration::days(7);
+                (prev_monday, this_monday - ChronoDuration::days(1))
+            }
+            DateWindow::MonthToDate => {
+                // On the 1st there is no elapsed day yet: report the day before
+                let from = yesterday.with_day(1).unwrap_or(yesterday);
+                (from, yesterday)
+            }
+            DateWindow::LastDays { days } => {
+                let days = (*days).max(1) as i64;
+                (yesterday - ChronoDuration::days(days - 1), yesterday)
+            }
+        }
+    }
+}
+
+fn next_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
+
+/// Where a generated report goes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Delivery {
+    /// Save the HTML in the owner's storage (`reports/<schedule>/<run>.html`)
+    Storage,
+    /// POST a JSON payload with the HTML to an https URL on a public host
+    Webhook { url: String },
+}
+
+impl Delivery {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Delivery::Storage => Ok(()),
+            Delivery::Webhook { url } => webhook_url(url).map(|_| ()),
+        }
+    }
+}
+
+/// Parse a webhook URL: https only, and no host that names a private,
+/// loopback or link-local address (resolved names are checked on delivery)
+fn webhook_url(url: &str) -> Result<reqwest::Url, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "https" {
+        return Err(format!(
+            "Unsupported webhook scheme: {} (https required)",
+            parsed.scheme()
+        ));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Webhook URL has no host".to_string())?;
+    let internal = match host
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => is_internal_ip(ip),
+        Err(_) => {
+            let domain = host.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost"
+                || [".localhost", ".local", ".internal"]
+                    .iter()
+                    .any(|suffix| domain.ends_with(suffix))
+        }
+    };
+    if internal {
+        return Err("Webhook host must be a public address".to_string());
+    }
+    Ok(parsed)
+}
+
+/// Loopback, private, link-local, carrier-grade NAT, unspecified, broadcast
+/// and documentation addresses (IPv4-mapped IPv6 included)
+fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..=127).contains(&b))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_internal_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+/// Schedule fields provided by the user (create and update)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleSpec {
+    pub name: String,
+    pub tenant_id: String,
+    pub recurrence: Recurrence,
+    pub window: DateWindow,
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// Builtin plugin theme ("dark", "light", "auto")
+    #[serde(default)]
+    pub theme: Option<String>,
+    #[serde(default)]
+    pub disabled_plugins: Option<Vec<String>>,
+    #[serde(default)]
+    pub template_id: Option<String>,
+    /// Risk score model for this tenant (default model when absent)
+    #[serde(default)]
+    pub risk_model: Option<RiskModel>,
+    /// Report preset applied to each run
+    #[serde(default)]
+    pub preset_id: Option<String>,
+    /// Report theme (colors, fonts, logo, cover, footer)
+    #[serde(default)]
+    pub branding: Option<ReportTheme>,
+    /// White-label the report with the theme/partner brand
+    #[serde(default)]
+    pub custom_branding: bool,
+    #[serde(default = "default_true")]
+    pub use_plugins: bool,
+    #[serde(default)]
+    pub include_threat_intel: bool,
+    /// Threat Hunting sources and search terms
+    #[serde(default)]
+    pub th_search: ThSearchConfig,
+    pub delivery: Delivery,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_language() -> String {
+    "es".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ScheduleSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Schedule name is required".to_string());
+        }
+        if self.tenant_id.trim().is_empty() {
+            return Err("Tenant ID is required".to_string());
+        }
+        if let DateWindow::LastDays { days } = self.window {
+            if !(1..=366).contains(&days) {
+                return Err("days must be between 1 and 366".to_string());
+            }
+        }
+        self.recurrence.validate()?;
+        self.delivery.validate()
+    }
+}
+
+/// Outcome of one scheduled run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Success,
+    Failed,
+}
+
+/// One execution of a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRun {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub from_date: String,
+    pub to_date: String,
+    pub status: RunStatus,
+    /// Manual "run now" instead of the recurrence
+    #[serde(default)]
+    pub manual: bool,
+    pub error: Option<String>,
+    /// Storage path or webhook URL the report was delivered to
+    pub delivered_to: Option<String>,
+    pub snapshot_id: Option<String>,
+}
+
+/// A persisted schedule with its run history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSchedule {
+    pub id: String,
+    pub user_id: String,
+    #[serde(flatten)]
+    pub spec: ScheduleSpec,
+    pub next_run: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Most recent runs first
+    #[serde(default)]
+    pub history: Vec<ScheduleRun>,
+}
+
+impl ReportSchedule {
+    pub fn new(user_id: &str, spec: ScheduleSpec, now: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            next_run: spec.recurrence.next_after(now),
+            spec,
+            created_at: now,
+            updated_at: now,
+            history: Vec::new(),
+        }
+    }
+
+    /// Replace the user fields and recompute the next run
+    pub fn update(&mut self, spec: ScheduleSpec, now: DateTime<Utc>) {
+        self.next_run = spec.recurrence.next_after(now);
+        self.spec = spec;
+        self.updated_at = now;
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.spec.enabled && self.next_run <= now
+    }
+
+    /// Build the report request for a run on `today`
+    pub fn report_request(&self, today: NaiveDate) -> GenerateReportRequest {
+        let (from, to) = self.spec.window.resolve(today);
+        GenerateReportRequest {
+            tenant_id: self.spec.tenant_id.clone(),
+            from_date: from.format("%Y-%m-%d").to_string(),
+            to_date: to.format("%Y-%m-%d").to_string(),
+            language: self.spec.language.clone(),
+            story_tag: None,
+            include_threat_intel: self.spec.include_threat_intel,
+            th_search: self.spec.th_search.clone(),
+            template_id: self.spec.template_id.clone(),
+            use_plugins: self.spec.use_plugins,
+            theme: self.spec.theme.clone(),
+            disabled_plugins: self.spec.disabled_plugins.clone(),
+            mock: false,
+            comparison: None,
+            risk_model: self.spec.risk_model.clone(),
+            preset_id: self.spec.preset_id.clone(),
+            branding: self.spec.branding.clone(),
+            custom_branding: self.spec.custom_branding,
+            slide_order: None,
+            slide_overrides: HashMap::new(),
+            redaction_override_id: None,
+            force_refresh: false,
+        }
+    }
+
+    /// Prepend a run, keeping the newest `MAX_RUN_HISTORY`
+    pub fn record_run(&mut self, run: ScheduleRun) {
+        self.history.insert(0, run);
+        self.history.truncate(MAX_RUN_HISTORY);
+    }
+
+    pub fn last_run(&self) -> Option<&ScheduleRun> {
+        self.history.first()
+    }
+}
+
+// =========== Store ===========
+
+/// In-memory schedule cache, written through to GitHub storage
+pub struct ScheduleStore {
+    schedules: RwLock<HashMap<String, ReportSchedule>>,
+    loaded: AtomicBool,
+}
+
+impl ScheduleStore {
+    pub fn new() -> Self {
+        Self {
+            schedules: RwLock::new(HashMap::new()),
+            loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Load all persisted schedules (once)
+    pub async fn load(&self) {
+        if self.loaded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Some(storage) = crate::github_storage::get_github_storage() else {
+            tracing::warn!("[Scheduler] Storage not configured, schedules will not persist");
+            return;
+        };
+
+        let ids = match storage.list_schedules().await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!("[Scheduler] Could not list schedules: {}", e);
+                return;
+            }
+        };
+
+        let mut schedules = self.schedules.write().await;
+        for file in ids {
+            let Some(id) = file.strip_suffix(".json") else {
+                continue;
+            };
+            match storage.load_schedule(id).await {
+                Ok(json) => match serde_json::from_str::<ReportSchedule>(&json) {
+                    Ok(schedule) => {
+                        schedules.insert(schedule.id.clone(), schedule);
+                    }
+                    Err(e) => tracing::warn!("[Scheduler] Corrupted schedule {}: {}", id, e),
+                },
+                Err(e) => tracing::warn!("[Scheduler] Failed to load schedule {}: {}", id, e),
+            }
+        }
+        tracing::info!("[Scheduler] Loaded {} schedules", schedules.len());
+    }
+
+    /// Insert or replace a schedule and persist it
+    pub async fn save(&self, schedule: ReportSchedule) -> Result<(), String> {
+        if let Some(storage) = crate::github_storage::get_github_storage() {
+            let json = serde_json::to_string(&schedule)
+                .map_err(|e| format!("Failed to serialize schedule: {}", e))?;
+            storage.save_schedule(&schedule.id, &json).await?;
+        }
+        self.schedules
+            .write()
+            .await
+            .insert(schedule.id.clone(), schedule);
+        Ok(())
+    }
+
+    pub async fn get(&self, id: &str) -> Option<ReportSchedule> {
+        self.schedules.read().await.get(id).cloned()
+    }
+
+    /// Schedules owned by a user, newest first
+    pub async fn for_user(&self, user_id: &str) -> Vec<ReportSchedule> {
+        let mut list: Vec<_> = self
+            .schedules
+            .read()
+            .await
+            .values()
+            .filter(|s| s.user_id == user_id)
+            .cloned()
+            .collect();
+        list.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        list
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<(), String> {
+        if let Some(storage) = crate::github_storage::get_github_storage() {
+            storage.delete_schedule(id).await?;
+        }
+        self.schedules.write().await.remove(id);
+        Ok(())
+    }
+
+    /// Schedules due at `now`
+    pub async fn due(&self, now: DateTime<Utc>) -> Vec<ReportSchedule> {
+        self.schedules
+            .read()
+            .await
+            .values()
+            .filter(|s| s.is_due(now))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for ScheduleStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static STORE: OnceLock<ScheduleStore> = OnceLock::new();
+
+/// Get the global schedule store
+pub fn get_store() -> &'static ScheduleStore {
+    STORE.get_or_init(ScheduleStore::new)
+}
+
+// =========== Owner Credentials ===========
+
+/// How long an Axur session is honoured after it was stored (the session
+/// cookie lifetime). Axur issues no refresh token, so the owner has to sign
+/// in again before this runs out.
+pub const OWNER_SESSION_TTL: ChronoDuration = ChronoDuration::days(7);
+
+/// Base64 AES-256 key the owner sessions are encrypted with in storage
+const CREDENTIALS_KEY_ENV: &str = "SCHEDULE_CREDENTIALS_KEY";
+
+#[derive(Debug, Clone)]
+struct OwnerSession {
+    token: String,
+    saved_at: DateTime<Utc>,
+    /// Written to storage
+    stored: bool,
+}
+
+impl OwnerSession {
+    fn expires_at(&self) -> DateTime<Utc> {
+        self.saved_at + OWNER_SESSION_TTL
+    }
+}
+
+/// Persisted form of an owner session
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredOwnerSession {
+    /// Base64 of nonce + AES-256-GCM ciphertext, bound to the user ID
+    sealed_token: String,
+    saved_at: DateTime<Utc>,
+}
+
+/// Axur sessions of schedule owners by user ID, written through to storage
+/// (encrypted) when `SCHEDULE_CREDENTIALS_KEY` is set
+static OWNER_SESSIONS: OnceLock<RwLock<HashMap<String, OwnerSession>>> = OnceLock::new();
+
+fn owner_sessions() -> &'static RwLock<HashMap<String, OwnerSession>> {
+    OWNER_SESSIONS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn credentials_key() -> Option<&'static [u8; 32]> {
+    static KEY: OnceLock<Option<[u8; 32]>> = OnceLock::new();
+    KEY.get_or_init(|| {
+        let encoded = std::env::var(CREDENTIALS_KEY_ENV).ok()?;
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+        if key.is_none() {
+            tracing::warn!(
+                "[Scheduler] {} must be 32 bytes of base64, owner sessions stay in memory",
+                CREDENTIALS_KEY_ENV
+            );
+        }
+        key
+    })
+    .as_ref()
+}
+
+/// Encrypt a session token for `user_id`
+fn seal_token(key: &[u8; 32], user_id: &str, token: &str) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(key.into());
+    let nonce: [u8; 12] = rand::random();
+    let mut sealed = nonce.to_vec();
+    sealed.extend(
+        cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: token.as_bytes(),
+                    aad: user_id.as_bytes(),
+                },
+            )
+            .map_err(|_| "Failed to encrypt owner session".to_string())?,
+    );
+    Ok(base64::engine::general_purpose::STANDARD.encode(sealed))
+}
+
+/// Decrypt a token sealed by `seal_token` for the same user
+fn open_token(key: &[u8; 32], user_id: &str, sealed: &str) -> Result<String, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(sealed)
+        .map_err(|e| format!("Invalid owner session: {}", e))?;
+    if bytes.len() < 12 {
+        return Err("Invalid owner session".to_string());
+    }
+    let (nonce, ciphertext) = bytes.split_at(12);
+    let plain = Aes256Gcm::new(key.into())
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: user_id.as_bytes(),
+            },
+        )
+        .map_err(|_| "Owner session could not be decrypted".to_string())?;
+    String::from_utf8(plain).map_err(|_| "Invalid owner session".to_string())
+}
+
+/// Remember the current session of a user for their scheduled runs. It is
+/// stored encrypted once the user has schedules.
+pub async fn remember_owner_token(user_id: &str, token: &str) {
+    let has_schedules = !get_store().for_user(user_id).await.is_empty();
+    let session = {
+        let mut sessions = owner_sessions().write().await;
+        let session = match sessions.get(user_id) {
+            // Same session: keep when it was issued, store it only once
+            Some(known) if known.token == token => {
+                if known.stored || !has_schedules {
+                    return;
+                }
+                known.clone()
+            }
+            _ => OwnerSession {
+                token: token.to_string(),
+                saved_at: Utc::now(),
+                stored: false,
+            },
+        };
+        sessions.insert(user_id.to_string(), session.clone());
+        session
+    };
+    if !has_schedules {
+        return;
+    }
+
+    let (Some(key), Some(storage)) = (
+        credentials_key(),
+        crate::github_storage::get_github_storage(),
+    ) else {
+        return;
+    };
+    let stored = match seal_token(key, user_id, token) {
+        Ok(sealed_token) => StoredOwnerSession {
+            sealed_token,
+            saved_at: session.saved_at,
+        },
+        Err(e) => {
+            tracing::warn!("[Scheduler] {}", e);
+            return;
+        }
+    };
+    let json = serde_json::to_string(&stored).unwrap_or_default();
+    match storage.save_schedule_credential(user_id, &json).await {
+        Ok(()) => {
+            if let Some(known) = owner_sessions().write().await.get_mut(user_id) {
+                if known.token == token {
+                    known.stored = true;
+                }
+            }
+        }
+        Err(e) => tracing::warn!("[Scheduler] Failed to store owner session: {}", e),
+    }
+}
+
+/// Drop a user's stored session once they have no schedules left
+pub async fn forget_owner_token(user_id: &str) {
+    owner_sessions().write().await.remove(user_id);
+    if credentials_key().is_none() {
+        return;
+    }
+    if let Some(storage) = crate::github_storage::get_github_storage() {
+        if let Err(e) = storage.delete_schedule_credential(user_id).await {
+            tracing::warn!("[Scheduler] Failed to delete owner session: {}", e);
+        }
+    }
+}
+
+/// The owner's session from memory, or from storage after a restart
+async fn owner_session(user_id: &str) -> Option<OwnerSession> {
+    if let Some(session) = owner_sessions().read().await.get(user_id) {
+        return Some(session.clone());
+    }
+
+    let key = credentials_key()?;
+    let storage = crate::github_storage::get_github_storage()?;
+    let json = storage.load_schedule_credential(user_id).await.ok()?;
+    let stored: StoredOwnerSession = serde_json::from_str(&json).ok()?;
+    let session = match open_token(key, user_id, &stored.sealed_token) {
+        Ok(token) => OwnerSession {
+            token,
+            saved_at: stored.saved_at,
+            stored: true,
+        },
+        Err(e) => {
+            tracing::warn!("[Scheduler] {}", e);
+            return None;
+        }
+    };
+    owner_sessions()
+        .write()
+        .await
+        .insert(user_id.to_string(), session.clone());
+    Some(session)
+}
+
+/// Whether the owner session can still run a schedule
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialState {
+    /// Valid past the next run
+    Active,
+    /// Runs out before the next run; the owner has to sign in again first
+    ExpiresBeforeNextRun,
+    /// Expired, or lost with a restart because it is not stored
+    Missing,
+}
+
+/// Owner session status shown with each schedule, so a run that is going
+/// to fail is visible before it shows up in the history
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleCredential {
+    pub state: CredentialState,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Stored encrypted and kept across restarts
+    pub persisted: bool,
+    pub warning: Option<String>,
+}
+
+impl ScheduleCredential {
+    fn evaluate(
+        expires_at: Option<DateTime<Utc>>,
+        persisted: bool,
+        next_run: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let state = match expires_at {
+            Some(at) if at > next_run => CredentialState::Active,
+            Some(at) if at > now => CredentialState::ExpiresBeforeNextRun,
+            _ => CredentialState::Missing,
+        };
+        let warning = match state {
+            CredentialState::Active if !persisted => Some(
+                "Your session is only kept in memory; a server restart stops scheduled runs until you sign in again"
+                    .to_string(),
+            ),
+            CredentialState::Active => None,
+            CredentialState::ExpiresBeforeNextRun => Some(
+                "Your session expires before the next run; sign in again to keep this schedule running"
+                    .to_string(),
+            ),
+            CredentialState::Missing => Some(
+                "No valid session for scheduled runs; sign in again or the next run fails"
+                    .to_string(),
+            ),
+        };
+        Self {
+            state,
+            expires_at,
+            persisted,
+            warning,
+        }
+    }
+}
+
+/// Owner session status for a schedule's next run
+pub async fn credential_status(schedule: &ReportSchedule) -> ScheduleCredential {
+    let expires_at = owner_session(&schedule.user_id)
+        .await
+        .map(|session| session.expires_at());
+    let persisted =
+        credentials_key().is_some() && crate::github_storage::get_github_storage().is_some();
+    ScheduleCredential::evaluate(expires_at, persisted, schedule.next_run, Utc::now())
+}
+
+// =========== Execution ===========
+
+/// Generate and deliver one run of a schedule, record it and advance `next_run`
+pub async fn run_schedule(mut schedule: ReportSchedule, manual: bool) -> ScheduleRun {
+    let started_at = Utc::now();
+    let request = schedule.report_request(started_at.date_naive());
+    let run_id = Uuid::new_v4().to_string();
+
+    tracing::info!(
+        schedule = %schedule.id,
+        tenant = %request.tenant_id,
+        from = %request.from_date,
+        to = %request.to_date,
+        "[Scheduler] Running schedule"
+    );
+
+    let outcome = execute(&schedule, &request, &run_id).await;
+    let (status, error, delivered_to, snapshot_id) = match outcome {
+        Ok((delivered, snapshot)) => (RunStatus::Success, None, Some(delivered), snapshot),
+        Err(e) => {
+            tracing::error!(schedule = %schedule.id, "[Scheduler] Run failed: {}", e);
+            (RunStatus::Failed, Some(e), None, None)
+        }
+    };
+
+    let run = ScheduleRun {
+        id: run_id,
+        started_at,
+        finished_at: Utc::now(),
+        from_date: request.from_date,
+        to_date: request.to_date,
+        status,
+        manual,
+        error,
+        delivered_to,
+        snapshot_id,
+    };
+
+    schedule.record_run(run.clone());
+    if !manual {
+        schedule.next_run = schedule.spec.recurrence.next_after(Utc::now());
+    }
+
+    // Keep edits made while the run was in flight; only history and next_run change
+    let store = get_store();
+    let mut latest = store.get(&schedule.id).await.unwrap_or(schedule.clone());
+    latest.history = schedule.history;
+    if !manual {
+        latest.next_run = schedule.next_run;
+    }
+    if let Err(e) = store.save(latest).await {
+        tracing::warn!("[Scheduler] Failed to persist run history: {}", e);
+    }
+
+    run
+}
+
+async fn execute(
+    schedule: &ReportSchedule,
+    request: &GenerateReportRequest,
+    run_id: &str,
+) -> Result<(String, Option<String>), String> {
+    let session = owner_session(&schedule.user_id).await.ok_or_else(|| {
+        "No session for the schedule owner; sign in again to resume scheduled runs".to_string()
+    })?;
+    if session.expires_at() <= Utc::now() {
+        return Err(format!(
+            "The schedule owner's session expired on {}; sign in again to resume scheduled runs",
+            session.expires_at().format("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    let token = session.token;
+    // The owner may have lost access since the schedule was saved
+    ReportService::ensure_tenant_access(&token, &request.tenant_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Share the Axur rate limit with interactive reports
+    let job = QueueJob::new(
+        schedule.user_id.clone(),
+        JobType::GenerateReport {
+            request: Box::new(request.clone()),
+        },
+        ApiType::Axur,
+    )
+    .with_token(token);
+    let result = get_queue()
+        .run_and_wait(job)
+        .await
+        .map_err(|e| format!("Report generation failed: {}", e))?;
+    let response: GenerateReportResponse =
+        serde_json::from_value(result).map_err(|e| format!("Invalid report job result: {}", e))?;
+
+    if !response.success {
+        return Err(response.error_message.unwrap_or(response.message));
+    }
+    let html = response
+        .html
+        .ok_or_else(|| "Report generated without HTML".to_string())?;
+
+    let delivered_to = match &schedule.spec.delivery {
+        Delivery::Storage => {
+            let storage = crate::github_storage::get_github_storage()
+                .ok_or_else(|| "Report storage not configured".to_string())?;
+            storage
+                .save_scheduled_report(&schedule.user_id, &schedule.id, run_id, &html)
+                .await?
+        }
+        Delivery::Webhook { url } => {
+            let (target, client) = webhook_client(url).await?;
+            let payload = serde_json::json!({
+                "schedule_id": schedule.id,
+                "schedule_name": schedule.spec.name,
+                "run_id": run_id,
+                "tenant_id": request.tenant_id,
+                "from_date": request.from_date,
+                "to_date": request.to_date,
+                "language": request.language,
+                "company_name": response.company_name,
+                "snapshot_id": response.snapshot_id,
+                "html": html,
+            });
+            let resp = client
+                .post(target)
+                .json(&payload)
+                .send()
+                .await
+                .map_err(|e| format!("Webhook request failed: {}", e))?;
+            if !resp.status().is_success() {
+                return Err(format!("Webhook returned HTTP {}", resp.status()));
+            }
+            url.clone()
+        }
+    };
+
+    Ok((delivered_to, response.snapshot_id))
+}
+
+/// Client for a webhook delivery: every address the host resolves to must be
+/// public, requests go to those addresses only and redirects are not followed
+async fn webhook_client(url: &str) -> Result<(reqwest::Url, reqwest::Client), String> {
+    let parsed = webhook_url(url)?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Webhook URL has no host".to_string())?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Could not resolve webhook host {}: {}", host, e))?
+        .collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| is_internal_ip(addr.ip())) {
+        return Err(format!("Webhook host {} is not a public address", host));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve_to_addrs(&host, &addrs)
+        .build()
+        .map_err(|e| format!("Webhook client error: {}", e))?;
+    Ok((parsed, client))
+}
+
+// =========== Background Loop ===========
+
+static SCHEDULER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Start the background scheduler. Should be called once at server startup.
+pub fn start_scheduler() {
+    if SCHEDULER_RUNNING.swap(true, Ordering::SeqCst) {
+        tracing::warn!("[Scheduler] Already running, skipping start");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let store = get_store();
+        store.load().await;
+        tracing::info!("[Scheduler] Started");
+
+        loop {
+            let now = Utc::now();
+            for schedule in store.due(now).await {
+                // Advance first so a slow run is not picked up again next tick
+                let mut claimed = schedule.clone();
+                claimed.next_run = schedule.spec.recurrence.next_after(now);
+                if let Err(e) = store.save(claimed).await {
+                    tracing::warn!("[Scheduler] Failed to claim {}: {}", schedule.id, e);
+                }
+                tokio::spawn(run_schedule(schedule, false));
+            }
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn spec() -> ScheduleSpec {
+        ScheduleSpec {
+            name: "Monthly".into(),
+            tenant_id: "tenant-x".into(),
+            recurrence: Recurrence::Monthly { day: 1, hour: 6 },
+            window: DateWindow::PreviousMonth,
+            language: "pt".into(),
+            theme: None,
+            disabled_plugins: Some(vec!["builtin.heatmap".into()]),
+            template_id: None,
+            risk_model: None,
+            preset_id: None,
+            branding: None,
+            custom_branding: false,
+            use_plugins: true,
+            include_threat_intel: false,
+            th_search: ThSearchConfig::default(),
+            delivery: Delivery::Storage,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_next_run() {
+        let monthly = Recurrence::Monthly { day: 1, hour: 6 };
+        assert_eq!(
+            monthly.next_after(utc(2025, 1, 1, 5, 0)),
+            utc(2025, 1, 1, 6, 0)
+        );
+        assert_eq!(
+            monthly.next_after(utc(2025, 12, 1, 6, 0)),
+            utc(2026, 1, 1, 6, 0)
+        );
+
+        // 2025-01-15 is a Wednesday
+        let weekly = Recurrence::Weekly {
+            weekday: 0,
+            hour: 8,
+        };
+        assert_eq!(
+            weekly.next_after(utc(2025, 1, 15, 12, 0)),
+            utc(2025, 1, 20, 8, 0)
+        );
+        assert_eq!(
+            weekly.next_after(utc(2025, 1, 20, 8, 0)),
+            utc(2025, 1, 27, 8, 0)
+        );
+
+        let daily = Recurrence::Daily { hour: 0 };
+        assert_eq!(
+            daily.next_after(utc(2025, 2, 28, 0, 0)),
+            utc(2025, 3, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_date_windows() {
+        let today = date(2025, 3, 1);
+        assert_eq!(
+            DateWindow::PreviousMonth.resolve(today),
+            (date(2025, 2, 1), date(2025, 2, 28))
+        );
+        // 2025-03-05 is a Wednesday
+        assert_eq!(
+            DateWindow::PreviousWeek.resolve(date(2025, 3, 5)),
+            (date(2025, 2, 24), date(2025, 3, 2))
+        );
+        assert_eq!(
+            DateWindow::MonthToDate.resolve(date(2025, 3, 10)),
+            (date(2025, 3, 1), date(2025, 3, 9))
+        );
+        assert_eq!(
+            DateWindow::LastDays { days: 7 }.resolve(date(2025, 3, 10)),
+            (date(2025, 3, 3), date(2025, 3, 9))
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(spec().validate().is_ok());
+
+        let mut bad = spec();
+        bad.recurrence = Recurrence::Monthly { day: 31, hour: 6 };
+        assert!(bad.validate().is_err());
+
+        let mut bad = spec();
+        bad.delivery = Delivery::Webhook {
+            url: "ftp://example.com".into(),
+        };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_webhook_urls() {
+        assert!(webhook_url("https://hooks.example.com/reports").is_ok());
+        for url in [
+            "http://hooks.example.com/reports",
+            "https://localhost/hook",
+            "https://metadata.google.internal/",
+            "https://127.0.0.1/hook",
+            "https://10.0.0.8/hook",
+            "https://169.254.169.254/latest",
+            "https://[::1]/hook",
+            "https://[::ffff:192.168.1.1]/hook",
+            "https://[fd00::1]/hook",
+        ] {
+            assert!(webhook_url(url).is_err(), "{} should be rejected", url);
+        }
+        assert!(!is_internal_ip("8.8.8.8".parse().unwrap()));
+        assert!(is_internal_ip("100.64.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_owner_session_encryption() {
+        let key = [7u8; 32];
+        let sealed = seal_token(&key, "owner@example.com", "axur-token").unwrap();
+        assert!(!sealed.contains("axur-token"));
+        assert_eq!(
+            open_token(&key, "owner@example.com", &sealed).unwrap(),
+            "axur-token"
+        );
+        // Bound to the owner and the key
+        assert!(open_token(&key, "other@example.com", &sealed).is_err());
+        assert!(open_token(&[8u8; 32], "owner@example.com", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_credential_state() {
+        let now = utc(2025, 1, 10, 0, 0);
+        let next_run = utc(2025, 2, 1, 6, 0);
+
+        let active = ScheduleCredential::evaluate(Some(utc(2025, 2, 2, 0, 0)), true, next_run, now);
+        assert_eq!(active.state, CredentialState::Active);
+        assert!(active.warning.is_none());
+        let in_memory =
+            ScheduleCredential::evaluate(Some(utc(2025, 2, 2, 0, 0)), false, next_run, now);
+        assert_eq!(in_memory.state, CredentialState::Active);
+        assert!(in_memory.warning.is_some());
+
+        let expiring =
+            ScheduleCredential::evaluate(Some(utc(2025, 1, 15, 0, 0)), true, next_run, now);
+        assert_eq!(expiring.state, CredentialState::ExpiresBeforeNextRun);
+        assert!(expiring.warning.is_some());
+
+        let expired =
+            ScheduleCredential::evaluate(Some(utc(2025, 1, 9, 0, 0)), true, next_run, now);
+        assert_eq!(expired.state, CredentialState::Missing);
+        assert_eq!(
+            ScheduleCredential::evaluate(None, true, next_run, now).state,
+            CredentialState::Missing
+        );
+    }
+
+    #[test]
+    fn test_schedule_request_and_history() {
+        let mut schedule = ReportSchedule::new("user-1", spec(), utc(2025, 1, 10, 0, 0));
+        assert_eq!(schedule.next_run, utc(2025, 2, 1, 6, 0));
+        assert!(!schedule.is_due(utc(2025, 1, 31, 0, 0)));
+        assert!(schedule.is_due(utc(2025, 2, 1, 6, 0)));
+
+        let request = schedule.report_request(date(2025, 2, 1));
+        assert_eq!(request.from_date, "2025-01-01");
+        assert_eq!(request.to_date, "2025-01-31");
+        assert_eq!(request.language, "pt");
+        assert_eq!(
+            request.disabled_plugins,
+            Some(vec!["builtin.heatmap".to_string()])
+        );
+
+        for i in 0..(MAX_RUN_HISTORY + 5) {
+            schedule.record_run(ScheduleRun {
+                id: i.to_string(),
+                started_at: utc(2025, 2, 1, 6, 0),
+                finished_at: utc(2025, 2, 1, 6, 1),
+                from_date: request.from_date.clone(),
+                to_date: request.to_date.clone(),
+                status: RunStatus::Failed,
+                manual: false,
+                error: Some("boom".into()),
+                delivered_to: None,
+                snapshot_id: None,
+            });
+        }
+        assert_eq!(schedule.history.len(), MAX_RUN_HISTORY);
+        assert_eq!(
+            schedule.last_run().map(|r| r.id.as_str()),
+            Some((MAX_RUN_HISTORY + 4).to_string().as_str())
+        );
+
+        // Serialized form flattens the user fields
+        let json = serde_json::to_value(&schedule).unwrap();
+        assert_eq!(json["tenant_id"], "tenant-x");
+        assert_eq!(json["recurrence"]["type"], "monthly");
+        let restored: ReportSchedule = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.spec.window, DateWindow::PreviousMonth);
+    }
+}
@@ -1,12 +1,35 @@
 use crate::error::ApiError;
 use crate::routes::templates::{self, GitHubConfig};
-use axur_core::api::report::fetch_full_report;
+use crate::routes::{presets, redaction, th_credits};
+use axur_core::api::context::ReportContext;
+use axur_core::api::insights::FiredInsight;
+use axur_core::api::report::{
+    fetch_available_tenants, fetch_comparison_data_with, fetch_full_report_with,
+    preview_threat_hunting_with, ComparisonPeriod, PocReportData,
+};
+use axur_core::api::risk::RiskModel;
+use axur_core::api::th_cache::ThCache;
+use axur_core::api::th_query::ThSearchConfig;
+use axur_core::api::transport::ApiTransport;
+use axur_core::editor::{PresentationTemplate, SlideDefinition};
 use axur_core::error_codes::{self, ErrorCode};
 use axur_core::i18n::{get_dictionary, Language, Translations};
-use axur_core::plugins::{PluginConfig, ThemeMode};
-use axur_core::report::html::{generate_full_report_html, generate_report_with_plugins};
+use axur_core::plugins::builtin::insights::evaluate_insights;
+use axur_core::plugins::builtin::{PdfExportPlugin, PptxExportPlugin};
+use axur_core::plugins::{
+    ExportPlugin, PluginConfig, ReportAnnotations, ReportTheme, SlideOverride, ThemeMode,
+};
+use axur_core::report::html::{
+    enrich_report_data, generate_full_report_html, generate_plugin_slides,
+    generate_report_with_plugins, render_enriched_slides,
+};
+use axur_core::report::template_renderer::{
+    render_template_pptx, render_template_report, render_template_slides,
+};
 use axur_core::report::OfflineAssets;
+use axur_core::templates::ReportPreset;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -25,11 +48,16 @@ pub struct GenerateReportRequest {
     pub tenant_id: String,
     pub from_date: String,
     pub to_date: String,
-    #[serde(default = "default_language")]
+    /// Report language; empty uses the preset language, then Spanish
+    #[serde(default)]
     pub language: String,
     pub story_tag: Option<String>,
     #[serde(default)]
     pub include_threat_intel: bool,
+    /// Threat Hunting sources and search terms (primary brand on the
+    /// forum, chat, social, credential and ads sources by default)
+    #[serde(default)]
+    pub th_search: ThSearchConfig,
     pub template_id: Option<String>,
     #[serde(default)]
     pub use_plugins: bool,
@@ -39,13 +67,56 @@ pub struct GenerateReportRequest {
     pub disabled_plugins: Option<Vec<String>>,
     #[serde(default)]
     pub mock: bool,
+    /// Period-over-period comparison window (previous period/month/quarter or custom)
+    #[serde(default)]
+    pub comparison: Option<ComparisonPeriod>,
+    /// Risk score weights, severities and thresholds (default model when absent)
+    #[serde(default)]
+    pub risk_model: Option<RiskModel>,
+    /// Report preset (builtin industry or user preset id)
+    #[serde(default)]
+    pub preset_id: Option<String>,
+    /// Report theme: colors, fonts, logo, cover image, footer text
+    #[serde(default)]
+    pub branding: Option<ReportTheme>,
+    /// White-label: the theme logo/brand (or partner name) replaces Axur's
+    #[serde(default)]
+    pub custom_branding: bool,
+    /// Slide plugin IDs in display order (unlisted plugins follow by priority)
+    #[serde(default)]
+    pub slide_order: Option<Vec<String>>,
+    /// Per-plugin title, subtitle and analyst commentary (plugin ID -> override)
+    #[serde(default)]
+    pub slide_overrides: HashMap<String, SlideOverride>,
+    /// Admin-approved credential redaction override (server policy when absent)
+    #[serde(default)]
+    pub redaction_override_id: Option<String>,
+    /// Run Threat Hunting searches again instead of reusing cached results
+    #[serde(default)]
+    pub force_refresh: bool,
 }
 
-fn default_language() -> String {
-    "es".to_string()
+impl GenerateReportRequest {
+    /// Render options of the request (a freshly generated report has no
+    /// draft, so no annotations)
+    pub fn render_options(&self) -> RenderSnapshotRequest {
+        RenderSnapshotRequest {
+            language: self.language.clone(),
+            theme: self.theme.clone(),
+            disabled_plugins: self.disabled_plugins.clone(),
+            template_id: self.template_id.clone(),
+            risk_model: self.risk_model.clone(),
+            preset_id: self.preset_id.clone(),
+            branding: self.branding.clone(),
+            custom_branding: self.custom_branding,
+            slide_order: self.slide_order.clone(),
+            slide_overrides: self.slide_overrides.clone(),
+            annotations: Default::default(),
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GenerateReportResponse {
     pub success: bool,
     pub html: Option<String>,
@@ -55,12 +126,113 @@ pub struct GenerateReportResponse {
     pub error_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Stored data snapshot, usable with `/api/reports/snapshots/:id/render`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    /// Insight rules that fired, with the metric values they read (audit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub insights: Vec<FiredInsight>,
+    /// Threat Hunting sources whose searches timed out (counts incomplete)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out_sources: Vec<String>,
+}
+
+/// Fetched report data persisted so it can be re-rendered without hitting the Axur API
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReportSnapshot {
+    pub id: String,
+    pub tenant_id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub story_tag: Option<String>,
+    pub include_threat_intel: bool,
+    pub created_at: String,
+    pub data: PocReportData,
+}
+
+impl ReportSnapshot {
+    pub fn new(
+        tenant_id: &str,
+        from_date: &str,
+        to_date: &str,
+        story_tag: Option<String>,
+        include_threat_intel: bool,
+        data: PocReportData,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            tenant_id: tenant_id.to_string(),
+            from_date: from_date.to_string(),
+            to_date: to_date.to_string(),
+            story_tag,
+            include_threat_intel,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            data,
+        }
+    }
+}
+
+/// Re-render options for a stored snapshot
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RenderSnapshotRequest {
+    /// Report language; empty uses the preset language, then Spanish
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub theme: Option<String>,
+    #[serde(default)]
+    pub disabled_plugins: Option<Vec<String>>,
+    /// Editor template to render instead of the plugin slides
+    #[serde(default)]
+    pub template_id: Option<String>,
+    /// Risk score model used when re-deriving the score
+    #[serde(default)]
+    pub risk_model: Option<RiskModel>,
+    /// Report preset (builtin industry or user preset id)
+    #[serde(default)]
+    pub preset_id: Option<String>,
+    /// Report theme: colors, fonts, logo, cover image, footer text
+    #[serde(default)]
+    pub branding: Option<ReportTheme>,
+    /// White-label: the theme logo/brand (or partner name) replaces Axur's
+    #[serde(default)]
+    pub custom_branding: bool,
+    /// Slide plugin IDs in display order (unlisted plugins follow by priority)
+    #[serde(default)]
+    pub slide_order: Option<Vec<String>>,
+    /// Per-plugin title, subtitle and analyst commentary (plugin ID -> override)
+    #[serde(default)]
+    pub slide_overrides: HashMap<String, SlideOverride>,
+    /// Analyst annotations from the report draft; set by the draft routes,
+    /// never read from the request body
+    #[serde(skip)]
+    pub annotations: ReportAnnotations,
+}
+
+/// Threat Hunting preview request (story tag within a tenant)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ThreatHuntingPreviewRequest {
+    pub tenant_id: String,
+    pub story_tag: String,
+    #[serde(default)]
+    pub use_user_credits: bool,
+    /// Run the searches again instead of reusing cached results
+    #[serde(default)]
+    pub force_refresh: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreatHuntingPreviewResponse {
+    pub success: bool,
+    pub preview: Option<axur_core::api::report::ThreatHuntingPreview>,
+    pub message: String,
 }
 
 pub struct ReportService;
 
 impl ReportService {
-    /// Generate HTML report safely (No Panic)
+    /// Generate HTML report safely (No Panic). Freshly fetched reports have
+    /// no draft, so they render without annotations and need no approval.
     pub async fn generate_report(
         payload: &GenerateReportRequest,
         token: &str,
@@ -69,166 +241,745 @@ impl ReportService {
         let _start_time = Instant::now();
 
         // 1. Fetch Data
-        // 1. Fetch Data
-        let report_data = if payload.mock {
-            tracing::info!("Using MOCK DATA for report generation");
-            axur_core::api::report::PocReportData::demo()
+        let client = ApiTransport::from_env()?;
+        let ctx = Self::report_context(
+            user_id,
+            &payload.tenant_id,
+            payload.redaction_override_id.as_deref(),
+            (payload.include_threat_intel && !payload.mock).then_some(&payload.th_search),
+            payload.force_refresh,
+        )
+        .await?;
+        let fetched = Self::fetch_report_data(payload, token, &client, &ctx).await;
+        th_credits::record_usage(&payload.tenant_id, user_id, "report", ctx.credits(), false).await;
+        let report_data = match fetched {
+            Ok(data) => data,
+            Err(e) => {
+                let error_code = classify_error(&e.to_string());
+                tracing::error!(
+                    error_code = %error_code.code(),
+                    context = %e.to_string(),
+                    "Report generation failed"
+                );
+
+                // 📝 Log error (Assuming log_error is handled by caller or we inject logger)
+                // Ideally logging relates to HTTP layer, but Service can log domain errors.
+
+                return Ok(GenerateReportResponse {
+                    success: false,
+                    html: None,
+                    company_name: None,
+                    message: e.to_string(),
+                    error_code: Some(error_code.code()),
+                    error_message: Some(get_user_friendly_message(&error_code)),
+                    snapshot_id: None,
+                    insights: Vec::new(),
+                    timed_out_sources: Vec::new(),
+                });
+            }
+        };
+
+        // Persist fetched data so the report can be re-rendered later
+        let snapshot_id = if payload.mock {
+            None
         } else {
-            match fetch_full_report(
+            Self::store_snapshot(
+                user_id,
+                ReportSnapshot::new(
+                    &payload.tenant_id,
+                    &payload.from_date,
+                    &payload.to_date,
+                    payload.story_tag.clone(),
+                    payload.include_threat_intel,
+                    report_data.clone(),
+                ),
+            )
+        };
+
+        // 2. Preset, language and plugin configuration
+        let (language, translations, config) =
+            Self::render_config(&payload.render_options(), user_id).await?;
+        let dict = get_dictionary(language);
+
+        // 3. Editor template (rendered server-side with the report data)
+        let template = match &payload.template_id {
+            Some(tid) => Self::load_template(tid, user_id).await,
+            None => None,
+        };
+
+        // 4. Generate HTML
+        let offline_assets = OfflineAssets::load_embedded();
+
+        let mut insights = Vec::new();
+        let html = if let Some(template) = &template {
+            tracing::info!(
+                "Rendering template '{}' with {} slides",
+                template.name,
+                template.slides.len()
+            );
+            let report_data = enrich_report_data(&report_data, &config);
+            render_template_report(template, &report_data, Some(&offline_assets))
+        } else if payload.use_plugins {
+            insights = fired_insights(&report_data, &config);
+            generate_report_with_plugins(
+                &report_data,
+                &translations,
+                Some(&offline_assets),
+                Some(config),
+            )
+        } else {
+            // The legacy renderer has no plugin pipeline; enrich up front
+            let report_data = enrich_report_data(&report_data, &config);
+            generate_full_report_html(&report_data, None, Some(&offline_assets), &dict)
+        };
+
+        Ok(GenerateReportResponse {
+            success: true,
+            html: Some(html),
+            company_name: Some(report_data.company_name),
+            message: "Report generated successfully".into(),
+            error_code: None,
+            error_message: None,
+            snapshot_id,
+            insights,
+            timed_out_sources: report_data.threat_intelligence.timed_out_sources,
+        })
+    }
+
+    /// Keys of the tenants the session can see
+    pub async fn accessible_tenants(token: &str) -> Result<Vec<String>, ApiError> {
+        let tenants = fetch_available_tenants(token)
+            .await
+            .map_err(|e| ApiError::ExternalApi(format!("Failed to fetch tenants: {}", e)))?;
+        Ok(tenants.into_iter().map(|t| t.key).collect())
+    }
+
+    /// Refuse (`Forbidden`) a tenant the session cannot see: the tenant must
+    /// be in the list the Axur API returns for the token
+    pub async fn ensure_tenant_access(token: &str, tenant_id: &str) -> Result<(), ApiError> {
+        let tenants = Self::accessible_tenants(token).await?;
+        if tenants.iter().any(|key| key == tenant_id) {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(format!(
+                "No access to tenant {}",
+                tenant_id
+            )))
+        }
+    }
+
+    /// Context of a report's Axur API calls: credentials masked with the
+    /// server policy or an approved override, and, with `threat_hunting`,
+    /// the selected searches limited by the tenant credit budget (refused
+    /// when it is exhausted) with cached results reused unless `force_refresh`
+    pub async fn report_context(
+        user_id: &str,
+        tenant_id: &str,
+        redaction_override_id: Option<&str>,
+        threat_hunting: Option<&ThSearchConfig>,
+        force_refresh: bool,
+    ) -> Result<ReportContext, ApiError> {
+        let redaction =
+            redaction::resolve_policy(user_id, tenant_id, redaction_override_id).await?;
+        let ctx = ReportContext::new()
+            .with_user(user_id)
+            .with_redaction(redaction)
+            .with_th_cache(ThCache::from_env().with_refresh(force_refresh));
+        let Some(search) = threat_hunting else {
+            return Ok(ctx);
+        };
+        Ok(ctx
+            .with_th_search(search.clone())
+            .with_credits(th_credits::start_meter(tenant_id, false).await?))
+    }
+
+    /// Fetch report data for a request (mock data, or the Axur API plus the
+    /// optional comparison window) with a context from [`Self::report_context`]
+    pub async fn fetch_report_data(
+        payload: &GenerateReportRequest,
+        token: &str,
+        client: &ApiTransport,
+        ctx: &ReportContext,
+    ) -> anyhow::Result<PocReportData> {
+        if payload.mock {
+            tracing::info!("Using MOCK DATA for report generation");
+            return Ok(PocReportData::demo());
+        }
+
+        let mut report_data = fetch_full_report_with(
+            client,
+            ctx,
+            token,
+            &payload.tenant_id,
+            &payload.from_date,
+            &payload.to_date,
+            payload.story_tag.clone(),
+            payload.include_threat_intel,
+        )
+        .await?;
+
+        // Previous window for the comparative, insights and ROI slides
+        if let Some(period) = &payload.comparison {
+            match fetch_comparison_data_with(
+                client,
                 token,
                 &payload.tenant_id,
                 &payload.from_date,
                 &payload.to_date,
-                payload.story_tag.clone(),
-                payload.include_threat_intel,
+                period,
             )
             .await
             {
-                Ok(data) => data,
-                Err(e) => {
-                    let error_code = classify_error(&e.to_string());
-                    tracing::error!(
-                        error_code = %error_code.code(),
-                        context = %e.to_string(),
-                        "Report generation failed"
-                    );
-
-                    // 📝 Log error (Assuming log_error is handled by caller or we inject logger)
-                    // Ideally logging relates to HTTP layer, but Service can log domain errors.
-
-                    return Ok(GenerateReportResponse {
-                        success: false,
-                        html: None,
-                        company_name: None,
-                        message: e.to_string(),
-                        error_code: Some(error_code.code()),
-                        error_message: Some(get_user_friendly_message(&error_code)),
-                    });
-                }
+                Ok(comparison) => report_data.comparison = Some(comparison),
+                Err(e) => tracing::warn!("Comparison fetch failed, skipping: {}", e),
             }
-        };
+        }
 
-        // 2. Load Language Safely
-        let language = match payload.language.to_lowercase().as_str() {
-            "en" => Language::En,
-            "pt" | "pt-br" => Language::PtBr,
-            _ => Language::Es,
-        };
+        Ok(report_data)
+    }
 
-        let lang_code = match language {
-            Language::En => "en",
-            Language::PtBr => "pt-br",
-            Language::Es => "es",
+    /// Load an editor template: builtin templates first, then the user's
+    /// private templates (Firestore index, content in GitHub storage)
+    pub async fn load_template(template_id: &str, user_id: &str) -> Option<PresentationTemplate> {
+        if let Some(template) = templates::get_mock_template(template_id) {
+            return Some(template);
+        }
+
+        let uuid = Uuid::parse_str(template_id).ok()?;
+        let firestore = crate::firebase::get_firestore()?;
+        let doc_path = format!("user_templates/{}/items", user_id);
+        let doc = match firestore
+            .get_doc::<serde_json::Value>(&doc_path, &uuid.to_string())
+            .await
+        {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return None,
+            Err(e) => {
+                tracing::warn!("Failed to look up template {}: {}", template_id, e);
+                return None;
+            }
         };
+        // Quick-saved drafts keep their slides inline instead of in GitHub
+        let Some(path) = doc
+            .get("github_path")
+            .and_then(|s| s.as_str())
+            .filter(|path| !path.is_empty())
+        else {
+            return inline_template(&doc);
+        };
+        let config = GitHubConfig::from_env()?;
 
-        // SAFETY FIX: Handle Translations::load gracefully
-        let translations = Translations::load(lang_code)
-            .or_else(|_| Translations::load("en"))
-            .map_err(|e| ApiError::Internal(format!("Failed to load translations: {}", e)))?;
+        match templates::fetch_template_from_github(&config, path).await {
+            Ok(template) => Some(template),
+            Err(e) => {
+                tracing::error!("Failed from GitHub: {}", e);
+                None
+            }
+        }
+    }
 
-        let dict = get_dictionary(language);
+    /// Store a snapshot of the fetched data (async, GitHub storage).
+    /// Returns the snapshot id, or None when storage is not configured.
+    pub fn store_snapshot(user_id: &str, snapshot: ReportSnapshot) -> Option<String> {
+        let storage = crate::github_storage::get_github_storage()?;
 
-        // 3. Template Logic (Simplified Migration)
-        let mut custom_template_slides: Option<Vec<String>> = None;
-        if let Some(tid) = &payload.template_id {
-            // Mock Templates
-            if let Some(tmpl) = crate::routes::templates::get_mock_template(tid) {
-                let slides: Vec<String> = tmpl
-                    .slides
-                    .iter()
-                    .filter_map(|s| s.canvas_json.clone())
-                    .collect();
-                if !slides.is_empty() {
-                    // SAFETY FIX: removed unwrap().len() logging to keep concise or use safe access
-                    tracing::info!(
-                        "Using mock template '{}' with {} slides",
-                        tmpl.name,
-                        slides.len()
-                    );
-                    custom_template_slides = Some(slides);
-                }
+        let json = match serde_json::to_string(&snapshot) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize report snapshot: {}", e);
+                return None;
             }
-            // Firestore Templates (if not mock)
-            if custom_template_slides.is_none() {
-                if let Ok(uuid) = Uuid::parse_str(tid) {
-                    if let Some(firestore) = crate::firebase::get_firestore() {
-                        let doc_path = format!("user_templates/{}/items", user_id);
-                        let doc_id = uuid.to_string();
-                        if let Ok(Some(doc)) = firestore
-                            .get_doc::<serde_json::Value>(&doc_path, &doc_id)
-                            .await
-                        {
-                            if let Some(path) = doc.get("github_path").and_then(|s| s.as_str()) {
-                                if let Some(config) = GitHubConfig::from_env() {
-                                    match templates::fetch_template_from_github(&config, path).await
-                                    {
-                                        Ok(tmpl) => {
-                                            let slides: Vec<String> = tmpl
-                                                .slides
-                                                .iter()
-                                                .filter_map(|s| s.canvas_json.clone())
-                                                .collect();
-                                            if !slides.is_empty() {
-                                                custom_template_slides = Some(slides);
-                                                tracing::info!(
-                                                    "Using custom private template: {}",
-                                                    tmpl.name
-                                                );
-                                            }
-                                        }
-                                        Err(e) => tracing::error!("Failed from GitHub: {}", e),
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        };
+
+        let id = snapshot.id;
+        let snapshot_id = id.clone();
+        let user_id = user_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = storage
+                .save_report_snapshot(&user_id, &snapshot_id, &json)
+                .await
+            {
+                tracing::warn!("Failed to store report snapshot {}: {}", snapshot_id, e);
             }
+        });
+
+        Some(id)
+    }
+
+    /// Load a stored snapshot for the given user
+    pub async fn load_snapshot(
+        user_id: &str,
+        snapshot_id: &str,
+    ) -> Result<ReportSnapshot, ApiError> {
+        if Uuid::parse_str(snapshot_id).is_err() {
+            return Err(ApiError::BadRequest("Invalid snapshot id".into()));
         }
 
-        // 4. Generate HTML
-        let offline_assets = OfflineAssets::load_embedded();
+        let storage = crate::github_storage::get_github_storage()
+            .ok_or_else(|| ApiError::Internal("Snapshot storage not configured".into()))?;
 
-        let html = if payload.use_plugins && custom_template_slides.is_none() {
-            let theme_mode = match payload.theme.as_deref() {
-                Some("light") => ThemeMode::Light,
-                Some("auto") => ThemeMode::Auto,
-                _ => ThemeMode::Dark, // Default
-            };
-            let config = PluginConfig::default()
-                .with_theme(theme_mode)
-                .disable_plugins(payload.disabled_plugins.clone().unwrap_or_default());
+        let json = storage
+            .load_report_snapshot(user_id, snapshot_id)
+            .await
+            .map_err(|e| ApiError::NotFound(format!("Snapshot not found: {}", e)))?;
 
-            generate_report_with_plugins(
-                &report_data,
-                &translations,
-                Some(&offline_assets),
-                Some(config),
-            )
-        } else {
-            generate_full_report_html(
-                &report_data,
-                custom_template_slides,
-                Some(&offline_assets),
-                &dict,
-            )
+        serde_json::from_str(&json)
+            .map_err(|e| ApiError::Internal(format!("Corrupted snapshot {}: {}", snapshot_id, e)))
+    }
+
+    /// Re-render a stored snapshot with a different language, theme, plugin set
+    /// or editor template
+    pub async fn render_snapshot(
+        snapshot: &ReportSnapshot,
+        request: &RenderSnapshotRequest,
+        user_id: &str,
+    ) -> Result<GenerateReportResponse, ApiError> {
+        let (_, translations, config) = Self::render_config(request, user_id).await?;
+        let offline_assets = OfflineAssets::load_embedded();
+
+        let mut insights = Vec::new();
+        let html = match Self::request_template(request, user_id).await? {
+            Some(template) => {
+                let data = enrich_report_data(&snapshot.data, &config);
+                render_template_report(&template, &data, Some(&offline_assets))
+            }
+            None => {
+                insights = fired_insights(&snapshot.data, &config);
+                generate_report_with_plugins(
+                    &snapshot.data,
+                    &translations,
+                    Some(&offline_assets),
+                    Some(config),
+                )
+            }
         };
 
         Ok(GenerateReportResponse {
             success: true,
             html: Some(html),
-            company_name: Some(report_data.company_name),
-            message: "Report generated successfully".into(),
+            company_name: Some(snapshot.data.company_name.clone()),
+            message: "Report re-rendered from snapshot".into(),
             error_code: None,
             error_message: None,
+            snapshot_id: Some(snapshot.id.clone()),
+            insights,
+            timed_out_sources: snapshot.data.threat_intelligence.timed_out_sources.clone(),
         })
     }
+
+    /// Render report data to a 16:9 PDF with the builtin PDF export plugin.
+    /// The headless browser runs on the blocking pool.
+    pub async fn export_pdf(
+        data: PocReportData,
+        request: &RenderSnapshotRequest,
+        user_id: &str,
+    ) -> Result<Vec<u8>, ApiError> {
+        let (_, translations, config) = Self::render_config(request, user_id).await?;
+        let template = Self::request_template(request, user_id).await?;
+
+        tokio::task::spawn_blocking(move || {
+            let css = config.document_css();
+            let slides = match &template {
+                Some(template) => {
+                    render_template_slides(template, &enrich_report_data(&data, &config))
+                }
+                None => generate_plugin_slides(&data, &translations, Some(config)),
+            };
+            PdfExportPlugin::new()
+                .with_title(&data.company_name)
+                .with_css(css)
+                .export(&slides)
+        })
+        .await
+        .map_err(|e| ApiError::Internal(format!("PDF export task failed: {}", e)))?
+        .map_err(|e| ApiError::Internal(format!("PDF export failed: {}", e)))
+    }
+
+    /// Build a native PPTX (shapes, tables and charts) from report data,
+    /// from the plugin slides or from an editor template
+    pub async fn export_pptx(
+        data: PocReportData,
+        request: &RenderSnapshotRequest,
+        user_id: &str,
+    ) -> Result<Vec<u8>, ApiError> {
+        let (_, translations, config) = Self::render_config(request, user_id).await?;
+        let template = Self::request_template(request, user_id).await?;
+
+        tokio::task::spawn_blocking(move || {
+            // Native charts read the data directly, so it needs the same enrichment
+            let data = enrich_report_data(&data, &config);
+            match &template {
+                Some(template) => render_template_pptx(template, &data),
+                None => {
+                    let theme = config.branding.pptx_theme();
+                    let overrides = config.slide_overrides.clone();
+                    // Same customer terminology as the plugin slides
+                    let native_translations = translations.with_overrides(&config.terminology);
+                    // Data plugins already ran above
+                    let slides = render_enriched_slides(&data, &translations, config);
+                    PptxExportPlugin::with_data(data)
+                        .with_theme(theme)
+                        .with_translations(native_translations)
+                        .with_slide_overrides(overrides)
+                        .export(&slides)
+                }
+            }
+        })
+        .await
+        .map_err(|e| ApiError::Internal(format!("PPTX export task failed: {}", e)))?
+        .map_err(|e| ApiError::Internal(format!("PPTX export failed: {}", e)))
+    }
+
+    /// Language, translations and plugin configuration of render options:
+    /// theme, disabled plugins, risk model and preset (theme, slides,
+    /// insight thresholds), then branding, slide order, overrides and draft
+    /// annotations. Shared by every renderer so they produce the same slides.
+    pub async fn render_config(
+        request: &RenderSnapshotRequest,
+        user_id: &str,
+    ) -> Result<(Language, Translations, PluginConfig), ApiError> {
+        let preset = Self::request_preset(request.preset_id.as_deref(), user_id).await?;
+        let (language, translations) =
+            load_language(report_language(&request.language, preset.as_ref()))?;
+        let config = with_branding(
+            plugin_config(
+                request.theme.as_deref(),
+                request.disabled_plugins.clone(),
+                request.risk_model.clone(),
+                preset.as_ref(),
+            )
+            .with_slides(request.slide_order.clone(), request.slide_overrides.clone())
+            .with_annotations(request.annotations.clone()),
+            request.branding.clone(),
+            request.custom_branding,
+        )?;
+        Ok((language, translations, config))
+    }
+
+    /// Template requested by render/export options; an unknown id is an error
+    /// rather than a silent fallback to the plugin slides
+    async fn request_template(
+        request: &RenderSnapshotRequest,
+        user_id: &str,
+    ) -> Result<Option<PresentationTemplate>, ApiError> {
+        match &request.template_id {
+            Some(tid) => Self::load_template(tid, user_id)
+                .await
+                .map(Some)
+                .ok_or_else(|| ApiError::NotFound(format!("Template '{}' not found", tid))),
+            None => Ok(None),
+        }
+    }
+
+    /// Preset requested by the options; an unknown id is an error
+    async fn request_preset(
+        preset_id: Option<&str>,
+        user_id: &str,
+    ) -> Result<Option<ReportPreset>, ApiError> {
+        match preset_id {
+            Some(id) => presets::load_preset(id, user_id)
+                .await
+                .map(Some)
+                .ok_or_else(|| ApiError::NotFound(format!("Preset '{}' not found", id))),
+            None => Ok(None),
+        }
+    }
+
+    /// Preview Threat Hunting results without consuming full credits.
+    /// Returns counts and estimated credits for user confirmation.
+    pub async fn threat_hunting_preview(
+        payload: &ThreatHuntingPreviewRequest,
+        token: &str,
+        user_id: &str,
+    ) -> ThreatHuntingPreviewResponse {
+        tracing::info!(
+            tenant = %payload.tenant_id,
+            story_tag = %payload.story_tag,
+            "Starting Threat Hunting preview"
+        );
+
+        // 📝 Log request
+        crate::routes::remote_log::log_request("th_preview", payload, Some(&payload.tenant_id));
+
+        // ⏱️ Start performance tracking
+        let start_time = std::time::Instant::now();
+
+        // FIXED: Fetch actual tickets with the story_tag instead of using tag as domain
+        let tickets = match axur_core::api::report::fetch_tagged_tickets_for_preview(
+            token,
+            &payload.tenant_id,
+            &payload.story_tag,
+        )
+        .await
+        {
+            Ok(t) => {
+                tracing::info!(
+                    "Fetched {} tickets with tag '{}'",
+                    t.len(),
+                    payload.story_tag
+                );
+                t
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch tagged tickets: {}, using empty list", e);
+                vec![]
+            }
+        };
+
+        if tickets.is_empty() {
+            return ThreatHuntingPreviewResponse {
+                success: false,
+                preview: None,
+                message: format!(
+                    "No tickets found with tag '{}' in tenant {}. Please verify the tag exists.",
+                    payload.story_tag, payload.tenant_id
+                ),
+            };
+        }
+
+        let started = th_credits::start_meter(&payload.tenant_id, payload.use_user_credits)
+            .await
+            .and_then(|meter| {
+                let ctx = ReportContext::new()
+                    .with_user(user_id)
+                    .with_credits(meter)
+                    .with_th_cache(ThCache::from_env().with_refresh(payload.force_refresh));
+                Ok((ApiTransport::from_env()?, ctx))
+            });
+        let (client, ctx) = match started {
+            Ok(started) => started,
+            Err(e) => {
+                return ThreatHuntingPreviewResponse {
+                    success: false,
+                    preview: None,
+                    message: e.to_string(),
+                }
+            }
+        };
+
+        let previewed = preview_threat_hunting_with(
+            &client,
+            &ctx,
+            token,
+            &payload.tenant_id,
+            &tickets,
+            &payload.story_tag,
+            payload.use_user_credits,
+        )
+        .await;
+        th_credits::record_usage(
+            &payload.tenant_id,
+            user_id,
+            "th_preview",
+            ctx.credits(),
+            payload.use_user_credits,
+        )
+        .await;
+
+        match previewed {
+            Ok(preview) => {
+                tracing::info!(
+                    total = preview.total_count,
+                    estimated_credits = preview.estimated_credits,
+                    tickets_used = tickets.len(),
+                    "Threat Hunting preview completed"
+                );
+
+                // ⏱️ Calculate duration
+                let duration_ms = start_time.elapsed().as_millis();
+
+                // 📝 Log successful response (metadata only, not full preview data)
+                crate::routes::remote_log::log_response(
+                    "th_preview",
+                    &serde_json::json!({
+                        "success": true,
+                        "total_count": preview.total_count,
+                        "estimated_credits": preview.estimated_credits,
+                        "signal_lake_count": preview.signal_lake_count,
+                        "credential_count": preview.credential_count,
+                        "tickets_used": tickets.len()
+                    }),
+                    duration_ms,
+                    Some(&payload.tenant_id),
+                    true,
+                );
+
+                // 📊 Log feature usage
+                crate::routes::remote_log::log_feature_usage(
+                    "preview_generation",
+                    Some(&payload.tenant_id),
+                    true,
+                    Some(serde_json::json!({
+                        "story_tag": &payload.story_tag,
+                        "tickets_count": tickets.len(),
+                        "total_results": preview.total_count,
+                        "estimated_credits": preview.estimated_credits,
+                        "duration_ms": duration_ms
+                    })),
+                );
+
+                ThreatHuntingPreviewResponse {
+                    success: true,
+                    preview: Some(preview),
+                    message: format!("Preview ready. Found {} tickets with tag.", tickets.len()),
+                }
+            }
+            Err(e) => {
+                tracing::error!("Threat Hunting preview failed: {}", e);
+
+                // 📝 Log error
+                crate::routes::remote_log::log_error(
+                    "th_preview",
+                    "TH-ERR",
+                    &e.to_string(),
+                    Some(&payload.tenant_id),
+                    serde_json::json!({
+                        "story_tag": &payload.story_tag,
+                        "tickets_count": tickets.len()
+                    }),
+                );
+
+                ThreatHuntingPreviewResponse {
+                    success: false,
+                    preview: None,
+                    message: format!("Preview failed: {}", e),
+                }
+            }
+        }
+    }
 }
 
 // ========================
 // HELPERS (Moved from routes/report.rs)
 // ========================
 
+/// Resolve a language code and load its translations (falls back to English)
+fn load_language(code: &str) -> Result<(Language, Translations), ApiError> {
+    let language = match code.to_lowercase().as_str() {
+        "en" => Language::En,
+        "pt" | "pt-br" => Language::PtBr,
+        _ => Language::Es,
+    };
+
+    let lang_code = match language {
+        Language::En => "en",
+        Language::PtBr => "pt-br",
+        Language::Es => "es",
+    };
+
+    // SAFETY FIX: Handle Translations::load gracefully
+    let translations = Translations::load(lang_code)
+        .or_else(|_| Translations::load("en"))
+        .map_err(|e| ApiError::Internal(format!("Failed to load translations: {}", e)))?;
+
+    Ok((language, translations))
+}
+
+/// Template from a quick-saved draft (`content.slides`, each with `canvas_json`)
+fn inline_template(doc: &serde_json::Value) -> Option<PresentationTemplate> {
+    let slides: Vec<SlideDefinition> = doc
+        .pointer("/content/slides")?
+        .as_array()?
+        .iter()
+        .filter_map(|slide| match slide.get("canvas_json")? {
+            serde_json::Value::String(json) => Some(json.clone()),
+            canvas => Some(canvas.to_string()),
+        })
+        .enumerate()
+        .map(|(i, canvas_json)| SlideDefinition {
+            order: i as i32,
+            canvas_json: Some(canvas_json),
+            ..Default::default()
+        })
+        .collect();
+    if slides.is_empty() {
+        return None;
+    }
+
+    Some(PresentationTemplate {
+        name: doc
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        slides,
+        ..Default::default()
+    })
+}
+
+/// Request language, else the preset default (empty falls back to Spanish)
+fn report_language<'a>(requested: &'a str, preset: Option<&'a ReportPreset>) -> &'a str {
+    if !requested.is_empty() {
+        return requested;
+    }
+    preset
+        .and_then(|p| p.language.as_deref())
+        .unwrap_or_default()
+}
+
+/// Build plugin configuration from request theme, disabled plugin list and
+/// risk model, then apply the preset (an explicit request theme still wins)
+fn plugin_config(
+    theme: Option<&str>,
+    disabled_plugins: Option<Vec<String>>,
+    risk_model: Option<RiskModel>,
+    preset: Option<&ReportPreset>,
+) -> PluginConfig {
+    let theme_mode = match theme {
+        Some("light") => ThemeMode::Light,
+        Some("auto") => ThemeMode::Auto,
+        _ => ThemeMode::Dark, // Default
+    };
+    let config = PluginConfig {
+        risk_model,
+        ..PluginConfig::default()
+    }
+    .disable_plugins(disabled_plugins.unwrap_or_default());
+    match preset {
+        Some(preset) if theme.is_none() => preset.apply(config),
+        Some(preset) => preset.apply(config).with_theme(theme_mode),
+        None => config.with_theme(theme_mode),
+    }
+}
+
+/// Insight rules that fire on the plugin report, logged and returned so the
+/// slide can be audited (none when the insights slide is disabled)
+fn fired_insights(data: &PocReportData, config: &PluginConfig) -> Vec<FiredInsight> {
+    if !config.is_plugin_enabled("builtin.insights") {
+        return Vec::new();
+    }
+    let (fired, _) = evaluate_insights(&enrich_report_data(data, config), config);
+    let rule_pack = config
+        .insight_rules
+        .as_ref()
+        .map_or("default", |pack| pack.id.as_str());
+    tracing::info!(
+        rule_pack,
+        rules = ?fired.iter().map(|f| f.rule_id.as_str()).collect::<Vec<_>>(),
+        "Insight rules fired"
+    );
+    fired
+}
+
+/// Apply the request theme on top of the plugin configuration (an invalid
+/// theme is rejected rather than rendered)
+fn with_branding(
+    config: PluginConfig,
+    branding: Option<ReportTheme>,
+    custom_branding: bool,
+) -> Result<PluginConfig, ApiError> {
+    match branding {
+        Some(theme) => {
+            theme.validate().map_err(ApiError::BadRequest)?;
+            Ok(config.with_branding(theme, custom_branding))
+        }
+        None => Ok(PluginConfig {
+            custom_branding,
+            ..config
+        }),
+    }
+}
+
 pub fn classify_error(error: &str) -> ErrorCode {
     let lower = error.to_lowercase();
     if lower.contains("timeout") {
@@ -275,3 +1026,64 @@ pub fn get_user_friendly_message(code: &ErrorCode) -> String {
         _ => "Ha ocurrido un error inesperado.".into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_snapshot_roundtrip_and_render() {
+        let snapshot = ReportSnapshot::new(
+            "acme",
+            "2025-01-01",
+            "2025-01-31",
+            None,
+            false,
+            PocReportData::demo(),
+        );
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: ReportSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.id, snapshot.id);
+        assert_eq!(restored.data.company_name, snapshot.data.company_name);
+
+        let request = RenderSnapshotRequest {
+            language: "en".into(),
+            theme: Some("light".into()),
+            disabled_plugins: None,
+            template_id: None,
+            risk_model: None,
+            preset_id: None,
+            branding: None,
+            custom_branding: false,
+            slide_order: None,
+            slide_overrides: HashMap::new(),
+            annotations: Default::default(),
+        };
+        let response = ReportService::render_snapshot(&restored, &request, "user")
+            .await
+            .unwrap();
+        assert!(response.success);
+        assert_eq!(response.snapshot_id.as_deref(), Some(snapshot.id.as_str()));
+        assert!(response.html.unwrap().contains("<html"));
+    }
+
+    #[test]
+    fn test_inline_template_from_quick_save() {
+        let doc = serde_json::json!({
+            "name": "Draft",
+            "content": {
+                "slides": [
+                    { "canvas_json": "{\"objects\":[]}" },
+                    { "canvas_json": { "objects": [] } },
+                    { "name": "no canvas" }
+                ]
+            }
+        });
+        let template = inline_template(&doc).unwrap();
+        assert_eq!(template.name, "Draft");
+        assert_eq!(template.slides.len(), 2);
+        assert_eq!(template.slides[1].order, 1);
+
+        assert!(inline_template(&serde_json::json!({ "content": {} })).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use axur_core::api::report::PocReportData;
 use axur_core::i18n::Translations;
+use axur_core::plugins::{PluginConfig, ThemeMode};
 use axur_core::report::html::generate_report_with_plugins;
 use std::fs::File;
 use std::io::Write;
@@ -23,9 +24,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         show_compliance: true,
         custom_branding: true,
         theme: ThemeMode::Dark,
-        disabled_plugins: vec![],
-        custom_css: None,
         show_style_showcase: true, // Enable for mock verification
+        ..Default::default()
     };
 
     // 2. Load translations (compiled-in)
@@ -33,12 +33,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Translations::load("es").expect("Failed to load Spanish translations (es.json)");
     println!("Translations: Loaded 'es' locale.");
 
-    // 3. Generate HTML
-    // Using default config (which includes our new style_showcase if enabled by default,
-    // but style_showcase might need explicit enable if it's not in default config.
-    // Let's check PluginRegistry::with_builtins() to see if it includes strictly default plugins.
-    // For now, we assume standard report generation.)
-    let html = generate_report_with_plugins(&data, &translations, None, None);
+    // 3. Generate HTML with the mock configuration
+    let html = generate_report_with_plugins(&data, &translations, None, Some(config));
     println!(
         "Generation: Report generated successfully. Size: {} bytes",
         html.len()
@@ -0,0 +1,198 @@
+//! Authentication against the Axur identity API
+//!
+//! Mirrors the 3-step login flow used by the backend proxy:
+//! 1. `login` - Email/password → temp token + correlation
+//! 2. `verify_2fa` - 2FA code → 2FA token + device id
+//! 3. `finalize` - Email/password + device id → master token
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{create_client, API_URL};
+use crate::errors::CliError;
+
+/// Intermediate session state carried between login steps
+#[derive(Debug, Clone, Default)]
+pub struct AuthSession {
+    pub token: String,
+    pub correlation: Option<String>,
+    pub device_id: Option<String>,
+}
+
+// Internal Axur API response
+#[derive(Debug, Deserialize)]
+struct AxurAuthResponse {
+    correlation: Option<String>,
+    token: Option<String>,
+    #[serde(rename = "deviceId")]
+    device_id: Option<String>,
+}
+
+/// Step 1: Initial login with email/password
+pub async fn login(email: &str, password: &str) -> Result<AuthSession, CliError> {
+    if email.is_empty() || password.is_empty() {
+        return Err(CliError::InputError("Email and password required".into()));
+    }
+
+    let client = create_client().map_err(|e| CliError::Unknown(e.to_string()))?;
+    let url = format!("{}/identity/session", API_URL);
+
+    let resp = client
+        .post(&url)
+        .json(&json!({
+            "email": email,
+            "password": password
+        }))
+        .send()
+        .await?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        tracing::warn!("Axur login failed: {} - {}", status, body);
+        return Err(match status.as_u16() {
+            401 | 403 => CliError::AuthError("Invalid credentials".into()),
+            429 => CliError::RateLimited,
+            _ => CliError::ApiError(format!("HTTP {}", status)),
+        });
+    }
+
+    let data: AxurAuthResponse = resp
+        .json()
+        .await
+        .map_err(|e| CliError::ApiError(format!("Failed to parse Axur response: {}", e)))?;
+
+    let token = data
+        .token
+        .ok_or_else(|| CliError::AuthError("No temporary token received".into()))?;
+    let correlation = data.correlation.or_else(|| correlation_from_jwt(&token));
+
+    Ok(AuthSession {
+        token,
+        correlation,
+        device_id: None,
+    })
+}
+
+/// Step 2: 2FA verification
+pub async fn verify_2fa(session: &AuthSession, code: &str) -> Result<AuthSession, CliError> {
+    let code: u32 = code
+        .trim()
+        .parse()
+        .map_err(|_| CliError::InputError("2FA code must be numeric".into()))?;
+
+    let client = create_client().map_err(|e| CliError::Unknown(e.to_string()))?;
+    let url = format!("{}/identity/session/tfa", API_URL);
+
+    let mut req = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", session.token))
+        .json(&json!({ "code": code }));
+
+    if let Some(ref corr) = session.correlation {
+        req = req.header("oxref-token", corr);
+    }
+
+    let resp = req.send().await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        tracing::warn!("Axur 2FA failed: {} - {}", status, body);
+        return Err(CliError::AuthError("Invalid 2FA code".into()));
+    }
+
+    let data: AxurAuthResponse = resp
+        .json()
+        .await
+        .map_err(|e| CliError::ApiError(format!("Failed to parse Axur response: {}", e)))?;
+
+    Ok(AuthSession {
+        token: data
+            .token
+            .ok_or_else(|| CliError::AuthError("No 2FA token received".into()))?,
+        correlation: data.correlation.or_else(|| session.correlation.clone()),
+        device_id: data.device_id,
+    })
+}
+
+/// Step 3: Finalize login and obtain the master token
+pub async fn finalize(
+    email: &str,
+    password: &str,
+    session: &AuthSession,
+) -> Result<String, CliError> {
+    let device_id = session
+        .device_id
+        .as_deref()
+        .ok_or_else(|| CliError::AuthError("No device id received from 2FA step".into()))?;
+
+    let client = create_client().map_err(|e| CliError::Unknown(e.to_string()))?;
+    let url = format!("{}/identity/session", API_URL);
+
+    let mut req = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", session.token))
+        .header("Device-Id", device_id)
+        .json(&json!({
+            "email": email,
+            "password": password
+        }));
+
+    if let Some(ref corr) = session.correlation {
+        req = req.header("oxref-token", corr);
+    }
+
+    let resp = req.send().await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        tracing::warn!("Axur finalize failed: {} - {}", status, body);
+        return Err(CliError::AuthError("Failed to finalize session".into()));
+    }
+
+    let data: AxurAuthResponse = resp
+        .json()
+        .await
+        .map_err(|e| CliError::ApiError(format!("Failed to parse Axur response: {}", e)))?;
+
+    data.token
+        .ok_or_else(|| CliError::AuthError("No master token received".into()))
+}
+
+/// Extract the correlation id (`crl` claim) from a JWT payload
+fn correlation_from_jwt(token: &str) -> Option<String> {
+    use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+    use base64::Engine as _;
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload)
+        .or_else(|_| STANDARD_NO_PAD.decode(payload))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    claims.get("crl").and_then(|v| v.as_str()).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine as _;
+
+    #[test]
+    fn test_correlation_from_jwt() {
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"user","crl":"abc-123"}"#);
+        let token = format!("header.{}.signature", payload);
+        assert_eq!(correlation_from_jwt(&token), Some("abc-123".to_string()));
+    }
+
+    #[test]
+    fn test_correlation_from_jwt_missing_claim() {
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"user"}"#);
+        let token = format!("header.{}.signature", payload);
+        assert_eq!(correlation_from_jwt(&token), None);
+        assert_eq!(correlation_from_jwt("not-a-jwt"), None);
+    }
+}
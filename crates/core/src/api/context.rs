@@ -0,0 +1,126 @@
+//! Per-report settings of the Axur API fetchers
+//!
+//! [`ApiTransport`](super::transport::ApiTransport) only decides how requests
+//! are sent. Everything that belongs to one report or preview travels next to
+//! it in a [`ReportContext`]: the credential redaction policy, the Threat
+//! Hunting credit meter, cache settings, searched sources, the progress
+//! callback and the user the report runs for. A transport can be shared by
+//! several reports; a context cannot.
+
+use super::credits::CreditMeter;
+use super::redaction::RedactionPolicy;
+use super::th_cache::{ThCache, ThSearchKey};
+use super::th_progress::ThProgress;
+use super::th_query::ThSearchConfig;
+
+/// Settings of one report or preview
+#[derive(Debug, Clone)]
+pub struct ReportContext {
+    redaction: RedactionPolicy,
+    credits: CreditMeter,
+    th_cache: ThCache,
+    th_search: ThSearchConfig,
+    th_progress: ThProgress,
+    user_id: Option<String>,
+}
+
+impl Default for ReportContext {
+    /// Server redaction policy and cache TTL, unlimited credits, default
+    /// searches, no progress callback and no user
+    fn default() -> Self {
+        Self {
+            redaction: RedactionPolicy::from_env(),
+            credits: CreditMeter::default(),
+            th_cache: ThCache::from_env(),
+            th_search: ThSearchConfig::default(),
+            th_progress: ThProgress::default(),
+            user_id: None,
+        }
+    }
+}
+
+impl ReportContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the credential redaction policy applied to fetched report data
+    pub fn with_redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.redaction = policy;
+        self
+    }
+
+    /// Credential redaction policy applied to fetched report data
+    pub fn redaction(&self) -> &RedactionPolicy {
+        &self.redaction
+    }
+
+    /// Set the meter that limits and records Threat Hunting credits
+    pub fn with_credits(mut self, meter: CreditMeter) -> Self {
+        self.credits = meter;
+        self
+    }
+
+    /// Threat Hunting credit meter (unlimited unless set)
+    pub fn credits(&self) -> &CreditMeter {
+        &self.credits
+    }
+
+    /// Set the Threat Hunting result cache settings (e.g. to force a refresh)
+    pub fn with_th_cache(mut self, cache: ThCache) -> Self {
+        self.th_cache = cache;
+        self
+    }
+
+    /// Threat Hunting result cache settings
+    pub fn th_cache(&self) -> &ThCache {
+        &self.th_cache
+    }
+
+    /// Set the Threat Hunting sources and terms searched for threat intelligence
+    pub fn with_th_search(mut self, config: ThSearchConfig) -> Self {
+        self.th_search = config;
+        self
+    }
+
+    /// Threat Hunting sources and terms searched for threat intelligence
+    pub fn th_search(&self) -> &ThSearchConfig {
+        &self.th_search
+    }
+
+    /// Set the callback told about the progress of Threat Hunting searches
+    pub fn with_th_progress(mut self, progress: ThProgress) -> Self {
+        self.th_progress = progress;
+        self
+    }
+
+    /// Threat Hunting search progress callback (does nothing unless set)
+    pub fn th_progress(&self) -> &ThProgress {
+        &self.th_progress
+    }
+
+    /// Set the user the report runs for
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// User the report runs for (None for the CLI)
+    pub fn user_id(&self) -> Option<&str> {
+        self.user_id.as_deref()
+    }
+
+    /// Cache key of a search run for `tenant`: searches paid with the
+    /// user's own credits (`own_credits`) are only reused by that user
+    pub fn th_search_key(
+        &self,
+        source: &str,
+        query: &str,
+        tenant: &str,
+        own_credits: bool,
+        window: Option<(&str, &str)>,
+    ) -> ThSearchKey {
+        let user = if own_credits { self.user_id() } else { None };
+        ThSearchKey::new(source, query, tenant, user, window)
+    }
+}
@@ -0,0 +1,262 @@
+//! Threat Hunting Credit Accounting
+//!
+//! Threat Hunting searches are billed per page of results (ten results per
+//! page). A [`CreditMeter`] travels in the `ReportContext`: the search
+//! starters refuse to start a search once the meter's limit is reached, and
+//! every search records the pages it consumed, capped to what the limit
+//! leaves (the fetcher keeps only the results of those pages), so the
+//! caller can enforce a tenant budget up front and write the usage to a
+//! ledger after.
+//! Credits reserved for the meter are given back through
+//! [`CreditMeter::on_release`] once the last clone is dropped.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Results per billed page
+pub const RESULTS_PER_PAGE: u64 = 10;
+
+/// Pages billed for a search that retrieved `results` (at least one)
+pub fn pages_for(results: u64) -> u64 {
+    results.div_ceil(RESULTS_PER_PAGE).max(1)
+}
+
+/// Tenant credit budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreditBudget {
+    /// Credits the tenant can spend per calendar month (None = unlimited)
+    pub monthly_allowance: Option<u64>,
+    /// Credits a single report or preview can spend (None = unlimited)
+    pub per_report_cap: Option<u64>,
+}
+
+impl CreditBudget {
+    /// Credits available to the next report given this month's spending
+    /// (None = unlimited)
+    pub fn report_limit(&self, spent_this_month: u64) -> Option<u64> {
+        let remaining = self
+            .monthly_allowance
+            .map(|allowance| allowance.saturating_sub(spent_this_month));
+        match (remaining, self.per_report_cap) {
+            (Some(remaining), Some(cap)) => Some(remaining.min(cap)),
+            (remaining, cap) => remaining.or(cap),
+        }
+    }
+}
+
+/// Credits consumed by one search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchUsage {
+    pub search_id: String,
+    /// Threat Hunting source ("signal-lake", "credential", ...)
+    pub source: String,
+    pub query: String,
+    /// Pages consumed (one as soon as the search starts)
+    pub pages: u64,
+    /// Results retrieved, once the search completed
+    pub results: Option<u64>,
+}
+
+/// Callback run when a meter is dropped
+struct ReleaseHook(Option<Box<dyn FnOnce() + Send>>);
+
+impl Drop for ReleaseHook {
+    fn drop(&mut self) {
+        if let Some(release) = self.0.take() {
+            release();
+        }
+    }
+}
+
+impl std::fmt::Debug for ReleaseHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReleaseHook")
+    }
+}
+
+#[derive(Debug, Default)]
+struct MeterState {
+    limit: Option<u64>,
+    searches: Vec<SearchUsage>,
+    on_release: Option<ReleaseHook>,
+}
+
+/// Shared credit counter for the searches of one report or preview.
+/// Clones share the same counter.
+#[derive(Debug, Clone, Default)]
+pub struct CreditMeter {
+    state: Arc<Mutex<MeterState>>,
+}
+
+impl CreditMeter {
+    /// Meter refusing new searches once `limit` credits are spent
+    /// (None = unlimited)
+    pub fn with_limit(limit: Option<u64>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MeterState {
+                limit,
+                ..MeterState::default()
+            })),
+        }
+    }
+
+    /// Run `release` once the last clone of the meter is dropped (e.g. to
+    /// give back credits reserved for it)
+    pub fn on_release(self, release: impl FnOnce() + Send + 'static) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.on_release = Some(ReleaseHook(Some(Box::new(release))));
+        }
+        self
+    }
+
+    pub fn limit(&self) -> Option<u64> {
+        self.state.lock().map(|s| s.limit).unwrap_or_default()
+    }
+
+    /// Credits consumed so far
+    pub fn spent(&self) -> u64 {
+        self.state
+            .lock()
+            .map(|s| s.searches.iter().map(|u| u.pages).sum())
+            .unwrap_or_default()
+    }
+
+    /// Searches run so far
+    pub fn usage(&self) -> Vec<SearchUsage> {
+        self.state
+            .lock()
+            .map(|s| s.searches.clone())
+            .unwrap_or_default()
+    }
+
+    /// Fail when a new search (at least one page) would exceed the limit
+    pub fn check(&self) -> Result<()> {
+        let spent = self.spent();
+        if let Some(limit) = self.limit() {
+            if spent + 1 > limit {
+                bail!(
+                    "Threat Hunting credit budget exhausted ({} of {} credits used)",
+                    spent,
+                    limit
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a started search (one page until it completes)
+    pub fn started(&self, search_id: &str, source: &str, query: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.searches.push(SearchUsage {
+                search_id: search_id.to_string(),
+                source: source.to_string(),
+                query: query.to_string(),
+                pages: 1,
+                results: None,
+            });
+        }
+    }
+
+    /// Record a completed search that retrieved `results`. Pages beyond
+    /// what the limit leaves (after the other searches) are not billed;
+    /// returns how many results the caller may keep.
+    pub fn completed(&self, search_id: &str, results: u64) -> u64 {
+        let Ok(mut state) = self.state.lock() else {
+            return results;
+        };
+        let others: u64 = state
+            .searches
+            .iter()
+            .filter(|u| u.search_id != search_id)
+            .map(|u| u.pages)
+            .sum();
+        // The started page was allowed by `check`, so it always counts
+        let allowed = state
+            .limit
+            .map_or(u64::MAX, |limit| limit.saturating_sub(others).max(1));
+        let pages = pages_for(results).min(allowed);
+        let kept = results.min(pages.saturating_mul(RESULTS_PER_PAGE));
+        if let Some(usage) = state.searches.iter_mut().find(|u| u.search_id == search_id) {
+            usage.pages = pages;
+            usage.results = Some(kept);
+        }
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_limit() {
+        assert_eq!(CreditBudget::default().report_limit(500), None);
+        let budget = CreditBudget {
+            monthly_allowance: Some(100),
+            per_report_cap: Some(30),
+        };
+        assert_eq!(budget.report_limit(0), Some(30));
+        assert_eq!(budget.report_limit(90), Some(10));
+        assert_eq!(budget.report_limit(120), Some(0));
+        let cap_only = CreditBudget {
+            per_report_cap: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(cap_only.report_limit(1000), Some(5));
+    }
+
+    #[test]
+    fn test_meter() {
+        let meter = CreditMeter::with_limit(Some(3));
+        let shared = meter.clone();
+        assert!(meter.check().is_ok());
+        shared.started("s1", "signal-lake", "domain=\"acme.com\"");
+        shared.completed("s1", 15);
+        assert_eq!(meter.spent(), 2);
+        assert!(meter.check().is_ok());
+        meter.started("s2", "chat-message", "content=\"acme\"");
+        assert_eq!(meter.spent(), 3);
+        assert!(meter.check().is_err());
+
+        meter.completed("s2", 0);
+        let usage = meter.usage();
+        assert_eq!(usage[1].pages, 1);
+        assert_eq!(usage[1].results, Some(0));
+        assert!(CreditMeter::default().check().is_ok());
+    }
+
+    #[test]
+    fn test_completed_is_capped_to_the_limit() {
+        let meter = CreditMeter::with_limit(Some(5));
+        meter.started("s1", "signal-lake", "domain=\"acme.com\"");
+        meter.started("s2", "chat-message", "content=\"acme\"");
+        // 120 results would be 12 pages; only 4 are left next to s2's page
+        assert_eq!(meter.completed("s1", 120), 40);
+        assert_eq!(meter.usage()[0].pages, 4);
+        // s2 keeps its started page and nothing more
+        assert_eq!(meter.completed("s2", 35), 10);
+        assert_eq!(meter.spent(), 5);
+
+        let unlimited = CreditMeter::default();
+        unlimited.started("s3", "signal-lake", "q");
+        assert_eq!(unlimited.completed("s3", 120), 120);
+        assert_eq!(unlimited.spent(), 12);
+    }
+
+    #[test]
+    fn test_release_after_last_clone() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let released = Arc::new(AtomicBool::new(false));
+        let flag = released.clone();
+        let meter =
+            CreditMeter::with_limit(Some(5)).on_release(move || flag.store(true, Ordering::SeqCst));
+        let shared = meter.clone();
+        drop(meter);
+        assert!(!released.load(Ordering::SeqCst));
+        drop(shared);
+        assert!(released.load(Ordering::SeqCst));
+    }
+}
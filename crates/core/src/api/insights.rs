@@ -0,0 +1,433 @@
+//! Insight rules engine
+//!
+//! Each insight is a rule: conditions over report metrics, a severity (with
+//! optional escalations for stronger signals), an icon and the translation
+//! keys of its title, description and recommended action. Conditions use the
+//! declarative slide syntax (`"credentials_total > 100"`) and descriptions
+//! may bind metrics (`{{takedown_rate|number}}`). The default pack reproduces
+//! the original fixed thresholds; tenants and industries can ship their own
+//! pack through a report preset. Evaluation returns the rules that fired and
+//! the metric values they read, so the output can be audited.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::report::{ComparisonData, PocReportData};
+use crate::plugins::declarative::{lookup, report_fields, report_value, Condition};
+
+/// Metrics derived from the report data, on top of its fields
+const DERIVED_METRICS: [&str; 8] = [
+    "takedown_total",
+    "takedown_open",
+    "takedown_rate",
+    "phishing_threats",
+    "social_threats",
+    "tickets_delta_pct",
+    "credentials_delta_pct",
+    "takedowns_delta_pct",
+];
+
+/// Insight severity, most severe first
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum InsightSeverity {
+    Critical,
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl InsightSeverity {
+    /// Translation key of the badge label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            InsightSeverity::Critical => "severity_critical",
+            InsightSeverity::High => "severity_high",
+            InsightSeverity::Medium => "severity_medium",
+            InsightSeverity::Low => "severity_low",
+        }
+    }
+}
+
+/// A higher (or lower) severity when more conditions hold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeverityEscalation {
+    pub when: Vec<String>,
+    pub severity: InsightSeverity,
+}
+
+/// One insight rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsightRule {
+    /// Stable identifier, reported when the rule fires
+    pub id: String,
+    #[serde(default)]
+    pub icon: String,
+    /// Conditions over report metrics that must all hold
+    #[serde(default)]
+    pub when: Vec<String>,
+    #[serde(default)]
+    pub severity: InsightSeverity,
+    /// Checked in order; the first whose conditions all hold sets the severity
+    #[serde(default)]
+    pub escalations: Vec<SeverityEscalation>,
+    pub title_key: String,
+    pub description_key: String,
+    pub action_key: String,
+}
+
+/// A set of rules for a tenant or industry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InsightRulePack {
+    pub id: String,
+    pub name: String,
+    pub rules: Vec<InsightRule>,
+    /// Shown when no rule fires
+    pub fallback: Option<InsightRule>,
+    /// Most insights shown on the slide (most severe first)
+    pub max_insights: usize,
+}
+
+/// A rule that fired, with the metric values its conditions read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredInsight {
+    pub rule_id: String,
+    pub severity: InsightSeverity,
+    pub icon: String,
+    pub title_key: String,
+    pub description_key: String,
+    pub action_key: String,
+    /// Metric -> value for every condition the rule and its escalations read
+    pub inputs: BTreeMap<String, Value>,
+}
+
+/// Rule with the default `insight_<id>_*` translation keys
+fn rule(id: &str, icon: &str, when: &[&str], severity: InsightSeverity) -> InsightRule {
+    InsightRule {
+        id: id.to_string(),
+        icon: icon.to_string(),
+        when: when.iter().map(|c| c.to_string()).collect(),
+        severity,
+        escalations: Vec::new(),
+        title_key: format!("insight_{}_title", id),
+        description_key: format!("insight_{}_desc", id),
+        action_key: format!("insight_{}_action", id),
+    }
+}
+
+impl InsightRule {
+    fn escalate(mut self, when: &[&str], severity: InsightSeverity) -> Self {
+        self.escalations.push(SeverityEscalation {
+            when: when.iter().map(|c| c.to_string()).collect(),
+            severity,
+        });
+        self
+    }
+
+    fn conditions(&self) -> impl Iterator<Item = &String> {
+        self.when
+            .iter()
+            .chain(self.escalations.iter().flat_map(|e| &e.when))
+    }
+
+    /// Severity when the rule fires on `metrics`, None when it doesn't
+    fn evaluate(&self, metrics: &Value) -> Option<FiredInsight> {
+        if !all_hold(&self.when, metrics) {
+            return None;
+        }
+        let severity = self
+            .escalations
+            .iter()
+            .find(|e| all_hold(&e.when, metrics))
+            .map_or(self.severity, |e| e.severity);
+
+        let inputs = self
+            .conditions()
+            .filter_map(|c| Condition::parse(c).ok())
+            .map(|c| {
+                let value = lookup(metrics, &c.path).cloned().unwrap_or(Value::Null);
+                (c.path, value)
+            })
+            .collect();
+
+        Some(FiredInsight {
+            rule_id: self.id.clone(),
+            severity,
+            icon: self.icon.clone(),
+            title_key: self.title_key.clone(),
+            description_key: self.description_key.clone(),
+            action_key: self.action_key.clone(),
+            inputs,
+        })
+    }
+}
+
+/// Unparseable conditions never hold (packs are validated before use)
+fn all_hold(conditions: &[String], metrics: &Value) -> bool {
+    conditions.iter().all(|c| {
+        Condition::parse(c)
+            .map(|c| c.holds(metrics))
+            .unwrap_or(false)
+    })
+}
+
+impl Default for InsightRulePack {
+    fn default() -> Self {
+        use InsightSeverity::*;
+
+        Self {
+            id: "default".to_string(),
+            name: "Default".to_string(),
+            rules: vec![
+                rule(
+                    "credential_exposure",
+                    "🔑",
+                    &["credentials_total > 100"],
+                    High,
+                )
+                .escalate(&["credentials_total > 500"], Critical),
+                rule(
+                    "low_takedown_rate",
+                    "⚡",
+                    &["takedown_rate < 50", "takedown_total > 20"],
+                    High,
+                ),
+                rule("code_secrets", "📦", &["secrets_total > 10"], Medium)
+                    .escalate(&["production_secrets > 5"], Critical),
+                rule("phishing_campaigns", "🎣", &["phishing_threats > 5"], High)
+                    .escalate(&["phishing_threats > 20"], Critical),
+                rule(
+                    "social_impersonation",
+                    "👤",
+                    &["social_threats > 3"],
+                    Medium,
+                ),
+                rule(
+                    "takedown_efficiency",
+                    "✅",
+                    &["takedown_rate > 80", "takedown_resolved > 10"],
+                    Low,
+                ),
+                rule("high_volume", "📈", &["total_tickets > 500"], Medium),
+                rule(
+                    "threat_growth",
+                    "📈",
+                    &["tickets_delta_pct >= 25", "total_tickets >= 20"],
+                    Medium,
+                )
+                .escalate(&["tickets_delta_pct >= 50"], High),
+                rule(
+                    "credential_growth",
+                    "🔐",
+                    &["credentials_delta_pct >= 25", "credentials_total >= 50"],
+                    High,
+                ),
+                rule(
+                    "takedown_growth",
+                    "🚀",
+                    &["takedowns_delta_pct >= 20", "takedown_resolved >= 5"],
+                    Low,
+                ),
+            ],
+            fallback: Some(rule("stable_posture", "📊", &[], Low)),
+            max_insights: 6,
+        }
+    }
+}
+
+impl InsightRulePack {
+    /// Check every condition parses and reads a known metric
+    pub fn validate(&self) -> Result<(), String> {
+        let fields = report_fields();
+        let known = |path: &str| {
+            let root = path.split('.').next().unwrap_or_default();
+            DERIVED_METRICS.contains(&root) || fields.iter().any(|f| f == root)
+        };
+
+        for rule in self.rules.iter().chain(&self.fallback) {
+            if rule.id.trim().is_empty() {
+                return Err("Insight rule without id".into());
+            }
+            for source in rule.conditions() {
+                let condition = Condition::parse(source)
+                    .map_err(|e| format!("Insight rule '{}': {}", rule.id, e))?;
+                if !known(&condition.path) {
+                    return Err(format!(
+                        "Insight rule '{}': unknown metric '{}'",
+                        rule.id, condition.path
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rules that fire on the report, most severe first (rule order within
+    /// a severity), or the fallback when none does
+    pub fn evaluate(&self, data: &PocReportData) -> Vec<FiredInsight> {
+        let metrics = report_metrics(data);
+        let mut fired: Vec<FiredInsight> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.evaluate(&metrics))
+            .collect();
+        if fired.is_empty() {
+            fired.extend(self.fallback.iter().filter_map(|r| r.evaluate(&metrics)));
+        }
+        fired.sort_by_key(|insight| insight.severity);
+        fired
+    }
+}
+
+/// Report fields plus the derived metrics, the values rules and
+/// descriptions refer to
+pub fn report_metrics(data: &PocReportData) -> Value {
+    let mut metrics = report_value(data);
+    let Value::Object(map) = &mut metrics else {
+        return metrics;
+    };
+
+    let takedown_total = data.takedown_resolved
+        + data.takedown_pending
+        + data.takedown_aborted
+        + data.takedown_unresolved;
+    let takedown_rate = if takedown_total > 0 {
+        data.takedown_resolved as f64 / takedown_total as f64 * 100.0
+    } else {
+        0.0
+    };
+    let threats = |matches: &dyn Fn(&str) -> bool| -> u64 {
+        data.threats_by_type
+            .iter()
+            .filter(|t| matches(&t.threat_type.to_lowercase()))
+            .map(|t| t.count)
+            .sum()
+    };
+
+    map.insert("takedown_total".into(), takedown_total.into());
+    map.insert(
+        "takedown_open".into(),
+        (takedown_total - data.takedown_resolved).into(),
+    );
+    map.insert("takedown_rate".into(), takedown_rate.into());
+    map.insert(
+        "phishing_threats".into(),
+        threats(&|t| t.contains("phishing")).into(),
+    );
+    map.insert(
+        "social_threats".into(),
+        threats(&|t| t.contains("social") || t.contains("fake")).into(),
+    );
+
+    if let Some(cmp) = &data.comparison {
+        let deltas = [
+            ("tickets_delta_pct", data.total_tickets, cmp.prev_tickets),
+            (
+                "credentials_delta_pct",
+                data.credentials_total,
+                cmp.prev_credentials,
+            ),
+            (
+                "takedowns_delta_pct",
+                data.takedown_resolved,
+                cmp.prev_takedowns,
+            ),
+        ];
+        for (key, current, previous) in deltas {
+            if let Some(delta) = ComparisonData::delta_pct(current, previous) {
+                map.insert(key.into(), delta.into());
+            }
+        }
+    }
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(fired: &[FiredInsight]) -> Vec<&str> {
+        fired.iter().map(|f| f.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_default_pack() {
+        let pack = InsightRulePack::default();
+        assert!(pack.validate().is_ok());
+
+        let mut data = PocReportData::demo();
+        data.credentials_total = 600;
+        data.secrets_total = 0;
+        data.total_tickets = 100;
+        data.comparison = None;
+        let fired = pack.evaluate(&data);
+
+        let credentials = fired
+            .iter()
+            .find(|f| f.rule_id == "credential_exposure")
+            .unwrap();
+        assert_eq!(credentials.severity, InsightSeverity::Critical);
+        assert_eq!(credentials.inputs["credentials_total"], 600);
+        assert_eq!(fired[0].severity, InsightSeverity::Critical);
+        assert!(!ids(&fired).contains(&"code_secrets"));
+        assert!(!ids(&fired).contains(&"threat_growth"));
+
+        // Nothing notable: the fallback insight
+        let quiet = InsightRulePack {
+            rules: vec![],
+            ..Default::default()
+        };
+        assert_eq!(ids(&quiet.evaluate(&data)), ["stable_posture"]);
+    }
+
+    #[test]
+    fn test_custom_pack() {
+        let pack: InsightRulePack = serde_json::from_value(serde_json::json!({
+            "id": "fintech",
+            "rules": [{
+                "id": "any_credentials",
+                "icon": "🔑",
+                "when": ["credentials_total > 0"],
+                "severity": "low",
+                "escalations": [{ "when": ["takedown_rate < 99"], "severity": "high" }],
+                "title_key": "insight_credential_exposure_title",
+                "description_key": "insight_credential_exposure_desc",
+                "action_key": "insight_credential_exposure_action"
+            }]
+        }))
+        .unwrap();
+        assert!(pack.validate().is_ok());
+        assert_eq!(pack.max_insights, 6);
+
+        let fired = pack.evaluate(&PocReportData::demo());
+        assert_eq!(ids(&fired), ["any_credentials"]);
+        assert_eq!(fired[0].severity, InsightSeverity::High);
+        assert!(fired[0].inputs.contains_key("takedown_rate"));
+
+        let unknown = InsightRulePack {
+            rules: vec![rule(
+                "typo",
+                "",
+                &["credential_total > 0"],
+                InsightSeverity::Low,
+            )],
+            ..Default::default()
+        };
+        assert!(unknown.validate().unwrap_err().contains("credential_total"));
+        let invalid = InsightRulePack {
+            rules: vec![rule(
+                "bad",
+                "",
+                &["credentials_total >"],
+                InsightSeverity::Low,
+            )],
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -1,7 +1,17 @@
 //! API module for Axur platform interactions
 
+pub mod auth;
+pub mod context;
+pub mod credits;
+pub mod insights;
+pub mod redaction;
 pub mod report;
 pub mod retry;
+pub mod risk;
+pub mod th_cache;
+pub mod th_progress;
+pub mod th_query;
+pub mod transport;
 
 /// Base API URL
 pub const API_URL: &str = "https://api.axur.com/gateway/1.0/api";
@@ -0,0 +1,226 @@
+//! Credential Redaction Policy
+//!
+//! Credential exposures come back from the exposure API with the raw user
+//! name and password (`fetch_critical_credentials` even selects passwords
+//! that contain the tenant name). The policy masks them once, when
+//! `fetch_full_report_with` assembles [`PocReportData`], so plugins, exports,
+//! stored snapshots and archived reports only ever see masked values.
+//! [`redact_log_text`] applies the same masking to debug dumps and remote
+//! log uploads.
+//!
+//! Environment overrides used by [`RedactionPolicy::from_env`]:
+//! - `AXUR_REDACT_PASSWORD` - `full`, `partial`, `hash` or `omit` (default `full`)
+//! - `AXUR_REDACT_USER` - same values (default `partial`)
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::report::{CredentialExposure, PocReportData};
+
+/// How a single credential field is masked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskMode {
+    /// Fixed mask, reveals nothing but presence
+    #[default]
+    Full,
+    /// First and last character plus the length ("s***3 (7)")
+    Partial,
+    /// Short SHA-256 fingerprint, so repeated values can still be correlated
+    HashOnly,
+    /// Value removed
+    Omit,
+}
+
+const FULL_MASK: &str = "********";
+
+impl MaskMode {
+    /// Parse a policy value ("full", "partial", "hash"/"hash_only", "omit")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "full" => Some(Self::Full),
+            "partial" => Some(Self::Partial),
+            "hash" | "hash_only" | "hash-only" => Some(Self::HashOnly),
+            "omit" => Some(Self::Omit),
+            _ => None,
+        }
+    }
+
+    /// Masked form of a value (None when omitted)
+    pub fn mask(self, value: &str) -> Option<String> {
+        match self {
+            Self::Full => Some(FULL_MASK.to_string()),
+            Self::Partial => {
+                let chars: Vec<char> = value.chars().collect();
+                // Too short to reveal anything without giving most of it away
+                if chars.len() <= 4 {
+                    return Some(format!("{} ({})", "*".repeat(chars.len()), chars.len()));
+                }
+                Some(format!(
+                    "{}***{} ({})",
+                    chars[0],
+                    chars[chars.len() - 1],
+                    chars.len()
+                ))
+            }
+            Self::HashOnly => {
+                let digest = Sha256::digest(value.as_bytes());
+                let hex: String = digest
+                    .iter()
+                    .take(6)
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                Some(format!("sha256:{}", hex))
+            }
+            Self::Omit => None,
+        }
+    }
+}
+
+/// Masking applied to credential exposures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    /// User name / e-mail of the leaked account
+    pub user: MaskMode,
+    /// Leaked password
+    pub password: MaskMode,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            user: MaskMode::Partial,
+            password: MaskMode::Full,
+        }
+    }
+}
+
+impl RedactionPolicy {
+    /// Policy configured by `AXUR_REDACT_USER` and `AXUR_REDACT_PASSWORD`
+    /// (unknown values keep the default)
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(mode) = std::env::var("AXUR_REDACT_USER")
+            .ok()
+            .and_then(|v| MaskMode::parse(&v))
+        {
+            policy.user = mode;
+        }
+        if let Some(mode) = std::env::var("AXUR_REDACT_PASSWORD")
+            .ok()
+            .and_then(|v| MaskMode::parse(&v))
+        {
+            policy.password = mode;
+        }
+        policy
+    }
+
+    /// Mask the user and password of one exposure
+    pub fn redact(&self, credential: &mut CredentialExposure) {
+        credential.user = credential
+            .user
+            .as_deref()
+            .and_then(|user| self.user.mask(user));
+        credential.password = credential
+            .password
+            .as_deref()
+            .and_then(|password| self.password.mask(password));
+    }
+
+    /// Mask every credential exposure of the report
+    pub fn apply(&self, data: &mut PocReportData) {
+        for credential in data
+            .credential_exposures
+            .iter_mut()
+            .chain(data.critical_credentials.iter_mut())
+        {
+            self.redact(credential);
+        }
+    }
+
+    /// Mask credential-shaped objects (those with a `password` key) anywhere
+    /// in a JSON value. Returns whether anything changed.
+    pub fn redact_json(&self, value: &mut serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut changed = false;
+                if map.contains_key("password") {
+                    for (key, mode) in [("user", self.user), ("password", self.password)] {
+                        if let Some(serde_json::Value::String(raw)) = map.get(key) {
+                            let masked = mode
+                                .mask(raw)
+                                .map(serde_json::Value::String)
+                                .unwrap_or(serde_json::Value::Null);
+                            map.insert(key.to_string(), masked);
+                            changed = true;
+                        }
+                    }
+                }
+                for nested in map.values_mut() {
+                    changed |= self.redact_json(nested);
+                }
+                changed
+            }
+            serde_json::Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |changed, item| self.redact_json(item) | changed),
+            _ => false,
+        }
+    }
+}
+
+/// Log-safe copy of a JSON body (API responses, debug dumps) under the
+/// configured policy. Text that is not JSON is returned unchanged.
+pub fn redact_log_text(text: &str) -> String {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return text.to_string();
+    };
+    if RedactionPolicy::from_env().redact_json(&mut value) {
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_modes() {
+        assert_eq!(MaskMode::Full.mask("acme2024").unwrap(), "********");
+        assert_eq!(MaskMode::Partial.mask("acme2024").unwrap(), "a***4 (8)");
+        assert_eq!(MaskMode::Partial.mask("abc").unwrap(), "*** (3)");
+        let hash = MaskMode::HashOnly.mask("acme2024").unwrap();
+        assert!(hash.starts_with("sha256:") && hash.len() == 19);
+        assert_eq!(hash, MaskMode::HashOnly.mask("acme2024").unwrap());
+        assert!(MaskMode::Omit.mask("acme2024").is_none());
+        assert_eq!(MaskMode::parse("Hash-Only"), Some(MaskMode::HashOnly));
+        assert!(MaskMode::parse("none").is_none());
+    }
+
+    #[test]
+    fn test_apply_to_report_and_logs() {
+        let mut data = PocReportData::demo();
+        data.critical_credentials = vec![CredentialExposure {
+            user: Some("maria@acme.com".into()),
+            password: Some("Acme@2024".into()),
+            password_length: Some(9),
+            ..Default::default()
+        }];
+        RedactionPolicy::default().apply(&mut data);
+        let cred = &data.critical_credentials[0];
+        assert_eq!(cred.user.as_deref(), Some("m***m (14)"));
+        assert_eq!(cred.password.as_deref(), Some("********"));
+        assert_eq!(cred.password_length, Some(9));
+
+        let body =
+            r#"{"detections":[{"user":"maria@acme.com","password":"Acme@2024"}],"user":"kept"}"#;
+        let redacted = redact_log_text(body);
+        assert!(!redacted.contains("Acme@2024"));
+        assert!(!redacted.contains("maria@acme.com"));
+        assert!(redacted.contains("kept"));
+        assert_eq!(redact_log_text("not json"), "not json");
+    }
+}
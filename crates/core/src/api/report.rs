@@ -1,8 +1,16 @@
 #![allow(dead_code)]
 #![allow(unused)]
 
+use crate::api::context::ReportContext;
+use crate::api::credits::pages_for;
 use crate::api::retry::retry_api_call;
-use crate::api::{create_client, API_URL};
+use crate::api::risk::RiskFactor;
+use crate::api::th_cache::ThSearchKey;
+use crate::api::th_progress::{SearchStatus, SearchTimeout};
+use crate::api::th_query::{MonitoredAssets, ThQuery};
+use crate::api::transport::ApiTransport;
+use crate::error_codes::ReportStep;
+use crate::i18n::Translations;
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Local;
@@ -17,11 +25,7 @@ use std::io::Write;
 // ========================
 
 /// Download image from Axur API with auth and convert to base64 data URI
-async fn download_image_as_base64(
-    client: &reqwest::Client,
-    auth: &str,
-    url: &str,
-) -> Option<String> {
+async fn download_image_as_base64(client: &ApiTransport, auth: &str, url: &str) -> Option<String> {
     // Est. requests: 1 (with up to 3 retries)
     let resp = retry_api_call(
         || async {
@@ -94,6 +98,7 @@ fn log_api_call(endpoint_name: &str, url: &str, status: u16, success: bool, body
     }
 
     ensure_debug_dir();
+    let body = &super::redaction::redact_log_text(body);
 
     // Save response JSON
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
@@ -143,9 +148,9 @@ pub struct TenantInfo {
 /// Fetch all tenants available to the authenticated user.
 /// Used for interactive tenant picker when no --tenants flag provided.
 pub async fn fetch_available_tenants(token: &str) -> Result<Vec<TenantInfo>> {
-    let client = create_client()?;
+    let client = ApiTransport::from_env()?;
     let auth_header = format!("Bearer {}", token);
-    let url = format!("{}/customers/customers", API_URL);
+    let url = format!("{}/customers/customers", client.base_url());
 
     let resp = client
         .get(&url)
@@ -253,12 +258,56 @@ pub struct CredentialCount {
 // RISK SCORE STRUCTURE
 // ========================
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct RiskScore {
     pub current: f64,
+    /// Scores of past periods, oldest first, ending with `current`
     pub history: Vec<f64>,
+    /// End date of each `history` period
+    #[serde(default)]
+    pub history_labels: Vec<String>,
     pub label: String,
     pub color: String,
+    /// Per-factor breakdown (volume, benchmark, hygiene, efficiency, reputation)
+    #[serde(default)]
+    pub factors: Vec<RiskFactor>,
+}
+
+/// Inputs of the risk score, captured from the risk metric endpoints
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RiskInputs {
+    /// Incident counts by ticket type (threat volume)
+    pub incidents_by_ticket_type: Vec<NameValuePair>,
+    /// Latest market-segment monthly median (None without benchmark data)
+    pub market_median: Option<f64>,
+    /// Critical credential leaks
+    pub critical_leaks: Option<u64>,
+    /// Takedown success rate in percent
+    pub takedown_success_rate: Option<f64>,
+    /// Takedown raw median uptime in milliseconds
+    pub takedown_median_uptime_ms: Option<f64>,
+    /// Web complaints volume
+    pub web_complaints: Option<u64>,
+}
+
+/// Risk score inputs of a past period
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RiskPeriodInputs {
+    pub start_date: String,
+    pub end_date: String,
+    pub inputs: RiskInputs,
+}
+
+/// Raw fetch results the builtin data plugins derive insights from
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EnrichmentInputs {
+    /// Repositories with most code leaks (from getCodeLeaksSummary)
+    pub top_repositories: Vec<NameValuePair>,
+    /// Risk score inputs
+    pub risk: RiskInputs,
+    /// Risk score inputs of the periods before the report, oldest first
+    #[serde(default)]
+    pub risk_history: Vec<RiskPeriodInputs>,
 }
 
 // ========================
@@ -266,7 +315,7 @@ pub struct RiskScore {
 // ========================
 
 /// Full PoC report data matching the exact structure of axur_presentation_tool
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PocReportData {
     // Customer Info
     pub company_name: String,
@@ -370,8 +419,17 @@ pub struct PocReportData {
     #[serde(default)]
     pub comparison: Option<ComparisonData>,
 
+    // NEW: Detection activity by weekday/hour (tenant time zone)
+    #[serde(default)]
+    pub activity_heatmap: ActivityHeatmap,
+
     // UI FLAGS
     pub is_dynamic_window: bool,
+
+    // Raw inputs for the builtin data plugins. None means the data is final
+    // (demo data, snapshots stored before data plugins existed).
+    #[serde(default)]
+    pub enrichment_inputs: Option<EnrichmentInputs>,
 }
 
 impl PocReportData {
@@ -619,6 +677,7 @@ impl PocReportData {
                 threats_detected: 2800,
                 takedown_resolution_rate: 98.0,
                 response_speed_multiplier: 12.0,
+                hours_saved_prev: Some(950.0),
                 has_significant_data: true,
             },
             story_tickets: vec![],
@@ -631,33 +690,328 @@ impl PocReportData {
                 history: vec![400.0, 420.0, 450.0],
                 label: "Medium".to_string(),
                 color: "#FFA500".to_string(),
+                ..Default::default()
             },
             comparison: Some(ComparisonData {
                 period_label: "vs. Last Month".to_string(),
                 prev_tickets: 200,
                 prev_takedowns: 100,
                 prev_credentials: 400,
+                ..Default::default()
             }),
+            activity_heatmap: ActivityHeatmap::demo(),
             is_dynamic_window: true,
+            enrichment_inputs: None,
         }
     }
 }
 
 /// Data for period-over-period comparison analysis
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ComparisonData {
-    /// Label for the comparison period (e.g., "vs. Enero 2024")
+    /// Label for the comparison period (e.g., "vs. Enero 2024"), used when
+    /// `period` is unknown
     pub period_label: String,
+    /// Comparison window the previous counts were fetched for
+    #[serde(default)]
+    pub period: Option<ComparisonPeriod>,
     /// Previous period ticket count
     pub prev_tickets: u64,
     /// Previous period takedown count
     pub prev_takedowns: u64,
     /// Previous period credentials count
     pub prev_credentials: u64,
+    /// Previous period start date (YYYY-MM-DD)
+    #[serde(default)]
+    pub prev_start_date: String,
+    /// Previous period end date (YYYY-MM-DD)
+    #[serde(default)]
+    pub prev_end_date: String,
+}
+
+impl ComparisonData {
+    /// Label of the comparison period in the report language
+    pub fn label(&self, t: &Translations) -> String {
+        match &self.period {
+            Some(period) if !self.prev_start_date.is_empty() => {
+                period.label(t, &self.prev_start_date, &self.prev_end_date)
+            }
+            _ => self.period_label.clone(),
+        }
+    }
+
+    /// Percentage change from previous to current (None when previous is 0)
+    pub fn delta_pct(current: u64, previous: u64) -> Option<f64> {
+        if previous == 0 {
+            return None;
+        }
+        Some((current as f64 - previous as f64) / previous as f64 * 100.0)
+    }
+}
+
+/// Which previous window to compare the report period against
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ComparisonPeriod {
+    /// Window of the same length immediately before the report period
+    PreviousPeriod,
+    /// Report period shifted back one calendar month
+    PreviousMonth,
+    /// Report period shifted back one quarter (3 months)
+    PreviousQuarter,
+    /// Explicit date range (YYYY-MM-DD)
+    Custom { from: String, to: String },
+}
+
+impl ComparisonPeriod {
+    /// Resolve the previous window for the given report period
+    pub fn window(&self, from: &str, to: &str) -> Option<(String, String)> {
+        use chrono::{Months, NaiveDate};
+
+        let from_date = NaiveDate::parse_from_str(from, "%Y-%m-%d").ok()?;
+        let to_date = NaiveDate::parse_from_str(to, "%Y-%m-%d").ok()?;
+        if to_date < from_date {
+            return None;
+        }
+
+        let (prev_from, prev_to) = match self {
+            ComparisonPeriod::PreviousPeriod => {
+                let days = (to_date - from_date).num_days();
+                let prev_to = from_date.pred_opt()?;
+                (prev_to - chrono::Duration::days(days), prev_to)
+            }
+            ComparisonPeriod::PreviousMonth => (
+                from_date.checked_sub_months(Months::new(1))?,
+                to_date.checked_sub_months(Months::new(1))?,
+            ),
+            ComparisonPeriod::PreviousQuarter => (
+                from_date.checked_sub_months(Months::new(3))?,
+                to_date.checked_sub_months(Months::new(3))?,
+            ),
+            ComparisonPeriod::Custom { from, to } => {
+                let f = NaiveDate::parse_from_str(from, "%Y-%m-%d").ok()?;
+                let t = NaiveDate::parse_from_str(to, "%Y-%m-%d").ok()?;
+                if t < f {
+                    return None;
+                }
+                (f, t)
+            }
+        };
+
+        Some((
+            prev_from.format("%Y-%m-%d").to_string(),
+            prev_to.format("%Y-%m-%d").to_string(),
+        ))
+    }
+
+    /// Short label shown on the comparative, insights and ROI slides
+    pub fn label(&self, t: &Translations, prev_from: &str, prev_to: &str) -> String {
+        let key = match self {
+            ComparisonPeriod::PreviousPeriod => "comparison_previous_period",
+            ComparisonPeriod::PreviousMonth => "comparison_previous_month",
+            ComparisonPeriod::PreviousQuarter => "comparison_previous_quarter",
+            ComparisonPeriod::Custom { .. } => "comparison_custom",
+        };
+        t.format(key, &[("from", prev_from), ("to", prev_to)])
+    }
+}
+
+/// Default tenant time zone, matching the `timezone=-03:00` used by the exposure API calls
+pub const DEFAULT_TENANT_TIMEZONE: &str = "-03:00";
+
+/// Minimum number of timestamped tickets before the heatmap is considered meaningful
+pub const HEATMAP_MIN_SAMPLES: u64 = 30;
+
+/// Detection activity bucketed by weekday and hour in the tenant's time zone
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ActivityHeatmap {
+    /// Time zone used for bucketing (e.g., "-03:00" or "America/Sao_Paulo")
+    pub timezone: String,
+    /// Offset from UTC in minutes (for zones with daylight saving, the
+    /// offset at the latest timestamp)
+    pub utc_offset_minutes: i32,
+    /// 7x24 grid, Monday first, hours 0-23 local time
+    pub grid: [[u32; 24]; 7],
+    /// Tickets that were placed in the grid
+    pub sample_size: u64,
+    /// Tickets in the period according to the API (may exceed `sample_size` when capped)
+    pub total_in_period: u64,
+}
+
+impl ActivityHeatmap {
+    /// Bucket RFC 3339 timestamps into the grid using the given time zone
+    /// (daylight saving applied per timestamp for IANA zones). Unknown zones
+    /// fall back to UTC. Unparseable timestamps are skipped; naive timestamps
+    /// are treated as UTC.
+    pub fn from_timestamps<'a, I>(timestamps: I, timezone: &str) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        use chrono::{Datelike, Offset, Timelike};
+
+        let (timezone, zone) = match TenantTimeZone::parse(timezone) {
+            Some(zone) => (timezone.trim().to_string(), zone),
+            None => {
+                tracing::warn!("Unknown tenant time zone '{}', using UTC", timezone);
+                ("UTC".to_string(), TenantTimeZone::Fixed(chrono::Utc.fix()))
+            }
+        };
+
+        let mut heatmap = ActivityHeatmap {
+            timezone,
+            ..Default::default()
+        };
+
+        let mut latest: Option<chrono::DateTime<chrono::Utc>> = None;
+        for ts in timestamps {
+            let Some(utc) = parse_ticket_timestamp(ts) else {
+                continue;
+            };
+            let local = zone.local(utc);
+            let day = local.weekday().num_days_from_monday() as usize;
+            heatmap.grid[day][local.hour() as usize] += 1;
+            heatmap.sample_size += 1;
+            latest = latest.max(Some(utc));
+        }
+
+        let reference = zone.local(latest.unwrap_or_else(chrono::Utc::now));
+        heatmap.utc_offset_minutes = reference.offset().local_minus_utc() / 60;
+        heatmap
+    }
+
+    /// Demo grid with a weekday business-hours pattern for mock reports
+    pub fn demo() -> Self {
+        let mut heatmap = ActivityHeatmap {
+            timezone: DEFAULT_TENANT_TIMEZONE.to_string(),
+            utc_offset_minutes: -180,
+            ..Default::default()
+        };
+        for (day, row) in heatmap.grid.iter_mut().enumerate() {
+            for (hour, cell) in row.iter_mut().enumerate() {
+                let base: u32 = if (9..=18).contains(&hour) { 12 } else { 3 };
+                *cell = if day < 5 { base } else { base / 3 } + ((day * 24 + hour) % 4) as u32;
+            }
+        }
+        heatmap.sample_size = heatmap.grid.iter().flatten().map(|&c| c as u64).sum();
+        heatmap.total_in_period = heatmap.sample_size;
+        heatmap
+    }
+
+    /// Whether there are enough real timestamps to draw the slide
+    pub fn has_enough_data(&self) -> bool {
+        self.sample_size >= HEATMAP_MIN_SAMPLES
+    }
+
+    /// Busiest cell as (day index, hour, count), None when the grid is empty
+    pub fn peak(&self) -> Option<(usize, usize, u32)> {
+        let mut peak: Option<(usize, usize, u32)> = None;
+        for (day, row) in self.grid.iter().enumerate() {
+            for (hour, &count) in row.iter().enumerate() {
+                if count > peak.map_or(0, |(_, _, max)| max) {
+                    peak = Some((day, hour, count));
+                }
+            }
+        }
+        peak
+    }
+
+    /// Human readable offset, e.g. "UTC-03:00"
+    pub fn utc_label(&self) -> String {
+        let sign = if self.utc_offset_minutes < 0 {
+            '-'
+        } else {
+            '+'
+        };
+        let minutes = self.utc_offset_minutes.abs();
+        format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+/// A tenant time zone: an IANA zone (with its daylight saving rules) or a
+/// fixed UTC offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantTimeZone {
+    Named(chrono_tz::Tz),
+    Fixed(chrono::FixedOffset),
+}
+
+impl TenantTimeZone {
+    /// Parse an IANA name ("America/Sao_Paulo", "Europe/Madrid") or an
+    /// offset accepted by [`parse_utc_offset`]
+    pub fn parse(tz: &str) -> Option<Self> {
+        let tz = tz.trim();
+        match tz.parse::<chrono_tz::Tz>() {
+            Ok(named) => Some(Self::Named(named)),
+            Err(_) => parse_utc_offset(tz).map(Self::Fixed),
+        }
+    }
+
+    /// Local time of a UTC instant
+    pub fn local(
+        &self,
+        utc: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::FixedOffset> {
+        match self {
+            Self::Named(tz) => utc.with_timezone(tz).fixed_offset(),
+            Self::Fixed(offset) => utc.with_timezone(offset),
+        }
+    }
+}
+
+/// Parse a fixed UTC offset ("-03:00", "+0530", "UTC-3", "GMT-03:00", "Z")
+pub fn parse_utc_offset(tz: &str) -> Option<chrono::FixedOffset> {
+    let tz = tz.trim();
+    match tz {
+        "" => return None,
+        "Z" | "UTC" | "GMT" => return chrono::FixedOffset::east_opt(0),
+        _ => {}
+    }
+
+    let offset = tz
+        .strip_prefix("UTC")
+        .or_else(|| tz.strip_prefix("GMT"))
+        .unwrap_or(tz);
+    let (sign, rest) = match offset.chars().next()? {
+        '+' => (1, &offset[1..]),
+        '-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        3 => (
+            digits[..1].parse::<i32>().ok()?,
+            digits[1..].parse::<i32>().ok()?,
+        ),
+        _ => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        ),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    chrono::FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Parse a ticket date from the tickets API (RFC 3339, or naive UTC)
+fn parse_ticket_timestamp(ts: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{DateTime, NaiveDateTime, Utc};
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(ts) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(ts, fmt).ok())
+        .map(|naive| naive.and_utc())
 }
 
 /// Aggregated threat intelligence data from Threat Hunting API
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ThreatIntelligence {
     // === Dimension 1: Threat Origin (Dark Web) ===
     pub dark_web_mentions: u64,
@@ -683,9 +1037,23 @@ pub struct ThreatIntelligence {
     // === Dimension 4: Attacker Investment ===
     pub paid_ads_detected: u64,
     pub ad_platforms: Vec<String>, // FB, IG, Google etc.
+    #[serde(default)]
+    pub infrastructure_signals: u64, // signal-lake hits on monitored domains
+    #[serde(default)]
+    pub fake_social_profiles: u64, // signal-lake-social-media
+
+    // === Dimension 5: Financial Data Exposure ===
+    #[serde(default)]
+    pub credit_card_leaks: u64, // Cards matching monitored BINs
+    #[serde(default)]
+    pub token_leaks: u64, // Text file tokens (emails, documents)
 
     // === Meta ===
     pub data_available: bool, // False if API unavailable or no results
+    #[serde(default)]
+    pub searched_sources: Vec<String>, // Sources searched for this report
+    #[serde(default)]
+    pub timed_out_sources: Vec<String>, // Sources with unfinished searches (counts incomplete)
 }
 
 impl ThreatIntelligence {
@@ -727,13 +1095,21 @@ impl ThreatIntelligence {
             // Ads
             paid_ads_detected: 3,
             ad_platforms: vec!["Facebook".to_string(), "Instagram".to_string()],
+            infrastructure_signals: 0,
+            fake_social_profiles: 0,
+
+            // Financial data
+            credit_card_leaks: 0,
+            token_leaks: 0,
 
             data_available: true,
+            searched_sources: vec![],
+            timed_out_sources: vec![],
         }
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StoryTicket {
     pub ticket_key: String,
     pub target: String,      // Domain/host
@@ -760,26 +1136,26 @@ pub struct StoryTicket {
     pub incident_age_hours: Option<i64>,     // Hours since incident started
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ThreatTypeCount {
     pub threat_type: String,
     pub count: u64,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IncidentTypeCount {
     pub incident_type: String,
     pub detections: u64,
     pub incidents: u64,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NameValuePair {
     pub name: String,
     pub value: u64,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PocEvidence {
     pub evidence_type: String,
     pub ticket_key: String,
@@ -797,7 +1173,7 @@ pub struct PocEvidence {
     pub has_login_form: Option<bool>,   // From prediction.has-authentication-form
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TakedownExample {
     pub name: String,
     pub ticket_type: String,
@@ -806,7 +1182,7 @@ pub struct TakedownExample {
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResolvedTakedown {
     pub ticket_key: String,
     pub name: String,
@@ -823,7 +1199,7 @@ pub struct ResolvedTakedown {
     pub screenshot_url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IncidentExample {
     pub ticket_key: String,
     pub name: String,
@@ -845,7 +1221,7 @@ pub struct IncidentExample {
 // ========================
 
 /// Result of deep investigation using signal-lake API
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DeepInvestigationResult {
     /// Original ticket key
     pub ticket_key: String,
@@ -874,7 +1250,7 @@ pub struct DeepInvestigationResult {
 }
 
 /// Infrastructure details from signal-lake investigation
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct InfrastructureInfo {
     pub ip: Option<String>,
     pub asn: Option<String>,
@@ -884,7 +1260,7 @@ pub struct InfrastructureInfo {
 }
 
 /// Enriched data from Signal-Lake AI inspection, geolocation, and site scanner
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct EnrichedSignalData {
     /// Screenshot image as base64 data URL (data:image/jpeg;base64,...)
     pub screenshot_base64: Option<String>,
@@ -917,7 +1293,7 @@ pub struct EnrichedSignalData {
 }
 
 /// Impersonated brand with confidence level
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ImpersonatedBrand {
     pub brand: String,
     pub level: String, // "high", "medium", "low"
@@ -925,7 +1301,7 @@ pub struct ImpersonatedBrand {
 }
 
 /// Geolocation information
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct GeoInfo {
     pub ip: Option<String>,
     pub country_code: Option<String>,
@@ -1086,6 +1462,8 @@ pub struct ThreatHuntingPreview {
     pub tickets_count: usize,
     /// Sample data for preview (first 3 items from each source)
     pub samples: ThreatHuntingSamples,
+    /// Domains whose search timed out (not included in the counts)
+    pub timed_out_domains: Vec<String>,
 }
 
 impl ThreatHuntingPreview {
@@ -1118,7 +1496,7 @@ pub struct CodeLeaksSummary {
     pub top_repositories: Vec<NameValuePair>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CredentialLeaksSummary {
     pub total_credentials: u64,
     pub unique_emails: u64,
@@ -1129,7 +1507,7 @@ pub struct CredentialLeaksSummary {
 }
 
 /// Deep Analytics computed insights - only populated sections are rendered
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DeepAnalyticsData {
     // Code Leak Insights
     pub top_repositories: Vec<NameValuePair>, // Top 5 repos with most leaks
@@ -1170,7 +1548,7 @@ const INDUSTRY_BENCHMARK_RESPONSE_HOURS: f64 = 48.0; // Conservative industry be
 
 /// Operational Impact Metrics for COO Executive Slide
 /// Focused on TIME and PEOPLE instead of money (better for LATAM)
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct OperationalMetrics {
     // ===== TIME SAVINGS =====
     /// Total hours saved in security operations
@@ -1218,6 +1596,11 @@ pub struct OperationalMetrics {
     /// Speed multiplier vs industry benchmark (e.g., 48h manual / Axur response time)
     pub response_speed_multiplier: f64,
 
+    // ===== PERIOD COMPARISON =====
+    /// Hours saved in the comparison period (tickets, credentials, takedowns)
+    #[serde(default)]
+    pub hours_saved_prev: Option<f64>,
+
     // ===== DISPLAY FLAGS =====
     /// Should this slide be shown? (based on data significance)
     pub has_significant_data: bool,
@@ -1289,6 +1672,15 @@ impl OperationalMetrics {
             0.0
         };
 
+        // ===== PERIOD COMPARISON =====
+        // Secrets are not fetched for the previous window, so compare like for like
+        let hours_saved_prev = data.comparison.as_ref().map(|c| {
+            (c.prev_tickets as f64 * MINUTES_PER_TICKET_VALIDATION
+                + c.prev_credentials as f64 * MINUTES_PER_CREDENTIAL_CHECK
+                + c.prev_takedowns as f64 * MINUTES_PER_TAKEDOWN_MANUAL)
+                / 60.0
+        });
+
         // ===== SIGNIFICANCE THRESHOLD =====
         // Show slide if we have meaningful operational impact
         let has_significant_data = hours_saved_total >= 10.0 || // At least 10 hours saved
@@ -1315,10 +1707,20 @@ impl OperationalMetrics {
             threats_detected: data.total_threats,
             takedown_resolution_rate,
             response_speed_multiplier,
+            hours_saved_prev,
             has_significant_data,
         }
     }
 
+    /// Change in hours saved vs the comparison period, in percent
+    /// (like-for-like: secrets are excluded on both sides)
+    pub fn hours_saved_delta_pct(&self) -> Option<f64> {
+        let prev = self.hours_saved_prev.filter(|p| *p > 0.0)?;
+        let current =
+            self.hours_saved_validation + self.hours_saved_credentials + self.hours_saved_takedowns;
+        Some((current - prev) / prev * 100.0)
+    }
+
     /// Format hours in a human-readable way
     pub fn format_hours(hours: f64) -> String {
         if hours >= 24.0 {
@@ -1583,7 +1985,7 @@ struct GeoIpResponse {
 }
 
 /// Resolve IP to Country using ip-api.com (free, rate limited 45/min)
-async fn resolve_ip_country(client: &reqwest::Client, ip: &str) -> Option<String> {
+async fn resolve_ip_country(client: &ApiTransport, ip: &str) -> Option<String> {
     let url = format!("http://ip-api.com/json/{}", ip);
     match client.get(&url).send().await {
         Ok(resp) => {
@@ -1626,6 +2028,13 @@ fn clamp_date_range(from: &str, to: &str, max_days: i64) -> (String, String) {
 // ========================
 
 /// Fetch complete PoC report data with parallel API calls
+///
+/// The result is raw: `deep_analytics`, `roi_metrics` and `risk_score` stay
+/// at their defaults and only `enrichment_inputs` is filled. They are
+/// computed by the builtin data plugins, which run in the slide pipeline
+/// (`generate_report_with_plugins`, `generate_plugin_slides`); callers that
+/// read those fields directly must run `report::html::enrich_report_data`
+/// first.
 pub async fn fetch_full_report(
     token: &str,
     tenant_id: &str,
@@ -1633,6 +2042,35 @@ pub async fn fetch_full_report(
     to: &str,
     story_tag: Option<String>,
     include_threat_intel: bool,
+) -> Result<PocReportData> {
+    let client = ApiTransport::from_env()?;
+    fetch_full_report_with(
+        &client,
+        &ReportContext::default(),
+        token,
+        tenant_id,
+        from,
+        to,
+        story_tag,
+        include_threat_intel,
+    )
+    .await
+}
+
+/// Fetch complete PoC report data through an explicit transport (custom base
+/// URL, or record/replay of fixtures for offline runs) and report context
+/// (redaction, Threat Hunting credits, cache, searches and progress).
+/// Like `fetch_full_report`, the data is not enriched yet.
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_full_report_with(
+    client: &ApiTransport,
+    ctx: &ReportContext,
+    token: &str,
+    tenant_id: &str,
+    from: &str,
+    to: &str,
+    story_tag: Option<String>,
+    include_threat_intel: bool,
 ) -> Result<PocReportData> {
     // TRACE EXECUTION
     {
@@ -1649,7 +2087,6 @@ pub async fn fetch_full_report(
     }
 
     let start_time = std::time::Instant::now();
-    let client = create_client()?;
     let auth = format!("Bearer {}", token);
 
     // FIX: Tickets API has a strict 90-day limit. Clamp range for ticket calls.
@@ -1661,41 +2098,66 @@ pub async fn fetch_full_report(
     // Phase 1: Fetch customer info and stats in batches to avoid rate limiting (Max 5 concurrent)
     // Batch 1A (3 reqs): Base Counts for OPEN / QUARANTINE + Customer Data
     let (customer_data, total_open, total_quarantine) = tokio::join!(
-        fetch_customer_data(&client, &auth, tenant_id),
-        fetch_ticket_count(&client, &auth, t_from, t_to, "open", tenant_id),
-        fetch_ticket_count(&client, &auth, t_from, t_to, "quarantine", tenant_id),
+        fetch_customer_data(client, &auth, tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "open", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "quarantine", tenant_id),
     );
 
     // Batch 1B (3 reqs): Base Counts for INCIDENT / TREATMENT / CLOSED
     let (total_incident, total_treatment, total_closed) = tokio::join!(
-        fetch_ticket_count(&client, &auth, t_from, t_to, "incident", tenant_id),
-        fetch_ticket_count(&client, &auth, t_from, t_to, "treatment", tenant_id),
-        fetch_ticket_count(&client, &auth, t_from, t_to, "closed", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "incident", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "treatment", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "closed", tenant_id),
     );
 
     // Batch 2 (3 reqs): Detailed Stats
     let (threats_by_type, credentials_total, code_leaks_total) = tokio::join!(
-        fetch_threats_by_type_map(&client, &auth, t_from, t_to, tenant_id),
-        fetch_credentials_total_all(&client, &auth, from, to, tenant_id), // Use full range
-        fetch_code_leaks_count(&client, &auth, from, to, tenant_id),      // Use full range
+        fetch_threats_by_type_map(client, &auth, t_from, t_to, tenant_id),
+        fetch_credentials_total_all(client, &auth, from, to, tenant_id), // Use full range
+        fetch_code_leaks_count(client, &auth, from, to, tenant_id),      // Use full range
     );
 
-    // Batch 3 (4 reqs): Advanced & Risk
-    let (takedown_stats, story_tickets_res, risk_metrics, credential_exposures_res) = tokio::join!(
-        fetch_takedown_stats_full(&client, &auth, t_from, t_to, tenant_id),
-        fetch_tagged_tickets(&client, &auth, tenant_id, story_tag.as_deref()),
-        fetch_risk_score_metrics(&client, &auth, tenant_id, t_from, t_to),
+    // Batch 3 (5 reqs): Advanced & Risk
+    let (
+        takedown_stats,
+        story_tickets_res,
+        risk_metrics,
+        credential_exposures_res,
+        ticket_timestamps,
+    ) = tokio::join!(
+        fetch_takedown_stats_full(client, &auth, t_from, t_to, tenant_id),
+        fetch_tagged_tickets(client, &auth, tenant_id, story_tag.as_deref()),
+        fetch_risk_score_metrics(client, &auth, tenant_id, t_from, t_to),
         async {
             if let Some(tag) = story_tag.as_deref() {
                 if !tag.is_empty() {
-                    return fetch_tagged_credentials(&client, &auth, tenant_id, tag).await;
+                    return fetch_tagged_credentials(client, &auth, tenant_id, tag).await;
                 }
             }
             Vec::new()
-        }
+        },
+        fetch_ticket_timestamps(client, &auth, t_from, t_to, tenant_id),
     );
 
     let customer = customer_data.unwrap_or_default();
+
+    // Bucket detection times in the tenant's time zone for the heatmap slide
+    let activity_heatmap = match ticket_timestamps {
+        Ok((timestamps, total)) => {
+            let timezone = customer
+                .timezone
+                .as_deref()
+                .unwrap_or(DEFAULT_TENANT_TIMEZONE);
+            let mut heatmap =
+                ActivityHeatmap::from_timestamps(timestamps.iter().map(String::as_str), timezone);
+            heatmap.total_in_period = total;
+            heatmap
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch ticket timestamps for heatmap: {}", e);
+            ActivityHeatmap::default()
+        }
+    };
     let threats_map = threats_by_type.unwrap_or_default();
     let tk = takedown_stats.unwrap_or(ReportTakedownStats {
         resolved: 0,
@@ -1746,13 +2208,13 @@ pub async fn fetch_full_report(
         credential_leaks_summary,
         critical_credentials_res,
     ) = tokio::join!(
-        fetch_smart_evidence(&client, &auth, from, to, &top_types, 3, tenant_id),
-        fetch_code_leaks_summary(&client, &auth, from, to, tenant_id),
-        fetch_takedown_examples(&client, &auth, from, to, 10, tenant_id),
-        fetch_resolved_takedowns(&client, &auth, from, to, 10, tenant_id),
-        fetch_latest_incidents(&client, &auth, from, to, 5, tenant_id),
-        fetch_credential_leaks_summary(&client, &auth, from, to, tenant_id),
-        fetch_critical_credentials(&client, &auth, tenant_id), // NEW
+        fetch_smart_evidence(client, &auth, from, to, &top_types, 3, tenant_id),
+        fetch_code_leaks_summary(client, &auth, from, to, tenant_id),
+        fetch_takedown_examples(client, &auth, from, to, 10, tenant_id),
+        fetch_resolved_takedowns(client, &auth, from, to, 10, tenant_id),
+        fetch_latest_incidents(client, &auth, from, to, 5, tenant_id),
+        fetch_credential_leaks_summary(client, &auth, from, to, tenant_id),
+        fetch_critical_credentials(client, &auth, tenant_id), // NEW
     );
 
     let evidence = smart_evidence.unwrap_or_default();
@@ -1813,15 +2275,15 @@ pub async fn fetch_full_report(
         })
         .collect();
 
-    // Compute Deep Analytics BEFORE building the struct
-    let mut deep_analytics = compute_deep_analytics(&code_summary, &cred_summary, &resolved_td);
+    // Batch 4 (5 reqs per period): risk inputs of past periods for the trend
+    let risk_history = fetch_risk_history(client, &auth, tenant_id, t_from, t_to).await;
 
-    // Enrich with Risk Score V3 data (more accurate takedown uptime)
-    if let Some(td) = &risk_metrics.takedown_stats {
-        let uptime_hours = td.total.raw_median_uptime / (1000.0 * 3600.0);
-        deep_analytics.avg_takedown_time_hours = Some(uptime_hours);
-        deep_analytics.has_takedown_insights = true;
-    }
+    // Raw inputs for the builtin data plugins (deep analytics, risk score)
+    let enrichment_inputs = EnrichmentInputs {
+        top_repositories: code_summary.top_repositories.clone(),
+        risk: RiskInputs::from(&risk_metrics),
+        risk_history,
+    };
 
     // FIXED: Prefer summary count which uses the detailed endpoint used for the slide
     let final_creds_count = if cred_summary.total_credentials > 0 {
@@ -1843,6 +2305,14 @@ pub async fn fetch_full_report(
         total_tickets
     };
 
+    let monitored = MonitoredAssets {
+        company_name: customer.name.clone(),
+        brands: customer.brands.clone(),
+        domains: customer.domains,
+        executives: customer.executives,
+        bins: customer.bins,
+    };
+
     let report = PocReportData {
         company_name: customer.name,
         partner_name: customer.partner,
@@ -1893,9 +2363,8 @@ pub async fn fetch_full_report(
         takedown_examples: takedown_ex,
         resolved_takedowns: resolved_td.clone(),
         latest_incidents: incidents,
-        // Computed Deep Analytics
-        deep_analytics,
-        // ROI placeholder (will be computed after struct creation)
+        // Deep Analytics, ROI and risk score are derived by the builtin data plugins
+        deep_analytics: DeepAnalyticsData::default(),
         roi_metrics: RoiMetrics::default(),
 
         // NEW: Story Tickets
@@ -1912,26 +2381,26 @@ pub async fn fetch_full_report(
         // NEW: Critical Credentials
         critical_credentials: critical_credentials_res,
 
-        // NEW: Risk Score V3
-        risk_score: calculate_risk_score_v3(&risk_metrics),
+        // NEW: Risk Score V3 (see RiskScoreDataPlugin)
+        risk_score: RiskScore::default(),
 
         // NEW: Comparison data (none by default, can be populated for comparative reports)
         comparison: None,
 
+        activity_heatmap,
+
         is_dynamic_window: false, // Default to fixed window
+
+        enrichment_inputs: Some(enrichment_inputs),
     };
 
     // Fetch Threat Intelligence if enabled (this is async and can take 1-2 min)
-    let threat_intel = if include_threat_intel {
-        // Use the first brand or company name as the search query
-        let query = report
-            .brands
-            .first()
-            .cloned() // brands is Vec<String>, clone the string directly
-            .unwrap_or_else(|| report.company_name.clone());
-
-        tracing::info!("Fetching threat intelligence for query: {}", query);
-        fetch_threat_intelligence(&client, &auth, &query, from, to).await
+    let mut threat_intel = if include_threat_intel {
+        // Searches built from the monitored assets and the report's source selection
+        let queries = ctx.th_search().queries(&monitored);
+
+        tracing::info!("Fetching threat intelligence: {} searches", queries.len());
+        fetch_threat_intelligence(client, ctx, &auth, tenant_id, &queries, from, to).await
     } else {
         ThreatIntelligence::default()
     };
@@ -1942,19 +2411,121 @@ pub async fn fetch_full_report(
             "Investigating {} tagged tickets via signal-lake",
             report.story_tickets.len()
         );
-        investigate_tagged_tickets(&client, &auth, tenant_id, &report.story_tickets).await
+        let (investigations, timed_out) =
+            investigate_tagged_tickets(client, ctx, &auth, tenant_id, &report.story_tickets).await;
+        // Tickets left uninvestigated make the signal-lake results incomplete
+        let source = ThreatHuntingSource::SignalLake.as_str().to_string();
+        if !timed_out.is_empty() && !threat_intel.timed_out_sources.contains(&source) {
+            threat_intel.timed_out_sources.push(source);
+        }
+        investigations
     } else {
         vec![]
     };
 
-    // Compute ROI metrics now that we have the full data
-    let roi = RoiMetrics::compute(&report);
-    let mut report_with_roi = report;
-    report_with_roi.roi_metrics = roi;
-    report_with_roi.threat_intelligence = threat_intel;
-    report_with_roi.deep_investigations = deep_investigations;
+    let mut report = report;
+    report.threat_intelligence = threat_intel;
+    report.deep_investigations = deep_investigations;
+
+    // Raw credentials never leave the fetcher
+    ctx.redaction().apply(&mut report);
+
+    Ok(report)
+}
+
+// ========================
+// PERIOD COMPARISON
+// ========================
+
+/// Fetch ticket, takedown and credential counts for the comparison window
+/// of a report period (previous month, quarter, same-length window or custom range)
+pub async fn fetch_comparison_data(
+    token: &str,
+    tenant_id: &str,
+    from: &str,
+    to: &str,
+    period: &ComparisonPeriod,
+) -> Result<ComparisonData> {
+    let client = ApiTransport::from_env()?;
+    fetch_comparison_data_with(&client, token, tenant_id, from, to, period).await
+}
+
+/// Same as [`fetch_comparison_data`] through an explicit transport
+pub async fn fetch_comparison_data_with(
+    client: &ApiTransport,
+    token: &str,
+    tenant_id: &str,
+    from: &str,
+    to: &str,
+    period: &ComparisonPeriod,
+) -> Result<ComparisonData> {
+    let (prev_from, prev_to) = period
+        .window(from, to)
+        .ok_or_else(|| anyhow!("Invalid comparison window for {} - {}", from, to))?;
+    let auth = format!("Bearer {}", token);
+
+    // Same ticket window rules as the main report (90-day limit)
+    let (ticket_from, ticket_to) = clamp_date_range(&prev_from, &prev_to, 90);
+    let t_from = &ticket_from;
+    let t_to = &ticket_to;
+
+    // Batch 1 (5 reqs): ticket lifecycle counts, summed like total_tickets
+    let (open, quarantine, incident, treatment, closed) = tokio::join!(
+        fetch_ticket_count(client, &auth, t_from, t_to, "open", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "quarantine", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "incident", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "treatment", tenant_id),
+        fetch_ticket_count(client, &auth, t_from, t_to, "closed", tenant_id),
+    );
+
+    // Batch 2 (2 reqs): takedowns and credentials
+    let (takedowns, credentials) = tokio::join!(
+        fetch_takedown_stats_full(client, &auth, t_from, t_to, tenant_id),
+        fetch_credentials_total_all(client, &auth, &prev_from, &prev_to, tenant_id),
+    );
+
+    let prev_tickets = [open, quarantine, incident, treatment, closed]
+        .into_iter()
+        .map(|r| r.unwrap_or(0))
+        .sum();
+
+    Ok(ComparisonData {
+        period_label: format!("vs. {} - {}", prev_from, prev_to),
+        period: Some(period.clone()),
+        prev_tickets,
+        prev_takedowns: takedowns.map(|t| t.resolved).unwrap_or(0),
+        prev_credentials: credentials.unwrap_or(0),
+        prev_start_date: prev_from,
+        prev_end_date: prev_to,
+    })
+}
+
+/// Compute Deep Analytics for fetched report data and its enrichment inputs
+pub fn compute_report_deep_analytics(
+    data: &PocReportData,
+    inputs: &EnrichmentInputs,
+) -> DeepAnalyticsData {
+    let code_summary = CodeLeaksSummary {
+        total_secrets: data.secrets_total,
+        unique_repos: data.unique_repos,
+        high_severity_secrets: data.production_secrets,
+        exposure_platforms: data.platform_breakdown.clone(),
+        secret_types: data.secret_types.clone(),
+        top_repositories: inputs.top_repositories.clone(),
+    };
+    let mut analytics = compute_deep_analytics(
+        &code_summary,
+        &data.credential_leaks_summary,
+        &data.resolved_takedowns,
+    );
+
+    // Enrich with Risk Score V3 data (more accurate takedown uptime)
+    if let Some(uptime_ms) = inputs.risk.takedown_median_uptime_ms {
+        analytics.avg_takedown_time_hours = Some(uptime_ms / (1000.0 * 3600.0));
+        analytics.has_takedown_insights = true;
+    }
 
-    Ok(report_with_roi)
+    analytics
 }
 
 /// Compute Deep Analytics insights from raw API data
@@ -2054,6 +2625,11 @@ struct CustomerData {
     ips_count: u32,
     bins_count: u32,
     domains_count: u32,
+    timezone: Option<String>,
+    // Asset names used as Threat Hunting terms
+    domains: Vec<String>,
+    executives: Vec<String>,
+    bins: Vec<String>,
 }
 
 // ========================
@@ -2061,11 +2637,11 @@ struct CustomerData {
 // ========================
 
 async fn fetch_customer_data(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     tenant_id: &str,
 ) -> Result<CustomerData> {
-    let url = format!("{}/customers/customers", API_URL);
+    let url = format!("{}/customers/customers", client.base_url());
 
     // Est. requests: 1 (with retries)
     let resp = match retry_api_call(
@@ -2158,6 +2734,8 @@ async fn fetch_customer_data(
             ips_count: 0,
             bins_count: 0,
             domains_count: 0,
+            timezone: None,
+            ..Default::default()
         };
 
         // Count assets by category
@@ -2172,20 +2750,40 @@ async fn fetch_customer_data(
                         data.brands.push(name.clone());
                     }
                 }
-                Some("EXECUTIVE") | Some("VIP") => data.executives_count += 1,
+                Some("EXECUTIVE") | Some("VIP") => {
+                    data.executives_count += 1;
+                    data.executives.extend(asset.name.clone());
+                }
                 Some("IP") | Some("IP_RANGE") => data.ips_count += 1,
-                Some("BIN") => data.bins_count += 1,
-                Some("DOMAIN") => data.domains_count += 1,
+                Some("BIN") => {
+                    data.bins_count += 1;
+                    data.bins.extend(asset.name.clone());
+                }
+                Some("DOMAIN") => {
+                    data.domains_count += 1;
+                    data.domains.extend(asset.name.clone());
+                }
                 _ => {}
             }
         }
 
         // Look for partner in properties
         for prop in &customer.properties {
-            if prop.key.as_deref() == Some("partner") {
-                if let Some(val) = &prop.value {
-                    data.partner = val.as_str().map(|s| s.to_string());
+            match prop.key.as_deref() {
+                Some("partner") => {
+                    if let Some(val) = &prop.value {
+                        data.partner = val.as_str().map(|s| s.to_string());
+                    }
                 }
+                Some("timezone") | Some("timeZone") | Some("time_zone") => {
+                    data.timezone = prop
+                        .value
+                        .as_ref()
+                        .and_then(|v| v.as_str())
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string());
+                }
+                _ => {}
             }
         }
 
@@ -2196,7 +2794,7 @@ async fn fetch_customer_data(
 }
 
 async fn fetch_ticket_count(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2205,7 +2803,11 @@ async fn fetch_ticket_count(
 ) -> Result<u64> {
     let url = format!(
         "{}/tickets-api/stats/customer?from={}&to={}&status={}&customer={}",
-        API_URL, from, to, status, customer
+        client.base_url(),
+        from,
+        to,
+        status,
+        customer
     );
 
     // Est. requests: 1 (with retries)
@@ -2282,7 +2884,7 @@ async fn fetch_ticket_count(
 
 /// Fetch total ticket count WITHOUT status filter (returns ALL tickets in period)
 async fn fetch_ticket_count_all(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2290,7 +2892,10 @@ async fn fetch_ticket_count_all(
 ) -> Result<u64> {
     let url = format!(
         "{}/tickets-api/stats/customer?from={}&to={}&customer={}",
-        API_URL, from, to, customer
+        client.base_url(),
+        from,
+        to,
+        customer
     );
     tracing::warn!("🎫 fetch_ticket_count_all URL: {}", url);
 
@@ -2334,7 +2939,7 @@ async fn fetch_ticket_count_all(
 }
 
 async fn fetch_threats_by_type_map(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2344,7 +2949,10 @@ async fn fetch_threats_by_type_map(
     // Note: This works for most tenants but CABALP seems to have a portal vs API discrepancy
     let url = format!(
         "{}/tickets-api/stats/incident/count/ticket-types?from={}&to={}&customer={}",
-        API_URL, from, to, customer
+        client.base_url(),
+        from,
+        to,
+        customer
     );
     let resp = client
         .get(&url)
@@ -2399,7 +3007,7 @@ async fn fetch_threats_by_type_map(
 }
 
 async fn fetch_credentials_total_all(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2410,7 +3018,7 @@ async fn fetch_credentials_total_all(
     // Reference: Axur API doc - MSSP users need to use the search endpoint for customer filtering
     let url = format!(
         "{}/exposure-api/credentials?created=ge:{}T00:00:00&created=le:{}T23:59:59&customer={}&pageSize=1&timezone=-03:00",
-        API_URL, from, to, customer
+        client.base_url(), from, to, customer
     );
     let resp = client
         .get(&url)
@@ -2444,7 +3052,7 @@ async fn fetch_credentials_total_all(
 }
 
 async fn fetch_code_leaks_count(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2452,7 +3060,7 @@ async fn fetch_code_leaks_count(
 ) -> Result<u64> {
     let url = format!(
         "{}/tickets-api/tickets?type=code-secret-leak&open.date=ge:{}&open.date=le:{}&page=1&pageSize=1&ticket.customer={}",
-        API_URL, from, to, customer
+        client.base_url(), from, to, customer
     );
     let resp = client
         .get(&url)
@@ -2496,7 +3104,7 @@ pub struct ReportTakedownStats {
 }
 
 async fn fetch_takedown_stats_full(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2504,7 +3112,10 @@ async fn fetch_takedown_stats_full(
 ) -> Result<ReportTakedownStats> {
     let url = format!(
         "{}/tickets-api/stats/takedown?from={}&to={}&customer={}",
-        API_URL, from, to, customer
+        client.base_url(),
+        from,
+        to,
+        customer
     );
     let resp = client
         .get(&url)
@@ -2592,7 +3203,7 @@ async fn fetch_takedown_stats_full(
 }
 
 async fn fetch_evidence_samples(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2620,7 +3231,7 @@ async fn fetch_evidence_samples(
     for threat_type in &threat_types {
         let url = format!(
             "{}/tickets-api/tickets?type={}&status=incident&open.date=ge:{}&open.date=le:{}&page=1&pageSize={}&include=fields,attachments&ticket.customer={}",
-            API_URL, threat_type, from, to, per_type, customer
+            client.base_url(), threat_type, from, to, per_type, customer
         );
 
         let resp = client.get(&url).header("Authorization", auth).send().await;
@@ -2679,7 +3290,7 @@ async fn fetch_evidence_samples(
 /// SMART: Fetch evidence only for top N threat types (based on incident counts)
 /// This is the intelligent approach - only query types that actually have incidents
 async fn fetch_smart_evidence(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2696,7 +3307,7 @@ async fn fetch_smart_evidence(
         // Filter by status to show active threats (open, quarantine, incident)
         let url = format!(
             "{}/tickets-api/tickets?type={}&status=open,quarantine,incident&pageSize={}&sortBy=open.date&order=desc&include=fields,attachments&open.date=ge:{}&open.date=le:{}&ticket.customer={}",
-            API_URL, threat_type, examples_per_type, from, to, customer
+            client.base_url(), threat_type, examples_per_type, from, to, customer
         );
 
         log_api_call(
@@ -2796,7 +3407,7 @@ async fn fetch_smart_evidence(
 
 /// Fetch code leaks summary with secret type classification using regex
 async fn fetch_code_leaks_summary(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2804,7 +3415,7 @@ async fn fetch_code_leaks_summary(
 ) -> Result<CodeLeaksSummary> {
     let url = format!(
         "{}/tickets-api/tickets?type=code-secret-leak&open.date=ge:{}&open.date=le:{}&page=1&pageSize=100&include=fields&order=desc&ticket.customer={}",
-        API_URL, from, to, customer
+        client.base_url(), from, to, customer
     );
 
     let resp = client
@@ -2966,7 +3577,7 @@ fn extract_repo_from_url(url: &str) -> Option<String> {
 
 /// Fetch takedown examples (requested takedowns)
 async fn fetch_takedown_examples(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -2975,7 +3586,7 @@ async fn fetch_takedown_examples(
 ) -> Result<Vec<TakedownExample>> {
     let url = format!(
         "{}/tickets-api/tickets?takedown=true&takedown.request.date=ge:{}&takedown.request.date=le:{}&pageSize={}&sortBy=takedown.request.date&order=desc&include=fields&ticket.customer={}",
-        API_URL, from, to, page_size, customer
+        client.base_url(), from, to, page_size, customer
     );
 
     let resp = client
@@ -3034,41 +3645,134 @@ async fn fetch_takedown_examples(
     Ok(examples)
 }
 
-/// Fetch resolved takedowns
-async fn fetch_resolved_takedowns(
-    client: &reqwest::Client,
+/// Max pages of tickets fetched for the activity heatmap (pageSize=200)
+const HEATMAP_MAX_PAGES: u32 = 10;
+
+/// Fetch detection timestamps of every ticket opened in the period.
+/// Returns (timestamps, total tickets reported by the API); capped at
+/// `HEATMAP_MAX_PAGES` pages, so the timestamps may be a sample.
+async fn fetch_ticket_timestamps(
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
-    page_size: usize,
     customer: &str,
-) -> Result<Vec<ResolvedTakedown>> {
-    let url = format!(
-        "{}/tickets-api/tickets?current.takedown.resolution=resolved&current.resolution=resolved&current.close.date=ge:{}&current.close.date=le:{}&pageSize={}&sortBy=current.close.date&order=desc&include=fields,attachments&ticket.customer={}",
-        API_URL, from, to, page_size, customer
-    );
-
-    let resp = client
-        .get(&url)
-        .header("Authorization", auth)
-        .send()
-        .await?;
+) -> Result<(Vec<String>, u64)> {
+    const PAGE_SIZE: u32 = 200;
 
-    let status = resp.status().as_u16();
-    let success = resp.status().is_success();
+    let mut timestamps = Vec::new();
+    let mut total = 0;
 
-    if !success {
-        log_api_call(
-            "fetch_resolved_takedowns",
-            &url,
-            status,
-            false,
-            "Request failed",
+    for page in 1..=HEATMAP_MAX_PAGES {
+        let url = format!(
+            "{}/tickets-api/tickets?open.date=ge:{}&open.date=le:{}&page={}&pageSize={}&sortBy=open.date&order=desc&include=fields&ticket.customer={}",
+            client.base_url(), from, to, page, PAGE_SIZE, customer
         );
-        return Ok(vec![]);
-    }
 
-    let text = resp.text().await?;
+        let resp = retry_api_call(
+            || async {
+                let res = client
+                    .get(&url)
+                    .header("Authorization", auth)
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e))?;
+
+                if res.status().is_server_error()
+                    || res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                {
+                    return Err(anyhow::anyhow!("API Error {}", res.status()));
+                }
+                Ok(res)
+            },
+            "fetch_ticket_timestamps",
+        )
+        .await?;
+
+        let status = resp.status().as_u16();
+        if !resp.status().is_success() {
+            log_api_call(
+                "fetch_ticket_timestamps",
+                &url,
+                status,
+                false,
+                "Request failed",
+            );
+            break;
+        }
+
+        let text = resp.text().await?;
+        log_api_call(
+            "fetch_ticket_timestamps",
+            &url,
+            status,
+            true,
+            &format!("{} bytes received", text.len()),
+        );
+
+        let data: TicketsResponse = serde_json::from_str(&text).unwrap_or(TicketsResponse {
+            tickets: vec![],
+            pageable: None,
+        });
+        if let Some(t) = data.pageable.as_ref().and_then(|p| p.total) {
+            total = t;
+        }
+
+        let count = data.tickets.len();
+        timestamps.extend(data.tickets.into_iter().filter_map(|t| {
+            // Detection time is the creation date; fall back to when the ticket was opened
+            let current = t.current?;
+            current
+                .creation_date_flat
+                .clone()
+                .or_else(|| current.open_date_flat.clone())
+                .or_else(|| current.open_date())
+        }));
+
+        if count < PAGE_SIZE as usize || (total > 0 && timestamps.len() as u64 >= total) {
+            break;
+        }
+    }
+
+    let total = total.max(timestamps.len() as u64);
+    Ok((timestamps, total))
+}
+
+/// Fetch resolved takedowns
+async fn fetch_resolved_takedowns(
+    client: &ApiTransport,
+    auth: &str,
+    from: &str,
+    to: &str,
+    page_size: usize,
+    customer: &str,
+) -> Result<Vec<ResolvedTakedown>> {
+    let url = format!(
+        "{}/tickets-api/tickets?current.takedown.resolution=resolved&current.resolution=resolved&current.close.date=ge:{}&current.close.date=le:{}&pageSize={}&sortBy=current.close.date&order=desc&include=fields,attachments&ticket.customer={}",
+        client.base_url(), from, to, page_size, customer
+    );
+
+    let resp = client
+        .get(&url)
+        .header("Authorization", auth)
+        .send()
+        .await?;
+
+    let status = resp.status().as_u16();
+    let success = resp.status().is_success();
+
+    if !success {
+        log_api_call(
+            "fetch_resolved_takedowns",
+            &url,
+            status,
+            false,
+            "Request failed",
+        );
+        return Ok(vec![]);
+    }
+
+    let text = resp.text().await?;
     log_api_call("fetch_resolved_takedowns", &url, status, true, &text);
 
     let data: TicketsResponse = match serde_json::from_str(&text) {
@@ -3187,7 +3891,7 @@ async fn fetch_resolved_takedowns(
 
 /// Fetch latest incidents for ALL threat types (all 30 detection types)
 async fn fetch_latest_incidents(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -3242,7 +3946,7 @@ async fn fetch_latest_incidents(
 
         let url = format!(
             "{}/tickets-api/tickets?type={}&status=incident&open.date=ge:{}&open.date=le:{}&sortBy=open.date&order=desc&pageSize={}&include=fields,attachments&ticket.customer={}",
-            API_URL, incident_type, from, to, page_size, customer
+            client.base_url(), incident_type, from, to, page_size, customer
         );
 
         let resp = client.get(&url).header("Authorization", auth).send().await;
@@ -3366,7 +4070,7 @@ async fn fetch_latest_incidents(
 /// Fetch credential leaks summary for Risk Profiling (Stealer Logs, Plaintext)
 /// Uses /exposure-api/credentials to get detailed leak info
 async fn fetch_credential_leaks_summary(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     from: &str,
     to: &str,
@@ -3375,7 +4079,7 @@ async fn fetch_credential_leaks_summary(
     // We need fields: leak.format, password.type, user
     let url = format!(
         "{}/exposure-api/credentials?created=ge:{}T00:00:00&created=le:{}T23:59:59&fields=leak.format,password.type,user,status&pageSize=100&customer={}&timezone=-03:00",
-        API_URL, from, to, customer
+        client.base_url(), from, to, customer
     );
 
     let resp = client
@@ -3579,7 +4283,7 @@ pub async fn fetch_report_data(
 
 /// Fetch tickets filtered by tag for the Story Slide
 async fn fetch_tagged_tickets(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     tenant_id: &str,
     tag: Option<&str>,
@@ -3596,7 +4300,9 @@ async fn fetch_tagged_tickets(
 
     let url = format!(
         "{}/tickets-api/tickets?ticket.customer={}&ticket.tags={}",
-        API_URL, tenant_id, tag
+        client.base_url(),
+        tenant_id,
+        tag
     );
 
     tracing::debug!("Fetching from URL: {}", url);
@@ -3778,7 +4484,7 @@ pub async fn fetch_tagged_tickets_for_preview(
     tenant_id: &str,
     tag: &str,
 ) -> Result<Vec<StoryTicket>> {
-    let client = create_client()?;
+    let client = ApiTransport::from_env()?;
     let auth = format!("Bearer {}", token);
 
     // Log for debugging
@@ -3799,7 +4505,12 @@ pub async fn fetch_tagged_tickets_for_preview(
              Tenant ID: {}\n\
              Tag: {}\n\
              API URL: {}/tickets-api/tickets?ticket.customer={}&ticket.tags={}\n",
-            timestamp, tenant_id, tag, API_URL, tenant_id, tag
+            timestamp,
+            tenant_id,
+            tag,
+            client.base_url(),
+            tenant_id,
+            tag
         );
         let _ = std::fs::write(&debug_file, &content);
     }
@@ -3842,7 +4553,8 @@ fn compute_hours_since(date: &str) -> Option<i64> {
 // ========================
 
 /// Source types for Threat Hunting searches
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ThreatHuntingSource {
     SignalLake,            // URLs/Domains (General)
     SignalLakeSocialMedia, // Social Media Profiles
@@ -3856,7 +4568,24 @@ pub enum ThreatHuntingSource {
 }
 
 impl ThreatHuntingSource {
-    fn as_str(&self) -> &'static str {
+    pub const ALL: [Self; 9] = [
+        Self::SignalLake,
+        Self::SignalLakeSocialMedia,
+        Self::SignalLakeAds,
+        Self::Credential,
+        Self::CreditCard,
+        Self::ChatMessage,
+        Self::ForumMessage,
+        Self::SocialMediaPosts,
+        Self::Tokens,
+    ];
+
+    /// Parse an API source name ("signal-lake", "credit-card", ...)
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == name)
+    }
+
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::SignalLake => "signal-lake",
             Self::SignalLakeSocialMedia => "signal-lake-social-media",
@@ -3869,6 +4598,19 @@ impl ThreatHuntingSource {
             Self::Tokens => "tokens",
         }
     }
+
+    /// Report step whose progress the source's searches are shown under
+    pub fn report_step(&self) -> ReportStep {
+        match self {
+            Self::ForumMessage | Self::Credential | Self::CreditCard | Self::Tokens => {
+                ReportStep::SearchingDarkWeb
+            }
+            Self::ChatMessage | Self::SocialMediaPosts | Self::SignalLakeSocialMedia => {
+                ReportStep::TrackingVirality
+            }
+            Self::SignalLakeAds | Self::SignalLake => ReportStep::DetectingAds,
+        }
+    }
 }
 
 /// Request body for starting a threat hunting search
@@ -3928,7 +4670,7 @@ impl ThreatSearchResultsResponse {
 }
 
 /// Individual search result
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 struct ThreatSearchResult {
     source: Option<String>,
     reference: Option<String>,
@@ -3946,14 +4688,16 @@ struct ThreatSearchResult {
 
 /// Start a threat hunting search (async)
 async fn start_threat_search(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     query: &str,
     source: ThreatHuntingSource,
     from: &str,
     to: &str,
 ) -> Result<Option<String>> {
-    let url = format!("{}/threat-hunting-api/external-search", API_URL);
+    ctx.credits().check()?;
+    let url = format!("{}/threat-hunting-api/external-search", client.base_url());
 
     let request = ThreatSearchRequest {
         query: query.to_string(),
@@ -3996,22 +4740,48 @@ async fn start_threat_search(
     }
 
     let response: ThreatSearchStartResponse = resp.json().await?;
+    if let Some(id) = response.get_id() {
+        ctx.credits().started(id, source.as_str(), query);
+        ctx.th_progress()
+            .emit(source.as_str(), query, Some(id), SearchStatus::Started);
+    }
     Ok(response.get_id().map(|s| s.to_string()))
 }
 
-/// Poll for search results (async with timeout)
+/// A Threat Hunting search: results reused from the cache, or a started
+/// search to poll (its results are cached under `key` once complete)
+enum PendingSearch<T> {
+    Cached(T),
+    Started { id: String, key: ThSearchKey },
+}
+
+/// Poll for search results, cached under `key` once complete. Fails with
+/// [`SearchTimeout`] if the search is still running after `max_attempts` polls.
 async fn poll_threat_search(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     search_id: &str,
+    key: &ThSearchKey,
     max_attempts: u32,
 ) -> Result<Vec<ThreatSearchResult>> {
     let url = format!(
         "{}/threat-hunting-api/external-search/{}",
-        API_URL, search_id
+        client.base_url(),
+        search_id
     );
 
     for attempt in 0..max_attempts {
+        ctx.th_progress().emit(
+            &key.source,
+            &key.query,
+            Some(search_id),
+            SearchStatus::Polling {
+                attempt: attempt + 1,
+                max_attempts,
+            },
+        );
+
         // Est. requests: 1 per attempt (wrapped in retry for transient network errors)
         let resp_result = retry_api_call(
             || async {
@@ -4060,7 +4830,25 @@ async fn poll_threat_search(
         let response: ThreatSearchResultsResponse = resp.json().await?;
 
         if response.is_complete() {
-            return Ok(response.get_results().to_vec());
+            let mut results = response.get_results().to_vec();
+            let found = results.len() as u64;
+            // Results beyond the credits left are dropped (and not cached)
+            let fetched = ctx.credits().completed(search_id, found);
+            if fetched < found {
+                results.truncate(fetched as usize);
+            } else {
+                ctx.th_cache().put(key, &results);
+            }
+            ctx.th_progress().emit(
+                &key.source,
+                &key.query,
+                Some(search_id),
+                SearchStatus::Completed {
+                    results: fetched,
+                    pages: pages_for(fetched),
+                },
+            );
+            return Ok(results);
         }
 
         // Wait before next poll (Standard 1s Rule)
@@ -4072,202 +4860,216 @@ async fn poll_threat_search(
         search_id,
         max_attempts
     );
-    Ok(vec![])
+    ctx.th_progress().emit(
+        &key.source,
+        &key.query,
+        Some(search_id),
+        SearchStatus::TimedOut {
+            attempts: max_attempts,
+        },
+    );
+    Err(SearchTimeout {
+        source: key.source.clone(),
+        search_id: search_id.to_string(),
+        attempts: max_attempts,
+    }
+    .into())
 }
 
-/// Fetch and aggregate all threat intelligence data
+/// Fetch and aggregate threat intelligence data for the searches built by
+/// [`crate::api::th_query::ThSearchConfig::queries`]
 pub async fn fetch_threat_intelligence(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
-    query: &str, // Brand domain or name to search
+    tenant_id: &str,
+    queries: &[ThQuery],
     from: &str,
     to: &str,
 ) -> ThreatIntelligence {
     let mut intel = ThreatIntelligence::default();
+    for query in queries {
+        let source = query.source.as_str().to_string();
+        if !intel.searched_sources.contains(&source) {
+            intel.searched_sources.push(source);
+        }
+    }
 
-    // Start all searches in parallel
-    let sources = [
-        ThreatHuntingSource::ForumMessage,
-        ThreatHuntingSource::ChatMessage,
-        ThreatHuntingSource::SocialMediaPosts,
-        ThreatHuntingSource::Credential,
-        ThreatHuntingSource::SignalLakeAds,
-    ];
-
-    // Start searches
-    let mut search_ids: Vec<(ThreatHuntingSource, Option<String>)> = vec![];
+    // Start searches (results cached by an earlier report are reused)
+    let mut searches: Vec<(ThreatHuntingSource, PendingSearch<Vec<ThreatSearchResult>>)> = vec![];
 
-    for source in sources {
-        let id = start_threat_search(client, auth, query, source, from, to)
-            .await
-            .ok()
-            .flatten();
-        search_ids.push((source, id));
+    for query in queries {
+        let source = query.source;
+        // Report searches are charged to the tenant budget, so any user of
+        // the tenant can reuse them
+        let key = ctx.th_search_key(
+            source.as_str(),
+            &query.query,
+            tenant_id,
+            false,
+            Some((from, to)),
+        );
+        if let Some(results) = ctx.th_cache().get(&key) {
+            ctx.th_progress()
+                .emit(&key.source, &key.query, None, SearchStatus::Cached);
+            searches.push((source, PendingSearch::Cached(results)));
+            continue;
+        }
+        match start_threat_search(client, ctx, auth, &query.query, source, from, to).await {
+            Ok(Some(id)) => searches.push((source, PendingSearch::Started { id, key })),
+            Ok(None) => ctx.th_progress().emit(
+                &key.source,
+                &key.query,
+                None,
+                SearchStatus::Failed {
+                    message: "Search rejected".to_string(),
+                },
+            ),
+            Err(e) => {
+                tracing::warn!("Threat search for {:?} not started: {}", source, e);
+                ctx.th_progress().emit(
+                    &key.source,
+                    &key.query,
+                    None,
+                    SearchStatus::Failed {
+                        message: e.to_string(),
+                    },
+                );
+            }
+        }
     }
 
-    // Poll for results in PARALLEL (with timeout of 10 attempts = ~30 seconds max per source)
-    // This reduces total time from 150s (sequential) to ~30s (parallel)
-    let (forum_results, chat_results, social_results, cred_results, ads_results) = tokio::join!(
-        async {
-            if let Some((_, Some(id))) = search_ids
-                .iter()
-                .find(|(s, _)| matches!(s, ThreatHuntingSource::ForumMessage))
-            {
-                poll_threat_search(client, auth, id, 10)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            }
-        },
-        async {
-            if let Some((_, Some(id))) = search_ids
-                .iter()
-                .find(|(s, _)| matches!(s, ThreatHuntingSource::ChatMessage))
-            {
-                poll_threat_search(client, auth, id, 10)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            }
-        },
-        async {
-            if let Some((_, Some(id))) = search_ids
-                .iter()
-                .find(|(s, _)| matches!(s, ThreatHuntingSource::SocialMediaPosts))
-            {
-                poll_threat_search(client, auth, id, 10)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            }
-        },
-        async {
-            if let Some((_, Some(id))) = search_ids
-                .iter()
-                .find(|(s, _)| matches!(s, ThreatHuntingSource::Credential))
-            {
-                poll_threat_search(client, auth, id, 10)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            }
-        },
-        async {
-            if let Some((_, Some(id))) = search_ids
-                .iter()
-                .find(|(s, _)| matches!(s, ThreatHuntingSource::SignalLakeAds))
-            {
-                poll_threat_search(client, auth, id, 10)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
+    // Poll for results in PARALLEL (with timeout of 10 attempts = ~30 seconds max per search)
+    // This keeps the total time close to the slowest search instead of their sum
+    let mut collected: Vec<(usize, ThreatHuntingSource, Vec<ThreatSearchResult>)> = vec![];
+    let mut polls = tokio::task::JoinSet::new();
+    for (idx, (source, search)) in searches.into_iter().enumerate() {
+        match search {
+            PendingSearch::Cached(results) => collected.push((idx, source, results)),
+            PendingSearch::Started { id, key } => {
+                let client = client.clone();
+                let ctx = ctx.clone();
+                let auth = auth.to_string();
+                polls.spawn(async move {
+                    let results = poll_threat_search(&client, &ctx, &auth, &id, &key, 10).await;
+                    (idx, source, results)
+                });
             }
         }
-    );
-
-    // Process Forum Message results
-    {
-        let results = forum_results;
-        intel.dark_web_mentions = results.len() as u64;
-
-        // Find earliest date
-        let mut earliest: Option<String> = None;
-        for r in &results {
-            if let Some(date) = &r.date {
-                if earliest.as_ref().map(|e| date < e).unwrap_or(true) {
-                    earliest = Some(date.clone());
+    }
+    while let Some(joined) = polls.join_next().await {
+        match joined {
+            Ok((idx, source, Ok(results))) => collected.push((idx, source, results)),
+            // Unfinished searches are reported as such, not as zero results
+            Ok((_, source, Err(e))) => {
+                tracing::warn!("Threat search for {:?} failed: {}", source, e);
+                let source = source.as_str().to_string();
+                if e.is::<SearchTimeout>() && !intel.timed_out_sources.contains(&source) {
+                    intel.timed_out_sources.push(source);
                 }
             }
+            Err(e) => tracing::warn!("Threat search poll task failed: {}", e),
         }
-        intel.earliest_dark_web_date = earliest;
-
-        // Collect unique sources
-        let sources: std::collections::HashSet<_> = results
-            .iter()
-            .filter_map(|r| r.source_name.clone().or(r.source.clone()))
-            .collect();
-        intel.dark_web_sources = sources.into_iter().collect();
-    }
-
-    // Process Chat Message results
-    {
-        let results = chat_results;
-        intel.chat_group_shares = results.len() as u64;
-
-        // Collect platforms
-        let platforms: std::collections::HashSet<_> =
-            results.iter().filter_map(|r| r.platform.clone()).collect();
-        intel.platforms_detected.extend(platforms);
     }
-
-    // Process Social Media Posts results
-    {
-        let results = social_results;
-        intel.social_media_mentions = results.len() as u64;
-
-        // Add social platforms
-        let platforms: std::collections::HashSet<_> =
-            results.iter().filter_map(|r| r.platform.clone()).collect();
-        intel.platforms_detected.extend(platforms);
-    }
-
-    // Process Credential results
-    {
-        let results = cred_results;
-        intel.total_credentials = results.len() as u64;
-
-        for r in &results {
-            // Classify by leak format
-            if let Some(format) = &r.leak_format {
-                let format_lower = format.to_lowercase();
-                if format_lower.contains("stealer") {
-                    intel.stealer_log_count += 1;
-                } else if format_lower.contains("combo") {
-                    intel.combolist_count += 1;
+    // Aggregate in search order
+    collected.sort_by_key(|(idx, _, _)| *idx);
+
+    let mut dark_web_sources: HashSet<String> = HashSet::new();
+    let mut platforms: HashSet<String> = HashSet::new();
+    let mut ad_platforms: HashSet<String> = HashSet::new();
+
+    for (_, source, results) in &collected {
+        let count = results.len() as u64;
+        match source {
+            // Dark web forums: mentions, earliest date and sources
+            ThreatHuntingSource::ForumMessage => {
+                intel.dark_web_mentions += count;
+                for r in results {
+                    if let Some(date) = &r.date {
+                        if intel
+                            .earliest_dark_web_date
+                            .as_ref()
+                            .map(|e| date < e)
+                            .unwrap_or(true)
+                        {
+                            intel.earliest_dark_web_date = Some(date.clone());
+                        }
+                    }
                 }
+                dark_web_sources.extend(
+                    results
+                        .iter()
+                        .filter_map(|r| r.source_name.clone().or(r.source.clone())),
+                );
             }
-
-            // Classify by password type
-            if let Some(pwd_type) = &r.password_type {
-                let type_lower = pwd_type.to_lowercase();
-                if type_lower.contains("plain") {
-                    intel.plain_password_count += 1;
-                } else {
-                    intel.hashed_password_count += 1;
-                }
+            // Chat groups and social media: virality and platforms
+            ThreatHuntingSource::ChatMessage => {
+                intel.chat_group_shares += count;
+                platforms.extend(results.iter().filter_map(|r| r.platform.clone()));
+            }
+            ThreatHuntingSource::SocialMediaPosts => {
+                intel.social_media_mentions += count;
+                platforms.extend(results.iter().filter_map(|r| r.platform.clone()));
+            }
+            ThreatHuntingSource::SignalLakeSocialMedia => {
+                intel.fake_social_profiles += count;
+                platforms.extend(results.iter().filter_map(|r| r.platform.clone()));
             }
+            // Credentials: leak format, password type and target portals
+            ThreatHuntingSource::Credential => {
+                intel.total_credentials += count;
+
+                for r in results {
+                    // Classify by leak format
+                    if let Some(format) = &r.leak_format {
+                        let format_lower = format.to_lowercase();
+                        if format_lower.contains("stealer") {
+                            intel.stealer_log_count += 1;
+                        } else if format_lower.contains("combo") {
+                            intel.combolist_count += 1;
+                        }
+                    }
+
+                    // Classify by password type
+                    if let Some(pwd_type) = &r.password_type {
+                        let type_lower = pwd_type.to_lowercase();
+                        if type_lower.contains("plain") {
+                            intel.plain_password_count += 1;
+                        } else {
+                            intel.hashed_password_count += 1;
+                        }
+                    }
 
-            // Collect access URLs
-            if let Some(url) = &r.access_url {
-                if intel.top_access_urls.len() < 5 && !intel.top_access_urls.contains(url) {
-                    intel.top_access_urls.push(url.clone());
+                    // Collect access URLs
+                    if let Some(url) = &r.access_url {
+                        if intel.top_access_urls.len() < 5 && !intel.top_access_urls.contains(url) {
+                            intel.top_access_urls.push(url.clone());
+                        }
+                    }
                 }
             }
-        }
-
-        // Compute percentages
-        if intel.total_credentials > 0 {
-            intel.stealer_log_percent =
-                (intel.stealer_log_count as f64 / intel.total_credentials as f64) * 100.0;
-            intel.plain_password_percent =
-                (intel.plain_password_count as f64 / intel.total_credentials as f64) * 100.0;
+            // Attacker investment: paid ads and infrastructure
+            ThreatHuntingSource::SignalLakeAds => {
+                intel.paid_ads_detected += count;
+                ad_platforms.extend(results.iter().filter_map(|r| r.platform.clone()));
+            }
+            ThreatHuntingSource::SignalLake => intel.infrastructure_signals += count,
+            // Financial data exposure
+            ThreatHuntingSource::CreditCard => intel.credit_card_leaks += count,
+            ThreatHuntingSource::Tokens => intel.token_leaks += count,
         }
     }
 
-    // Process Signal Lake Ads results
-    {
-        let results = ads_results;
-        intel.paid_ads_detected = results.len() as u64;
+    intel.dark_web_sources = dark_web_sources.into_iter().collect();
+    intel.platforms_detected = platforms.into_iter().collect();
+    intel.ad_platforms = ad_platforms.into_iter().collect();
 
-        // Collect ad platforms
-        let platforms: std::collections::HashSet<_> =
-            results.iter().filter_map(|r| r.platform.clone()).collect();
-        intel.ad_platforms = platforms.into_iter().collect();
+    // Compute credential percentages
+    if intel.total_credentials > 0 {
+        intel.stealer_log_percent =
+            (intel.stealer_log_count as f64 / intel.total_credentials as f64) * 100.0;
+        intel.plain_password_percent =
+            (intel.plain_password_count as f64 / intel.total_credentials as f64) * 100.0;
     }
 
     // Compute days before public (if we have dark web data and incident data)
@@ -4278,7 +5080,11 @@ pub async fn fetch_threat_intelligence(
         || intel.chat_group_shares > 0
         || intel.social_media_mentions > 0
         || intel.total_credentials > 0
-        || intel.paid_ads_detected > 0;
+        || intel.paid_ads_detected > 0
+        || intel.infrastructure_signals > 0
+        || intel.fake_social_profiles > 0
+        || intel.credit_card_leaks > 0
+        || intel.token_leaks > 0;
 
     intel
 }
@@ -4298,12 +5104,14 @@ const SIGNAL_LAKE_MAX_POLLS: u32 = 30;
 
 /// Start a signal-lake search for a domain
 async fn start_signal_lake_search(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     tenant_id: &str,
     domain: &str,
 ) -> Result<String> {
-    let url = format!("{}/threat-hunting-api/external-search", API_URL);
+    ctx.credits().check()?;
+    let url = format!("{}/threat-hunting-api/external-search", client.base_url());
 
     let request = SignalLakeSearchRequest {
         query: format!("domain=\"{}\"", domain),
@@ -4324,19 +5132,31 @@ async fn start_signal_lake_search(
 
     let data: SignalLakeSearchInitResponse = resp.json().await?;
 
-    data.search_id
-        .ok_or_else(|| anyhow::anyhow!("No searchId in response"))
+    let search_id = data
+        .search_id
+        .ok_or_else(|| anyhow::anyhow!("No searchId in response"))?;
+    ctx.credits()
+        .started(&search_id, &request.source, &request.query);
+    ctx.th_progress().emit(
+        &request.source,
+        &request.query,
+        Some(&search_id),
+        SearchStatus::Started,
+    );
+    Ok(search_id)
 }
 
-/// Poll signal-lake for search results
+/// Poll signal-lake for the first page of search results (raw, so it can be
+/// cached and shared with the preview counts)
 async fn poll_signal_lake_results(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     search_id: &str,
-) -> Result<SignalLakePollResponse> {
+) -> Result<serde_json::Value> {
     let url = format!(
         "{}/threat-hunting-api/external-search/{}?page=1",
-        API_URL, search_id
+        client.base_url(),
+        search_id
     );
 
     let resp = client
@@ -4349,36 +5169,74 @@ async fn poll_signal_lake_results(
         anyhow::bail!("Signal-lake poll failed: {}", resp.status());
     }
 
-    let data: SignalLakePollResponse = resp.json().await?;
-    Ok(data)
+    resp.json().await
 }
 
-/// Investigate a single ticket using signal-lake
-async fn investigate_ticket(
-    client: &reqwest::Client,
-    auth: &str,
-    tenant_id: &str,
-    ticket: &StoryTicket,
-) -> Option<DeepInvestigationResult> {
-    // Extract domain from target
-    let domain = &ticket.target;
-    if domain.is_empty() {
-        return None;
+/// Bill a completed signal-lake results page, dropping the results beyond
+/// the credits left, and cache it when complete. Returns the results kept.
+fn complete_signal_lake_page(
+    ctx: &ReportContext,
+    search_id: &str,
+    key: &ThSearchKey,
+    page: &mut serde_json::Value,
+) -> u64 {
+    let Some(items) = page
+        .pointer_mut("/result/data")
+        .and_then(|d| d.as_array_mut())
+    else {
+        ctx.credits().completed(search_id, 0);
+        ctx.th_cache().put(key, page);
+        return 0;
+    };
+    let found = items.len() as u64;
+    let kept = ctx.credits().completed(search_id, found);
+    if kept < found {
+        items.truncate(kept as usize);
+    } else {
+        ctx.th_cache().put(key, page);
     }
+    kept
+}
 
+/// Whether a signal-lake results page is still being filled
+fn signal_lake_running(page: &serde_json::Value) -> bool {
+    page.get("result")
+        .and_then(|r| r.get("status"))
+        .and_then(|s| s.get("running"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true) // Default to running if missing
+}
+
+/// Start a signal-lake search for a ticket domain and poll its first page,
+/// cached under `key` once complete. Fails with [`SearchTimeout`] if the
+/// search is still running after the last poll.
+async fn poll_ticket_search(
+    client: &ApiTransport,
+    ctx: &ReportContext,
+    auth: &str,
+    tenant_id: &str,
+    domain: &str,
+    key: &ThSearchKey,
+) -> Result<serde_json::Value> {
     // Start the search
-    let search_id = match start_signal_lake_search(client, auth, tenant_id, domain).await {
+    let search_id = match start_signal_lake_search(client, ctx, auth, tenant_id, domain).await {
         Ok(id) => id,
         Err(e) => {
             tracing::error!("Failed to start signal-lake search for {}: {}", domain, e);
-            return None;
+            ctx.th_progress().emit(
+                &key.source,
+                &key.query,
+                None,
+                SearchStatus::Failed {
+                    message: e.to_string(),
+                },
+            );
+            return Err(e);
         }
     };
 
     // Poll for results
     let mut attempts = 0;
-    let mut result_data: Vec<SignalLakeDataItem> = Vec::new();
-    let mut total_signals = 0u64;
 
     loop {
         tokio::time::sleep(std::time::Duration::from_millis(
@@ -4386,42 +5244,100 @@ async fn investigate_ticket(
         ))
         .await;
         attempts += 1;
+        ctx.th_progress().emit(
+            &key.source,
+            &key.query,
+            Some(&search_id),
+            SearchStatus::Polling {
+                attempt: attempts,
+                max_attempts: SIGNAL_LAKE_MAX_POLLS,
+            },
+        );
 
         match poll_signal_lake_results(client, auth, &search_id).await {
-            Ok(response) => {
-                // Check running status from nested result object
-                let running = response
-                    .result
-                    .as_ref()
-                    .and_then(|r| r.status.as_ref())
-                    .and_then(|s| s.running)
-                    .unwrap_or(true); // Default to running if missing
-
-                if !running {
-                    if let Some(result) = response.result {
-                        if let Some(data) = result.data {
-                            result_data = data;
-                        }
-                        if let Some(pagination) = result.pagination {
-                            total_signals = pagination.total.unwrap_or(0);
-                        }
-                    }
-                    break;
+            Ok(mut page) => {
+                if !signal_lake_running(&page) {
+                    let fetched = complete_signal_lake_page(ctx, &search_id, key, &mut page);
+                    ctx.th_progress().emit(
+                        &key.source,
+                        &key.query,
+                        Some(&search_id),
+                        SearchStatus::Completed {
+                            results: fetched,
+                            pages: pages_for(fetched),
+                        },
+                    );
+                    return Ok(page);
                 }
                 // Still processing, continue polling
-                // Still processing, continue polling
             }
             Err(e) => {
                 tracing::warn!("Error polling signal-lake for {}: {}", domain, e);
-                if attempts >= SIGNAL_LAKE_MAX_POLLS {
-                    return None;
-                }
             }
         }
 
         if attempts >= SIGNAL_LAKE_MAX_POLLS {
             tracing::warn!("Signal-lake polling timeout for {}", domain);
-            return None;
+            ctx.th_progress().emit(
+                &key.source,
+                &key.query,
+                Some(&search_id),
+                SearchStatus::TimedOut { attempts },
+            );
+            return Err(SearchTimeout {
+                source: key.source.clone(),
+                search_id,
+                attempts,
+            }
+            .into());
+        }
+    }
+}
+
+/// Investigate a single ticket using signal-lake (None for tickets without
+/// a target). Fails if its search could not be started or did not finish.
+async fn investigate_ticket(
+    client: &ApiTransport,
+    ctx: &ReportContext,
+    auth: &str,
+    tenant_id: &str,
+    ticket: &StoryTicket,
+) -> Result<Option<DeepInvestigationResult>> {
+    // Extract domain from target
+    let domain = &ticket.target;
+    if domain.is_empty() {
+        return Ok(None);
+    }
+
+    // Results of the same search by a recent preview or report are reused
+    let key = ctx.th_search_key(
+        ThreatHuntingSource::SignalLake.as_str(),
+        &format!("domain=\"{}\"", domain),
+        tenant_id,
+        false,
+        None,
+    );
+    let page = match ctx.th_cache().get::<serde_json::Value>(&key) {
+        Some(page) => {
+            ctx.th_progress()
+                .emit(&key.source, &key.query, None, SearchStatus::Cached);
+            page
+        }
+        None => poll_ticket_search(client, ctx, auth, tenant_id, domain, &key).await?,
+    };
+
+    let mut result_data: Vec<SignalLakeDataItem> = Vec::new();
+    let mut total_signals = 0u64;
+    if let Ok(SignalLakePollResponse {
+        result: Some(result),
+        ..
+    }) = serde_json::from_value::<SignalLakePollResponse>(page)
+    {
+        if let Some(data) = result.data {
+            result_data = data;
+        }
+        if let Some(pagination) = result.pagination {
+            total_signals = pagination.total.unwrap_or(0);
         }
     }
 
@@ -4471,7 +5387,7 @@ async fn investigate_ticket(
     // Extract enrichment data from the first item that has it (prioritize items with screenshots)
     let enrichment = extract_enrichment_data(&result_data);
 
-    Some(DeepInvestigationResult {
+    Ok(Some(DeepInvestigationResult {
         ticket_key: ticket.ticket_key.clone(),
         target: domain.clone(),
         status: ticket.status.clone(),
@@ -4484,7 +5400,7 @@ async fn investigate_ticket(
         first_seen,
         last_seen,
         enrichment,
-    })
+    }))
 }
 
 /// Extract enrichment data from Signal-Lake results
@@ -4592,21 +5508,35 @@ fn format_unix_timestamp_ms(timestamp_ms: i64) -> String {
 }
 
 /// Investigate multiple tagged tickets using signal-lake
-/// Returns enriched investigation results for each ticket
+/// Returns enriched investigation results for each ticket, and the keys of
+/// the tickets whose search timed out
 pub async fn investigate_tagged_tickets(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     tenant_id: &str,
     tickets: &[StoryTicket],
-) -> Vec<DeepInvestigationResult> {
+) -> (Vec<DeepInvestigationResult>, Vec<String>) {
     let mut results = Vec::new();
+    let mut timed_out = Vec::new();
 
     // Limit to prevent excessive API usage
     let tickets_to_process = tickets.iter().take(MAX_TICKETS_TO_INVESTIGATE);
 
     for ticket in tickets_to_process {
-        if let Some(result) = investigate_ticket(client, auth, tenant_id, ticket).await {
-            results.push(result);
+        match investigate_ticket(client, ctx, auth, tenant_id, ticket).await {
+            Ok(Some(result)) => results.push(result),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "Investigation of ticket {} failed: {}",
+                    ticket.ticket_key,
+                    e
+                );
+                if e.is::<SearchTimeout>() {
+                    timed_out.push(ticket.ticket_key.clone());
+                }
+            }
         }
     }
 
@@ -4619,7 +5549,7 @@ pub async fn investigate_tagged_tickets(
         }
     }
 
-    results
+    (results, timed_out)
 }
 
 // ========================
@@ -4663,7 +5593,7 @@ struct CredentialPageable {
 
 /// Fetch credentials filtered by a specific tag
 pub async fn fetch_tagged_credentials(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     tenant_id: &str,
     tag: &str,
@@ -4675,7 +5605,8 @@ pub async fn fetch_tagged_credentials(
     // Assuming tags={tag} works based on user requirement.
     // We request 50 items for the slide.
     let url = format!(
-        "https://api.axur.com/gateway/1.0/api/exposure-api/credentials?tags={}&customer={}&pageSize=50",
+        "{}/exposure-api/credentials?tags={}&customer={}&pageSize=50",
+        client.base_url(),
         tag,
         tenant_id
     );
@@ -4710,7 +5641,7 @@ pub async fn fetch_tagged_credentials(
 
 /// Fetch critical credentials where password contains the tenant ID/NAME
 pub async fn fetch_critical_credentials(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     tenant_id: &str,
 ) -> Vec<CredentialExposure> {
@@ -4721,7 +5652,8 @@ pub async fn fetch_critical_credentials(
     // "password=contains:{tenant_id}"
     // We request 10 items.
     let url = format!(
-        "https://api.axur.com/gateway/1.0/api/exposure-api/credentials?password=contains:{}&customer={}&pageSize=10&sortBy=created&order=desc",
+        "{}/exposure-api/credentials?password=contains:{}&customer={}&pageSize=10&sortBy=created&order=desc",
+        client.base_url(),
         tenant_id,
         tenant_id
     );
@@ -4768,13 +5700,15 @@ pub async fn fetch_critical_credentials(
 
 /// Start a search on any Threat Hunting source
 async fn start_threat_hunting_search(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     customer: Option<&str>,
     query: &str,
     source: ThreatHuntingSource,
 ) -> Result<String> {
-    let url = format!("{}/threat-hunting-api/external-search", API_URL);
+    ctx.credits().check()?;
+    let url = format!("{}/threat-hunting-api/external-search", client.base_url());
 
     let request = SignalLakeSearchRequest {
         query: query.to_string(),
@@ -4822,7 +5756,10 @@ async fn start_threat_hunting_search(
             ));
             let log_content = format!(
                 "HTTP Status: {}\nURL: {}\nRetry: {}\n\nResponse Body:\n{}",
-                status, url, retry_count, resp_text
+                status,
+                url,
+                retry_count,
+                super::redaction::redact_log_text(&resp_text)
             );
             let _ = std::fs::write(&resp_file, &log_content);
             tracing::info!("DEBUG: Saved POST response to {:?}", resp_file);
@@ -4861,22 +5798,93 @@ async fn start_threat_hunting_search(
         }
 
         let data: SignalLakeSearchInitResponse = serde_json::from_str(&resp_text)?;
-        return data
+        let search_id = data
             .search_id
-            .ok_or_else(|| anyhow::anyhow!("No searchId in response"));
+            .ok_or_else(|| anyhow::anyhow!("No searchId in response"))?;
+        ctx.credits().started(&search_id, source.as_str(), query);
+        ctx.th_progress().emit(
+            source.as_str(),
+            query,
+            Some(&search_id),
+            SearchStatus::Started,
+        );
+        return Ok(search_id);
     }
 }
 
-/// Poll and get count from any source (without consuming too many credits)
+/// Total results and a few samples from the first page of a Threat Hunting search
+fn th_page_summary(json: &serde_json::Value) -> (u64, Vec<String>) {
+    let total = json
+        .get("result")
+        .and_then(|r| r.get("pagination"))
+        .and_then(|p| p.get("total"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    // Extract samples from first few items
+    let samples: Vec<String> = json
+        .get("result")
+        .and_then(|r| r.get("data"))
+        .and_then(|d| d.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .take(3)
+                .filter_map(|item| {
+                    item.get("url")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .or_else(|| {
+                            item.get("domain")
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string())
+                        })
+                        .or_else(|| {
+                            item.get("ip")
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string())
+                        })
+                        .or_else(|| {
+                            item.get("name")
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string())
+                        })
+                        .or_else(|| {
+                            item.get("accessUrl") // Check camelCase first
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string())
+                        })
+                        .or_else(|| {
+                            item.get("access_url") // Check snake_case just in case
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string())
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    (total, samples)
+}
+
+/// Maximum poll attempts of a Threat Hunting count search
+const TH_COUNT_MAX_POLLS: u32 = 20;
+
+/// Poll and get count from any source (without consuming too many credits),
+/// caching the first page under `key` once complete. Fails with
+/// [`SearchTimeout`] if the search is still running after the last poll.
 async fn poll_threat_hunting_count(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
     search_id: &str,
+    key: &ThSearchKey,
 ) -> Result<(u64, Vec<String>)> {
     // Just get first page for count and samples
     let url = format!(
         "{}/threat-hunting-api/external-search/{}?page=1",
-        API_URL, search_id
+        client.base_url(),
+        search_id
     );
 
     let debug_dir = std::path::Path::new("debug_logs");
@@ -4890,10 +5898,33 @@ async fn poll_threat_hunting_count(
         tracing::info!("DEBUG: Saved GET request to {:?}", req_file);
     }
 
+    let timed_out = |attempts: u32| {
+        ctx.th_progress().emit(
+            &key.source,
+            &key.query,
+            Some(search_id),
+            SearchStatus::TimedOut { attempts },
+        );
+        anyhow::Error::from(SearchTimeout {
+            source: key.source.clone(),
+            search_id: search_id.to_string(),
+            attempts,
+        })
+    };
+
     let mut attempts = 0;
     loop {
         tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
         attempts += 1;
+        ctx.th_progress().emit(
+            &key.source,
+            &key.query,
+            Some(search_id),
+            SearchStatus::Polling {
+                attempt: attempts,
+                max_attempts: TH_COUNT_MAX_POLLS,
+            },
+        );
 
         let resp = client
             .get(&url)
@@ -4912,7 +5943,10 @@ async fn poll_threat_hunting_count(
             ));
             let log_content = format!(
                 "HTTP Status: {}\nURL: {}\nAttempt: {}\n\nResponse Body:\n{}",
-                status_code, url, attempts, resp_text
+                status_code,
+                url,
+                attempts,
+                super::redaction::redact_log_text(&resp_text)
             );
             let _ = std::fs::write(&resp_file, &log_content);
             tracing::info!(
@@ -4923,78 +5957,32 @@ async fn poll_threat_hunting_count(
         }
 
         if !status_code.is_success() {
-            if attempts >= 20 {
-                return Ok((0, vec![]));
+            if attempts >= TH_COUNT_MAX_POLLS {
+                return Err(timed_out(attempts));
             }
             continue;
         }
 
-        let json: serde_json::Value = serde_json::from_str(&resp_text)?;
-
-        // Check running status from nested result object
-        let running = json
-            .get("result")
-            .and_then(|r| r.get("status"))
-            .and_then(|s| s.get("running"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true); // Default to running if missing
-
-        if !running {
-            let total = json
-                .get("result")
-                .and_then(|r| r.get("pagination"))
-                .and_then(|p| p.get("total"))
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
-
-            // Extract samples from first few items
-            let samples: Vec<String> = json
-                .get("result")
-                .and_then(|r| r.get("data"))
-                .and_then(|d| d.as_array())
-                .map(|items| {
-                    items
-                        .iter()
-                        .take(3)
-                        .filter_map(|item| {
-                            item.get("url")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string())
-                                .or_else(|| {
-                                    item.get("domain")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string())
-                                })
-                                .or_else(|| {
-                                    item.get("ip")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string())
-                                })
-                                .or_else(|| {
-                                    item.get("name")
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string())
-                                })
-                                .or_else(|| {
-                                    item.get("accessUrl") // Check camelCase first
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string())
-                                })
-                                .or_else(|| {
-                                    item.get("access_url") // Check snake_case just in case
-                                        .and_then(|v| v.as_str())
-                                        .map(|s| s.to_string())
-                                })
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
+        let mut json: serde_json::Value = serde_json::from_str(&resp_text)?;
+
+        if !signal_lake_running(&json) {
+            // Only the first page is fetched
+            let fetched = complete_signal_lake_page(ctx, search_id, key, &mut json);
+            ctx.th_progress().emit(
+                &key.source,
+                &key.query,
+                Some(search_id),
+                SearchStatus::Completed {
+                    results: fetched,
+                    pages: pages_for(fetched),
+                },
+            );
 
-            return Ok((total, samples));
+            return Ok(th_page_summary(&json));
         }
 
-        if attempts >= 20 {
-            return Ok((0, vec![]));
+        if attempts >= TH_COUNT_MAX_POLLS {
+            return Err(timed_out(attempts));
         }
     }
 }
@@ -5008,7 +5996,30 @@ pub async fn preview_threat_hunting(
     story_tag: &str,
     use_user_credits: bool,
 ) -> Result<ThreatHuntingPreview> {
-    let client = create_client()?;
+    let client = ApiTransport::from_env()?;
+    preview_threat_hunting_with(
+        &client,
+        &ReportContext::default(),
+        token,
+        tenant_id,
+        tickets,
+        story_tag,
+        use_user_credits,
+    )
+    .await
+}
+
+/// Same as [`preview_threat_hunting`] through an explicit transport and
+/// report context (e.g. one carrying a credit meter)
+pub async fn preview_threat_hunting_with(
+    client: &ApiTransport,
+    ctx: &ReportContext,
+    token: &str,
+    tenant_id: &str,
+    tickets: &[StoryTicket],
+    story_tag: &str,
+    use_user_credits: bool,
+) -> Result<ThreatHuntingPreview> {
     let auth = format!("Bearer {}", token);
 
     let mut preview = ThreatHuntingPreview {
@@ -5019,7 +6030,7 @@ pub async fn preview_threat_hunting(
     // 1. Fetch Tagged Credentials using Exposure API (correct approach)
     // The Threat Hunting API does NOT support tag: queries for credentials
     // Must use /exposure-api/credentials?tags=contains:{tag} instead
-    let mut search_ids: Vec<(ThreatHuntingSource, String, String)> = Vec::new();
+    let mut search_ids = Vec::new();
 
     if !story_tag.is_empty() {
         tracing::info!(
@@ -5034,9 +6045,10 @@ pub async fn preview_threat_hunting(
             format!("&customer={}", tenant_id)
         };
 
-        let exposure_url = format!(
+        let exposure_url =
+            format!(
             "{}/exposure-api/credentials?tags=contains:{}&pageSize=100&sortBy=created&order=desc{}",
-            API_URL, story_tag, customer_param
+            client.base_url(), story_tag, customer_param
         );
 
         tracing::debug!("Exposure API URL: {}", exposure_url);
@@ -5057,7 +6069,10 @@ pub async fn preview_threat_hunting(
                             let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
                             let debug_file =
                                 debug_dir.join(format!("exposure_credentials_{}.json", timestamp));
-                            let _ = std::fs::write(&debug_file, &body);
+                            let _ = std::fs::write(
+                                &debug_file,
+                                super::redaction::redact_log_text(&body),
+                            );
                         }
 
                         // Parse the response
@@ -5180,15 +6195,17 @@ pub async fn preview_threat_hunting(
     for domain in &unique_domains {
         // No quotes around domain value - matches Axur web UI format
         let query = format!("domain=\"{}\"", domain);
+        let source = ThreatHuntingSource::SignalLake;
+        let key = ctx.th_search_key(source.as_str(), &query, tenant_id, use_user_credits, None);
+        if let Some(page) = ctx.th_cache().get::<serde_json::Value>(&key) {
+            ctx.th_progress()
+                .emit(&key.source, &key.query, None, SearchStatus::Cached);
+            let counted = PendingSearch::Cached(th_page_summary(&page));
+            search_ids.push((source, counted, domain.clone()));
+            continue;
+        }
 
-        match start_threat_hunting_search(
-            &client,
-            &auth,
-            search_customer,
-            &query,
-            ThreatHuntingSource::SignalLake,
-        )
-        .await
+        match start_threat_hunting_search(client, ctx, &auth, search_customer, &query, source).await
         {
             Ok(id) => {
                 tracing::info!(
@@ -5196,10 +6213,18 @@ pub async fn preview_threat_hunting(
                     domain,
                     id
                 );
-                search_ids.push((ThreatHuntingSource::SignalLake, id, domain.clone()));
+                search_ids.push((source, PendingSearch::Started { id, key }, domain.clone()));
             }
             Err(e) => {
                 tracing::warn!("Failed to start Signal-Lake search for '{}': {}", domain, e);
+                ctx.th_progress().emit(
+                    &key.source,
+                    &key.query,
+                    None,
+                    SearchStatus::Failed {
+                        message: e.to_string(),
+                    },
+                );
             }
         }
 
@@ -5208,8 +6233,14 @@ pub async fn preview_threat_hunting(
     }
 
     // Poll each search for counts (aggregate results)
-    for (source, search_id, domain) in search_ids {
-        match poll_threat_hunting_count(&client, &auth, &search_id).await {
+    for (source, search, domain) in search_ids {
+        let counted = match search {
+            PendingSearch::Cached(counted) => Ok(counted),
+            PendingSearch::Started { id, key } => {
+                poll_threat_hunting_count(client, ctx, &auth, &id, &key).await
+            }
+        };
+        match counted {
             Ok((count, samples)) => {
                 tracing::info!(
                     "Got {} results for {} on domain '{}'",
@@ -5242,6 +6273,9 @@ pub async fn preview_threat_hunting(
                     domain,
                     e
                 );
+                if e.is::<SearchTimeout>() {
+                    preview.timed_out_domains.push(domain);
+                }
             }
         }
     }
@@ -5260,26 +6294,32 @@ pub async fn preview_threat_hunting(
 /// Public function for SSE streaming - starts a TH search and polls for count
 /// This is a simplified version for the streaming endpoint
 pub async fn start_and_poll_th_search(
-    client: &reqwest::Client,
+    client: &ApiTransport,
+    ctx: &ReportContext,
     auth: &str,
-    customer: Option<&str>,
+    tenant_id: &str,
+    use_user_credits: bool,
     query: &str,
     source_str: &str,
 ) -> Result<u64> {
     // Map source string to enum
-    let source = match source_str {
-        "credential" => ThreatHuntingSource::Credential,
-        "signal-lake" => ThreatHuntingSource::SignalLake,
-        "chat-message" => ThreatHuntingSource::ChatMessage,
-        "forum-message" => ThreatHuntingSource::ForumMessage,
-        _ => ThreatHuntingSource::SignalLake,
-    };
+    let source = ThreatHuntingSource::parse(source_str).unwrap_or(ThreatHuntingSource::SignalLake);
+    // None = billed to the user's own credits
+    let customer = (!use_user_credits).then_some(tenant_id);
+
+    // Results of the same search by a recent preview or report are reused
+    let key = ctx.th_search_key(source.as_str(), query, tenant_id, use_user_credits, None);
+    if let Some(page) = ctx.th_cache().get::<serde_json::Value>(&key) {
+        ctx.th_progress()
+            .emit(&key.source, &key.query, None, SearchStatus::Cached);
+        return Ok(th_page_summary(&page).0);
+    }
 
     // Start the search
-    let search_id = start_threat_hunting_search(client, auth, customer, query, source).await?;
+    let search_id = start_threat_hunting_search(client, ctx, auth, customer, query, source).await?;
 
     // Poll for results
-    let (count, _samples) = poll_threat_hunting_count(client, auth, &search_id).await?;
+    let (count, _samples) = poll_threat_hunting_count(client, ctx, auth, &search_id, &key).await?;
 
     Ok(count)
 }
@@ -5303,7 +6343,7 @@ struct RiskMetricsRaw {
 }
 
 async fn fetch_risk_score_metrics(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth_header: &str,
     customer_key: &str,
     start_date: &str,
@@ -5312,34 +6352,44 @@ async fn fetch_risk_score_metrics(
     // 1. Threat Volume (Incident Counts by Type)
     let threats_url = format!(
         "{}/tickets-api/stats/incident/count/ticket-types?from={}&to={}&customer={}",
-        API_URL, start_date, end_date, customer_key
+        client.base_url(),
+        start_date,
+        end_date,
+        customer_key
     );
 
     // 2. Market Benchmark (Median Incidents)
     // Use "to" date only as per API, it looks back 13 months
     let benchmark_url = format!(
         "{}/tickets-api/stats/incident/customer/market-segment/median?to={}&customer={}",
-        API_URL, end_date, customer_key
+        client.base_url(),
+        end_date,
+        customer_key
     );
 
     // 3. Critical Hygiene (Credential Leaks Count)
     // Filter for specific types if desired, but "total" endpoint is efficient
     let leaks_url = format!(
         "{}/exposure-api/credentials/total?created=ge:{}T00:00:00&created=le:{}T23:59:59&customer={}&timezone=-03:00",
-        API_URL, start_date, end_date, customer_key
+        client.base_url(), start_date, end_date, customer_key
     );
 
     // 4. Efficiency (Takedown Stats)
     let takedown_url = format!(
         "{}/tickets-api/stats/takedown?from={}&to={}&customer={}",
-        API_URL, start_date, end_date, customer_key
+        client.base_url(),
+        start_date,
+        end_date,
+        customer_key
     );
 
     // 5. Reputation (Web Complaints)
     // Using initialDate and finalDate
     let complaints_url = format!(
         "{}/web-complaints/results?initialDate={}&finalDate={}&page=1&pageSize=1",
-        API_URL, start_date, end_date
+        client.base_url(),
+        start_date,
+        end_date
     );
 
     // Batch 1: Threats, Benchmark, Leaks (3 concurrent)
@@ -5364,8 +6414,40 @@ async fn fetch_risk_score_metrics(
     }
 }
 
+/// Past periods scored for the risk score history
+const RISK_HISTORY_PERIODS: usize = 3;
+
+/// Risk inputs of the windows of the same length before the report period,
+/// oldest first. Periods are fetched one after another to stay under the
+/// rate limit.
+async fn fetch_risk_history(
+    client: &ApiTransport,
+    auth_header: &str,
+    customer_key: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Vec<RiskPeriodInputs> {
+    let mut periods = Vec::new();
+    let (mut from, mut to) = (start_date.to_string(), end_date.to_string());
+    for _ in 0..RISK_HISTORY_PERIODS {
+        let Some((prev_from, prev_to)) = ComparisonPeriod::PreviousPeriod.window(&from, &to) else {
+            break;
+        };
+        let raw =
+            fetch_risk_score_metrics(client, auth_header, customer_key, &prev_from, &prev_to).await;
+        periods.push(RiskPeriodInputs {
+            start_date: prev_from.clone(),
+            end_date: prev_to.clone(),
+            inputs: RiskInputs::from(&raw),
+        });
+        (from, to) = (prev_from, prev_to);
+    }
+    periods.reverse();
+    periods
+}
+
 async fn fetch_json_metric<T: for<'de> Deserialize<'de>>(
-    client: &reqwest::Client,
+    client: &ApiTransport,
     auth: &str,
     url: &str,
 ) -> Option<T> {
@@ -5385,108 +6467,133 @@ async fn fetch_json_metric<T: for<'de> Deserialize<'de>>(
     }
 }
 
-fn calculate_risk_score_v3(data: &RiskMetricsRaw) -> RiskScore {
-    // === 1. Weighted Threat Volume (30%) ===
-    // Sum of incidents weighted by severity
-    // Weights: Phishing/Malware=3, Social Media/Brand=2, Others=1
-    let mut threat_score_raw = 0.0;
-    if let Some(stats) = &data.threat_counts {
-        if let Some(types) = &stats.total_by_ticket_type {
-            for t in types {
-                let weight = match t.ticket_type.as_str() {
-                    "phishing" | "malware" | "ransomware-attack" => 3.0,
-                    "fake-social-media-profile" | "fraudulent-brand-use" | "fake-mobile-app" => 2.0,
-                    _ => 1.0,
-                };
-                threat_score_raw += (t.count as f64) * weight;
-            }
+impl From<&RiskMetricsRaw> for RiskInputs {
+    fn from(data: &RiskMetricsRaw) -> Self {
+        RiskInputs {
+            incidents_by_ticket_type: data
+                .threat_counts
+                .as_ref()
+                .and_then(|s| s.total_by_ticket_type.as_ref())
+                .map(|types| {
+                    types
+                        .iter()
+                        .map(|t| NameValuePair {
+                            name: t.ticket_type.clone(),
+                            value: t.count,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            market_median: data
+                .market_benchmark
+                .as_ref()
+                .map(|bench| bench.medians.last().map(|m| m.value).unwrap_or(0.0)),
+            critical_leaks: data.critical_leaks.as_ref().map(|l| l.total),
+            takedown_success_rate: data.takedown_stats.as_ref().map(|t| t.total.success_rate),
+            takedown_median_uptime_ms: data
+                .takedown_stats
+                .as_ref()
+                .map(|t| t.total.raw_median_uptime),
+            web_complaints: data.web_complaints.as_ref().map(|c| c.total_elements),
         }
     }
-    // Normalize: Assume > 100 weighted points is "High" (10.0 score)
-    let s1_volume = (threat_score_raw / 100.0).min(1.0) * 10.0;
-
-    // === 2. Market Benchmarking (20%) ===
-    // Compare volume to market median. If > median, score increases.
-    // If no data, assume neutral (5.0)
-    let s2_benchmark = if let Some(bench) = &data.market_benchmark {
-        let median_last_month = bench.medians.last().map(|m| m.value).unwrap_or(0.0);
-        // Simple comparison: Ratio of current raw count to median
-        // We need raw count (unweighted)
-        let total_count: u64 = data
-            .threat_counts
-            .as_ref()
-            .and_then(|s| s.total_by_ticket_type.as_ref())
-            .map(|v| v.iter().map(|t| t.count).sum())
-            .unwrap_or(0);
-
-        if median_last_month > 0.0 {
-            let ratio = (total_count as f64) / median_last_month;
-            // logic: ratio 1.0 -> 5.0 score. ratio 2.0 -> 10.0 score. ratio 0.5 -> 2.5 score.
-            (ratio * 5.0).min(10.0)
-        } else {
-            0.0 // No market activity? Good.
-        }
-    } else {
-        5.0 // Default if no benchmark data
-    };
+}
 
-    // === 3. Critical Hygiene (20%) ===
-    // Count of critical credential leaks
-    let s3_hygiene = if let Some(leaks) = &data.critical_leaks {
-        // Assume > 50 leaks is critical (10.0)
-        (leaks.total as f64 / 50.0).min(1.0) * 10.0
-    } else {
-        0.0 // Optimistic default
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // === 4. Operational Efficiency (15%) ===
-    // Inverse of Success Rate and Uptime. Higher efficiency -> Lower Risk Score.
-    // We calculate "Inefficiency Score" to add to Risk.
-    let s4_efficiency = if let Some(td) = &data.takedown_stats {
-        // Success Rate: 100% -> 0 risk. 80% -> 2 risk.
-        let success_risk = ((100.0 - td.total.success_rate) / 10.0).max(0.0).min(10.0);
+    #[test]
+    fn test_poc_report_data_json_roundtrip() {
+        let data = PocReportData::demo();
+        let json = serde_json::to_string(&data).unwrap();
+        let restored: PocReportData = serde_json::from_str(&json).unwrap();
 
-        // Uptime: > 5 days (432000000 ms) -> high risk
-        let uptime_days = td.total.raw_median_uptime / (1000.0 * 3600.0 * 24.0);
-        let uptime_risk = (uptime_days / 5.0).min(1.0) * 10.0;
+        assert_eq!(restored.company_name, data.company_name);
+        assert_eq!(restored.total_tickets, data.total_tickets);
+        assert_eq!(restored.poc_examples.len(), data.poc_examples.len());
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&data).unwrap()
+        );
+    }
 
-        (success_risk + uptime_risk) / 2.0
-    } else {
-        5.0 // Neutral
-    };
+    #[test]
+    fn test_comparison_windows() {
+        let w = |p: ComparisonPeriod| p.window("2025-03-01", "2025-03-31").unwrap();
 
-    // === 5. Reputational Factor (15%) ===
-    // Based on Web Complaints volume
-    let s5_reputation = if let Some(complaints) = &data.web_complaints {
-        // Assume > 20 complaints is critical
-        (complaints.total_elements as f64 / 20.0).min(1.0) * 10.0
-    } else {
-        0.0
-    };
+        assert_eq!(
+            w(ComparisonPeriod::PreviousPeriod),
+            ("2025-01-29".to_string(), "2025-02-28".to_string())
+        );
+        assert_eq!(
+            w(ComparisonPeriod::PreviousMonth),
+            ("2025-02-01".to_string(), "2025-02-28".to_string())
+        );
+        assert_eq!(
+            w(ComparisonPeriod::PreviousQuarter),
+            ("2024-12-01".to_string(), "2024-12-31".to_string())
+        );
+        assert_eq!(
+            w(ComparisonPeriod::Custom {
+                from: "2024-03-01".into(),
+                to: "2024-03-31".into()
+            }),
+            ("2024-03-01".to_string(), "2024-03-31".to_string())
+        );
+        assert!(ComparisonPeriod::PreviousMonth
+            .window("2025-03-31", "2025-03-01")
+            .is_none());
+    }
 
-    // === TOTAL WEIGHTED SCORE ===
-    // S1(30%) + S2(20%) + S3(20%) + S4(15%) + S5(15%)
-    let total_score = (s1_volume * 0.30)
-        + (s2_benchmark * 0.20)
-        + (s3_hygiene * 0.20)
-        + (s4_efficiency * 0.15)
-        + (s5_reputation * 0.15);
-
-    // Scale 0-10 -> 0-100 for display
-    let final_score = (total_score * 10.0).round();
-
-    let (label, color) = if final_score < 40.0 {
-        ("Low Risk".to_string(), "#28a745".to_string()) // Green
-    } else if final_score < 70.0 {
-        ("Moderate Risk".to_string(), "#ffc107".to_string()) // Yellow/Orange
-    } else {
-        ("Critical Risk".to_string(), "#dc3545".to_string()) // Red
-    };
+    #[test]
+    fn test_comparison_period_serde() {
+        let p: ComparisonPeriod = serde_json::from_str(r#"{"mode":"previous_month"}"#).unwrap();
+        assert_eq!(p, ComparisonPeriod::PreviousMonth);
+        let p: ComparisonPeriod =
+            serde_json::from_str(r#"{"mode":"custom","from":"2025-01-01","to":"2025-01-31"}"#)
+                .unwrap();
+        assert!(matches!(p, ComparisonPeriod::Custom { .. }));
+    }
+
+    #[test]
+    fn test_comparison_label_follows_report_language() {
+        let cmp = ComparisonData {
+            period_label: "vs. 2025-02-01 - 2025-02-28".into(),
+            period: Some(ComparisonPeriod::PreviousMonth),
+            prev_start_date: "2025-02-01".into(),
+            prev_end_date: "2025-02-28".into(),
+            ..Default::default()
+        };
+        let en = Translations::load("en").unwrap();
+        let es = Translations::load("es").unwrap();
+        assert_eq!(
+            cmp.label(&en),
+            "vs. previous month (2025-02-01 - 2025-02-28)"
+        );
+        assert_eq!(cmp.label(&es), "vs. mes anterior (2025-02-01 - 2025-02-28)");
+
+        // Snapshots saved before the period was stored keep their label
+        let old = ComparisonData {
+            period: None,
+            ..cmp
+        };
+        assert_eq!(old.label(&en), "vs. 2025-02-01 - 2025-02-28");
+    }
 
-    RiskScore {
-        current: final_score,
-        history: vec![],
-        label,
-        color,
+    #[test]
+    fn test_roi_hours_saved_delta() {
+        let mut data = PocReportData::demo();
+        data.comparison = None;
+        assert_eq!(RoiMetrics::compute(&data).hours_saved_delta_pct(), None);
+
+        data.comparison = Some(ComparisonData {
+            prev_tickets: data.total_tickets,
+            prev_takedowns: data.takedown_resolved,
+            prev_credentials: data.credentials_total,
+            ..Default::default()
+        });
+        let delta = RoiMetrics::compute(&data).hours_saved_delta_pct().unwrap();
+        assert!(delta.abs() < 1e-9);
     }
 }
@@ -0,0 +1,368 @@
+//! Risk Score V3 model
+//!
+//! The score (0-100) is a weighted sum of five factors, each scored 0-10:
+//! threat volume, market benchmark, credential hygiene, takedown efficiency
+//! and reputation. Weights, ticket type severities, normalisation caps and
+//! label bands live in `RiskModel` so they can be tuned per tenant or
+//! industry; the defaults reproduce the original fixed model.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::report::{RiskInputs, RiskPeriodInputs, RiskScore};
+
+/// Factor score used when an input is missing and the factor is neutral
+const NEUTRAL_SCORE: f64 = 5.0;
+
+/// Relative weight of each factor (normalised to sum 1 when scoring)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RiskWeights {
+    pub volume: f64,
+    pub benchmark: f64,
+    pub hygiene: f64,
+    pub efficiency: f64,
+    pub reputation: f64,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        Self {
+            volume: 0.30,
+            benchmark: 0.20,
+            hygiene: 0.20,
+            efficiency: 0.15,
+            reputation: 0.15,
+        }
+    }
+}
+
+/// Configurable Risk Score V3 model
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RiskModel {
+    pub weights: RiskWeights,
+    /// Severity multiplier per ticket type (e.g. "phishing": 3.0)
+    pub severity: HashMap<String, f64>,
+    /// Severity of ticket types not listed in `severity`
+    pub default_severity: f64,
+    /// Weighted incidents that max out the volume factor
+    pub volume_cap: f64,
+    /// Incidents / market median ratio that maxes out the benchmark factor
+    pub benchmark_ratio_cap: f64,
+    /// Critical credential leaks that max out the hygiene factor
+    pub critical_leaks_cap: f64,
+    /// Median takedown uptime (days) that maxes out its half of efficiency
+    pub uptime_days_cap: f64,
+    /// Web complaints that max out the reputation factor
+    pub complaints_cap: f64,
+    /// Scores from this value are "Moderate Risk"
+    pub moderate_from: f64,
+    /// Scores from this value are "Critical Risk"
+    pub critical_from: f64,
+}
+
+impl Default for RiskModel {
+    fn default() -> Self {
+        let severity = [
+            ("phishing", 3.0),
+            ("malware", 3.0),
+            ("ransomware-attack", 3.0),
+            ("fake-social-media-profile", 2.0),
+            ("fraudulent-brand-use", 2.0),
+            ("fake-mobile-app", 2.0),
+        ]
+        .into_iter()
+        .map(|(ticket_type, weight)| (ticket_type.to_string(), weight))
+        .collect();
+
+        Self {
+            weights: RiskWeights::default(),
+            severity,
+            default_severity: 1.0,
+            volume_cap: 100.0,
+            benchmark_ratio_cap: 2.0,
+            critical_leaks_cap: 50.0,
+            uptime_days_cap: 5.0,
+            complaints_cap: 20.0,
+            moderate_from: 40.0,
+            critical_from: 70.0,
+        }
+    }
+}
+
+/// Contribution of one factor to the score
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RiskFactor {
+    /// "volume", "benchmark", "hygiene", "efficiency" or "reputation"
+    pub key: String,
+    /// Factor score (0-10)
+    pub score: f64,
+    /// Normalised weight (0-1)
+    pub weight: f64,
+    /// Points added to the 0-100 score
+    pub points: f64,
+    /// Input values behind the score
+    pub detail: String,
+}
+
+/// 0-1 ratio of a value against its cap (a cap <= 0 means "any value is max")
+fn ratio(value: f64, cap: f64) -> f64 {
+    if cap > 0.0 {
+        (value / cap).clamp(0.0, 1.0)
+    } else if value > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+impl RiskModel {
+    /// Score the inputs of one period, with the per-factor breakdown
+    pub fn score(&self, data: &RiskInputs) -> RiskScore {
+        // === 1. Weighted Threat Volume ===
+        let weighted: f64 = data
+            .incidents_by_ticket_type
+            .iter()
+            .map(|t| {
+                let severity = self
+                    .severity
+                    .get(&t.name)
+                    .copied()
+                    .unwrap_or(self.default_severity);
+                t.value as f64 * severity
+            })
+            .sum();
+        let volume = ratio(weighted, self.volume_cap) * 10.0;
+        let volume_detail = format!(
+            "{:.0} weighted incidents (cap {:.0})",
+            weighted, self.volume_cap
+        );
+
+        // === 2. Market Benchmarking ===
+        // Raw incidents vs the market median; no benchmark data is neutral
+        let total: u64 = data.incidents_by_ticket_type.iter().map(|t| t.value).sum();
+        let (benchmark, benchmark_detail) = match data.market_median {
+            Some(median) if median > 0.0 => (
+                ratio(total as f64 / median, self.benchmark_ratio_cap) * 10.0,
+                format!("{} incidents vs market median {:.1}", total, median),
+            ),
+            Some(_) => (0.0, "no market activity".to_string()),
+            None => (NEUTRAL_SCORE, "no benchmark data".to_string()),
+        };
+
+        // === 3. Critical Hygiene ===
+        let (hygiene, hygiene_detail) = match data.critical_leaks {
+            Some(leaks) => (
+                ratio(leaks as f64, self.critical_leaks_cap) * 10.0,
+                format!(
+                    "{} critical credential leaks (cap {:.0})",
+                    leaks, self.critical_leaks_cap
+                ),
+            ),
+            None => (0.0, "no credential data".to_string()),
+        };
+
+        // === 4. Operational Efficiency ===
+        // Failed takedowns and long uptime both add risk
+        let (efficiency, efficiency_detail) =
+            match (data.takedown_success_rate, data.takedown_median_uptime_ms) {
+                (Some(success_rate), Some(uptime_ms)) => {
+                    let success_risk = ((100.0 - success_rate) / 10.0).clamp(0.0, 10.0);
+                    let uptime_days = uptime_ms / (1000.0 * 3600.0 * 24.0);
+                    let uptime_risk = ratio(uptime_days, self.uptime_days_cap) * 10.0;
+                    (
+                        (success_risk + uptime_risk) / 2.0,
+                        format!(
+                            "{:.1}% takedown success, {:.1} days median uptime",
+                            success_rate, uptime_days
+                        ),
+                    )
+                }
+                _ => (NEUTRAL_SCORE, "no takedown data".to_string()),
+            };
+
+        // === 5. Reputational Factor ===
+        let (reputation, reputation_detail) = match data.web_complaints {
+            Some(complaints) => (
+                ratio(complaints as f64, self.complaints_cap) * 10.0,
+                format!(
+                    "{} web complaints (cap {:.0})",
+                    complaints, self.complaints_cap
+                ),
+            ),
+            None => (0.0, "no complaint data".to_string()),
+        };
+
+        let w = &self.weights;
+        let weight_sum: f64 = [w.volume, w.benchmark, w.hygiene, w.efficiency, w.reputation]
+            .iter()
+            .map(|weight| weight.max(0.0))
+            .sum();
+        let factors: Vec<RiskFactor> = [
+            ("volume", volume, w.volume, volume_detail),
+            ("benchmark", benchmark, w.benchmark, benchmark_detail),
+            ("hygiene", hygiene, w.hygiene, hygiene_detail),
+            ("efficiency", efficiency, w.efficiency, efficiency_detail),
+            ("reputation", reputation, w.reputation, reputation_detail),
+        ]
+        .into_iter()
+        .map(|(key, score, weight, detail)| {
+            let weight = if weight_sum > 0.0 {
+                weight.max(0.0) / weight_sum
+            } else {
+                0.0
+            };
+            RiskFactor {
+                key: key.to_string(),
+                score,
+                weight,
+                points: score * weight * 10.0,
+                detail,
+            }
+        })
+        .collect();
+
+        let current = factors.iter().map(|f| f.points).sum::<f64>().round();
+        let (label, color) = self.band(current);
+
+        RiskScore {
+            current,
+            history: vec![],
+            history_labels: vec![],
+            label: label.to_string(),
+            color: color.to_string(),
+            factors,
+        }
+    }
+
+    /// Score the current period and attach the history of past periods
+    /// (oldest first, the current period last)
+    pub fn score_with_history(
+        &self,
+        current: &RiskInputs,
+        current_label: &str,
+        past: &[RiskPeriodInputs],
+    ) -> RiskScore {
+        let mut score = self.score(current);
+        if past.is_empty() {
+            return score;
+        }
+        for period in past {
+            score.history.push(self.score(&period.inputs).current);
+            score.history_labels.push(period.end_date.clone());
+        }
+        score.history.push(score.current);
+        score.history_labels.push(current_label.to_string());
+        score
+    }
+
+    /// Label and color of a 0-100 score
+    pub fn band(&self, score: f64) -> (&'static str, &'static str) {
+        if score < self.moderate_from {
+            ("Low Risk", "#28a745") // Green
+        } else if score < self.critical_from {
+            ("Moderate Risk", "#ffc107") // Yellow/Orange
+        } else {
+            ("Critical Risk", "#dc3545") // Red
+        }
+    }
+}
+
+/// Risk Score V3 (0-100) from its captured inputs, with the default model
+pub fn calculate_risk_score(data: &RiskInputs) -> RiskScore {
+    RiskModel::default().score(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::report::NameValuePair;
+
+    fn inputs(phishing: u64) -> RiskInputs {
+        RiskInputs {
+            incidents_by_ticket_type: vec![
+                NameValuePair {
+                    name: "phishing".to_string(),
+                    value: phishing,
+                },
+                NameValuePair {
+                    name: "paid-search".to_string(),
+                    value: 10,
+                },
+            ],
+            market_median: Some(40.0),
+            critical_leaks: Some(25),
+            takedown_success_rate: Some(90.0),
+            takedown_median_uptime_ms: Some(2.5 * 24.0 * 3600.0 * 1000.0),
+            web_complaints: None,
+        }
+    }
+
+    #[test]
+    fn test_default_model_breakdown() {
+        let score = calculate_risk_score(&inputs(20));
+
+        // volume 70/100 -> 7.0, benchmark 30/40 -> 3.75, hygiene 5.0,
+        // efficiency (1.0 + 5.0) / 2 = 3.0, reputation 0
+        let factor_scores: Vec<f64> = score.factors.iter().map(|f| f.score).collect();
+        assert_eq!(factor_scores, vec![7.0, 3.75, 5.0, 3.0, 0.0]);
+        assert_eq!(score.current, 43.0);
+        assert_eq!(score.label, "Moderate Risk");
+        let points: f64 = score.factors.iter().map(|f| f.points).sum();
+        assert!((points - 43.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_custom_model() {
+        let mut model = RiskModel {
+            weights: RiskWeights {
+                volume: 1.0,
+                benchmark: 0.0,
+                hygiene: 1.0,
+                efficiency: 0.0,
+                reputation: 0.0,
+            },
+            critical_from: 60.0,
+            ..Default::default()
+        };
+        model.severity.insert("paid-search".to_string(), 3.0);
+
+        let score = model.score(&inputs(20));
+        // volume (60 + 30) / 100 -> 9.0, hygiene 5.0, each weighted 0.5
+        assert_eq!(score.factors[0].score, 9.0);
+        assert_eq!(score.factors[1].weight, 0.0);
+        assert_eq!(score.current, 70.0);
+        assert_eq!(score.label, "Critical Risk");
+    }
+
+    #[test]
+    fn test_history_is_scored_with_the_same_model() {
+        let past = vec![
+            RiskPeriodInputs {
+                start_date: "2025-01-01".to_string(),
+                end_date: "2025-01-31".to_string(),
+                inputs: inputs(0),
+            },
+            RiskPeriodInputs {
+                start_date: "2025-02-01".to_string(),
+                end_date: "2025-02-28".to_string(),
+                inputs: inputs(10),
+            },
+        ];
+        let model = RiskModel::default();
+        let score = model.score_with_history(&inputs(20), "2025-03-31", &past);
+
+        assert_eq!(score.history.len(), 3);
+        assert_eq!(*score.history.last().unwrap(), score.current);
+        assert!(score.history[0] < score.history[1] && score.history[1] < score.current);
+        assert_eq!(
+            score.history_labels,
+            vec!["2025-01-31", "2025-02-28", "2025-03-31"]
+        );
+        assert!(model
+            .score_with_history(&inputs(20), "2025-03-31", &[])
+            .history
+            .is_empty());
+    }
+}
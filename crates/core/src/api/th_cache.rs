@@ -0,0 +1,188 @@
+//! Threat Hunting Result Cache
+//!
+//! Threat Hunting searches cost credits and take up to a minute to complete,
+//! so completed results are kept in memory for a while and reused by the
+//! next search with the same source, query, tenant, payer and date range: a
+//! regenerated report, or a full report after its preview (both run the
+//! `domain="..."` signal-lake searches of the tagged tickets). A cache hit
+//! starts no search, so it is not billed. Results are never shared across
+//! tenants, and searches paid with a user's own credits only with that user.
+//! [`ThCache::with_refresh`] skips the lookup and stores the fresh results.
+//!
+//! Environment overrides used by [`ThCache::from_env`]:
+//! - `AXUR_TH_CACHE_TTL_SECS` - how long results are reused (default one
+//!   hour, `0` disables the cache)
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default time-to-live of cached results
+pub const DEFAULT_TTL_SECS: u64 = 3600;
+
+/// What identifies a search's results
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThSearchKey {
+    pub source: String,
+    pub query: String,
+    /// Tenant the report or preview runs for
+    pub tenant: String,
+    /// User whose own credits pay for the search (None = billed to the tenant)
+    pub user: Option<String>,
+    /// Date range (from, to), for searches limited to one
+    pub window: Option<(String, String)>,
+}
+
+impl ThSearchKey {
+    pub fn new(
+        source: &str,
+        query: &str,
+        tenant: &str,
+        user: Option<&str>,
+        window: Option<(&str, &str)>,
+    ) -> Self {
+        Self {
+            source: source.to_string(),
+            query: query.to_string(),
+            tenant: tenant.to_string(),
+            user: user.map(str::to_string),
+            window: window.map(|(from, to)| (from.to_string(), to.to_string())),
+        }
+    }
+}
+
+struct CacheEntry {
+    value: serde_json::Value,
+    expires_at: Instant,
+}
+
+fn store() -> &'static Mutex<HashMap<ThSearchKey, CacheEntry>> {
+    static STORE: OnceLock<Mutex<HashMap<ThSearchKey, CacheEntry>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Cache settings of a report (the results themselves are process-wide)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThCache {
+    ttl: Duration,
+    refresh: bool,
+}
+
+impl Default for ThCache {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            refresh: false,
+        }
+    }
+}
+
+impl ThCache {
+    /// Cache with the TTL from `AXUR_TH_CACHE_TTL_SECS`
+    pub fn from_env() -> Self {
+        let ttl = std::env::var("AXUR_TH_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_TTL_SECS);
+        Self::with_ttl(Duration::from_secs(ttl))
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            refresh: false,
+        }
+    }
+
+    /// Ignore cached results (fresh results are still stored)
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Cached results of a search, unless expired or refreshing
+    pub fn get<T: DeserializeOwned>(&self, key: &ThSearchKey) -> Option<T> {
+        if self.refresh || self.ttl.is_zero() {
+            return None;
+        }
+        let store = store().lock().ok()?;
+        let entry = store.get(key).filter(|e| e.expires_at > Instant::now())?;
+        let value = serde_json::from_value(entry.value.clone()).ok()?;
+        tracing::info!(source = %key.source, query = %key.query, "Threat Hunting cache hit");
+        Some(value)
+    }
+
+    /// Store the results of a completed search
+    pub fn put<T: Serialize>(&self, key: &ThSearchKey, results: &T) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let Ok(value) = serde_json::to_value(results) else {
+            return;
+        };
+        if let Ok(mut store) = store().lock() {
+            let now = Instant::now();
+            store.retain(|_, e| e.expires_at > now);
+            store.insert(
+                key.clone(),
+                CacheEntry {
+                    value,
+                    expires_at: now + self.ttl,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_refresh_and_window() {
+        let cache = ThCache::default();
+        let key = ThSearchKey::new(
+            "signal-lake",
+            "domain=\"cache-test.example\"",
+            "acme",
+            None,
+            None,
+        );
+        assert!(cache.get::<Vec<u64>>(&key).is_none());
+        cache.put(&key, &[1u64, 2, 3]);
+        assert_eq!(cache.get::<Vec<u64>>(&key), Some(vec![1, 2, 3]));
+
+        // Refresh bypasses the lookup, a zero TTL disables the cache
+        assert!(cache.with_refresh(true).get::<Vec<u64>>(&key).is_none());
+        assert!(ThCache::with_ttl(Duration::ZERO)
+            .get::<Vec<u64>>(&key)
+            .is_none());
+
+        // Other date ranges, tenants and payers are different searches
+        let other = ThSearchKey::new(
+            "signal-lake",
+            "domain=\"cache-test.example\"",
+            "acme",
+            None,
+            Some(("2026-01-01", "2026-01-31")),
+        );
+        assert!(cache.get::<Vec<u64>>(&other).is_none());
+        let tenant = ThSearchKey {
+            tenant: "globex".into(),
+            ..key.clone()
+        };
+        assert!(cache.get::<Vec<u64>>(&tenant).is_none());
+        let own = ThSearchKey {
+            user: Some("alice".into()),
+            ..key.clone()
+        };
+        assert!(cache.get::<Vec<u64>>(&own).is_none());
+        cache.put(&own, &[4u64]);
+        let other_user = ThSearchKey {
+            user: Some("bob".into()),
+            ..key
+        };
+        assert!(cache.get::<Vec<u64>>(&other_user).is_none());
+    }
+}
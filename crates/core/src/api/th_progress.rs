@@ -0,0 +1,157 @@
+//! Threat Hunting Search Progress
+//!
+//! Threat Hunting searches are started, then polled until their results are
+//! ready, which can take a minute. A [`ThProgress`] callback travels in the
+//! `ReportContext` and is told about every search of a report or preview:
+//! when it starts (or is served from the cache), each poll, when its results
+//! were fetched and when it gave up. Streaming endpoints forward these events
+//! to the browser.
+//!
+//! A search that is still running after its last poll fails with
+//! [`SearchTimeout`] instead of returning no results, so callers can tell
+//! "nothing found" from "didn't finish".
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use super::report::ThreatHuntingSource;
+use crate::error_codes::ReportStep;
+
+/// What happened to a search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SearchStatus {
+    Started,
+    /// Results reused from the cache (no search started)
+    Cached,
+    /// Results not ready yet
+    Polling {
+        attempt: u32,
+        max_attempts: u32,
+    },
+    /// Results fetched
+    Completed {
+        results: u64,
+        pages: u64,
+    },
+    /// Still running after the last poll
+    TimedOut {
+        attempts: u32,
+    },
+    /// The search could not be started
+    Failed {
+        message: String,
+    },
+}
+
+/// Progress of one Threat Hunting search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchProgress {
+    /// Threat Hunting source ("signal-lake", "credential", ...)
+    pub source: String,
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_id: Option<String>,
+    /// Report step the source feeds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<ReportStep>,
+    #[serde(flatten)]
+    pub status: SearchStatus,
+}
+
+impl SearchProgress {
+    pub fn new(source: &str, query: &str, search_id: Option<&str>, status: SearchStatus) -> Self {
+        Self {
+            source: source.to_string(),
+            query: query.to_string(),
+            search_id: search_id.map(str::to_string),
+            step: ThreatHuntingSource::parse(source).map(|s| s.report_step()),
+            status,
+        }
+    }
+}
+
+/// Progress callback of a report (does nothing unless set).
+/// Clones share the same callback.
+#[derive(Clone, Default)]
+pub struct ThProgress {
+    callback: Option<Arc<dyn Fn(SearchProgress) + Send + Sync>>,
+}
+
+impl fmt::Debug for ThProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThProgress")
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl ThProgress {
+    /// Report progress to `callback` (called from the polling tasks, so it
+    /// should only hand the event over, e.g. to a channel)
+    pub fn new(callback: impl Fn(SearchProgress) + Send + Sync + 'static) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+        }
+    }
+
+    /// Report the progress of a search
+    pub fn emit(&self, source: &str, query: &str, search_id: Option<&str>, status: SearchStatus) {
+        if let Some(callback) = &self.callback {
+            callback(SearchProgress::new(source, query, search_id, status));
+        }
+    }
+}
+
+/// A search still running after its last poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTimeout {
+    pub source: String,
+    pub search_id: String,
+    pub attempts: u32,
+}
+
+impl fmt::Display for SearchTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Threat Hunting {} search {} timed out after {} attempts",
+            self.source, self.search_id, self.attempts
+        )
+    }
+}
+
+impl std::error::Error for SearchTimeout {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_events() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let progress = ThProgress::new(move |p| sink.lock().unwrap().push(p));
+
+        progress.emit("forum-message", "Acme", Some("s1"), SearchStatus::Started);
+        progress.clone().emit(
+            "signal-lake-ads",
+            "Acme",
+            Some("s2"),
+            SearchStatus::TimedOut { attempts: 10 },
+        );
+        ThProgress::default().emit("tokens", "Acme", None, SearchStatus::Cached);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].step, Some(ReportStep::SearchingDarkWeb));
+        assert_eq!(seen[1].step, Some(ReportStep::DetectingAds));
+
+        let json = serde_json::to_value(&seen[1]).unwrap();
+        assert_eq!(json["status"], "timed_out");
+        assert_eq!(json["attempts"], 10);
+        assert_eq!(json["step"], "detecting_ads");
+    }
+}
@@ -0,0 +1,212 @@
+//! Threat Hunting Query Builder
+//!
+//! Threat intelligence for a report runs one search per selected source and
+//! search term. Terms come from the tenant's monitored assets: the primary
+//! brand (or every brand), brand aliases given with the request, executive
+//! names, domains and card BINs. Each source searches the assets it can
+//! match: chatter, social and ads sources the brand terms, `signal-lake`
+//! the domains, `credit-card` the BINs and `tokens` domains and brands.
+//! [`ThSearchConfig::default`] searches the primary brand on the forum,
+//! chat, social post, credential and ads sources.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::report::ThreatHuntingSource;
+
+/// Sources searched when a report doesn't select any
+pub const DEFAULT_SOURCES: [ThreatHuntingSource; 5] = [
+    ThreatHuntingSource::ForumMessage,
+    ThreatHuntingSource::ChatMessage,
+    ThreatHuntingSource::SocialMediaPosts,
+    ThreatHuntingSource::Credential,
+    ThreatHuntingSource::SignalLakeAds,
+];
+
+/// Default limit of terms searched per source
+pub const DEFAULT_MAX_TERMS: usize = 5;
+
+/// Monitored assets of a tenant that search terms are built from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonitoredAssets {
+    /// Fallback term when the tenant has no brand
+    pub company_name: String,
+    pub brands: Vec<String>,
+    pub domains: Vec<String>,
+    pub executives: Vec<String>,
+    pub bins: Vec<String>,
+}
+
+/// Per-report selection of Threat Hunting sources and terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThSearchConfig {
+    pub sources: Vec<ThreatHuntingSource>,
+    /// Extra brand terms (aliases, product names)
+    pub aliases: Vec<String>,
+    /// Search every brand instead of the primary one
+    pub all_brands: bool,
+    /// Search executive names on the chatter and social sources
+    pub include_executives: bool,
+    /// Terms searched per source at most (each search costs credits)
+    pub max_terms_per_source: usize,
+}
+
+impl Default for ThSearchConfig {
+    fn default() -> Self {
+        Self {
+            sources: DEFAULT_SOURCES.to_vec(),
+            aliases: Vec::new(),
+            all_brands: false,
+            include_executives: false,
+            max_terms_per_source: DEFAULT_MAX_TERMS,
+        }
+    }
+}
+
+/// One search to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThQuery {
+    pub source: ThreatHuntingSource,
+    /// Asset the search is about
+    pub term: String,
+    /// Query sent to Threat Hunting
+    pub query: String,
+}
+
+/// Parse a comma-separated list of source names ("credit-card,tokens")
+pub fn parse_sources(list: &str) -> Result<Vec<ThreatHuntingSource>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            ThreatHuntingSource::parse(name)
+                .ok_or_else(|| anyhow!("Unknown Threat Hunting source '{}'", name))
+        })
+        .collect()
+}
+
+impl ThSearchConfig {
+    /// Searches to run for a tenant's assets, in source order
+    pub fn queries(&self, assets: &MonitoredAssets) -> Vec<ThQuery> {
+        let mut queries = Vec::new();
+        for &source in &self.sources {
+            for term in self.terms_for(source, assets) {
+                let query = match source {
+                    ThreatHuntingSource::SignalLake => format!("domain=\"{}\"", term),
+                    _ => term.clone(),
+                };
+                queries.push(ThQuery {
+                    source,
+                    term,
+                    query,
+                });
+            }
+        }
+        queries
+    }
+
+    /// Terms a source can match, capped at `max_terms_per_source`
+    fn terms_for(&self, source: ThreatHuntingSource, assets: &MonitoredAssets) -> Vec<String> {
+        let mut brands: Vec<&String> = if self.all_brands {
+            assets.brands.iter().collect()
+        } else {
+            assets.brands.iter().take(1).collect()
+        };
+        if brands.is_empty() {
+            brands.push(&assets.company_name);
+        }
+        brands.extend(&self.aliases);
+
+        let candidates: Vec<&String> = match source {
+            ThreatHuntingSource::SignalLake => assets.domains.iter().collect(),
+            ThreatHuntingSource::CreditCard => assets.bins.iter().collect(),
+            ThreatHuntingSource::Tokens => assets.domains.iter().chain(brands).collect(),
+            ThreatHuntingSource::Credential => brands,
+            ThreatHuntingSource::ChatMessage
+            | ThreatHuntingSource::ForumMessage
+            | ThreatHuntingSource::SocialMediaPosts
+            | ThreatHuntingSource::SignalLakeSocialMedia
+            | ThreatHuntingSource::SignalLakeAds => {
+                if self.include_executives {
+                    brands.into_iter().chain(&assets.executives).collect()
+                } else {
+                    brands
+                }
+            }
+        };
+
+        let mut terms: Vec<String> = Vec::new();
+        for term in candidates.into_iter().map(|t| t.trim()) {
+            if !term.is_empty() && !terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+                terms.push(term.to_string());
+            }
+        }
+        terms.truncate(self.max_terms_per_source.max(1));
+        terms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assets() -> MonitoredAssets {
+        MonitoredAssets {
+            company_name: "Banco Acme S.A.".into(),
+            brands: vec!["Acme".into(), "Acme Pay".into()],
+            domains: vec!["acme.com".into(), "acmepay.com".into()],
+            executives: vec!["Maria Silva".into()],
+            bins: vec!["451234".into()],
+        }
+    }
+
+    #[test]
+    fn test_default_searches_primary_brand() {
+        let queries = ThSearchConfig::default().queries(&assets());
+        assert_eq!(queries.len(), DEFAULT_SOURCES.len());
+        assert!(queries.iter().all(|q| q.query == "Acme"));
+
+        let no_brand = MonitoredAssets {
+            brands: vec![],
+            ..assets()
+        };
+        let queries = ThSearchConfig::default().queries(&no_brand);
+        assert_eq!(queries[0].query, "Banco Acme S.A.");
+    }
+
+    #[test]
+    fn test_fintech_selection() {
+        let config = ThSearchConfig {
+            sources: parse_sources("credit-card, tokens, signal-lake, chat-message").unwrap(),
+            aliases: vec!["acme".into(), "AcmeBank".into()],
+            include_executives: true,
+            max_terms_per_source: 3,
+            ..Default::default()
+        };
+        let queries = config.queries(&assets());
+        let of = |source: ThreatHuntingSource| -> Vec<&str> {
+            queries
+                .iter()
+                .filter(|q| q.source == source)
+                .map(|q| q.query.as_str())
+                .collect()
+        };
+        assert_eq!(of(ThreatHuntingSource::CreditCard), vec!["451234"]);
+        assert_eq!(
+            of(ThreatHuntingSource::Tokens),
+            vec!["acme.com", "acmepay.com", "Acme"]
+        );
+        assert_eq!(
+            of(ThreatHuntingSource::SignalLake),
+            vec!["domain=\"acme.com\"", "domain=\"acmepay.com\""]
+        );
+        // "acme" duplicates the brand
+        assert_eq!(
+            of(ThreatHuntingSource::ChatMessage),
+            vec!["Acme", "AcmeBank", "Maria Silva"]
+        );
+
+        assert!(parse_sources("forum-message,darknet").is_err());
+    }
+}
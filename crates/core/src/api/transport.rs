@@ -0,0 +1,417 @@
+//! Pluggable HTTP transport for Axur API fetchers
+//!
+//! All fetchers in `report.rs` go through [`ApiTransport`] instead of a raw
+//! `reqwest::Client`, which makes the base URL injectable and adds two
+//! offline modes:
+//! - **Record**: performs live calls and saves every response as a fixture
+//! - **Replay**: serves responses from previously recorded fixtures, no network
+//!
+//! Fixtures are plain JSON files (`<endpoint>_<hash>.json`) keyed by method,
+//! URL and request body. The `Authorization` header is never part of the key
+//! nor stored, and credential users and passwords in recorded bodies are
+//! masked with the redaction policy, so a captured customer session can be
+//! shared and replayed.
+//!
+//! Environment overrides used by [`ApiTransport::from_env`]:
+//! - `AXUR_API_URL` - base URL (defaults to [`API_URL`])
+//! - `AXUR_RECORD_DIR` - record fixtures into this directory
+//! - `AXUR_REPLAY_DIR` - replay fixtures from this directory
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{create_client, API_URL};
+
+/// How requests are served
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TransportMode {
+    /// Plain network calls
+    #[default]
+    Live,
+    /// Network calls, responses saved as fixtures in the given directory
+    Record(PathBuf),
+    /// Responses served from fixtures in the given directory (no network)
+    Replay(PathBuf),
+}
+
+/// HTTP transport shared by all Axur API fetchers
+#[derive(Debug, Clone)]
+pub struct ApiTransport {
+    client: reqwest::Client,
+    base_url: String,
+    mode: TransportMode,
+}
+
+impl ApiTransport {
+    /// Live transport against the production API
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_client(create_client()?))
+    }
+
+    /// Live transport using an existing client
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            base_url: API_URL.to_string(),
+            mode: TransportMode::Live,
+        }
+    }
+
+    /// Transport configured from `AXUR_API_URL`, `AXUR_RECORD_DIR` and `AXUR_REPLAY_DIR`
+    pub fn from_env() -> Result<Self> {
+        let mut transport = Self::new()?;
+        if let Ok(url) = std::env::var("AXUR_API_URL") {
+            if !url.is_empty() {
+                transport = transport.with_base_url(url);
+            }
+        }
+        if let Ok(dir) = std::env::var("AXUR_REPLAY_DIR") {
+            if !dir.is_empty() {
+                return Ok(transport.with_mode(TransportMode::Replay(dir.into())));
+            }
+        }
+        if let Ok(dir) = std::env::var("AXUR_RECORD_DIR") {
+            if !dir.is_empty() {
+                return Ok(transport.with_mode(TransportMode::Record(dir.into())));
+            }
+        }
+        Ok(transport)
+    }
+
+    /// Override the API base URL (trailing slashes are trimmed)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set record/replay mode
+    pub fn with_mode(mut self, mode: TransportMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Base URL used to build endpoint URLs
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn mode(&self) -> &TransportMode {
+        &self.mode
+    }
+
+    pub fn get(&self, url: impl Into<String>) -> ApiRequest<'_> {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl Into<String>) -> ApiRequest<'_> {
+        self.request(Method::POST, url)
+    }
+
+    fn request(&self, method: Method, url: impl Into<String>) -> ApiRequest<'_> {
+        ApiRequest {
+            transport: self,
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    async fn execute(&self, request: ApiRequest<'_>) -> Result<ApiResponse> {
+        let key = fixture_key(&request.method, &request.url, request.body.as_ref());
+
+        if let TransportMode::Replay(dir) = &self.mode {
+            return Fixture::load(dir, &request.url, &key)?.into_response();
+        }
+
+        let mut builder = self.client.request(request.method.clone(), &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        let resp = builder.send().await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?.to_vec();
+
+        if let TransportMode::Record(dir) = &self.mode {
+            let fixture = Fixture::new(&request, status, &headers, &body);
+            if let Err(e) = fixture.save(dir, &key) {
+                tracing::warn!("Failed to record fixture for {}: {}", request.url, e);
+            }
+        }
+
+        Ok(ApiResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Request builder mirroring the subset of `reqwest::RequestBuilder` used by fetchers
+pub struct ApiRequest<'a> {
+    transport: &'a ApiTransport,
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<serde_json::Value>,
+}
+
+impl ApiRequest<'_> {
+    pub fn header(mut self, name: impl Into<String>, value: impl AsRef<str>) -> Self {
+        self.headers.push((name.into(), value.as_ref().to_string()));
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.body = serde_json::to_value(body).ok();
+        self
+    }
+
+    pub async fn send(self) -> Result<ApiResponse> {
+        let transport = self.transport;
+        transport.execute(self).await
+    }
+}
+
+/// Fully buffered response (so it can be recorded and replayed)
+#[derive(Debug, Clone)]
+pub struct ApiResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl ApiResponse {
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub async fn text(self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.body).into_owned())
+    }
+
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    pub async fn bytes(self) -> Result<Vec<u8>> {
+        Ok(self.body)
+    }
+}
+
+// ========================
+// FIXTURES
+// ========================
+
+/// Recorded request/response pair as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_body: Option<serde_json::Value>,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    /// UTF-8 body (JSON responses)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    /// Base64 body (binary responses such as screenshots)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+impl Fixture {
+    fn new(request: &ApiRequest<'_>, status: StatusCode, headers: &HeaderMap, body: &[u8]) -> Self {
+        let (text, binary) = match std::str::from_utf8(body) {
+            Ok(s) => (Some(super::redaction::redact_log_text(s)), None),
+            Err(_) => (None, Some(general_purpose::STANDARD.encode(body))),
+        };
+        Self {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            request_body: request.body.clone(),
+            status: status.as_u16(),
+            content_type: headers
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from),
+            body: text,
+            body_base64: binary,
+        }
+    }
+
+    fn path(dir: &Path, url: &str, key: &str) -> PathBuf {
+        dir.join(format!("{}_{}.json", endpoint_name(url), key))
+    }
+
+    fn save(&self, dir: &Path, key: &str) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::path(dir, &self.url, key), json)?;
+        Ok(())
+    }
+
+    fn load(dir: &Path, url: &str, key: &str) -> Result<Self> {
+        let path = Self::path(dir, url, key);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow!(
+                "No recorded fixture for {} ({}): {}",
+                url,
+                path.display(),
+                e
+            )
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn into_response(self) -> Result<ApiResponse> {
+        let mut headers = HeaderMap::new();
+        if let Some(ct) = self.content_type.as_deref() {
+            if let Ok(value) = HeaderValue::from_str(ct) {
+                headers.insert(CONTENT_TYPE, value);
+            }
+        }
+        let body = match (self.body, self.body_base64) {
+            (_, Some(b64)) => general_purpose::STANDARD.decode(b64)?,
+            (Some(text), None) => text.into_bytes(),
+            (None, None) => Vec::new(),
+        };
+        Ok(ApiResponse {
+            status: StatusCode::from_u16(self.status)?,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Stable fixture key (FNV-1a over method, URL and body)
+fn fixture_key(method: &Method, url: &str, body: Option<&serde_json::Value>) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let body = body.map(|b| b.to_string()).unwrap_or_default();
+    for part in [method.as_str(), url, body.as_str()] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Human-readable file prefix from the URL path (e.g. `tickets-api_stats`)
+fn endpoint_name(url: &str) -> String {
+    let path = url
+        .split("://")
+        .nth(1)
+        .unwrap_or(url)
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    let path = path.split_once('/').map(|(_, p)| p).unwrap_or_default();
+    let path = path.strip_prefix("gateway/1.0/api/").unwrap_or(path);
+
+    let name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(60)
+        .collect();
+    if name.is_empty() {
+        "root".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_key_is_stable() {
+        let a = fixture_key(&Method::GET, "https://x/api?a=1", None);
+        let b = fixture_key(&Method::GET, "https://x/api?a=1", None);
+        let c = fixture_key(&Method::GET, "https://x/api?a=2", None);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.len(), 16);
+    }
+
+    #[test]
+    fn test_endpoint_name() {
+        assert_eq!(
+            endpoint_name("https://api.axur.com/gateway/1.0/api/tickets-api/stats/x?from=1"),
+            "tickets-api_stats_x"
+        );
+        assert_eq!(endpoint_name("http://127.0.0.1:8080"), "root");
+    }
+
+    #[test]
+    fn test_recorded_credentials_are_masked() {
+        let transport = ApiTransport::new().unwrap();
+        let request =
+            transport.get("https://api.axur.com/gateway/1.0/api/exposure-api/credentials");
+        let body = r#"{"detections":[{"user":"jdoe@acme.com","password":"acme2024"}]}"#;
+        let fixture = Fixture::new(&request, StatusCode::OK, &HeaderMap::new(), body.as_bytes());
+        let recorded = fixture.body.unwrap();
+        assert!(!recorded.contains("acme2024"));
+        assert!(!recorded.contains("jdoe@acme.com"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_serves_recorded_fixture() {
+        let dir = std::env::temp_dir().join(format!("axur_fixtures_{}", uuid::Uuid::new_v4()));
+        let url = "https://api.axur.com/gateway/1.0/api/customers/customers";
+        let key = fixture_key(&Method::GET, url, None);
+        let fixture = Fixture {
+            method: "GET".into(),
+            url: url.into(),
+            request_body: None,
+            status: 200,
+            content_type: Some("application/json".into()),
+            body: Some(r#"[{"key":"acme"}]"#.into()),
+            body_base64: None,
+        };
+        fixture.save(&dir, &key).unwrap();
+
+        let transport = ApiTransport::new()
+            .unwrap()
+            .with_mode(TransportMode::Replay(dir.clone()));
+        let resp = transport
+            .get(url)
+            .header("Authorization", "Bearer secret")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+        let value: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(value[0]["key"], "acme");
+
+        // Unrecorded endpoints fail instead of hitting the network
+        assert!(transport
+            .get(format!("{}?page=2", url))
+            .send()
+            .await
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,311 @@
+//! Axur CLI - generate reports from the command line
+//!
+//! Runs the same login → 2FA → finalize flow as the web app, lists the
+//! tenants available to the account and renders the plugin-based HTML
+//! report to a file. The master token is cached in a session file so
+//! `report` can be scheduled from cron without prompting.
+//!
+//! Token lookup order: `--token`, `AXUR_TOKEN`, session file.
+
+use clap::{Parser, Subcommand};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+use axur_core::api::auth;
+use axur_core::api::report::{fetch_available_tenants, fetch_full_report};
+use axur_core::errors::CliError;
+use axur_core::i18n::Translations;
+use axur_core::plugins::{PluginConfig, ThemeMode};
+use axur_core::report::html::generate_report_with_plugins;
+use axur_core::report::OfflineAssets;
+
+#[derive(Parser, Debug)]
+#[command(name = "axur", author, version, about, long_about = None)]
+struct Cli {
+    /// Master token (overrides AXUR_TOKEN and the saved session)
+    #[arg(long, global = true)]
+    token: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Log in (email, password, 2FA) and save the session token
+    Login {
+        /// Account email (prompted if omitted)
+        #[arg(short, long)]
+        email: Option<String>,
+    },
+    /// Remove the saved session token
+    Logout,
+    /// List tenants available to the current session
+    #[command(alias = "tenants")]
+    Quick,
+    /// Fetch data for a tenant and write the HTML report
+    Report {
+        /// Tenant key (see 'quick')
+        #[arg(short, long)]
+        tenant: String,
+
+        /// Start date (YYYY-MM-DD)
+        #[arg(long)]
+        from: String,
+
+        /// End date (YYYY-MM-DD)
+        #[arg(long)]
+        to: String,
+
+        /// Report language (en, es, pt-br)
+        #[arg(short, long, default_value = "es")]
+        lang: String,
+
+        /// Theme (dark, light, auto)
+        #[arg(long, default_value = "dark")]
+        theme: String,
+
+        /// Plugin ids to disable (comma separated)
+        #[arg(long, value_delimiter = ',')]
+        disable: Vec<String>,
+
+        /// Only include tickets with this story tag
+        #[arg(long)]
+        story_tag: Option<String>,
+
+        /// Include Threat Hunting data (consumes credits)
+        #[arg(long)]
+        threat_intel: bool,
+
+        /// Output file (defaults to report_<tenant>_<from>_<to>.html)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(cli).await {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
+    match cli.command {
+        Command::Login { email } => login(email).await,
+        Command::Logout => logout(),
+        Command::Quick => {
+            let token = resolve_token(cli.token)?;
+            list_tenants(&token).await
+        }
+        Command::Report {
+            tenant,
+            from,
+            to,
+            lang,
+            theme,
+            disable,
+            story_tag,
+            threat_intel,
+            output,
+        } => {
+            let token = resolve_token(cli.token)?;
+            validate_date(&from)?;
+            validate_date(&to)?;
+
+            let tenants = fetch_available_tenants(&token)
+                .await
+                .map_err(map_api_error)?;
+            if !tenants.iter().any(|t| t.key == tenant) {
+                return Err(CliError::InvalidTenant(tenant));
+            }
+
+            eprintln!("Fetching report data for {} ({} → {})...", tenant, from, to);
+            let data = fetch_full_report(&token, &tenant, &from, &to, story_tag, threat_intel)
+                .await
+                .map_err(map_api_error)?;
+
+            let translations = Translations::load(&lang.to_lowercase()).map_err(|e| {
+                CliError::InputError(format!("Unsupported language '{}': {}", lang, e))
+            })?;
+
+            let theme_mode = match theme.to_lowercase().as_str() {
+                "light" => ThemeMode::Light,
+                "auto" => ThemeMode::Auto,
+                _ => ThemeMode::Dark,
+            };
+            let config = PluginConfig::default()
+                .with_theme(theme_mode)
+                .disable_plugins(disable);
+
+            let offline_assets = OfflineAssets::load_embedded();
+            let html = generate_report_with_plugins(
+                &data,
+                &translations,
+                Some(&offline_assets),
+                Some(config),
+            );
+
+            let path = output.unwrap_or_else(|| {
+                PathBuf::from(format!("report_{}_{}_{}.html", tenant, from, to))
+            });
+            std::fs::write(&path, html)?;
+            println!(
+                "✅ Report for {} written to {}",
+                data.company_name,
+                path.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+// ========================
+// COMMANDS
+// ========================
+
+async fn login(email: Option<String>) -> Result<(), CliError> {
+    let translations = Translations::load("en").ok();
+    if let Some(t) = &translations {
+        println!("{}", t.get("welcome_message"));
+    }
+
+    let email = match email {
+        Some(e) => e,
+        None => prompt(
+            &translations
+                .as_ref()
+                .map(|t| t.get("login_prompt_email"))
+                .unwrap_or_else(|| "Email".into()),
+        )?,
+    };
+    let password = match std::env::var("AXUR_PASSWORD") {
+        Ok(p) if !p.is_empty() => p,
+        _ => rpassword::prompt_password("Password: ")?,
+    };
+
+    let session = auth::login(&email, &password).await?;
+    let code = prompt("2FA code")?;
+    let session = auth::verify_2fa(&session, &code).await?;
+    let master_token = auth::finalize(&email, &password, &session).await?;
+
+    let path = session_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_private(&path, &master_token)?;
+
+    println!("✅ Logged in. Session saved to {}", path.display());
+    Ok(())
+}
+
+fn logout() -> Result<(), CliError> {
+    let path = session_path()?;
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    println!("👋 Logged out");
+    Ok(())
+}
+
+async fn list_tenants(token: &str) -> Result<(), CliError> {
+    let tenants = fetch_available_tenants(token)
+        .await
+        .map_err(map_api_error)?;
+    if tenants.is_empty() {
+        println!("No tenants available for this account");
+        return Ok(());
+    }
+    for tenant in tenants {
+        println!("{:<24} {}", tenant.key, tenant.name);
+    }
+    Ok(())
+}
+
+// ========================
+// HELPERS
+// ========================
+
+fn prompt(label: &str) -> Result<String, CliError> {
+    print!("{}: ", label);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+/// Session file location (`AXUR_SESSION_FILE` or `~/.axur/session`)
+fn session_path() -> Result<PathBuf, CliError> {
+    if let Ok(path) = std::env::var("AXUR_SESSION_FILE") {
+        return Ok(PathBuf::from(path));
+    }
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| CliError::InputError("Cannot determine home directory".into()))?;
+    Ok(PathBuf::from(home).join(".axur").join("session"))
+}
+
+fn resolve_token(flag: Option<String>) -> Result<String, CliError> {
+    if let Some(token) = flag.filter(|t| !t.is_empty()) {
+        return Ok(token);
+    }
+    if let Ok(token) = std::env::var("AXUR_TOKEN") {
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+    match std::fs::read_to_string(session_path()?) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(CliError::SessionExpired),
+    }
+}
+
+/// Write a file only the current user can read (created with mode 0600,
+/// so the token is never readable by others, even briefly)
+#[cfg(unix)]
+fn write_private(path: &std::path::Path, contents: &str) -> Result<(), CliError> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files: tighten an existing session first
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &std::path::Path, contents: &str) -> Result<(), CliError> {
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+fn validate_date(date: &str) -> Result<(), CliError> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|_| ())
+        .map_err(|_| CliError::InvalidDate(date.to_string()))
+}
+
+/// Convert fetcher errors (anyhow) into user-facing CLI errors
+fn map_api_error(err: anyhow::Error) -> CliError {
+    match err.downcast::<reqwest::Error>() {
+        Ok(e) => CliError::from(e),
+        Err(e) => {
+            let msg = e.to_string();
+            if msg.contains("401") {
+                CliError::SessionExpired
+            } else if msg.contains("429") {
+                CliError::RateLimited
+            } else {
+                CliError::ApiError(msg)
+            }
+        }
+    }
+}
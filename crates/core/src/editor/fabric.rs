@@ -0,0 +1,323 @@
+//! Fabric.js canvas conversion
+//!
+//! The editor saves slides as raw Fabric.js JSON (`canvas_json`). This turns
+//! that JSON into typed `Element`s so the server can render the slide
+//! without a browser. Coordinates are converted from the 1280x720 canvas to
+//! the percentage-based `Position`/`Size`.
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::types::*;
+
+/// Editor canvas width in pixels
+pub const CANVAS_WIDTH: f64 = 1280.0;
+/// Editor canvas height in pixels
+pub const CANVAS_HEIGHT: f64 = 720.0;
+
+/// Resolve a slide to typed elements: `canvas_json`, when present, overrides
+/// `elements` and provides the background.
+pub fn resolve_canvas_slide(slide: &SlideDefinition) -> SlideDefinition {
+    let mut resolved = slide.clone();
+    let Some(canvas) = slide
+        .canvas_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<Value>(json).ok())
+    else {
+        return resolved;
+    };
+
+    resolved.elements = elements_from_canvas(&canvas);
+    if let Some(background) = canvas_background(&canvas) {
+        resolved.background = background;
+    }
+    resolved.canvas_json = None;
+    resolved
+}
+
+/// Convert the objects of a Fabric.js canvas into elements (z-order kept)
+pub fn elements_from_canvas(canvas: &Value) -> Vec<Element> {
+    let mut elements = Vec::new();
+    if let Some(objects) = canvas.get("objects").and_then(Value::as_array) {
+        for object in objects {
+            push_object(object, (0.0, 0.0), (1.0, 1.0), &mut elements);
+        }
+    }
+    elements
+}
+
+fn canvas_background(canvas: &Value) -> Option<Background> {
+    if let Some(src) = canvas
+        .get("backgroundImage")
+        .and_then(|image| image.get("src"))
+        .and_then(Value::as_str)
+    {
+        return Some(Background::Image {
+            url: src.to_string(),
+            opacity: 1.0,
+        });
+    }
+    canvas
+        .get("background")
+        .and_then(Value::as_str)
+        .filter(|color| !color.is_empty())
+        .map(|color| Background::Solid {
+            color: color.to_string(),
+        })
+}
+
+fn num(object: &Value, key: &str) -> Option<f64> {
+    object.get(key).and_then(Value::as_f64)
+}
+
+fn text(object: &Value, key: &str) -> Option<String> {
+    object.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Paint value as a CSS color (gradients and patterns fall back to None)
+fn paint(object: &Value, key: &str) -> Option<String> {
+    text(object, key).filter(|c| !c.is_empty() && c != "transparent")
+}
+
+/// Absolute pixel box of an object: (left, top, width, height)
+fn pixel_box(object: &Value, offset: (f64, f64), scale: (f64, f64)) -> (f64, f64, f64, f64) {
+    let scale_x = num(object, "scaleX").unwrap_or(1.0) * scale.0;
+    let scale_y = num(object, "scaleY").unwrap_or(1.0) * scale.1;
+    let width = num(object, "width").unwrap_or(0.0) * scale_x;
+    let height = num(object, "height").unwrap_or(0.0) * scale_y;
+
+    let mut left = offset.0 + num(object, "left").unwrap_or(0.0) * scale.0;
+    let mut top = offset.1 + num(object, "top").unwrap_or(0.0) * scale.1;
+    match object.get("originX").and_then(Value::as_str) {
+        Some("center") => left -= width / 2.0,
+        Some("right") => left -= width,
+        _ => {}
+    }
+    match object.get("originY").and_then(Value::as_str) {
+        Some("center") => top -= height / 2.0,
+        Some("bottom") => top -= height,
+        _ => {}
+    }
+    (left, top, width, height)
+}
+
+fn frame(bbox: (f64, f64, f64, f64)) -> (Position, Size) {
+    (
+        Position {
+            x: bbox.0 / CANVAS_WIDTH * 100.0,
+            y: bbox.1 / CANVAS_HEIGHT * 100.0,
+        },
+        Size {
+            width: bbox.2 / CANVAS_WIDTH * 100.0,
+            height: bbox.3 / CANVAS_HEIGHT * 100.0,
+        },
+    )
+}
+
+fn push_object(object: &Value, offset: (f64, f64), scale: (f64, f64), out: &mut Vec<Element>) {
+    if object.get("visible").and_then(Value::as_bool) == Some(false) {
+        return;
+    }
+
+    let bbox = pixel_box(object, offset, scale);
+    let (position, size) = frame(bbox);
+    let id = Uuid::new_v4();
+    let kind = object.get("type").and_then(Value::as_str).unwrap_or("");
+
+    match kind {
+        "group" => {
+            // Editor placeholders are groups (dashed rect + label) tagged with their key
+            if let Some(key) = text(object, "placeholderKey") {
+                out.push(Element::Placeholder {
+                    id,
+                    key,
+                    position,
+                    size,
+                    locked: true,
+                });
+                return;
+            }
+
+            // Children are positioned relative to the group center
+            let child_scale = (
+                num(object, "scaleX").unwrap_or(1.0) * scale.0,
+                num(object, "scaleY").unwrap_or(1.0) * scale.1,
+            );
+            let center = (bbox.0 + bbox.2 / 2.0, bbox.1 + bbox.3 / 2.0);
+            if let Some(children) = object.get("objects").and_then(Value::as_array) {
+                for child in children {
+                    push_object(child, center, child_scale, out);
+                }
+            }
+        }
+        "text" | "i-text" | "textbox" => {
+            let font_scale = num(object, "scaleY").unwrap_or(1.0) * scale.1;
+            let font_weight = match object.get("fontWeight") {
+                Some(Value::Number(n)) => n.to_string(),
+                Some(Value::String(s)) => s.clone(),
+                _ => "normal".to_string(),
+            };
+            let text_align = match object.get("textAlign").and_then(Value::as_str) {
+                Some("center") => TextAlign::Center,
+                Some("right") => TextAlign::Right,
+                Some("justify") => TextAlign::Justify,
+                _ => TextAlign::Left,
+            };
+            out.push(Element::Text {
+                id,
+                content: text(object, "text").unwrap_or_default(),
+                style: TextStyle {
+                    font_family: text(object, "fontFamily").unwrap_or_else(|| "Inter".into()),
+                    font_size: (num(object, "fontSize").unwrap_or(16.0) * font_scale).round()
+                        as u32,
+                    font_weight,
+                    color: paint(object, "fill").unwrap_or_else(|| "#FFFFFF".into()),
+                    text_align,
+                    line_height: num(object, "lineHeight").unwrap_or(1.16),
+                },
+                position,
+                size,
+                locked: true,
+            });
+        }
+        "image" => {
+            let Some(src) = text(object, "src") else {
+                return;
+            };
+            let src = match src
+                .strip_prefix("data:")
+                .and_then(|rest| rest.split_once(";base64,"))
+            {
+                Some((mime_type, data)) => ImageSource::Base64 {
+                    data: data.to_string(),
+                    mime_type: mime_type.to_string(),
+                },
+                None => ImageSource::Url { url: src },
+            };
+            out.push(Element::Image {
+                id,
+                src,
+                position,
+                size,
+                locked: true,
+            });
+        }
+        "rect" | "circle" | "ellipse" | "triangle" | "line" => {
+            let radius = num(object, "rx").unwrap_or(0.0);
+            let shape_type = match kind {
+                "rect" if radius > 0.0 => ShapeType::RoundedRect,
+                "rect" => ShapeType::Rectangle,
+                "circle" => ShapeType::Circle,
+                "ellipse" => ShapeType::Ellipse,
+                "triangle" => ShapeType::Triangle,
+                _ => ShapeType::Line,
+            };
+            out.push(Element::Shape {
+                id,
+                shape_type,
+                style: ShapeStyle {
+                    fill_color: paint(object, "fill").unwrap_or_else(|| "transparent".into()),
+                    stroke_color: paint(object, "stroke").unwrap_or_else(|| "transparent".into()),
+                    stroke_width: num(object, "strokeWidth").unwrap_or(0.0),
+                    border_radius: radius,
+                    opacity: num(object, "opacity").unwrap_or(1.0),
+                },
+                position,
+                size,
+                locked: true,
+            });
+        }
+        // Paths, polygons and other free-form objects have no element equivalent
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canvas_to_elements() {
+        let canvas = serde_json::json!({
+            "background": "#0F172A",
+            "objects": [
+                { "type": "textbox", "left": 128, "top": 72, "width": 640, "height": 50,
+                  "text": "Report for {{company_name}}", "fontSize": 32, "fontWeight": "bold",
+                  "fill": "#FF671F", "textAlign": "center" },
+                { "type": "rect", "left": 0, "top": 648, "width": 640, "height": 36,
+                  "scaleX": 2, "scaleY": 2, "fill": "#18181B", "rx": 8 },
+                { "type": "group", "left": 640, "top": 360, "width": 200, "height": 80,
+                  "placeholderKey": "risk_score_value",
+                  "objects": [{ "type": "rect", "left": -100, "top": -40, "width": 200, "height": 80 }] }
+            ]
+        });
+
+        let slide = SlideDefinition {
+            canvas_json: Some(canvas.to_string()),
+            ..Default::default()
+        };
+        let resolved = resolve_canvas_slide(&slide);
+
+        assert!(resolved.canvas_json.is_none());
+        assert!(
+            matches!(resolved.background, Background::Solid { ref color } if color == "#0F172A")
+        );
+        assert_eq!(resolved.elements.len(), 3);
+
+        match &resolved.elements[0] {
+            Element::Text {
+                content,
+                style,
+                position,
+                ..
+            } => {
+                assert_eq!(content, "Report for {{company_name}}");
+                assert_eq!(style.font_size, 32);
+                assert!(matches!(style.text_align, TextAlign::Center));
+                assert!((position.x - 10.0).abs() < 1e-9);
+                assert!((position.y - 10.0).abs() < 1e-9);
+            }
+            other => panic!("expected text, got {:?}", other),
+        }
+        match &resolved.elements[1] {
+            Element::Shape {
+                shape_type, size, ..
+            } => {
+                assert!(matches!(shape_type, ShapeType::RoundedRect));
+                assert!((size.width - 100.0).abs() < 1e-9);
+                assert!((size.height - 10.0).abs() < 1e-9);
+            }
+            other => panic!("expected shape, got {:?}", other),
+        }
+        assert!(
+            matches!(&resolved.elements[2], Element::Placeholder { key, .. } if key == "risk_score_value")
+        );
+    }
+
+    #[test]
+    fn test_group_children_are_flattened() {
+        let canvas = serde_json::json!({
+            "objects": [{
+                "type": "group", "left": 100, "top": 100, "width": 200, "height": 100,
+                "objects": [
+                    { "type": "text", "left": -100, "top": -50, "width": 100, "height": 20, "text": "A" },
+                    { "type": "image", "left": 0, "top": 0, "width": 100, "height": 50,
+                      "src": "data:image/png;base64,iVBORw0KGgo=" }
+                ]
+            }]
+        });
+
+        let elements = elements_from_canvas(&canvas);
+        assert_eq!(elements.len(), 2);
+        match &elements[0] {
+            Element::Text { position, .. } => {
+                assert!((position.x - 100.0 / CANVAS_WIDTH * 100.0).abs() < 1e-9);
+            }
+            other => panic!("expected text, got {:?}", other),
+        }
+        assert!(matches!(
+            &elements[1],
+            Element::Image { src: ImageSource::Base64 { mime_type, .. }, .. } if mime_type == "image/png"
+        ));
+    }
+}
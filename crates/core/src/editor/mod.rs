@@ -3,10 +3,14 @@
 //! This module contains all the core types for the Slide Editor Platform,
 //! including templates, slides, elements, and placeholders.
 
+mod fabric;
 mod placeholders;
+mod pptx_import;
 mod storage;
 mod types;
 
+pub use fabric::*;
 pub use placeholders::*;
+pub use pptx_import::*;
 pub use storage::*;
 pub use types::*;
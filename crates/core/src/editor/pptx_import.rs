@@ -0,0 +1,1216 @@
+//! Local PPTX import
+//!
+//! Parses a .pptx package into an editable [`PresentationTemplate`] without
+//! any external service: slides are read from `ppt/slides/slideN.xml` in
+//! presentation order, and text boxes, pictures, shapes, charts and tables
+//! are converted to editor elements with percentage-based positions.
+//!
+//! Placeholder shapes without their own position inherit it from the slide
+//! layout (or master), and theme colors (`schemeClr`) are resolved against
+//! `ppt/theme/theme1.xml`. Text that is exactly `{{key}}` for a known
+//! placeholder key becomes an [`Element::Placeholder`].
+
+use super::{
+    get_placeholder, Background, ChartStyle, ChartType, Element, ImageSource, LayoutType, Position,
+    PresentationTemplate, ShapeStyle, ShapeType, Size, SlideDefinition, TextAlign, TextStyle,
+    Theme,
+};
+use base64::Engine;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use uuid::Uuid;
+use zip::ZipArchive;
+
+/// Editor canvas width in pixels; font sizes are scaled to it
+const CANVAS_WIDTH_PX: f64 = 1280.0;
+
+/// Default slide size (16:9) when `p:sldSz` is missing
+const DEFAULT_SLIDE_SIZE: (f64, f64) = (12_192_000.0, 6_858_000.0);
+
+/// Largest decompressed size of a single package part
+const MAX_PART_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Largest decompressed size read from a package in total
+const MAX_PACKAGE_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Relationship namespaces (transitional and strict OOXML) of `r:id`/`r:embed`
+const RELATIONSHIP_NS: [&str; 2] = [
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+    "http://purl.oclc.org/ooxml/officeDocument/relationships",
+];
+
+/// Parse a PPTX file into an editable template named `name`
+pub fn import_pptx(bytes: &[u8], name: &str) -> Result<PresentationTemplate, String> {
+    let mut package = Package::open(bytes)?;
+
+    let presentation = package
+        .xml("ppt/presentation.xml")?
+        .ok_or("Not a PowerPoint file: ppt/presentation.xml is missing")?;
+    let slide_size = presentation
+        .find("sldSz")
+        .map(|n| (n.attr_f64("cx"), n.attr_f64("cy")))
+        .filter(|(cx, cy)| *cx > 0.0 && *cy > 0.0)
+        .unwrap_or(DEFAULT_SLIDE_SIZE);
+
+    let colors = match package.xml("ppt/theme/theme1.xml")? {
+        Some(theme) => ThemeColors::from_theme(&theme),
+        None => ThemeColors::default(),
+    };
+
+    let slide_paths = package.slide_paths(&presentation)?;
+    if slide_paths.is_empty() {
+        return Err("Presentation has no slides".to_string());
+    }
+
+    let mut slides = Vec::with_capacity(slide_paths.len());
+    for (index, path) in slide_paths.iter().enumerate() {
+        let mut ctx = SlideContext {
+            package: &mut package,
+            path: path.clone(),
+            slide_size,
+            colors: &colors,
+            layout_shapes: Vec::new(),
+        };
+        slides.push(ctx.parse(index)?);
+    }
+
+    Ok(PresentationTemplate {
+        name: name.to_string(),
+        description: Some(format!("Imported from PPTX ({} slides)", slides.len())),
+        slides,
+        theme: colors.to_theme(),
+        ..Default::default()
+    })
+}
+
+// ========================
+// PACKAGE
+// ========================
+
+struct Package {
+    archive: ZipArchive<Cursor<Vec<u8>>>,
+    /// Decompressed bytes read so far (bounded by `MAX_PACKAGE_BYTES`)
+    bytes_read: u64,
+}
+
+impl Package {
+    fn open(bytes: &[u8]) -> Result<Self, String> {
+        let archive = ZipArchive::new(Cursor::new(bytes.to_vec()))
+            .map_err(|e| format!("Invalid PPTX archive: {}", e))?;
+        Ok(Self {
+            archive,
+            bytes_read: 0,
+        })
+    }
+
+    /// Decompressed content of a part. Reads are capped regardless of the
+    /// sizes the archive declares, so a zip bomb fails instead of
+    /// exhausting memory.
+    fn bytes(&mut self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        let file = match self.archive.by_name(path) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(format!("Failed to open {}: {}", path, e)),
+        };
+        let limit = MAX_PART_BYTES.min(MAX_PACKAGE_BYTES.saturating_sub(self.bytes_read));
+        let mut content = Vec::new();
+        file.take(limit + 1)
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if content.len() as u64 > limit {
+            return Err(format!(
+                "{} is too large to import (limits: {} MB per part, {} MB per file)",
+                path,
+                MAX_PART_BYTES / (1024 * 1024),
+                MAX_PACKAGE_BYTES / (1024 * 1024)
+            ));
+        }
+        self.bytes_read += content.len() as u64;
+        Ok(Some(content))
+    }
+
+    fn xml(&mut self, path: &str) -> Result<Option<XmlNode>, String> {
+        match self.bytes(path)? {
+            Some(bytes) => {
+                let text = String::from_utf8_lossy(&bytes);
+                parse_xml(&text)
+                    .map(Some)
+                    .map_err(|e| format!("Invalid XML in {}: {}", path, e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Relationship id -> (type, resolved target path) for a part
+    fn rels(&mut self, part: &str) -> Result<HashMap<String, (String, String)>, String> {
+        let (dir, file) = part.rsplit_once('/').unwrap_or(("", part));
+        let rels_path = format!("{}/_rels/{}.rels", dir, file);
+        let mut rels = HashMap::new();
+        if let Some(root) = self.xml(&rels_path)? {
+            for rel in root.children_named("Relationship") {
+                let target = rel.attr("Target").unwrap_or_default();
+                let kind = rel.attr("Type").unwrap_or_default();
+                rels.insert(
+                    rel.attr("Id").unwrap_or_default().to_string(),
+                    (
+                        kind.rsplit('/').next().unwrap_or_default().to_string(),
+                        resolve_path(dir, target),
+                    ),
+                );
+            }
+        }
+        Ok(rels)
+    }
+
+    /// Slide part paths in presentation order (falls back to file numbering)
+    fn slide_paths(&mut self, presentation: &XmlNode) -> Result<Vec<String>, String> {
+        let rels = self.rels("ppt/presentation.xml")?;
+        let ordered: Vec<String> = presentation
+            .find("sldIdLst")
+            .map(|list| {
+                list.children_named("sldId")
+                    .filter_map(|id| id.rel_attr("id"))
+                    .filter_map(|rid| rels.get(rid).map(|(_, target)| target.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !ordered.is_empty() {
+            return Ok(ordered);
+        }
+
+        let mut numbered: Vec<(usize, String)> = self
+            .archive
+            .file_names()
+            .filter_map(|name| {
+                let num = name
+                    .strip_prefix("ppt/slides/slide")?
+                    .strip_suffix(".xml")?
+                    .parse()
+                    .ok()?;
+                Some((num, name.to_string()))
+            })
+            .collect();
+        numbered.sort();
+        Ok(numbered.into_iter().map(|(_, name)| name).collect())
+    }
+}
+
+/// Resolve a relationship target relative to the source part directory
+fn resolve_path(dir: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
+}
+
+// ========================
+// THEME
+// ========================
+
+/// Theme color scheme (hex with '#') and fonts
+#[derive(Debug, Clone)]
+struct ThemeColors {
+    scheme: HashMap<String, String>,
+    major_font: String,
+    minor_font: String,
+}
+
+impl Default for ThemeColors {
+    fn default() -> Self {
+        let scheme = [
+            ("dk1", "#000000"),
+            ("lt1", "#FFFFFF"),
+            ("dk2", "#44546A"),
+            ("lt2", "#E7E6E6"),
+            ("accent1", "#4472C4"),
+            ("accent2", "#ED7D31"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        Self {
+            scheme,
+            major_font: "Inter".to_string(),
+            minor_font: "Inter".to_string(),
+        }
+    }
+}
+
+impl ThemeColors {
+    fn from_theme(theme: &XmlNode) -> Self {
+        let mut colors = Self::default();
+        if let Some(scheme) = theme.find("clrScheme") {
+            for entry in &scheme.children {
+                let color = entry
+                    .find("srgbClr")
+                    .and_then(|c| c.attr("val"))
+                    .or_else(|| entry.find("sysClr").and_then(|c| c.attr("lastClr")));
+                if let Some(color) = color {
+                    colors
+                        .scheme
+                        .insert(entry.local_name().to_string(), format!("#{}", color));
+                }
+            }
+        }
+        let font = |name: &str| {
+            theme
+                .find(name)
+                .and_then(|f| f.find("latin"))
+                .and_then(|l| l.attr("typeface"))
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+        };
+        if let Some(major) = font("majorFont") {
+            colors.major_font = major;
+        }
+        if let Some(minor) = font("minorFont") {
+            colors.minor_font = minor;
+        }
+        colors
+    }
+
+    /// Resolve a scheme color name (including the tx/bg aliases)
+    fn scheme_color(&self, name: &str) -> Option<String> {
+        let key = match name {
+            "tx1" => "dk1",
+            "bg1" => "lt1",
+            "tx2" => "dk2",
+            "bg2" => "lt2",
+            other => other,
+        };
+        self.scheme.get(key).cloned()
+    }
+
+    /// Color of the first `srgbClr`/`schemeClr` under a fill node
+    fn color_of(&self, fill: &XmlNode) -> Option<String> {
+        if let Some(rgb) = fill.find("srgbClr").and_then(|c| c.attr("val")) {
+            return Some(format!("#{}", rgb.to_uppercase()));
+        }
+        fill.find("schemeClr")
+            .and_then(|c| c.attr("val"))
+            .and_then(|name| self.scheme_color(name))
+    }
+
+    fn to_theme(&self) -> Theme {
+        let get = |key: &str, fallback: &str| {
+            self.scheme
+                .get(key)
+                .cloned()
+                .unwrap_or_else(|| fallback.to_string())
+        };
+        Theme {
+            primary_color: get("accent1", "#6366F1"),
+            secondary_color: get("accent2", "#EC4899"),
+            background_color: get("lt1", "#FFFFFF"),
+            text_color: get("dk1", "#000000"),
+            font_family: self.minor_font.clone(),
+            heading_font: self.major_font.clone(),
+        }
+    }
+}
+
+// ========================
+// SLIDES
+// ========================
+
+/// Affine mapping from group child coordinates to slide EMUs
+#[derive(Debug, Clone, Copy)]
+struct GroupTransform {
+    offset: (f64, f64),
+    child_offset: (f64, f64),
+    scale: (f64, f64),
+}
+
+impl GroupTransform {
+    const IDENTITY: Self = Self {
+        offset: (0.0, 0.0),
+        child_offset: (0.0, 0.0),
+        scale: (1.0, 1.0),
+    };
+
+    fn apply(&self, (x, y, w, h): (f64, f64, f64, f64)) -> (f64, f64, f64, f64) {
+        (
+            self.offset.0 + (x - self.child_offset.0) * self.scale.0,
+            self.offset.1 + (y - self.child_offset.1) * self.scale.1,
+            w * self.scale.0,
+            h * self.scale.1,
+        )
+    }
+
+    /// Compose with a nested `p:grpSp` transform
+    fn nested(&self, group: &XmlNode) -> Self {
+        let Some(xfrm) = group.find("grpSpPr").and_then(|p| p.find("xfrm")) else {
+            return *self;
+        };
+        let (off, ext) = (xfrm.find("off"), xfrm.find("ext"));
+        let (ch_off, ch_ext) = (xfrm.find("chOff"), xfrm.find("chExt"));
+        let pair = |node: Option<&XmlNode>, a: &str, b: &str| {
+            node.map(|n| (n.attr_f64(a), n.attr_f64(b)))
+                .unwrap_or((0.0, 0.0))
+        };
+        let (ox, oy) = pair(off, "x", "y");
+        let (cx, cy) = pair(ext, "cx", "cy");
+        let (chx, chy) = pair(ch_off, "x", "y");
+        let (chcx, chcy) = pair(ch_ext, "cx", "cy");
+        let scale = (
+            if chcx > 0.0 { cx / chcx } else { 1.0 },
+            if chcy > 0.0 { cy / chcy } else { 1.0 },
+        );
+        let local = Self {
+            offset: (ox, oy),
+            child_offset: (chx, chy),
+            scale,
+        };
+        // Outer transform applied after the group's own mapping
+        Self {
+            offset: (
+                self.offset.0 + (local.offset.0 - self.child_offset.0) * self.scale.0,
+                self.offset.1 + (local.offset.1 - self.child_offset.1) * self.scale.1,
+            ),
+            child_offset: local.child_offset,
+            scale: (local.scale.0 * self.scale.0, local.scale.1 * self.scale.1),
+        }
+    }
+}
+
+struct SlideContext<'a> {
+    package: &'a mut Package,
+    path: String,
+    slide_size: (f64, f64),
+    colors: &'a ThemeColors,
+    /// Placeholder shapes from the layout and master (for inherited positions)
+    layout_shapes: Vec<XmlNode>,
+}
+
+impl SlideContext<'_> {
+    fn parse(&mut self, index: usize) -> Result<SlideDefinition, String> {
+        let root = self
+            .package
+            .xml(&self.path)?
+            .ok_or_else(|| format!("Missing slide part {}", self.path))?;
+        let rels = self.package.rels(&self.path)?;
+        self.load_layout_shapes(&rels)?;
+
+        let mut elements = Vec::new();
+        let mut title = None;
+        if let Some(tree) = root.find("spTree") {
+            self.walk(
+                tree,
+                GroupTransform::IDENTITY,
+                &rels,
+                &mut elements,
+                &mut title,
+            )?;
+        }
+
+        let background = match root.find("bg") {
+            Some(bg) => self.background(bg, &rels)?,
+            None => Background::Solid {
+                color: self
+                    .colors
+                    .scheme_color("lt1")
+                    .unwrap_or_else(|| "#FFFFFF".to_string()),
+            },
+        };
+
+        Ok(SlideDefinition {
+            id: Uuid::new_v4(),
+            name: title.unwrap_or_else(|| format!("Slide {}", index + 1)),
+            layout: LayoutType::Blank,
+            elements,
+            background,
+            order: index as i32,
+            visible: root.attr("show") != Some("0"),
+            canvas_json: None,
+        })
+    }
+
+    fn load_layout_shapes(
+        &mut self,
+        rels: &HashMap<String, (String, String)>,
+    ) -> Result<(), String> {
+        let Some((_, layout_path)) = rels.values().find(|(kind, _)| kind == "slideLayout") else {
+            return Ok(());
+        };
+        let layout_path = layout_path.clone();
+        let mut parts = vec![layout_path.clone()];
+        let layout_rels = self.package.rels(&layout_path)?;
+        if let Some((_, master)) = layout_rels.values().find(|(kind, _)| kind == "slideMaster") {
+            parts.push(master.clone());
+        }
+        for part in parts {
+            if let Some(root) = self.package.xml(&part)? {
+                let mut shapes = Vec::new();
+                collect_named(&root, "sp", &mut shapes);
+                self.layout_shapes.extend(
+                    shapes
+                        .into_iter()
+                        .filter(|s| placeholder_of(s).is_some())
+                        .cloned(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn walk(
+        &mut self,
+        tree: &XmlNode,
+        transform: GroupTransform,
+        rels: &HashMap<String, (String, String)>,
+        elements: &mut Vec<Element>,
+        title: &mut Option<String>,
+    ) -> Result<(), String> {
+        for node in &tree.children {
+            match node.local_name() {
+                "grpSp" => {
+                    let nested = transform.nested(node);
+                    self.walk(node, nested, rels, elements, title)?;
+                }
+                "sp" => {
+                    if let Some(element) = self.shape(node, transform, title) {
+                        elements.push(element);
+                    }
+                }
+                "cxnSp" => {
+                    if let Some((position, size)) = self.frame(node, transform) {
+                        elements.push(Element::Shape {
+                            id: Uuid::new_v4(),
+                            shape_type: ShapeType::Line,
+                            style: self.shape_style(node),
+                            position,
+                            size,
+                            locked: false,
+                        });
+                    }
+                }
+                "pic" => {
+                    if let Some(element) = self.picture(node, transform, rels)? {
+                        elements.push(element);
+                    }
+                }
+                "graphicFrame" => {
+                    if let Some(element) = self.graphic_frame(node, transform) {
+                        elements.push(element);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Position and size as slide percentages
+    fn frame(&self, node: &XmlNode, transform: GroupTransform) -> Option<(Position, Size)> {
+        let xfrm = node
+            .children
+            .iter()
+            .find(|c| matches!(c.local_name(), "spPr" | "grpSpPr"))
+            .and_then(|p| p.find("xfrm"))
+            .or_else(|| node.children_named("xfrm").next())
+            .or_else(|| self.inherited_xfrm(node))?;
+        let off = xfrm.find("off")?;
+        let ext = xfrm.find("ext")?;
+        let (x, y, w, h) = transform.apply((
+            off.attr_f64("x"),
+            off.attr_f64("y"),
+            ext.attr_f64("cx"),
+            ext.attr_f64("cy"),
+        ));
+        let (sw, sh) = self.slide_size;
+        Some((
+            Position {
+                x: round2(x / sw * 100.0),
+                y: round2(y / sh * 100.0),
+            },
+            Size {
+                width: round2(w / sw * 100.0),
+                height: round2(h / sh * 100.0),
+            },
+        ))
+    }
+
+    /// Position of the matching placeholder on the layout or master
+    fn inherited_xfrm(&self, node: &XmlNode) -> Option<&XmlNode> {
+        let (kind, idx) = placeholder_of(node)?;
+        let matches = |shape: &&XmlNode| {
+            let Some((other_kind, other_idx)) = placeholder_of(shape) else {
+                return false;
+            };
+            (idx.is_some() && idx == other_idx) || (kind == other_kind && kind != "body")
+        };
+        self.layout_shapes
+            .iter()
+            .filter(matches)
+            .find_map(|shape| shape.find("spPr").and_then(|p| p.find("xfrm")))
+    }
+
+    fn shape(
+        &self,
+        node: &XmlNode,
+        transform: GroupTransform,
+        title: &mut Option<String>,
+    ) -> Option<Element> {
+        let (position, size) = self.frame(node, transform)?;
+        let text = node.find("txBody").map(text_of).unwrap_or_default();
+
+        if !text.trim().is_empty() {
+            if let Some((kind, _)) = placeholder_of(node) {
+                if title.is_none() && matches!(kind.as_str(), "title" | "ctrTitle") {
+                    *title = Some(text.lines().next().unwrap_or_default().trim().to_string());
+                }
+            }
+
+            let trimmed = text.trim();
+            if let Some(key) = trimmed
+                .strip_prefix("{{")
+                .and_then(|t| t.strip_suffix("}}"))
+                .map(str::trim)
+                .filter(|key| get_placeholder(key).is_some())
+            {
+                return Some(Element::Placeholder {
+                    id: Uuid::new_v4(),
+                    key: key.to_string(),
+                    position,
+                    size,
+                    locked: false,
+                });
+            }
+
+            return Some(Element::Text {
+                id: Uuid::new_v4(),
+                content: text,
+                style: self.text_style(node),
+                position,
+                size,
+                locked: false,
+            });
+        }
+
+        // Shapes without text: keep only visible geometry
+        let style = self.shape_style(node);
+        let visible = style.fill_color != "transparent" || style.stroke_width > 0.0;
+        if !visible || placeholder_of(node).is_some() {
+            return None;
+        }
+        let preset = node
+            .find("prstGeom")
+            .and_then(|g| g.attr("prst"))
+            .unwrap_or("rect");
+        Some(Element::Shape {
+            id: Uuid::new_v4(),
+            shape_type: shape_type(preset),
+            style,
+            position,
+            size,
+            locked: false,
+        })
+    }
+
+    fn text_style(&self, node: &XmlNode) -> TextStyle {
+        let mut style = TextStyle::default();
+        let body = node.find("txBody");
+        let run_props = body.and_then(|b| b.find("rPr")).or_else(|| {
+            body.and_then(|b| b.find("defRPr"))
+                .or_else(|| body.and_then(|b| b.find("endParaRPr")))
+        });
+
+        if let Some(rpr) = run_props {
+            if let Some(sz) = rpr.attr("sz").and_then(|s| s.parse::<f64>().ok()) {
+                // Hundredths of a point -> canvas pixels (96 DPI, scaled to canvas width)
+                let scale = CANVAS_WIDTH_PX / (self.slide_size.0 / 9525.0);
+                style.font_size = (sz / 100.0 * 96.0 / 72.0 * scale).round().max(1.0) as u32;
+            }
+            if rpr.attr("b") == Some("1") {
+                style.font_weight = "bold".to_string();
+            }
+            if let Some(color) = rpr
+                .children
+                .iter()
+                .find(|c| c.local_name() == "solidFill")
+                .and_then(|fill| self.colors.color_of(fill))
+            {
+                style.color = color;
+            }
+            if let Some(font) = rpr
+                .find("latin")
+                .and_then(|l| l.attr("typeface"))
+                .filter(|t| !t.starts_with('+'))
+            {
+                style.font_family = font.to_string();
+            } else {
+                style.font_family = self.colors.minor_font.clone();
+            }
+        } else {
+            style.font_family = self.colors.minor_font.clone();
+        }
+
+        style.text_align = match body
+            .and_then(|b| b.find("pPr"))
+            .and_then(|p| p.attr("algn"))
+        {
+            Some("ctr") => TextAlign::Center,
+            Some("r") => TextAlign::Right,
+            Some("just") => TextAlign::Justify,
+            _ => TextAlign::Left,
+        };
+        style
+    }
+
+    fn shape_style(&self, node: &XmlNode) -> ShapeStyle {
+        let mut style = ShapeStyle::default();
+        let sp_pr = node.find("spPr");
+        let fill = sp_pr.and_then(|p| {
+            p.children
+                .iter()
+                .find(|c| matches!(c.local_name(), "solidFill" | "noFill" | "gradFill"))
+        });
+        style.fill_color = match fill {
+            Some(f) if f.local_name() == "noFill" => "transparent".to_string(),
+            Some(f) => self
+                .colors
+                .color_of(f)
+                .unwrap_or_else(|| "transparent".to_string()),
+            // Style reference (p:style/a:fillRef) or no fill at all
+            None => node
+                .find("fillRef")
+                .and_then(|r| self.colors.color_of(r))
+                .unwrap_or_else(|| "transparent".to_string()),
+        };
+
+        match sp_pr.and_then(|p| p.find("ln")) {
+            Some(ln) if ln.find("noFill").is_some() => style.stroke_width = 0.0,
+            Some(ln) => {
+                if let Some(color) = self.colors.color_of(ln) {
+                    style.stroke_color = color;
+                }
+                let width = ln.attr_f64("w");
+                style.stroke_width = if width > 0.0 {
+                    round2(width / 12700.0)
+                } else {
+                    1.0
+                };
+            }
+            None => style.stroke_width = 0.0,
+        }
+
+        if let Some(alpha) = fill.and_then(|f| f.find("alpha")) {
+            style.opacity = (alpha.attr_f64("val") / 100_000.0).clamp(0.0, 1.0);
+        }
+        style
+    }
+
+    fn picture(
+        &mut self,
+        node: &XmlNode,
+        transform: GroupTransform,
+        rels: &HashMap<String, (String, String)>,
+    ) -> Result<Option<Element>, String> {
+        let Some((position, size)) = self.frame(node, transform) else {
+            return Ok(None);
+        };
+        let Some(rid) = node.find("blip").and_then(|b| b.rel_attr("embed")) else {
+            return Ok(None);
+        };
+        let Some((_, target)) = rels.get(rid) else {
+            return Ok(None);
+        };
+        let target = target.clone();
+        let Some(src) = self.image_source(&target)? else {
+            return Ok(None);
+        };
+        Ok(Some(Element::Image {
+            id: Uuid::new_v4(),
+            src,
+            position,
+            size,
+            locked: false,
+        }))
+    }
+
+    fn image_source(&mut self, path: &str) -> Result<Option<ImageSource>, String> {
+        let Some(bytes) = self.package.bytes(path)? else {
+            return Ok(None);
+        };
+        let extension = path.rsplit('.').next().unwrap_or_default().to_lowercase();
+        let mime_type = match extension.as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "svg" => "image/svg+xml",
+            "bmp" => "image/bmp",
+            "webp" => "image/webp",
+            // EMF/WMF and other formats cannot be shown on the canvas
+            _ => return Ok(None),
+        };
+        Ok(Some(ImageSource::Base64 {
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            mime_type: mime_type.to_string(),
+        }))
+    }
+
+    fn graphic_frame(&self, node: &XmlNode, transform: GroupTransform) -> Option<Element> {
+        let (position, size) = self.frame(node, transform)?;
+        let data = node.find("graphicData")?;
+        let uri = data.attr("uri").unwrap_or_default();
+
+        if uri.ends_with("/chart") {
+            return Some(Element::Chart {
+                id: Uuid::new_v4(),
+                chart_type: ChartType::Bar,
+                data_source: String::new(),
+                style: ChartStyle::default(),
+                position,
+                size,
+                locked: false,
+            });
+        }
+
+        if let Some(table) = data.find("tbl") {
+            let rows: Vec<String> = table
+                .children_named("tr")
+                .map(|row| {
+                    row.children_named("tc")
+                        .map(|cell| text_of(cell).replace('\n', " "))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                })
+                .collect();
+            return Some(Element::Text {
+                id: Uuid::new_v4(),
+                content: rows.join("\n"),
+                style: self.text_style(table),
+                position,
+                size,
+                locked: false,
+            });
+        }
+        None
+    }
+
+    fn background(
+        &mut self,
+        bg: &XmlNode,
+        rels: &HashMap<String, (String, String)>,
+    ) -> Result<Background, String> {
+        if let Some(rid) = bg.find("blip").and_then(|b| b.rel_attr("embed")) {
+            if let Some((_, target)) = rels.get(rid) {
+                let target = target.clone();
+                if let Some(ImageSource::Base64 { data, mime_type }) = self.image_source(&target)? {
+                    return Ok(Background::Image {
+                        url: format!("data:{};base64,{}", mime_type, data),
+                        opacity: 1.0,
+                    });
+                }
+            }
+        }
+
+        if let Some(grad) = bg.find("gradFill") {
+            let stops: Vec<String> = grad
+                .find("gsLst")
+                .map(|list| {
+                    list.children
+                        .iter()
+                        .filter_map(|stop| self.colors.color_of(stop))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let (Some(start), Some(end)) = (stops.first(), stops.last()) {
+                return Ok(Background::Gradient {
+                    start_color: start.clone(),
+                    end_color: end.clone(),
+                    direction: Default::default(),
+                });
+            }
+        }
+
+        let color = bg
+            .find("solidFill")
+            .and_then(|f| self.colors.color_of(f))
+            .or_else(|| bg.find("bgRef").and_then(|r| self.colors.color_of(r)))
+            .unwrap_or_else(|| "#FFFFFF".to_string());
+        Ok(Background::Solid { color })
+    }
+}
+
+/// Placeholder type and index of a shape (`p:nvPr/p:ph`)
+fn placeholder_of(node: &XmlNode) -> Option<(String, Option<String>)> {
+    let ph = node
+        .children
+        .iter()
+        .find(|c| c.local_name().starts_with("nv"))?
+        .find("ph")?;
+    Some((
+        ph.attr("type").unwrap_or("body").to_string(),
+        ph.attr("idx").map(str::to_string),
+    ))
+}
+
+/// Paragraph texts joined with newlines
+fn text_of(body: &XmlNode) -> String {
+    let mut paragraphs = Vec::new();
+    collect_paragraphs(body, &mut paragraphs);
+    paragraphs.join("\n")
+}
+
+fn collect_paragraphs(node: &XmlNode, out: &mut Vec<String>) {
+    for child in &node.children {
+        if child.local_name() == "p" {
+            let mut line = String::new();
+            for part in &child.children {
+                match part.local_name() {
+                    "r" | "fld" => {
+                        if let Some(t) = part.find("t") {
+                            line.push_str(&t.text);
+                        }
+                    }
+                    "br" => line.push('\n'),
+                    _ => {}
+                }
+            }
+            out.push(line);
+        } else {
+            collect_paragraphs(child, out);
+        }
+    }
+}
+
+fn collect_named<'a>(node: &'a XmlNode, name: &str, out: &mut Vec<&'a XmlNode>) {
+    for child in &node.children {
+        if child.local_name() == name {
+            out.push(child);
+        }
+        collect_named(child, name, out);
+    }
+}
+
+fn shape_type(preset: &str) -> ShapeType {
+    match preset {
+        "roundRect" | "round1Rect" | "round2SameRect" => ShapeType::RoundedRect,
+        "ellipse" => ShapeType::Ellipse,
+        "triangle" | "rtTriangle" => ShapeType::Triangle,
+        "line" | "straightConnector1" => ShapeType::Line,
+        p if p.contains("Arrow") => ShapeType::Arrow,
+        _ => ShapeType::Rectangle,
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// ========================
+// XML TREE
+// ========================
+
+/// Element node of a parsed XML document. Elements are matched by local
+/// name; attributes keep their namespace so `r:id` is found whatever
+/// prefix the document binds to the relationships namespace.
+#[derive(Debug, Clone, Default)]
+struct XmlNode {
+    name: String,
+    attrs: Vec<XmlAttr>,
+    children: Vec<XmlNode>,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+struct XmlAttr {
+    namespace: Option<String>,
+    name: String,
+    value: String,
+}
+
+impl XmlNode {
+    fn local_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Attribute without a namespace
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|a| a.namespace.is_none() && a.name == name)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Attribute in the relationships namespace (`r:id`, `r:embed`)
+    fn rel_attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|a| {
+                a.name == name
+                    && a.namespace
+                        .as_deref()
+                        .is_some_and(|ns| RELATIONSHIP_NS.contains(&ns))
+            })
+            .map(|a| a.value.as_str())
+    }
+
+    fn attr_f64(&self, name: &str) -> f64 {
+        self.attr(name).and_then(|v| v.parse().ok()).unwrap_or(0.0)
+    }
+
+    fn children_named<'a>(&'a self, local: &'a str) -> impl Iterator<Item = &'a XmlNode> {
+        self.children
+            .iter()
+            .filter(move |c| c.local_name() == local)
+    }
+
+    /// First descendant (depth-first) with the given local name
+    fn find(&self, local: &str) -> Option<&XmlNode> {
+        for child in &self.children {
+            if child.local_name() == local {
+                return Some(child);
+            }
+            if let Some(found) = child.find(local) {
+                return Some(found);
+            }
+        }
+        None
+    }
+}
+
+/// Parse an XML document into its root element
+fn parse_xml(input: &str) -> Result<XmlNode, String> {
+    use quick_xml::events::Event;
+    use quick_xml::reader::NsReader;
+
+    let mut reader = NsReader::from_str(input);
+    let mut stack: Vec<XmlNode> = vec![XmlNode::default()];
+    let error = |reader: &NsReader<&[u8]>, e: quick_xml::Error| {
+        format!("{} at byte {}", e, reader.error_position())
+    };
+
+    loop {
+        match reader.read_event().map_err(|e| error(&reader, e))? {
+            Event::Start(start) => {
+                let node = element(&reader, &start).map_err(|e| error(&reader, e))?;
+                stack.push(node);
+            }
+            Event::Empty(start) => {
+                let node = element(&reader, &start).map_err(|e| error(&reader, e))?;
+                stack
+                    .last_mut()
+                    .ok_or("Unbalanced tag")?
+                    .children
+                    .push(node);
+            }
+            Event::End(_) => {
+                let node = stack.pop().ok_or("Unbalanced end tag")?;
+                stack
+                    .last_mut()
+                    .ok_or("Unbalanced end tag")?
+                    .children
+                    .push(node);
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|e| error(&reader, e))?;
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(&text);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text
+                        .push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Event::Eof => break,
+            // Declarations, comments, processing instructions, doctype
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err("Unclosed elements at end of document".to_string());
+    }
+    stack
+        .pop()
+        .and_then(|doc| doc.children.into_iter().next())
+        .ok_or_else(|| "Empty document".to_string())
+}
+
+/// Element node (without children) for a start tag
+fn element(
+    reader: &quick_xml::reader::NsReader<&[u8]>,
+    start: &quick_xml::events::BytesStart,
+) -> Result<XmlNode, quick_xml::Error> {
+    use quick_xml::name::ResolveResult;
+
+    let mut node = XmlNode {
+        name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+        ..Default::default()
+    };
+    for attr in start.attributes() {
+        let attr = attr?;
+        if attr.key.as_namespace_binding().is_some() {
+            continue;
+        }
+        let (namespace, name) = reader.resolve_attribute(attr.key);
+        node.attrs.push(XmlAttr {
+            // Undeclared prefixes are treated as no namespace
+            namespace: match namespace {
+                ResolveResult::Bound(ns) => Some(String::from_utf8_lossy(ns.as_ref()).into_owned()),
+                ResolveResult::Unbound | ResolveResult::Unknown(_) => None,
+            },
+            name: String::from_utf8_lossy(name.as_ref()).into_owned(),
+            value: attr.unescape_value()?.into_owned(),
+        });
+    }
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pptx_writer::{
+        Chart, ChartKind, ChartSeries, Frame, Paragraph, PptxPresentation, PptxSlide, PptxTheme,
+        Shape,
+    };
+
+    fn sample_deck() -> Vec<u8> {
+        let theme = PptxTheme::default();
+        let mut deck = PptxPresentation::new("Sample", theme.clone());
+
+        let mut first = PptxSlide::new("cover");
+        first.add(Shape::text(
+            Frame::new(128.0, 72.0, 640.0, 72.0),
+            Paragraph::new("Hello & welcome", 24.0, "FF671F").bold(),
+        ));
+        first.add(Shape::text(
+            Frame::new(128.0, 200.0, 320.0, 72.0),
+            Paragraph::new("{{company_name}}", 18.0, "FFFFFF"),
+        ));
+        first.add(Shape::rect(Frame::new(0.0, 0.0, 12.0, 720.0), "FF671F"));
+        first.add(
+            Shape::picture(
+                Frame::new(640.0, 360.0, 320.0, 180.0),
+                b"\x89PNG....".to_vec(),
+            )
+            .unwrap(),
+        );
+        deck.push(first);
+
+        let mut second = PptxSlide::new("data");
+        second.add(Shape::table(
+            Frame::new(64.0, 64.0, 640.0, 80.0),
+            vec![
+                vec!["Tipo".into(), "Total".into()],
+                vec!["phishing".into(), "10".into()],
+            ],
+            12.0,
+        ));
+        second.add(Shape::chart(
+            Frame::new(64.0, 300.0, 640.0, 360.0),
+            Chart::new(
+                ChartKind::Column,
+                vec!["a".into()],
+                vec![ChartSeries {
+                    name: "s".into(),
+                    values: vec![1.0],
+                    color: theme.primary.clone(),
+                }],
+            ),
+        ));
+        deck.push(second);
+        deck.to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_import_extracts_elements() {
+        let template = import_pptx(&sample_deck(), "Sample").unwrap();
+        assert_eq!(template.slides.len(), 2);
+        assert_eq!(template.theme.primary_color, "#FF671F");
+
+        let first = &template.slides[0];
+        assert_eq!(first.elements.len(), 4);
+        match &first.elements[0] {
+            Element::Text {
+                content,
+                style,
+                position,
+                size,
+                ..
+            } => {
+                assert_eq!(content, "Hello & welcome");
+                assert_eq!(style.font_weight, "bold");
+                assert_eq!(style.color, "#FF671F");
+                assert_eq!(style.font_size, 32);
+                assert_eq!(position.x, 10.0);
+                assert_eq!(position.y, 10.0);
+                assert_eq!(size.width, 50.0);
+            }
+            other => panic!("expected text, got {:?}", other),
+        }
+        assert!(
+            matches!(&first.elements[1], Element::Placeholder { key, .. } if key == "company_name")
+        );
+        assert!(
+            matches!(&first.elements[2], Element::Shape { style, .. } if style.fill_color == "#FF671F")
+        );
+        assert!(matches!(
+            &first.elements[3],
+            Element::Image { src: ImageSource::Base64 { mime_type, .. }, .. } if mime_type == "image/png"
+        ));
+
+        let second = &template.slides[1];
+        assert!(
+            matches!(&second.elements[0], Element::Text { content, .. } if content == "Tipo | Total\nphishing | 10")
+        );
+        assert!(matches!(&second.elements[1], Element::Chart { .. }));
+        assert_eq!(second.order, 1);
+    }
+
+    #[test]
+    fn test_rejects_non_pptx() {
+        assert!(import_pptx(b"not a zip", "x").is_err());
+    }
+
+    #[test]
+    fn test_group_transform() {
+        let group = parse_xml(
+            r#"<p:grpSp><p:grpSpPr><a:xfrm><a:off x="100" y="200"/><a:ext cx="200" cy="200"/><a:chOff x="0" y="0"/><a:chExt cx="100" cy="100"/></a:xfrm></p:grpSpPr></p:grpSp>"#,
+        )
+        .unwrap();
+        let t = GroupTransform::IDENTITY.nested(&group);
+        assert_eq!(
+            t.apply((10.0, 10.0, 50.0, 50.0)),
+            (120.0, 220.0, 100.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_xml_and_entities() {
+        let root = parse_xml(
+            r#"<?xml version="1.0"?><!-- c --><a:p x='1 > 0'><a:t>A &amp; B &#x41;&#66;</a:t><a:br/></a:p>"#,
+        )
+        .unwrap();
+        assert_eq!(root.attr("x"), Some("1 > 0"));
+        assert_eq!(root.find("t").unwrap().text, "A & B AB");
+        assert!(parse_xml("<a><b></a>").is_err());
+
+        // Relationship ids are found by namespace, not by prefix
+        let root = parse_xml(
+            r#"<p:pic xmlns:p="urn:p" xmlns:rel="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><p:blip rel:embed="rId7"/><p:t><![CDATA[1 < 2]]></p:t></p:pic>"#,
+        )
+        .unwrap();
+        assert_eq!(root.find("blip").unwrap().rel_attr("embed"), Some("rId7"));
+        assert_eq!(root.find("blip").unwrap().attr("embed"), None);
+        assert_eq!(root.find("t").unwrap().text, "1 < 2");
+        assert_eq!(
+            resolve_path("ppt/slides", "../media/image1.png"),
+            "ppt/media/image1.png"
+        );
+    }
+}
@@ -7,7 +7,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 
 /// Translations loader from JSON files
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Translations {
     #[serde(flatten)]
     data: HashMap<String, serde_json::Value>,
@@ -56,6 +56,17 @@ impl Translations {
     pub fn has_key(&self, key: &str) -> bool {
         self.data.contains_key(key)
     }
+
+    /// Copy with some keys replaced (customer terminology)
+    pub fn with_overrides(&self, overrides: &HashMap<String, String>) -> Self {
+        let mut translations = self.clone();
+        for (key, text) in overrides {
+            translations
+                .data
+                .insert(key.clone(), serde_json::Value::String(text.clone()));
+        }
+        translations
+    }
 }
 
 /// Errors that can occur when loading translations
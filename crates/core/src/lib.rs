@@ -10,6 +10,7 @@ pub mod errors;
 pub mod i18n;
 pub mod plugins;
 pub mod pptx_mapper;
+pub mod pptx_writer;
 pub mod report;
 
 pub mod templates;
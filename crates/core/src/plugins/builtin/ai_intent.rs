@@ -124,7 +124,7 @@ impl SlidePlugin for AiIntentSlidePlugin {
             top_pct = top_pct,
             top_label = top_label,
             bars = bars_html,
-            footer = footer_dark(13, &t.get("footer_text")),
+            footer = footer_dark(13, ctx),
         );
 
         vec![SlideOutput {
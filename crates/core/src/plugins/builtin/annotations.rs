@@ -0,0 +1,411 @@
+//! Analyst Annotations
+//!
+//! Executive summary, highlighted tickets and recommendations written by an
+//! analyst on a report draft. They are rendered as narrative slides around the
+//! data-generated ones; per-plugin callouts are pinned on the plugin's slides
+//! as analyst notes (see `PluginConfig::with_annotations`).
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::helpers::footer_dark;
+use crate::api::report::PocReportData;
+use crate::plugins::declarative::escape_html;
+use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
+
+/// Most highlighted tickets that fit on the summary slide
+pub const MAX_HIGHLIGHTED_TICKETS: usize = 6;
+/// Most recommendations that fit on the recommendations slide
+pub const MAX_RECOMMENDATIONS: usize = 8;
+/// Longest executive summary, in characters
+pub const MAX_SUMMARY_CHARS: usize = 2000;
+/// Longest note, recommendation or callout, in characters
+pub const MAX_NOTE_CHARS: usize = 500;
+
+/// Analyst annotations for one report
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportAnnotations {
+    /// Executive summary, shown on its own slide after the agenda
+    pub executive_summary: Option<String>,
+    /// Tickets called out on the summary slide
+    pub highlighted_tickets: Vec<HighlightedTicket>,
+    /// Analyst recommendations, shown after the generated insights
+    pub recommendations: Vec<String>,
+    /// Notes pinned on the slides of a plugin (plugin ID -> note)
+    pub callouts: HashMap<String, String>,
+}
+
+/// A ticket highlighted by the analyst
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightedTicket {
+    pub ticket_key: String,
+    /// Why this ticket matters
+    pub note: String,
+}
+
+impl ReportAnnotations {
+    /// True when there is nothing to render
+    pub fn is_empty(&self) -> bool {
+        self.summary().is_none()
+            && self.highlighted_tickets.is_empty()
+            && self.recommendations().next().is_none()
+            && self.callouts.values().all(|note| note.trim().is_empty())
+    }
+
+    /// Check the annotations fit on their slides
+    pub fn validate(&self) -> Result<(), String> {
+        if self.summary().unwrap_or_default().chars().count() > MAX_SUMMARY_CHARS {
+            return Err(format!(
+                "executive_summary is longer than {} characters",
+                MAX_SUMMARY_CHARS
+            ));
+        }
+        if self.highlighted_tickets.len() > MAX_HIGHLIGHTED_TICKETS {
+            return Err(format!(
+                "at most {} highlighted tickets are allowed",
+                MAX_HIGHLIGHTED_TICKETS
+            ));
+        }
+        if self.recommendations.len() > MAX_RECOMMENDATIONS {
+            return Err(format!(
+                "at most {} recommendations are allowed",
+                MAX_RECOMMENDATIONS
+            ));
+        }
+        if let Some(ticket) = self
+            .highlighted_tickets
+            .iter()
+            .find(|t| t.ticket_key.trim().is_empty())
+        {
+            return Err(format!(
+                "highlighted ticket without ticket_key (note: '{}')",
+                ticket.note
+            ));
+        }
+
+        let notes = self
+            .highlighted_tickets
+            .iter()
+            .map(|t| &t.note)
+            .chain(&self.recommendations)
+            .chain(self.callouts.values());
+        for note in notes {
+            if note.chars().count() > MAX_NOTE_CHARS {
+                return Err(format!(
+                    "notes, recommendations and callouts are limited to {} characters",
+                    MAX_NOTE_CHARS
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn summary(&self) -> Option<&str> {
+        self.executive_summary
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    fn recommendations(&self) -> impl Iterator<Item = &str> {
+        self.recommendations
+            .iter()
+            .map(|r| r.trim())
+            .filter(|r| !r.is_empty())
+    }
+}
+
+/// Ticket details found in the report data: (type, target, status)
+fn ticket_details(data: &PocReportData, key: &str) -> Option<(String, String, String)> {
+    let story = data.story_tickets.iter().find(|t| t.ticket_key == key);
+    if let Some(t) = story {
+        return Some((t.threat_type.clone(), t.target.clone(), t.status.clone()));
+    }
+    let incident = data.latest_incidents.iter().find(|t| t.ticket_key == key);
+    if let Some(t) = incident {
+        return Some((t.ticket_type.clone(), t.host.clone(), t.status.clone()));
+    }
+    let takedown = data.resolved_takedowns.iter().find(|t| t.ticket_key == key);
+    if let Some(t) = takedown {
+        return Some((t.ticket_type.clone(), t.host.clone(), t.status.clone()));
+    }
+    data.poc_examples
+        .iter()
+        .find(|t| t.ticket_key == key)
+        .map(|t| {
+            let target = t.domain.clone().unwrap_or_else(|| t.reference_url.clone());
+            (t.evidence_type.clone(), target, t.status.clone())
+        })
+}
+
+/// Paragraphs separated by blank lines, escaped
+fn paragraphs(text: &str, class: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            format!(
+                r#"<p class="{}">{}</p>"#,
+                class,
+                escape_html(p).replace('\n', "<br>")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Plugin that renders the analyst executive summary and highlighted tickets
+pub struct AnalystSummarySlidePlugin;
+
+impl SlidePlugin for AnalystSummarySlidePlugin {
+    fn id(&self) -> &'static str {
+        "builtin.analyst_summary"
+    }
+    fn name(&self) -> &'static str {
+        "Analyst Executive Summary"
+    }
+    fn priority(&self) -> i32 {
+        95
+    } // After the agenda, before the metrics
+
+    fn is_enabled(&self, ctx: &PluginContext) -> bool {
+        let annotations = &ctx.config.annotations;
+        (annotations.summary().is_some() || !annotations.highlighted_tickets.is_empty())
+            && ctx.config.is_plugin_enabled(self.id())
+    }
+
+    fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
+        let t = ctx.translations;
+        let annotations = &ctx.config.annotations;
+
+        let summary = annotations
+            .summary()
+            .map(|s| paragraphs(s, "text-xl text-zinc-300 leading-relaxed mb-4"))
+            .unwrap_or_default();
+
+        let tickets: String = annotations
+            .highlighted_tickets
+            .iter()
+            .map(|ticket| {
+                let key = ticket.ticket_key.trim();
+                let details = match ticket_details(ctx.data, key) {
+                    Some((kind, target, status)) => format!(
+                        r#"<p class="text-xs text-zinc-500 mb-2 truncate">{} · {} · {}</p>"#,
+                        escape_html(&kind),
+                        escape_html(&target),
+                        escape_html(&status)
+                    ),
+                    None => String::new(),
+                };
+                format!(
+                    r#"<div class="bg-zinc-900/60 border border-zinc-800 border-l-4 border-l-orange-500 rounded-lg p-4">
+  <p class="font-mono font-bold text-brand-primary mb-1">{key}</p>
+  {details}
+  <p class="text-sm text-zinc-300 leading-snug">{note}</p>
+</div>"#,
+                    key = escape_html(key),
+                    details = details,
+                    note = escape_html(ticket.note.trim()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let highlights = if tickets.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"<h3 class="text-sm font-bold tracking-wider uppercase text-zinc-400 mb-3">{}</h3>
+  <div class="grid grid-cols-3 gap-4">
+    {}
+  </div>"#,
+                t.get("analyst_highlights_title"),
+                tickets
+            )
+        };
+
+        let html = format!(
+            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-14 mb-8 relative bg-[#09090b] text-white overflow-hidden">
+<div class="flex-grow h-full flex flex-col">
+  <div class="mb-4">
+    <span class="bg-brand-primary text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">{tag}</span>
+  </div>
+  <h2 class="text-4xl font-black mb-6 uppercase tracking-tight">{title}</h2>
+  <div class="flex-grow overflow-hidden">
+  {summary}
+  </div>
+  {highlights}
+</div>
+{footer}
+</div></div>"#,
+            tag = t.get("analyst_summary_tag"),
+            title = t.get("analyst_summary_title"),
+            summary = summary,
+            highlights = highlights,
+            footer = footer_dark(4, ctx),
+        );
+
+        vec![SlideOutput {
+            id: "analyst_summary".into(),
+            html,
+        }]
+    }
+}
+
+/// Plugin that renders the analyst's own recommendations
+pub struct AnalystRecommendationsSlidePlugin;
+
+impl SlidePlugin for AnalystRecommendationsSlidePlugin {
+    fn id(&self) -> &'static str {
+        "builtin.analyst_recommendations"
+    }
+    fn name(&self) -> &'static str {
+        "Analyst Recommendations"
+    }
+    fn priority(&self) -> i32 {
+        24
+    } // Right after the generated insights
+
+    fn is_enabled(&self, ctx: &PluginContext) -> bool {
+        ctx.config.annotations.recommendations().next().is_some()
+            && ctx.config.is_plugin_enabled(self.id())
+    }
+
+    fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
+        let t = ctx.translations;
+
+        let items: String = ctx
+            .config
+            .annotations
+            .recommendations()
+            .enumerate()
+            .map(|(i, recommendation)| {
+                format!(
+                    r#"<div class="flex gap-4 bg-zinc-900/50 border border-zinc-800 rounded-lg p-4">
+  <span class="text-3xl font-black text-brand-primary leading-none">{:02}</span>
+  <p class="text-base text-zinc-200 leading-snug">{}</p>
+</div>"#,
+                    i + 1,
+                    escape_html(recommendation)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let html = format!(
+            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-14 mb-8 relative bg-[#09090b] text-white overflow-hidden">
+<div class="flex-grow h-full flex flex-col">
+  <div class="mb-4">
+    <span class="bg-brand-primary text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">{tag}</span>
+  </div>
+  <h2 class="text-4xl font-black mb-6 uppercase tracking-tight">{title}</h2>
+  <div class="grid grid-cols-2 gap-4">
+    {items}
+  </div>
+</div>
+{footer}
+</div></div>"#,
+            tag = t.get("label_analyst_note"),
+            title = t.get("analyst_recommendations_title"),
+            items = items,
+            footer = footer_dark(14, ctx),
+        );
+
+        vec![SlideOutput {
+            id: "analyst_recommendations".into(),
+            html,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Translations;
+    use crate::plugins::PluginConfig;
+
+    fn context<'a>(
+        data: &'a PocReportData,
+        translations: &'a Translations,
+        config: PluginConfig,
+    ) -> PluginContext<'a> {
+        PluginContext {
+            data,
+            translations,
+            tenant_name: &data.company_name,
+            config,
+        }
+    }
+
+    fn annotations() -> ReportAnnotations {
+        ReportAnnotations {
+            executive_summary: Some("Phishing doubled.\n\nTwo <b>kits</b> reused.".into()),
+            highlighted_tickets: vec![HighlightedTicket {
+                ticket_key: "TK-1234".into(),
+                note: "Active kit".into(),
+            }],
+            recommendations: vec!["Enable DMARC".into(), "  ".into()],
+            callouts: HashMap::from([("builtin.metrics".into(), "Seasonal peak".into())]),
+        }
+    }
+
+    #[test]
+    fn test_analyst_slides() {
+        let data = PocReportData::demo();
+        let translations = Translations::load("en").unwrap();
+        let ctx = context(
+            &data,
+            &translations,
+            PluginConfig::default().with_annotations(annotations()),
+        );
+
+        assert!(AnalystSummarySlidePlugin.is_enabled(&ctx));
+        let summary = &AnalystSummarySlidePlugin.generate_slides(&ctx)[0].html;
+        assert!(summary.contains(
+            "<p class=\"text-xl text-zinc-300 leading-relaxed mb-4\">Phishing doubled.</p>"
+        ));
+        assert!(summary.contains("Two &lt;b&gt;kits&lt;/b&gt; reused."));
+        assert!(summary.contains("TK-1234"));
+        assert!(summary.contains("Active kit"));
+
+        let recommendations = &AnalystRecommendationsSlidePlugin.generate_slides(&ctx)[0].html;
+        assert!(recommendations.contains("Enable DMARC"));
+        assert!(!recommendations.contains(">02<"));
+
+        // Callouts become commentary on the plugin's slides
+        assert_eq!(
+            ctx.config.slide_overrides["builtin.metrics"]
+                .commentary
+                .as_deref(),
+            Some("Seasonal peak")
+        );
+
+        let ctx = context(&data, &translations, PluginConfig::default());
+        assert!(!AnalystSummarySlidePlugin.is_enabled(&ctx));
+        assert!(!AnalystRecommendationsSlidePlugin.is_enabled(&ctx));
+    }
+
+    #[test]
+    fn test_annotation_validation() {
+        assert!(annotations().validate().is_ok());
+        assert!(ReportAnnotations::default().is_empty());
+        assert!(!annotations().is_empty());
+
+        let mut too_many = annotations();
+        too_many.recommendations = vec!["x".into(); MAX_RECOMMENDATIONS + 1];
+        assert!(too_many.validate().is_err());
+
+        let mut missing_key = annotations();
+        missing_key.highlighted_tickets[0].ticket_key = " ".into();
+        assert!(missing_key.validate().is_err());
+
+        let mut long_note = annotations();
+        long_note
+            .callouts
+            .insert("builtin.toc".into(), "x".repeat(MAX_NOTE_CHARS + 1));
+        assert!(long_note.validate().is_err());
+    }
+}
@@ -77,7 +77,7 @@ impl SlidePlugin for ClosingSlidePlugin {
             cta_activate_desc = t.get("closing_cta_activate_desc"),
             cta_meet = t.get("closing_cta_meet"),
             cta_meet_desc = t.get("closing_cta_meet_desc"),
-            footer = footer_dark(15, &t.get("footer_text")),
+            footer = footer_dark(15, ctx),
         );
 
         // Aligning CTA colors
@@ -4,6 +4,7 @@
 //! Shows trends: threats, takedowns, exposure, and efficiency changes.
 
 use super::helpers::{footer_dark, format_number};
+use crate::i18n::Translations;
 use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
 
 /// Plugin that generates the Comparative Analysis slide
@@ -63,11 +64,18 @@ impl SlidePlugin for ComparativeSlidePlugin {
 
         let title = t.get("comparative_title");
 
+        let subtitle = match prev.map(|c| c.label(t)) {
+            Some(label) if !label.is_empty() => {
+                t.format("comparative_subtitle_period", &[("period", &label)])
+            }
+            _ => t.get("comparative_subtitle"),
+        };
+
         // Premium Header
         let header = crate::plugins::builtin::theme::section_header_premium(
-            "TENDENCIAS",
+            &t.get("comparative_tag"),
             &title,
-            Some("Evolución de indicadores clave respecto al periodo anterior."),
+            Some(&subtitle),
         );
 
         let html = format!(
@@ -83,7 +91,7 @@ impl SlidePlugin for ComparativeSlidePlugin {
     <!-- Threats Delta -->
     <div class="glass-panel p-6 hover:scale-[1.02] hover:border-orange-500/30 transition-all duration-300">
       <div class="flex items-center justify-between mb-4">
-        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">Amenazas Detectadas</h3>
+        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">{threats_label}</h3>
         {tickets_badge}
       </div>
       <div class="flex items-baseline gap-4">
@@ -91,14 +99,14 @@ impl SlidePlugin for ComparativeSlidePlugin {
         <span class="text-xl text-zinc-500">vs {prev_tickets}</span>
       </div>
       <div class="mt-4 h-2 bg-zinc-800 rounded-full overflow-hidden">
-        <div class="h-full bg-[#FF671F] rounded-full" style="width: {tickets_bar}%"></div>
+        <div class="h-full bg-brand-primary rounded-full" style="width: {tickets_bar}%"></div>
       </div>
     </div>
     
     <!-- Takedowns Delta -->
     <div class="glass-panel p-6 hover:scale-[1.02] hover:border-green-500/30 transition-all duration-300">
       <div class="flex items-center justify-between mb-4">
-        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">Takedowns Exitosos</h3>
+        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">{takedowns_label}</h3>
         {takedowns_badge}
       </div>
       <div class="flex items-baseline gap-4">
@@ -113,7 +121,7 @@ impl SlidePlugin for ComparativeSlidePlugin {
     <!-- Credentials Delta -->
     <div class="glass-panel p-6 hover:scale-[1.02] hover:border-yellow-500/30 transition-all duration-300">
       <div class="flex items-center justify-between mb-4">
-        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">Credenciales Expuestas</h3>
+        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">{credentials_label}</h3>
         {credentials_badge}
       </div>
       <div class="flex items-baseline gap-4">
@@ -128,7 +136,7 @@ impl SlidePlugin for ComparativeSlidePlugin {
     <!-- Efficiency Delta -->
     <div class="glass-panel p-6 hover:scale-[1.02] hover:border-purple-500/30 transition-all duration-300">
       <div class="flex items-center justify-between mb-4">
-        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">Horas Ahorradas</h3>
+        <h3 class="text-sm font-bold text-zinc-500 uppercase tracking-widest">{hours_label}</h3>
         {efficiency_badge}
       </div>
       <div class="flex items-baseline gap-4">
@@ -144,7 +152,7 @@ impl SlidePlugin for ComparativeSlidePlugin {
   <!-- Summary -->
   <div class="mt-6 glass-panel p-4">
     <p class="text-zinc-400 text-sm">
-      <span class="text-white font-bold">Resumen:</span> 
+      <span class="text-white font-bold">{summary_label}:</span> 
       {summary}
     </p>
   </div>
@@ -154,6 +162,11 @@ impl SlidePlugin for ComparativeSlidePlugin {
             </div></div>"#,
             bg_pattern = crate::plugins::builtin::helpers::geometric_pattern(),
             header = header,
+            threats_label = t.get("comparative_threats"),
+            takedowns_label = t.get("comparative_takedowns"),
+            credentials_label = t.get("comparative_credentials"),
+            hours_label = t.get("comparative_hours"),
+            summary_label = t.get("comparative_summary"),
             current_tickets = format_number(current_tickets),
             prev_tickets = format_number(prev_tickets),
             tickets_badge = delta_badge(&tickets_delta),
@@ -170,8 +183,8 @@ impl SlidePlugin for ComparativeSlidePlugin {
             hours_prev = hours_prev,
             efficiency_badge = delta_badge(&efficiency_delta),
             efficiency_bar = calc_bar_width(hours_current, hours_prev),
-            summary = generate_summary(&tickets_delta, &takedowns_delta, &credentials_delta),
-            footer = footer_dark(13, &t.get("footer_text")),
+            summary = generate_summary(&tickets_delta, &takedowns_delta, &credentials_delta, t),
+            footer = footer_dark(13, ctx),
         );
 
         vec![SlideOutput {
@@ -234,29 +247,32 @@ fn delta_badge_inverted(delta: &Delta) -> String {
     )
 }
 
-fn generate_summary(tickets: &Delta, takedowns: &Delta, credentials: &Delta) -> String {
+fn generate_summary(
+    tickets: &Delta,
+    takedowns: &Delta,
+    credentials: &Delta,
+    t: &Translations,
+) -> String {
+    let pct = |delta: &Delta| format!("{:.0}", delta.percent);
     let mut parts = Vec::new();
 
     if tickets.is_increase {
-        parts.push(format!(
-            "Las amenazas aumentaron {:.0}%, indicando mayor actividad de actores maliciosos",
-            tickets.percent
-        ));
+        parts.push(t.format("comparative_threats_up", &[("pct", &pct(tickets))]));
     } else {
-        parts.push(format!("Las amenazas disminuyeron {:.0}%", tickets.percent));
+        parts.push(t.format("comparative_threats_down", &[("pct", &pct(tickets))]));
     }
 
     if takedowns.is_increase {
-        parts.push(format!("Los takedowns mejoraron {:.0}%", takedowns.percent));
+        parts.push(t.format("comparative_takedowns_up", &[("pct", &pct(takedowns))]));
     }
 
     if !credentials.is_increase && credentials.percent > 10.0 {
-        parts.push(format!(
-            "La exposición de credenciales se redujo {:.0}%",
-            credentials.percent
+        parts.push(t.format(
+            "comparative_credentials_down",
+            &[("pct", &pct(credentials))],
         ));
     } else if credentials.is_increase && credentials.percent > 10.0 {
-        parts.push("se recomienda revisar fuentes de filtración".to_string());
+        parts.push(t.get("comparative_credentials_up"));
     }
 
     parts.join(". ") + "."
@@ -6,7 +6,6 @@
 //! Persuasion: Authority (brand power) + Unity (shared identity with client)
 //! Design: Full-bleed dark, split layout, prominent brand + client identity
 
-use super::theme;
 use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
 
 /// Base64 encoded cover image (person with orange smoke)
@@ -32,7 +31,7 @@ impl SlidePlugin for CoverSlidePlugin {
 
         // UNITY: Show the client prominently — they are the hero
         let partner_html = data.partner_name.as_ref().map(|p| format!(
-            r##"<div class="mt-8 border-l-2 border-[#FF671F] pl-4"><p class="text-[#FF671F] font-semibold uppercase tracking-widest text-xs mb-1">{}</p><p class="text-2xl font-bold text-white">{}</p></div>"##,
+            r##"<div class="mt-8 border-l-2 border-brand-primary pl-4"><p class="text-brand-primary font-semibold uppercase tracking-widest text-xs mb-1">{}</p><p class="text-2xl font-bold text-white">{}</p></div>"##,
             t.get("label_partner"), p
         )).unwrap_or_default();
 
@@ -42,6 +41,13 @@ impl SlidePlugin for CoverSlidePlugin {
             t.get("cover_title_static")
         };
 
+        // Theme cover image, else the embedded Axur one
+        let cover_image = ctx
+            .theme()
+            .cover_image
+            .clone()
+            .unwrap_or_else(|| format!("data:image/png;base64,{}", COVER_IMAGE_BASE64.trim()));
+
         // Format dates for "Analysis Period"
         // start_date and end_date are already strings (YYYY-MM-DD)
         let date_range = format!("{} — {}", data.start_date, data.end_date);
@@ -52,7 +58,7 @@ impl SlidePlugin for CoverSlidePlugin {
                 <!-- BACKGROUND: Base64 Image + Animated Overlay -->
                 <!-- Image with slow zoom effect -->
                 <div class="absolute inset-0 z-0 overflow-hidden">
-                    <img src="data:image/png;base64,{image}" alt="Cover" class="absolute inset-0 w-full h-full object-cover object-center scale-110" style="filter: contrast(1.2) brightness(0.6); animation: scale-in 20s ease-out forwards;"/>
+                    <img src="{image}" alt="Cover" class="absolute inset-0 w-full h-full object-cover object-center scale-110" style="filter: contrast(1.2) brightness(0.6); animation: scale-in 20s ease-out forwards;"/>
                     
                     <!-- Animated Gradient Overlay -->
                     <div class="absolute inset-0 bg-gradient-to-r from-black via-black/60 to-transparent z-10"></div>
@@ -69,7 +75,7 @@ impl SlidePlugin for CoverSlidePlugin {
                     <div class="flex items-start justify-between">
                          <!-- TLP Badge (AUTHORITY: Pulse animation) -->
                         <div>
-                            <div class="inline-flex items-center gap-3 px-5 py-2.5 bg-[#FF671F] text-white rounded-r-full shadow-[0_0_20px_rgba(255,103,31,0.4)] animate-[pulse-orange_3s_ease-in-out_infinite]">
+                            <div class="inline-flex items-center gap-3 px-5 py-2.5 bg-brand-primary text-white rounded-r-full shadow-[0_0_20px_rgba(var(--color-primary-rgb),0.4)] animate-[pulse-orange_3s_ease-in-out_infinite]">
                                 <span class="w-2 h-2 rounded-full bg-white animate-pulse"></span>
                                 <span class="font-bold text-lg tracking-widest">{tlp_lbl}{tlp}</span>
                             </div>
@@ -100,8 +106,8 @@ impl SlidePlugin for CoverSlidePlugin {
                     <!-- BOTTOM: Client + Partner -->
                     <div class="mt-auto">
                         <div>
-                            <p class="text-[#FF671F] font-semibold uppercase tracking-widest text-xs mb-2 flex items-center gap-2">
-                                <span class="w-8 h-[1px] bg-[#FF671F]"></span>
+                            <p class="text-brand-primary font-semibold uppercase tracking-widest text-xs mb-2 flex items-center gap-2">
+                                <span class="w-8 h-[1px] bg-brand-primary"></span>
                                 {company_lbl}
                             </p>
                             <p class="text-4xl font-bold display-text text-white tracking-wide">{company}</p>
@@ -115,7 +121,7 @@ impl SlidePlugin for CoverSlidePlugin {
                      <!-- Tech decorative elements -->
                      <svg class="absolute top-10 right-10 w-32 h-32 opacity-20 animate-[spin_20s_linear_infinite]" viewBox="0 0 100 100">
                         <circle cx="50" cy="50" r="48" stroke="white" stroke-width="1" fill="none" stroke-dasharray="10 5"/>
-                        <circle cx="50" cy="50" r="30" stroke="#FF671F" stroke-width="1" fill="none"/>
+                        <circle cx="50" cy="50" r="30" stroke="var(--color-primary)" stroke-width="1" fill="none"/>
                      </svg>
                 </div>
                 
@@ -126,21 +132,12 @@ impl SlidePlugin for CoverSlidePlugin {
             company_lbl = t.get("label_company"),
             company = data.company_name,
             partner = partner_html,
-            logo = theme::axur_logo_styled("lg"),
-            image = COVER_IMAGE_BASE64.trim(),
+            logo = ctx.brand_logo("lg"),
+            image = cover_image,
             title = title,
             dates = date_range,
         );
 
-        // Define semantic replacements for hardcoded hex values
-        // Note: Using replace() here as a quick refactor strategy, though proper templating would be better long-term.
-        // This ensures all instances of #FF671F (and variations) map to our new CSS variables/classes.
-        let html = html
-            .replace("#FF671F", "var(--color-primary)")
-            .replace("bg-[#FF671F]", "bg-brand-primary")
-            .replace("text-[#FF671F]", "text-brand-primary")
-            .replace("border-[#FF671F]", "border-brand-primary");
-
         vec![SlideOutput {
             id: "cover".into(),
             html,
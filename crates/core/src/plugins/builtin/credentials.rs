@@ -29,17 +29,9 @@ impl SlidePlugin for CredentialsSlidePlugin {
         let total = data.credential_exposures.len();
         let critical = data.critical_credentials.len();
 
-        // Show top 5 exposures (masked)
+        // Show top 5 exposures (already masked by the redaction policy)
         let examples_html: String = data.credential_exposures.iter().take(5).map(|cred| {
-            let user = cred.user.as_deref().unwrap_or("unknown");
-            let chars: Vec<char> = user.chars().collect();
-            let masked_user = if chars.len() > 6 {
-                let first: String = chars[..3].iter().collect();
-                let last: String = chars[chars.len()-2..].iter().collect();
-                format!("{}...{}", first, last)
-            } else {
-                user.to_string()
-            };
+            let masked_user = cred.user.as_deref().unwrap_or("unknown");
             let source = cred.leak_name.as_deref().unwrap_or("unknown");
             format!(
                 r#"<div class="flex items-center justify-between p-4 glass-panel hover:border-red-500/30 hover:scale-[1.01] transition-all duration-300 mb-3">
@@ -126,7 +118,7 @@ impl SlidePlugin for CredentialsSlidePlugin {
                 Some("Malware Stealer")
             ),
             examples = examples_html,
-            footer = footer_dark(15, &t.get("footer_text")),
+            footer = footer_dark(15, ctx),
         );
 
         vec![SlideOutput {
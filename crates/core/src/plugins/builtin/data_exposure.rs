@@ -171,7 +171,7 @@ impl SlidePlugin for DataExposureSlidePlugin {
                 &t.get("stealer_box_high_risk"),
                 Some("Usuarios VIP/Admin")
             ),
-            footer = footer_dark(8, &t.get("footer_text")),
+            footer = footer_dark(8, ctx),
         );
 
         vec![SlideOutput {
@@ -0,0 +1,170 @@
+//! Enrichment Data Plugins
+//!
+//! Derive insights from fetched report data before the slides render:
+//! deep analytics, risk score and operational (ROI) metrics. They only run
+//! when the data carries `enrichment_inputs` (i.e. it came from the fetcher);
+//! demo data and older snapshots are already final.
+
+use crate::api::report::{compute_report_deep_analytics, OperationalMetrics, PocReportData};
+use crate::api::risk::RiskModel;
+use crate::plugins::{DataPlugin, PluginConfig};
+
+/// Derives `deep_analytics` from code leaks, credentials and takedowns
+pub struct DeepAnalyticsDataPlugin;
+
+impl DataPlugin for DeepAnalyticsDataPlugin {
+    fn id(&self) -> &'static str {
+        "builtin.data.deep_analytics"
+    }
+
+    fn transform(&self, data: &mut PocReportData) {
+        if let Some(inputs) = &data.enrichment_inputs {
+            data.deep_analytics = compute_report_deep_analytics(data, inputs);
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        30
+    }
+}
+
+/// Derives `risk_score` (Risk Score V3) and its history from the risk
+/// metric inputs, with the configured `RiskModel`
+pub struct RiskScoreDataPlugin;
+
+impl RiskScoreDataPlugin {
+    fn apply(model: &RiskModel, data: &mut PocReportData) {
+        if let Some(inputs) = &data.enrichment_inputs {
+            data.risk_score =
+                model.score_with_history(&inputs.risk, &data.end_date, &inputs.risk_history);
+        }
+    }
+}
+
+impl DataPlugin for RiskScoreDataPlugin {
+    fn id(&self) -> &'static str {
+        "builtin.data.risk_score"
+    }
+
+    fn transform(&self, data: &mut PocReportData) {
+        Self::apply(&RiskModel::default(), data);
+    }
+
+    fn transform_with_config(&self, data: &mut PocReportData, config: &PluginConfig) {
+        match &config.risk_model {
+            Some(model) => Self::apply(model, data),
+            None => self.transform(data),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        20
+    }
+}
+
+/// Derives `roi_metrics` from the final counts, so it runs last
+pub struct OperationalMetricsDataPlugin;
+
+impl DataPlugin for OperationalMetricsDataPlugin {
+    fn id(&self) -> &'static str {
+        "builtin.data.operational_metrics"
+    }
+
+    fn transform(&self, data: &mut PocReportData) {
+        if data.enrichment_inputs.is_some() {
+            data.roi_metrics = OperationalMetrics::compute(data);
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::report::{EnrichmentInputs, NameValuePair, RiskInputs, RiskPeriodInputs};
+
+    fn fetched_data() -> PocReportData {
+        let mut data = PocReportData::demo();
+        data.roi_metrics = OperationalMetrics::default();
+        data.enrichment_inputs = Some(EnrichmentInputs {
+            top_repositories: vec![NameValuePair {
+                name: "acme/api".to_string(),
+                value: 12,
+            }],
+            risk: RiskInputs {
+                incidents_by_ticket_type: vec![NameValuePair {
+                    name: "phishing".to_string(),
+                    value: 40,
+                }],
+                critical_leaks: Some(25),
+                takedown_median_uptime_ms: Some(2.0 * 3600.0 * 1000.0),
+                takedown_success_rate: Some(90.0),
+                ..Default::default()
+            },
+            risk_history: vec![RiskPeriodInputs {
+                start_date: "2024-12-01".to_string(),
+                end_date: "2024-12-31".to_string(),
+                inputs: RiskInputs::default(),
+            }],
+        });
+        data
+    }
+
+    #[test]
+    fn test_final_data_is_left_untouched() {
+        let mut data = PocReportData::demo();
+        let before = data.risk_score.current;
+        let hours = data.roi_metrics.hours_saved_total;
+
+        RiskScoreDataPlugin.transform(&mut data);
+        OperationalMetricsDataPlugin.transform(&mut data);
+
+        assert_eq!(data.risk_score.current, before);
+        assert_eq!(data.roi_metrics.hours_saved_total, hours);
+    }
+
+    #[test]
+    fn test_enrichment_from_inputs() {
+        let mut data = fetched_data();
+
+        DeepAnalyticsDataPlugin.transform(&mut data);
+        RiskScoreDataPlugin.transform(&mut data);
+        OperationalMetricsDataPlugin.transform(&mut data);
+
+        assert_eq!(data.deep_analytics.avg_takedown_time_hours, Some(2.0));
+        assert!(data.deep_analytics.has_takedown_insights);
+        assert!(data.risk_score.current > 0.0);
+        assert_eq!(data.risk_score.factors.len(), 5);
+        assert_eq!(data.risk_score.history.len(), 2);
+        assert_eq!(data.risk_score.history[1], data.risk_score.current);
+        assert_eq!(
+            data.roi_metrics.hours_saved_total,
+            OperationalMetrics::compute(&data).hours_saved_total
+        );
+        assert!(data.roi_metrics.hours_saved_total > 0.0);
+    }
+
+    #[test]
+    fn test_configured_risk_model() {
+        let mut data = fetched_data();
+        let default_score = {
+            let mut data = data.clone();
+            RiskScoreDataPlugin.transform(&mut data);
+            data.risk_score.current
+        };
+
+        let config = PluginConfig {
+            risk_model: Some(RiskModel {
+                critical_leaks_cap: 25.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        RiskScoreDataPlugin.transform_with_config(&mut data, &config);
+
+        assert!(data.risk_score.current > default_score);
+    }
+}
@@ -35,7 +35,7 @@ impl SlidePlugin for TakedownExamplesSlidePlugin {
             };
 
             format!(
-                r#"<div class="bg-zinc-900 rounded-lg border border-zinc-800 p-5 flex flex-col gap-3 hover:border-[#FF671F]/40 transition-colors">
+                r#"<div class="bg-zinc-900 rounded-lg border border-zinc-800 p-5 flex flex-col gap-3 hover:border-orange-500/40 transition-colors">
                     <div class="flex items-start justify-between">
                         <span class="text-xs font-bold px-2 py-1 rounded bg-zinc-800 text-zinc-400 uppercase tracking-wider">{type_}</span>
                         <span class="text-xs font-bold px-2 py-1 rounded border {status_class}">{status}</span>
@@ -57,10 +57,10 @@ impl SlidePlugin for TakedownExamplesSlidePlugin {
         }).collect();
 
         let html = format!(
-            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-zinc-950 text-white"><div class="absolute inset-0 opacity-10" style="background-image: radial-gradient(circle at 80% 80%, #FF671F 0%, transparent 40%);"></div><div class="flex-grow h-full overflow-hidden"><div class="h-full flex flex-col"><div class="mb-4"><span class="bg-[#FF671F] text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">RESULTADOS</span></div><h2 class="text-4xl font-black mb-8 uppercase tracking-tight">{title}</h2><div class="grid grid-cols-2 gap-6 flex-grow">{examples}</div></div></div>{footer}</div></div>"#,
+            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-zinc-950 text-white"><div class="absolute inset-0 opacity-10" style="background-image: radial-gradient(circle at 80% 80%, var(--color-primary) 0%, transparent 40%);"></div><div class="flex-grow h-full overflow-hidden"><div class="h-full flex flex-col"><div class="mb-4"><span class="bg-brand-primary text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">RESULTADOS</span></div><h2 class="text-4xl font-black mb-8 uppercase tracking-tight">{title}</h2><div class="grid grid-cols-2 gap-6 flex-grow">{examples}</div></div></div>{footer}</div></div>"#,
             title = t.get("examples_takedowns_title"),
             examples = examples_html,
-            footer = footer_dark(13, &t.get("footer_text")),
+            footer = footer_dark(13, ctx),
         );
 
         vec![SlideOutput {
@@ -183,7 +183,7 @@ impl SlidePlugin for PocExamplesSlidePlugin {
 
         let html = format!(
             r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-zinc-950 text-white overflow-hidden">
-<div class="absolute inset-0 opacity-20" style="background-image: radial-gradient(circle at 80% 20%, #FF671F 0%, transparent 30%);"></div>
+<div class="absolute inset-0 opacity-20" style="background-image: radial-gradient(circle at 80% 20%, var(--color-primary) 0%, transparent 30%);"></div>
 <div class="relative flex-grow h-full overflow-hidden z-10"><div class="h-full flex flex-col">
 <div class="flex items-start justify-between mb-3">
     <div>
@@ -230,7 +230,7 @@ impl SlidePlugin for PocExamplesSlidePlugin {
             stats_det = t.get("evidence_stats_detection"),
             examples = examples_html,
             no_data = no_data_html,
-            footer = footer_dark(14, &t.get("footer_text")),
+            footer = footer_dark(14, ctx),
         );
 
         vec![SlideOutput {
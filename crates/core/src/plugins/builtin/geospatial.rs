@@ -292,7 +292,7 @@ impl SlidePlugin for GeospatialSlidePlugin {
             ip_count = ip_count,
             host_count = host_count,
             country_badges = country_badges_html,
-            footer = footer_dark(14, &t.get("footer_text")),
+            footer = footer_dark(14, ctx),
         );
 
         vec![SlideOutput {
@@ -2,6 +2,10 @@
 //!
 //! Displays a 24x7 grid showing attack patterns by hour and day of week.
 //! Helps identify when attacks are most frequent.
+//!
+//! The grid comes from real ticket detection timestamps bucketed in the
+//! tenant's time zone (`PocReportData::activity_heatmap`). The slide is
+//! skipped when there are too few timestamps to show a real pattern.
 
 use super::helpers::footer_dark;
 use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
@@ -23,17 +27,16 @@ impl SlidePlugin for HeatmapSlidePlugin {
     }
 
     fn is_enabled(&self, ctx: &PluginContext) -> bool {
-        // Only show if we have enough data points
-        ctx.data.total_tickets >= 10 && ctx.config.is_plugin_enabled(self.id())
+        // Only show if we have enough real data points
+        ctx.data.activity_heatmap.has_enough_data() && ctx.config.is_plugin_enabled(self.id())
     }
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let data = ctx.data;
         let t = ctx.translations;
 
-        // Generate heatmap data from available timestamps
-        // For now, use simulated data based on total_tickets distribution
-        let heatmap = generate_simulated_heatmap(data.total_tickets);
+        let activity = &data.activity_heatmap;
+        let heatmap = &activity.grid;
 
         // Find max value for color scaling
         let max_val = heatmap.iter().flatten().copied().max().unwrap_or(1);
@@ -95,11 +98,29 @@ impl SlidePlugin for HeatmapSlidePlugin {
         }
 
         // Peak hours analysis
-        let (peak_day, peak_hour, peak_count) = find_peak(&heatmap);
-        let peak_text = format!(
-            "{} a las {}:00 ({} eventos)",
-            days[peak_day], peak_hour, peak_count
-        );
+        let peak_text = match activity.peak() {
+            Some((peak_day, peak_hour, peak_count)) => format!(
+                "{} a las {}:00 ({} eventos)",
+                days[peak_day], peak_hour, peak_count
+            ),
+            None => "Sin datos".to_string(),
+        };
+
+        // Time zone and sample note
+        let sample_text = if activity.sample_size < activity.total_in_period {
+            format!(
+                "Horario {} · muestra de {} de {} tickets",
+                activity.utc_label(),
+                activity.sample_size,
+                activity.total_in_period
+            )
+        } else {
+            format!(
+                "Horario {} · {} tickets",
+                activity.utc_label(),
+                activity.sample_size
+            )
+        };
 
         // Premium Header
         let header = crate::plugins::builtin::theme::section_header_premium(
@@ -153,6 +174,7 @@ impl SlidePlugin for HeatmapSlidePlugin {
             <div class="w-4 h-4 rounded-sm" style="background: rgba(255, 75, 0, 1)"></div>
           </div>
           <span class="text-xs text-zinc-500">Más</span>
+          <span class="text-xs text-zinc-600 ml-4">{sample_text}</span>
         </div>
         
         <!-- Peak Info -->
@@ -175,7 +197,8 @@ impl SlidePlugin for HeatmapSlidePlugin {
             day_labels = day_labels,
             cells = cells_html,
             peak_text = peak_text,
-            footer = footer_dark(14, &t.get("footer_text")),
+            sample_text = sample_text,
+            footer = footer_dark(14, ctx),
         );
 
         let html = html.replace("ring-orange-500", "ring-brand-primary");
@@ -187,89 +210,97 @@ impl SlidePlugin for HeatmapSlidePlugin {
     }
 }
 
-/// Generate simulated heatmap data based on total ticket count
-/// Returns a 7x24 matrix (days x hours)
-fn generate_simulated_heatmap(total_tickets: u64) -> [[u32; 24]; 7] {
-    let mut heatmap = [[0u32; 24]; 7];
-
-    // Distribute tickets with realistic patterns:
-    // - More activity on weekdays (Mon-Fri)
-    // - Peak hours during business hours (9-18)
-    // - Some activity at night (attackers in different timezones)
-
-    let base = (total_tickets / 168) as u32; // Average per hour
-
-    for (day, day_row) in heatmap.iter_mut().enumerate() {
-        let day_multiplier = if day < 5 { 1.2 } else { 0.6 }; // Weekdays vs weekends
-
-        for (hour, cell) in day_row.iter_mut().enumerate() {
-            let hour_multiplier = match hour {
-                0..=5 => 0.3,   // Night (low)
-                6..=8 => 0.8,   // Early morning
-                9..=12 => 1.5,  // Morning peak
-                13..=14 => 1.0, // Lunch dip
-                15..=18 => 1.4, // Afternoon peak
-                19..=21 => 0.9, // Evening
-                _ => 0.5,       // Late night
-            };
-
-            // Add some randomness using a simple hash
-            let noise = ((day * 24 + hour) % 5) as f64 * 0.1;
-            let value = (base as f64 * day_multiplier * hour_multiplier * (1.0 + noise)) as u32;
-
-            *cell = value.max(if total_tickets > 100 { 1 } else { 0 });
-        }
-    }
-
-    heatmap
-}
-
-/// Convert intensity (0-100) to Axur orange gradient color
+/// Convert intensity (0-100) to a primary color gradient
 fn intensity_to_color(intensity: u32) -> String {
     let alpha = intensity as f64 / 100.0;
     if alpha < 0.05 {
         "rgba(39, 39, 42, 0.5)".to_string() // zinc-800 for zero/near-zero
     } else {
-        // Use brand primary RGB (255, 103, 31)
-        format!("rgba(255, 103, 31, {:.2})", alpha.min(1.0).max(0.1))
+        // Theme primary color (Axur orange by default)
+        format!(
+            "rgba(var(--color-primary-rgb), {:.2})",
+            alpha.clamp(0.1, 1.0)
+        )
     }
 }
 
-/// Find the peak hour in the heatmap
-fn find_peak(heatmap: &[[u32; 24]; 7]) -> (usize, usize, u32) {
-    let mut max_day = 0;
-    let mut max_hour = 0;
-    let mut max_count = 0;
-
-    for (day, day_data) in heatmap.iter().enumerate() {
-        for (hour, &count) in day_data.iter().enumerate() {
-            if count > max_count {
-                max_count = count;
-                max_day = day;
-                max_hour = hour;
-            }
-        }
-    }
-
-    (max_day, max_hour, max_count)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::report::{parse_utc_offset, ActivityHeatmap, PocReportData};
+    use crate::i18n::Translations;
+    use crate::plugins::PluginConfig;
+
+    #[test]
+    fn test_heatmap_buckets_in_tenant_timezone() {
+        // 2024-01-01 was a Monday; 02:30 UTC is Sunday 23:30 in UTC-03:00
+        let heatmap = ActivityHeatmap::from_timestamps(
+            ["2024-01-01T02:30:00Z", "2024-01-01T15:00:00Z", "not-a-date"],
+            "America/Sao_Paulo",
+        );
+        assert_eq!(heatmap.sample_size, 2);
+        assert_eq!(heatmap.utc_offset_minutes, -180);
+        assert_eq!(heatmap.grid[6][23], 1);
+        assert_eq!(heatmap.grid[0][12], 1);
+        assert_eq!(heatmap.utc_label(), "UTC-03:00");
+        assert!(!heatmap.has_enough_data());
+    }
+
+    #[test]
+    fn test_heatmap_applies_daylight_saving() {
+        // New York is UTC-05:00 in January and UTC-04:00 (EDT) in July
+        let heatmap = ActivityHeatmap::from_timestamps(
+            ["2024-01-02T15:00:00Z", "2024-07-01T15:00:00Z"],
+            "America/New_York",
+        );
+        assert_eq!(heatmap.grid[1][10], 1);
+        assert_eq!(heatmap.grid[0][11], 1);
+        assert_eq!(heatmap.utc_offset_minutes, -240);
+
+        // Unknown zones are bucketed in UTC
+        let heatmap = ActivityHeatmap::from_timestamps(["2024-01-02T15:00:00Z"], "Mars/Olympus");
+        assert_eq!(heatmap.timezone, "UTC");
+        assert_eq!(heatmap.grid[1][15], 1);
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(
+            parse_utc_offset("-03:00").unwrap().local_minus_utc(),
+            -3 * 3600
+        );
+        assert_eq!(
+            parse_utc_offset("UTC+5:30").unwrap().local_minus_utc(),
+            19800
+        );
+        assert_eq!(
+            parse_utc_offset("GMT-5").unwrap().local_minus_utc(),
+            -5 * 3600
+        );
+        assert_eq!(parse_utc_offset("Z").unwrap().local_minus_utc(), 0);
+        assert!(parse_utc_offset("Europe/Madrid").is_none());
+        assert!(parse_utc_offset("+25:00").is_none());
+    }
 
     #[test]
-    fn test_generate_heatmap() {
-        let heatmap = generate_simulated_heatmap(1000);
-        let total: u32 = heatmap.iter().flatten().sum();
-        assert!(total > 0);
+    fn test_slide_hidden_without_real_data() {
+        let mut data = PocReportData::demo();
+        data.activity_heatmap = ActivityHeatmap::default();
+        let translations = Translations::load("es").unwrap();
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: "Acme",
+            config: PluginConfig::default(),
+        };
+        assert!(!HeatmapSlidePlugin.is_enabled(&ctx));
     }
 
     #[test]
     fn test_intensity_to_color() {
         assert!(intensity_to_color(0).contains("39, 39, 42"));
-        assert!(intensity_to_color(50).contains("255, 75, 0"));
-        assert!(intensity_to_color(100).contains("255, 75, 0"));
+        assert!(intensity_to_color(50).contains("var(--color-primary-rgb), 0.50"));
+        assert!(intensity_to_color(100).contains("var(--color-primary-rgb), 1.00"));
     }
 
     #[test]
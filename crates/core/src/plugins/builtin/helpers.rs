@@ -1,5 +1,8 @@
 //! Shared helpers for builtin plugins
 
+use crate::plugins::declarative::escape_html;
+use crate::plugins::PluginContext;
+
 /// Formats a number with thousand separators
 pub fn format_number(n: u64) -> String {
     let s = n.to_string();
@@ -14,22 +17,35 @@ pub fn format_number(n: u64) -> String {
     result.chars().rev().collect()
 }
 
-/// Dark footer (for dark backgrounds)
-pub fn footer_dark(page: usize, footer_text: &str) -> String {
+/// Dark footer (for dark backgrounds) with the report brand and footer text
+pub fn footer_dark(page: usize, ctx: &PluginContext) -> String {
+    let brand = if ctx.config.custom_branding {
+        let tagline = ctx.theme().tagline.as_ref().map(|t| {
+            format!(
+                r#"<span class="mx-3 text-zinc-700">|</span><span class="uppercase tracking-widest text-[10px]">{}</span>"#,
+                escape_html(t)
+            )
+        });
+        format!("{}{}", ctx.brand_logo("sm"), tagline.unwrap_or_default())
+    } else {
+        r##"<span class="text-brand-primary text-2xl -mr-1">///</span>
+                <span class="text-xl text-white">AXUR</span>
+                <span class="mx-3 text-zinc-700">|</span>
+                <span class="uppercase tracking-widest text-[10px]">Digital Experiences Made Safe</span>"##
+            .to_string()
+    };
     format!(
         r##"<footer class="absolute bottom-12 left-12 right-12 flex justify-between items-center text-[10px] text-zinc-600 font-mono tracking-widest uppercase opacity-60">
             <div class="footer-brand flex items-center font-black tracking-wider select-none h-5">
-                <span class="text-[#FF671F] text-2xl -mr-1">///</span>
-                <span class="text-xl text-white">AXUR</span>
-                <span class="mx-3 text-zinc-700">|</span>
-                <span class="uppercase tracking-widest text-[10px]">Digital Experiences Made Safe</span>
+                {brand}
             </div>
             <div class="flex items-center gap-6">
                 <span class="uppercase tracking-widest">{text}</span>
                 <span class="font-mono text-zinc-600">0{page}</span>
             </div>
         </footer>"##,
-        text = footer_text,
+        brand = brand,
+        text = ctx.footer_text(),
         page = page
     )
 }
@@ -39,7 +55,7 @@ pub fn footer_light(page: u32, footer_text: &str) -> String {
     format!(
         r##"<footer class="footer-premium absolute bottom-8 left-14 right-14 flex justify-between items-center text-zinc-500 text-xs tracking-wide border-t border-zinc-200 pt-3">
             <div class="footer-brand flex items-center font-black tracking-wider select-none h-5">
-                <span class="text-[#FF671F] text-2xl -mr-1">///</span>
+                <span class="text-brand-primary text-2xl -mr-1">///</span>
                 <span class="text-xl text-zinc-900">AXUR</span>
                 <span class="mx-3 text-zinc-300">|</span>
                 <span class="uppercase tracking-widest text-[10px] text-zinc-400">Digital Experiences Made Safe</span>
@@ -56,7 +72,7 @@ pub fn footer_light(page: u32, footer_text: &str) -> String {
 
 /// Axur logo SVG
 pub fn axur_logo() -> &'static str {
-    r##"<div class="flex items-center font-black tracking-wider select-none text-white h-5"><span class="text-[#FF671F] text-2xl -mr-1">///</span><span class="text-xl">AXUR</span></div>"##
+    r##"<div class="flex items-center font-black tracking-wider select-none text-white h-5"><span class="text-brand-primary text-2xl -mr-1">///</span><span class="text-xl">AXUR</span></div>"##
 }
 
 /// Geometric pattern: Tech grid with subtle glow nodes
@@ -80,15 +96,15 @@ pub fn geometric_pattern() -> &'static str {
             <rect width="100%" height="100%" fill="url(#techGrid)"/>
         </svg>
         
-        <div class="absolute top-1/4 right-1/4 w-2 h-2 bg-orange-500 rounded-full" style="box-shadow: 0 0 20px rgba(255,103,31,0.8), 0 0 40px rgba(255,103,31,0.4)"></div>
-        <div class="absolute top-2/3 right-1/3 w-1.5 h-1.5 bg-orange-400 rounded-full" style="box-shadow: 0 0 15px rgba(255,103,31,0.6)"></div>
+        <div class="absolute top-1/4 right-1/4 w-2 h-2 bg-orange-500 rounded-full" style="box-shadow: 0 0 20px rgba(var(--color-primary-rgb),0.8), 0 0 40px rgba(var(--color-primary-rgb),0.4)"></div>
+        <div class="absolute top-2/3 right-1/3 w-1.5 h-1.5 bg-orange-400 rounded-full" style="box-shadow: 0 0 15px rgba(var(--color-primary-rgb),0.6)"></div>
         
         <svg class="absolute inset-0 w-full h-full opacity-20" viewBox="0 0 100 100" preserveAspectRatio="none">
             <line x1="60" y1="0" x2="100" y2="100" stroke="url(#slashGrad)" stroke-width="1"/>
             <defs>
                 <linearGradient id="slashGrad" x1="0" y1="0" x2="1" y2="1">
                     <stop offset="0%" stop-color="transparent"/>
-                    <stop offset="50%" stop-color="rgba(255,103,31,0.3)"/>
+                    <stop offset="50%" stop-color="rgba(var(--color-primary-rgb),0.3)"/>
                     <stop offset="100%" stop-color="transparent"/>
                 </linearGradient>
             </defs>
@@ -109,7 +125,7 @@ pub fn cover_pattern() -> &'static str {
         <!-- Triple slash signature motif -->
         <svg class="absolute right-8 top-1/2 -translate-y-1/2 h-3/4" viewBox="0 0 120 200" fill="none">
             <!-- Main orange slash -->
-            <path d="M80 0 L120 0 L40 200 L0 200 Z" fill="rgba(255,103,31,0.9)"/>
+            <path d="M80 0 L120 0 L40 200 L0 200 Z" fill="rgba(var(--color-primary-rgb),0.9)"/>
             <!-- White slash -->
             <path d="M55 0 L85 0 L5 200 L-25 200 Z" fill="rgba(255,255,255,0.85)"/>
             <!-- Gray slash behind -->
@@ -120,7 +136,7 @@ pub fn cover_pattern() -> &'static str {
         <svg class="absolute inset-0 w-full h-full opacity-10">
             <defs>
                 <pattern id="coverGrid" width="60" height="60" patternUnits="userSpaceOnUse">
-                    <circle cx="30" cy="30" r="1" fill="rgba(255,103,31,0.5)"/>
+                    <circle cx="30" cy="30" r="1" fill="rgba(var(--color-primary-rgb),0.5)"/>
                 </pattern>
             </defs>
             <rect width="100%" height="100%" fill="url(#coverGrid)"/>
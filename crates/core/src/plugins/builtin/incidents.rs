@@ -178,7 +178,7 @@ impl SlidePlugin for IncidentsSlidePlugin {
             card_detections = card_detections,
             card_incidents = card_incidents,
             card_resolved = card_resolved,
-            footer = footer_dark(10, &t.get("footer_text")),
+            footer = footer_dark(10, ctx),
         );
 
         vec![SlideOutput {
@@ -1,9 +1,14 @@
 //! Insights Slide Plugin
 //!
-//! Generates automated insights and recommendations based on report data.
-//! Analyzes patterns and suggests actionable next steps.
+//! Renders the insights and recommendations produced by the insight rules
+//! engine (`api::insights`) with the configured rule pack. The ids of the
+//! rules that fired are kept on the slide (`data-insight-rules`) for audit.
 
-use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
+use crate::api::insights::{FiredInsight, InsightRulePack, InsightSeverity};
+use crate::api::report::PocReportData;
+use crate::i18n::Translations;
+use crate::plugins::declarative::{escape_html, fill_template};
+use crate::plugins::{PluginConfig, PluginContext, SlideOutput, SlidePlugin};
 
 /// Plugin that generates the Insights & Recommendations slide
 pub struct InsightsSlidePlugin;
@@ -26,28 +31,35 @@ impl SlidePlugin for InsightsSlidePlugin {
     }
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
-        let data = ctx.data;
         let t = ctx.translations;
+        let (fired, max_insights) = evaluate_insights(ctx.data, &ctx.config);
+        let shown = &fired[..fired.len().min(max_insights)];
+        let mut metrics = crate::api::insights::report_metrics(ctx.data);
+        if let (Some(cmp), Some(label)) = (
+            &ctx.data.comparison,
+            metrics.pointer_mut("/comparison/period_label"),
+        ) {
+            *label = cmp.label(t).into();
+        }
 
-        // Generate insights based on data patterns
-        let insights = generate_insights(data);
-
-        let title = t.get("insights_title");
-
-        // Build insight cards HTML
-        let insight_cards: String = insights
+        let insight_cards: String = shown
             .iter()
-            .map(render_insight_card)
+            .map(|insight| render_insight_card(insight, &metrics, t))
             .collect::<Vec<_>>()
             .join("\n");
+        let rule_ids = fired
+            .iter()
+            .map(|f| f.rule_id.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
 
         let html = format!(
-            r##"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-black text-white">
+            r##"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-black text-white" data-insight-rules="{rule_ids}">
 <div class="flex-grow h-full overflow-hidden">
 <div class="h-full flex flex-col">
   <!-- Header -->
   <div class="mb-4">
-    <span class="bg-[#FF671F] text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">RECOMENDACIONES</span>
+    <span class="bg-brand-primary text-white px-4 py-2 text-sm font-bold tracking-wider uppercase">{tag}</span>
   </div>
   <h2 class="text-4xl font-black mb-6 uppercase tracking-tight">{title}</h2>
   
@@ -59,7 +71,7 @@ impl SlidePlugin for InsightsSlidePlugin {
   <!-- Summary Footer -->
   <div class="mt-4 bg-zinc-900/30 p-4 rounded-lg border border-zinc-800">
     <p class="text-zinc-400 text-sm">
-      <span class="text-[#FF671F] font-bold">💡 Próximos Pasos:</span> 
+      <span class="text-brand-primary font-bold">💡 {next_steps}:</span> 
       {summary}
     </p>
   </div>
@@ -67,14 +79,13 @@ impl SlidePlugin for InsightsSlidePlugin {
 </div>
 {footer}
 </div></div>"##,
-            title = if title.is_empty() {
-                "Insights & Recomendaciones".to_string()
-            } else {
-                title
-            },
+            rule_ids = escape_html(&rule_ids),
+            tag = t.get("insights_tag"),
+            title = t.get("insights_title"),
             cards = insight_cards,
-            summary = generate_action_summary(&insights),
-            footer = Self::render_footer(t.get("footer_text")),
+            next_steps = t.get("insights_next_steps"),
+            summary = generate_action_summary(shown, t),
+            footer = Self::render_footer(ctx),
         );
 
         vec![SlideOutput {
@@ -85,245 +96,80 @@ impl SlidePlugin for InsightsSlidePlugin {
 }
 
 impl InsightsSlidePlugin {
-    fn render_footer(footer_text: String) -> String {
+    fn render_footer(ctx: &PluginContext) -> String {
+        let brand = if ctx.config.custom_branding {
+            ctx.brand_logo("sm")
+        } else {
+            r##"<span class="text-brand-primary text-2xl -mr-1">///</span>
+  <span class="text-xl">AXUR</span>"##
+                .to_string()
+        };
         format!(
             r##"<footer class="absolute bottom-8 left-14 right-14 flex justify-between items-center">
 <div class="flex items-center font-black tracking-wider select-none text-white h-5">
-  <span class="text-[#FF671F] text-2xl -mr-1">///</span>
-  <span class="text-xl">AXUR</span>
+  {}
 </div>
 <div class="flex items-center text-xs text-zinc-500">
   <span>{}</span>
 </div>
 </footer>"##,
-            footer_text
+            brand,
+            ctx.footer_text()
         )
     }
 }
 
-/// Insight with priority and recommendation
-struct Insight {
-    icon: &'static str,
-    title: String,
-    description: String,
-    priority: Priority,
-    action: String,
-}
-
-#[derive(Clone, Copy)]
-enum Priority {
-    Critical,
-    High,
-    Medium,
-    Low,
-}
-
-impl Priority {
-    fn label(&self) -> &'static str {
-        match self {
-            Priority::Critical => "CRÍTICO",
-            Priority::High => "ALTO",
-            Priority::Medium => "MEDIO",
-            Priority::Low => "BAJO",
+/// Rules fired with the configured pack (default pack when none) and the
+/// most insights the slide shows
+pub fn evaluate_insights(
+    data: &PocReportData,
+    config: &PluginConfig,
+) -> (Vec<FiredInsight>, usize) {
+    match &config.insight_rules {
+        Some(pack) => (pack.evaluate(data), pack.max_insights),
+        None => {
+            let pack = InsightRulePack::default();
+            (pack.evaluate(data), pack.max_insights)
         }
     }
 }
 
-/// Generate insights from report data
-fn generate_insights(data: &crate::api::report::PocReportData) -> Vec<Insight> {
-    let mut insights = Vec::new();
-
-    // Insight 1: High credential exposure
-    if data.credentials_total > 100 {
-        insights.push(Insight {
-            icon: "🔑",
-            title: "Exposición de Credenciales Elevada".to_string(),
-            description: format!(
-                "{} credenciales detectadas. Riesgo de account takeover.",
-                data.credentials_total
-            ),
-            priority: if data.credentials_total > 500 {
-                Priority::Critical
-            } else {
-                Priority::High
-            },
-            action: "Activar MFA y revisar políticas de contraseñas".to_string(),
-        });
-    }
-
-    // Insight 2: Low takedown rate
-    let total_takedowns = data.takedown_resolved
-        + data.takedown_pending
-        + data.takedown_aborted
-        + data.takedown_unresolved;
-    let takedown_rate = if total_takedowns > 0 {
-        (data.takedown_resolved as f64 / total_takedowns as f64) * 100.0
-    } else {
-        0.0
-    };
-
-    if takedown_rate < 50.0 && total_takedowns > 20 {
-        insights.push(Insight {
-            icon: "⚡",
-            title: "Tasa de Takedown Baja".to_string(),
-            description: format!(
-                "Solo {:.0}% de takedowns exitosos. {} pendientes.",
-                takedown_rate,
-                total_takedowns - data.takedown_resolved
-            ),
-            priority: Priority::High,
-            action: "Escalar con proveedores y revisar SLAs".to_string(),
-        });
-    }
-
-    // Insight 3: Code secrets exposed
-    if data.secrets_total > 10 {
-        insights.push(Insight {
-            icon: "📦",
-            title: "Secretos en Código Expuestos".to_string(),
-            description: format!(
-                "{} secretos detectados en repositorios públicos.",
-                data.secrets_total
-            ),
-            priority: if data.production_secrets > 5 {
-                Priority::Critical
-            } else {
-                Priority::Medium
-            },
-            action: "Rotar credenciales y auditar repositorios".to_string(),
-        });
-    }
-
-    // Insight 4: Phishing campaigns
-    let phishing_count: u64 = data
-        .threats_by_type
-        .iter()
-        .filter(|t| t.threat_type.to_lowercase().contains("phishing"))
-        .map(|t| t.count)
-        .sum();
-
-    if phishing_count > 5 {
-        insights.push(Insight {
-            icon: "🎣",
-            title: "Campañas de Phishing Activas".to_string(),
-            description: format!(
-                "{} sitios de phishing detectados contra la marca.",
-                phishing_count
-            ),
-            priority: if phishing_count > 20 {
-                Priority::Critical
-            } else {
-                Priority::High
-            },
-            action: "Alertar a clientes y reforzar awareness".to_string(),
-        });
-    }
-
-    // Insight 5: Social media fake profiles
-    let social_count: u64 = data
-        .threats_by_type
-        .iter()
-        .filter(|t| {
-            t.threat_type.to_lowercase().contains("social")
-                || t.threat_type.to_lowercase().contains("fake")
-        })
-        .map(|t| t.count)
-        .sum();
-
-    if social_count > 3 {
-        insights.push(Insight {
-            icon: "👤",
-            title: "Suplantación en Redes Sociales".to_string(),
-            description: format!("{} perfiles falsos identificados.", social_count),
-            priority: Priority::Medium,
-            action: "Reportar a plataformas y monitorear VIPs".to_string(),
-        });
-    }
-
-    // Insight 6: Good efficiency (positive)
-    if takedown_rate > 80.0 && data.takedown_resolved > 10 {
-        insights.push(Insight {
-            icon: "✅",
-            title: "Excelente Tasa de Resolución".to_string(),
-            description: format!(
-                "{:.0}% de takedowns exitosos. Tiempo de respuesta efectivo.",
-                takedown_rate
-            ),
-            priority: Priority::Low,
-            action: "Mantener el proceso actual y documentar".to_string(),
-        });
-    }
-
-    // Insight 7: High threat volume
-    if data.total_tickets > 500 {
-        insights.push(Insight {
-            icon: "📈",
-            title: "Volumen de Amenazas Elevado".to_string(),
-            description: format!(
-                "{} detecciones totales. Considerar recursos adicionales.",
-                data.total_tickets
-            ),
-            priority: Priority::Medium,
-            action: "Evaluar automatización y priorización".to_string(),
-        });
-    }
-
-    // If no specific insights, add general one
-    if insights.is_empty() {
-        insights.push(Insight {
-            icon: "📊",
-            title: "Postura de Seguridad Estable".to_string(),
-            description: "No se detectaron anomalías críticas en el período.".to_string(),
-            priority: Priority::Low,
-            action: "Continuar monitoreo proactivo".to_string(),
-        });
-    }
-
-    // Sort by priority
-    insights.sort_by_key(|i| match i.priority {
-        Priority::Critical => 0,
-        Priority::High => 1,
-        Priority::Medium => 2,
-        Priority::Low => 3,
-    });
-
-    // Limit to 6 insights
-    insights.truncate(6);
-
-    insights
-}
-
-fn render_insight_card(insight: &Insight) -> String {
+fn render_insight_card(
+    insight: &FiredInsight,
+    metrics: &serde_json::Value,
+    t: &Translations,
+) -> String {
+    let text = |key: &str| fill_template(&t.get(key), metrics, t);
     crate::plugins::builtin::theme::action_card(
-        insight.priority.label(),
-        &format!("{} {}", insight.icon, insight.title),
-        &insight.description,
-        &insight.action,
+        &t.get(insight.severity.label_key()),
+        &format!(
+            "{} {}",
+            escape_html(&insight.icon),
+            text(&insight.title_key)
+        ),
+        &text(&insight.description_key),
+        &text(&insight.action_key),
     )
 }
 
-fn generate_action_summary(insights: &[Insight]) -> String {
-    let critical_count = insights
-        .iter()
-        .filter(|i| matches!(i.priority, Priority::Critical))
-        .count();
-    let high_count = insights
-        .iter()
-        .filter(|i| matches!(i.priority, Priority::High))
-        .count();
+fn generate_action_summary(insights: &[FiredInsight], t: &Translations) -> String {
+    let count =
+        |severity: InsightSeverity| insights.iter().filter(|i| i.severity == severity).count();
+    let critical_count = count(InsightSeverity::Critical);
+    let high_count = count(InsightSeverity::High);
 
     if critical_count > 0 {
-        format!(
-            "Se identificaron {} issues críticos que requieren atención inmediata.",
-            critical_count
+        t.format(
+            "insights_summary_critical",
+            &[("count", &critical_count.to_string())],
         )
     } else if high_count > 0 {
-        format!(
-            "{} áreas de mejora prioritarias identificadas. Revisar plan de acción.",
-            high_count
+        t.format(
+            "insights_summary_high",
+            &[("count", &high_count.to_string())],
         )
     } else {
-        "La postura de seguridad es estable. Continuar con monitoreo proactivo.".to_string()
+        t.get("insights_summary_stable")
     }
 }
 
@@ -333,8 +179,9 @@ mod tests {
 
     #[test]
     fn test_priority_labels() {
-        assert_eq!(Priority::Critical.label(), "CRÍTICA");
-        assert_eq!(Priority::Low.label(), "BAJA");
+        let t = Translations::load("es").unwrap();
+        assert_eq!(t.get(InsightSeverity::Critical.label_key()), "CRÍTICA");
+        assert_eq!(t.get(InsightSeverity::Low.label_key()), "BAJA");
     }
 
     #[test]
@@ -343,4 +190,24 @@ mod tests {
         assert_eq!(plugin.id(), "builtin.insights");
         assert_eq!(plugin.priority(), 25);
     }
+
+    #[test]
+    fn test_insights_slide() {
+        let mut data = PocReportData::demo();
+        data.credentials_total = 600;
+        let translations = Translations::load("en").unwrap();
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig::default(),
+        };
+
+        let html = &InsightsSlidePlugin.generate_slides(&ctx)[0].html;
+        assert!(html.contains("data-insight-rules=\"credential_exposure"));
+        assert!(html.contains("600 credentials detected"));
+        assert!(html.contains("CRITICAL"));
+        assert!(!html.contains("MISSING"));
+        assert!(!html.contains("RECOMENDACIONES"));
+    }
 }
@@ -40,7 +40,7 @@ impl SlidePlugin for IntroSlidePlugin {
                 <div class="flex-grow grid grid-cols-12 gap-8 h-full relative z-10">
                     <!-- Left: Title (F-PATTERN: Top-left anchor) -->
                     <div class="col-span-4 flex flex-col justify-center">
-                         <div class="w-20 h-1 bg-orange-500 mb-8 shadow-[0_0_15px_#FF671F]"></div>
+                         <div class="w-20 h-1 bg-orange-500 mb-8 shadow-[0_0_15px_var(--color-primary)]"></div>
                          <h2 class="text-6xl font-black leading-tight display-text uppercase tracking-tight">{title}</h2>
                     </div>
                     
@@ -64,15 +64,9 @@ impl SlidePlugin for IntroSlidePlugin {
             bg_pattern = crate::plugins::builtin::helpers::geometric_pattern(),
             text = text,
             closing = t.get("intro_text_closing"),
-            footer = crate::plugins::builtin::helpers::footer_dark(2, &t.get("footer_text")),
+            footer = crate::plugins::builtin::helpers::footer_dark(2, ctx),
         );
 
-        let html = html
-            .replace("#FF671F", "var(--color-primary)")
-            // Intro uses orange-500, let's align it to brand primary for consistency
-            .replace("bg-orange-500", "bg-brand-primary")
-            .replace("text-orange-500", "text-brand-primary");
-
         vec![SlideOutput {
             id: "intro".into(),
             html,
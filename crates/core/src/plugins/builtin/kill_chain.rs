@@ -31,7 +31,6 @@ impl SlidePlugin for KillChainSlidePlugin {
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let data = ctx.data;
-        let t = ctx.translations;
 
         // ─── Find best examples: takedowns with most complete date chains ───
         let mut candidates: Vec<_> = data
@@ -255,7 +254,7 @@ impl SlidePlugin for KillChainSlidePlugin {
             avg_uptime = avg_uptime,
             success_rate = success_rate,
             total_resolved = total_resolved,
-            footer = footer_dark(12, &t.get("footer_text")),
+            footer = footer_dark(12, ctx),
         );
 
         vec![SlideOutput {
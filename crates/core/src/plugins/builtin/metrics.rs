@@ -29,7 +29,6 @@ impl SlidePlugin for MetricsSlidePlugin {
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let data = ctx.data;
-        let t = ctx.translations;
         let roi = &data.roi_metrics;
 
         // Use ROI metrics for consistent calculations
@@ -84,7 +83,7 @@ impl SlidePlugin for MetricsSlidePlugin {
 
                     <!-- ANCHOR CONTRAST: The "After" State (Dominant, glowing) -->
                     <!-- VON RESTORFF: This is the ONLY glowing element on the slide -->
-                    <div class="glass-panel-premium p-10 flex flex-col justify-center items-center relative overflow-hidden ring-1 ring-orange-500/30 shadow-[0_0_50px_rgba(255,103,31,0.15)] transform scale-105">
+                    <div class="glass-panel-premium p-10 flex flex-col justify-center items-center relative overflow-hidden ring-1 ring-orange-500/30 shadow-[0_0_50px_rgba(var(--color-primary-rgb),0.15)] transform scale-105">
                         <div class="absolute inset-0 bg-gradient-to-br from-orange-500/10 to-transparent"></div>
                         <div class="bg-orb-orange w-64 h-64 -top-20 -right-20 opacity-40"></div>
                         
@@ -129,7 +128,7 @@ impl SlidePlugin for MetricsSlidePlugin {
                 "CON AXUR",
                 &format!("{:.0}h recuperadas", hours_saved),
             ),
-            footer = footer_dark(6, &t.get("footer_text")),
+            footer = footer_dark(6, ctx),
         );
 
         // Semantic token replacement
@@ -138,7 +137,7 @@ impl SlidePlugin for MetricsSlidePlugin {
             .replace("bg-orange-500", "bg-brand-primary")
             .replace("ring-orange-500", "ring-brand-primary")
             .replace(
-                "shadow-[0_0_50px_rgba(255,103,31,0.15)]",
+                "shadow-[0_0_50px_rgba(var(--color-primary-rgb),0.15)]",
                 "shadow-brand-glow",
             )
             .replace("from-orange-500", "from-[var(--color-primary)]");
@@ -3,11 +3,13 @@
 //! Default plugins that ship with the core library.
 
 pub mod ai_intent;
+pub mod annotations; // Analyst narrative slides
 pub mod closing;
 pub mod comparative;
 pub mod cover;
 pub mod credentials;
 pub mod data_exposure;
+pub mod enrichment; // Data plugins
 pub mod examples;
 pub mod geospatial;
 pub mod google_slides; // Cloud export
@@ -18,7 +20,9 @@ pub mod insights;
 pub mod intro;
 pub mod kill_chain;
 pub mod metrics;
+pub mod pdf; // PDF export
 pub mod poc_data;
+pub mod pptx; // Native PPTX export
 pub mod radar;
 pub mod roi;
 pub mod solutions;
@@ -33,11 +37,13 @@ pub mod velocity;
 pub mod virality;
 
 pub use ai_intent::AiIntentSlidePlugin;
+pub use annotations::{AnalystRecommendationsSlidePlugin, AnalystSummarySlidePlugin};
 pub use closing::ClosingSlidePlugin;
 pub use comparative::ComparativeSlidePlugin;
 pub use cover::CoverSlidePlugin;
 pub use credentials::CredentialsSlidePlugin;
 pub use data_exposure::DataExposureSlidePlugin;
+pub use enrichment::{DeepAnalyticsDataPlugin, OperationalMetricsDataPlugin, RiskScoreDataPlugin};
 pub use examples::{PocExamplesSlidePlugin, TakedownExamplesSlidePlugin};
 pub use geospatial::GeospatialSlidePlugin;
 pub use heatmap::HeatmapSlidePlugin;
@@ -46,7 +52,9 @@ pub use insights::InsightsSlidePlugin;
 pub use intro::IntroSlidePlugin;
 pub use kill_chain::KillChainSlidePlugin;
 pub use metrics::MetricsSlidePlugin;
+pub use pdf::PdfExportPlugin;
 pub use poc_data::PocDataSlidePlugin;
+pub use pptx::PptxExportPlugin;
 pub use radar::RadarSlidePlugin;
 pub use roi::RoiSlidePlugin;
 pub use solutions::SolutionsSlidePlugin;
@@ -0,0 +1,272 @@
+//! PDF Export Plugin
+//!
+//! Renders the slide outputs to a paginated 16:9 PDF using a local
+//! headless Chromium/Chrome. The slides are wrapped in the same document
+//! as the HTML report (embedded Tailwind, Chart.js and the Axur design
+//! system), so the print CSS gives one slide per 16in x 9in page with
+//! charts and screenshots rendered exactly as in the browser.
+//!
+//! No external service is involved: the browser binary is resolved from
+//! `CHROME_PATH` or the usual install locations.
+
+use crate::plugins::{ExportPlugin, SlideOutput};
+use crate::report::html::render_slides_document_with_css;
+use crate::report::OfflineAssets;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Browser executables looked up in PATH, in order
+const CHROME_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+    "msedge",
+];
+
+/// Absolute install locations checked when nothing is found in PATH
+const CHROME_INSTALL_PATHS: &[&str] = &[
+    "/usr/bin/chromium",
+    "/usr/bin/chromium-browser",
+    "/usr/bin/google-chrome",
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "/Applications/Chromium.app/Contents/MacOS/Chromium",
+    r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+];
+
+/// Plugin that exports slides to PDF through headless Chromium
+pub struct PdfExportPlugin {
+    title: String,
+    css: Option<String>,
+    chrome_path: Option<PathBuf>,
+    timeout: Duration,
+}
+
+impl Default for PdfExportPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfExportPlugin {
+    /// Create exporter with auto-detected browser and a 90s render timeout
+    pub fn new() -> Self {
+        Self {
+            title: "Reporte".to_string(),
+            css: None,
+            chrome_path: None,
+            timeout: Duration::from_secs(90),
+        }
+    }
+
+    /// Document title (shown in the PDF metadata)
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// CSS appended after the design system (`PluginConfig::document_css`)
+    pub fn with_css(mut self, css: String) -> Self {
+        self.css = Some(css);
+        self
+    }
+
+    /// Use a specific browser executable instead of auto-detection
+    pub fn with_chrome_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.chrome_path = Some(path.into());
+        self
+    }
+
+    /// Maximum time allowed for the browser to render the document
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Locate a Chromium-based browser (`CHROME_PATH`, PATH, install dirs)
+    pub fn find_chrome() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var("CHROME_PATH") {
+            let path = PathBuf::from(path);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        if let Some(paths) = std::env::var_os("PATH") {
+            for dir in std::env::split_paths(&paths) {
+                for name in CHROME_CANDIDATES {
+                    let candidate = dir.join(name);
+                    if candidate.is_file() {
+                        return Some(candidate);
+                    }
+                    let exe = dir.join(format!("{}.exe", name));
+                    if exe.is_file() {
+                        return Some(exe);
+                    }
+                }
+            }
+        }
+
+        CHROME_INSTALL_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .find(|p| p.is_file())
+    }
+
+    /// Whether a browser is available to render PDFs
+    pub fn is_available(&self) -> bool {
+        self.chrome_path.is_some() || Self::find_chrome().is_some()
+    }
+
+    fn render(&self, chrome: &Path, html: &str) -> Result<Vec<u8>, String> {
+        let work_dir = std::env::temp_dir().join(format!("axur-pdf-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&work_dir)
+            .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+
+        let result = self.render_in(chrome, html, &work_dir);
+        let _ = std::fs::remove_dir_all(&work_dir);
+        result
+    }
+
+    fn render_in(&self, chrome: &Path, html: &str, work_dir: &Path) -> Result<Vec<u8>, String> {
+        let input = work_dir.join("report.html");
+        let output = work_dir.join("report.pdf");
+        std::fs::write(&input, html).map_err(|e| format!("Failed to write HTML: {}", e))?;
+
+        let mut child = Command::new(chrome)
+            .arg("--headless")
+            .arg("--disable-gpu")
+            .arg("--no-sandbox")
+            .arg("--hide-scrollbars")
+            .arg("--no-pdf-header-footer")
+            .arg("--run-all-compositor-stages-before-draw")
+            // Give Chart.js time to draw before printing
+            .arg("--virtual-time-budget=10000")
+            .arg(format!(
+                "--user-data-dir={}",
+                work_dir.join("profile").display()
+            ))
+            .arg(format!("--print-to-pdf={}", output.display()))
+            .arg(file_url(&input))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", chrome.display(), e))?;
+
+        let started = Instant::now();
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => break,
+                Ok(Some(status)) => return Err(format!("Browser exited with {}", status)),
+                Ok(None) if started.elapsed() > self.timeout => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "PDF rendering timed out after {}s",
+                        self.timeout.as_secs()
+                    ));
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+                Err(e) => return Err(format!("Failed to wait for browser: {}", e)),
+            }
+        }
+
+        let bytes = std::fs::read(&output).map_err(|e| format!("No PDF produced: {}", e))?;
+        if !bytes.starts_with(b"%PDF") {
+            return Err("Browser output is not a PDF".to_string());
+        }
+        Ok(bytes)
+    }
+}
+
+impl ExportPlugin for PdfExportPlugin {
+    fn id(&self) -> &'static str {
+        "builtin.export.pdf"
+    }
+
+    fn format(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn export(&self, slides: &[SlideOutput]) -> Result<Vec<u8>, String> {
+        if slides.is_empty() {
+            return Err("No slides to export".to_string());
+        }
+
+        let chrome = self.chrome_path.clone().or_else(Self::find_chrome).ok_or(
+            "PDF export requires Chromium or Chrome; install it or set CHROME_PATH".to_string(),
+        )?;
+
+        // Embedded assets: the render must not depend on CDNs
+        let assets = OfflineAssets::load_embedded();
+        let html = render_slides_document_with_css(
+            slides,
+            &self.title,
+            Some(&assets),
+            self.css.as_deref(),
+        );
+
+        tracing::info!(
+            "Rendering {} slides to PDF with {}",
+            slides.len(),
+            chrome.display()
+        );
+        self.render(&chrome, &html)
+    }
+}
+
+/// Build a file:// URL for a local path
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:///{}", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_metadata() {
+        let plugin = PdfExportPlugin::new();
+        assert_eq!(plugin.id(), "builtin.export.pdf");
+        assert_eq!(plugin.format(), "pdf");
+    }
+
+    #[test]
+    fn test_export_without_slides_fails() {
+        let plugin = PdfExportPlugin::new().with_chrome_path("/nonexistent/chrome");
+        assert!(plugin.export(&[]).is_err());
+    }
+
+    #[test]
+    fn test_missing_browser_reports_error() {
+        let plugin = PdfExportPlugin::new().with_chrome_path("/nonexistent/chrome");
+        let slides = vec![SlideOutput {
+            id: "cover".into(),
+            html: "<div class=\"printable-slide\">Cover</div>".into(),
+        }];
+        let err = plugin.export(&slides).unwrap_err();
+        assert!(err.contains("Failed to start"));
+    }
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            file_url(Path::new("/tmp/report.html")),
+            "file:///tmp/report.html"
+        );
+        assert_eq!(
+            file_url(Path::new(r"C:\tmp\report.html")),
+            "file:///C:/tmp/report.html"
+        );
+    }
+}
@@ -106,7 +106,7 @@ impl SlidePlugin for PocDataSlidePlugin {
                 format!(
                     r#"<div class="absolute flex flex-col items-center gap-2 transform -translate-x-1/2 -translate-y-1/2 group/node"
                             style="left: calc(50% + {x}px); top: calc(50% + {y}px);">
-                        <div class="w-12 h-12 bg-black border border-orange-500/50 rounded-full flex items-center justify-center shadow-[0_0_15px_rgba(255,103,31,0.3)] z-10 group-hover/node:scale-110 transition-transform duration-500">
+                        <div class="w-12 h-12 bg-black border border-orange-500/50 rounded-full flex items-center justify-center shadow-[0_0_15px_rgba(var(--color-primary-rgb),0.3)] z-10 group-hover/node:scale-110 transition-transform duration-500">
                              <svg class="w-6 h-6 text-orange-500" fill="none" viewBox="0 0 24 24" stroke="currentColor"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 12l2 2 4-4m5.618-4.016A11.955 11.955 0 0112 2.944a11.955 11.955 0 01-8.618 3.04A12.02 12.02 0 003 9c0 5.591 3.824 10.29 9 11.622 5.176-1.332 9-6.03 9-11.622 0-1.042-.133-2.052-.382-3.016z" /></svg>
                         </div>
                         <div class="px-3 py-1 bg-black/80 border border-zinc-800 rounded-full text-xs font-bold text-white tracking-wide whitespace-nowrap backdrop-blur-md opacity-0 group-hover/node:opacity-100 transition-opacity duration-300 transform translate-y-2 group-hover/node:translate-y-0">
@@ -175,7 +175,7 @@ impl SlidePlugin for PocDataSlidePlugin {
                             <div class="absolute inset-[80px] border border-dashed border-orange-500/20 rounded-full animate-[spin_40s_linear_infinite_reverse]"></div>
 
                             <!-- Inner Ring (Brands) -->
-                            <div class="absolute inset-[160px] border border-orange-500/30 rounded-full shadow-[0_0_30px_rgba(255,103,31,0.1)]"></div>
+                            <div class="absolute inset-[160px] border border-orange-500/30 rounded-full shadow-[0_0_30px_rgba(var(--color-primary-rgb),0.1)]"></div>
 
                             <!-- Radar Scan Effect -->
                             <div class="absolute inset-[20px] rounded-full opacity-50 pointer-events-none mix-blend-screen">
@@ -183,7 +183,7 @@ impl SlidePlugin for PocDataSlidePlugin {
                             </div>
 
                             <!-- Core (Company Logo) -->
-                            <div class="absolute w-24 h-24 bg-zinc-900 border-2 border-orange-500 rounded-full flex items-center justify-center shadow-[0_0_50px_rgba(255,103,31,0.4)] z-20 relative">
+                            <div class="absolute w-24 h-24 bg-zinc-900 border-2 border-orange-500 rounded-full flex items-center justify-center shadow-[0_0_50px_rgba(var(--color-primary-rgb),0.4)] z-20 relative">
                                 <div class="absolute inset-0 bg-orange-500/10 rounded-full animate-ping opacity-20"></div>
                                 <div class="text-center">
                                     <div class="text-2xl font-black text-white tracking-widest leading-none">AXUR</div>
@@ -212,7 +212,7 @@ impl SlidePlugin for PocDataSlidePlugin {
             end = data.end_date,
             radar = radar_scan,
             brands = brand_nodes,
-            footer = footer_dark(5, &t.get("footer_text")),
+            footer = footer_dark(5, ctx),
         );
 
         vec![SlideOutput {
@@ -0,0 +1,903 @@
+//! Native PPTX Export Plugin
+//!
+//! Builds an editable .pptx from scratch with [`crate::pptx_writer`], without
+//! Google Drive or a template file. Builtin slides are mapped to native
+//! PowerPoint objects (text boxes, KPI cards, tables and charts) from the
+//! report data; any other slide falls back to its title and body text
+//! extracted from the HTML. Native slides use the report translations and
+//! the analyst's slide overrides (title, subtitle and pinned note).
+
+use super::google_slides::GoogleSlidesExportPlugin;
+use crate::api::report::PocReportData;
+use crate::i18n::Translations;
+use crate::plugins::{ExportPlugin, SlideOutput, SlideOverride};
+use crate::pptx_writer::{
+    Align, Chart, ChartKind, ChartSeries, Frame, Paragraph, PptxPresentation, PptxSlide, PptxTheme,
+    Shape,
+};
+use std::collections::HashMap;
+
+/// Point colors for doughnut charts
+const PALETTE: &[&str] = &["FF671F", "7C3AED", "0EA5E9", "22C55E", "EAB308", "EF4444"];
+
+/// Plugin that exports slides to a native PowerPoint file
+pub struct PptxExportPlugin {
+    data: Option<PocReportData>,
+    title: String,
+    theme: PptxTheme,
+    translations: Translations,
+    /// Plugin ID -> analyst override (`PluginConfig::slide_overrides`)
+    slide_overrides: HashMap<String, SlideOverride>,
+}
+
+impl Default for PptxExportPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxExportPlugin {
+    /// Create exporter without report data (text-only slides)
+    pub fn new() -> Self {
+        Self {
+            data: None,
+            title: "Reporte".to_string(),
+            theme: PptxTheme::default(),
+            translations: Translations::load("es").expect("Failed to load Spanish translations"),
+            slide_overrides: HashMap::new(),
+        }
+    }
+
+    /// Create exporter with the report data used for native charts and tables
+    pub fn with_data(data: PocReportData) -> Self {
+        Self {
+            title: data.company_name.clone(),
+            data: Some(data),
+            ..Self::new()
+        }
+    }
+
+    /// Presentation title (document metadata)
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Colors and font of the deck (e.g. `ReportTheme::pptx_theme`)
+    pub fn with_theme(mut self, theme: PptxTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Report language of the native slides (Spanish unless set)
+    pub fn with_translations(mut self, translations: Translations) -> Self {
+        self.translations = translations;
+        self
+    }
+
+    /// Analyst overrides by plugin ID, as in `PluginConfig::slide_overrides`
+    /// (annotation callouts are merged in by `PluginConfig::with_annotations`)
+    pub fn with_slide_overrides(mut self, overrides: HashMap<String, SlideOverride>) -> Self {
+        self.slide_overrides = overrides;
+        self
+    }
+
+    /// Override of the builtin plugin that rendered a slide
+    fn slide_override(&self, slide_id: &str) -> Option<&SlideOverride> {
+        self.slide_overrides
+            .get(&format!("builtin.{}", slide_id))
+            .filter(|o| !o.is_empty())
+    }
+
+    /// Non-blank text of an override field
+    fn override_text<'a>(
+        &'a self,
+        slide_id: &str,
+        field: fn(&SlideOverride) -> &Option<String>,
+    ) -> Option<&'a str> {
+        self.slide_override(slide_id)
+            .and_then(|o| field(o).as_deref())
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Build the native slide for a plugin output
+    fn build_slide(&self, slide: &SlideOutput, number: usize) -> PptxSlide {
+        let native = self.data.as_ref().and_then(|data| {
+            let mut out = PptxSlide::new(&slide.id);
+            let built = match slide.id.as_str() {
+                "cover" => self.cover(&mut out, data),
+                "metrics" => self.metrics(&mut out, data),
+                "threats" => self.threats(&mut out, data),
+                "takedowns" => self.takedowns(&mut out, data),
+                "credentials" => self.credentials(&mut out, data),
+                "incidents" => self.incidents(&mut out, data),
+                "roi" => self.roi(&mut out, data),
+                "heatmap" => self.heatmap(&mut out, data),
+                "comparative" => self.comparative(&mut out, data),
+                "closing" => self.closing(&mut out, data),
+                _ => false,
+            };
+            built.then_some(out)
+        });
+
+        // Fallback slides carry the override in their HTML already
+        let mut out = match native {
+            Some(mut out) => {
+                self.note(&mut out, &slide.id);
+                out
+            }
+            None => self.fallback(slide),
+        };
+        if slide.id != "cover" && slide.id != "closing" {
+            self.footer(&mut out, number);
+        }
+        out
+    }
+
+    // ========================
+    // BUILDING BLOCKS
+    // ========================
+
+    /// Translated text of a key
+    fn tr(&self, key: &str) -> String {
+        self.translations.get(key)
+    }
+
+    /// Eyebrow and title of a content slide; the analyst's title replaces
+    /// the default one and the subtitle goes under it
+    fn header(&self, slide: &mut PptxSlide, eyebrow: &str, title: &str) {
+        let t = &self.theme;
+        let title = self
+            .override_text(&slide.source_id, |o| &o.title)
+            .unwrap_or(title)
+            .to_string();
+        let subtitle = self
+            .override_text(&slide.source_id, |o| &o.subtitle)
+            .map(str::to_string);
+        slide.add(Shape::rect(Frame::new(64.0, 56.0, 40.0, 4.0), &t.primary));
+        slide.add(Shape::text(
+            Frame::new(56.0, 66.0, 900.0, 28.0),
+            Paragraph::new(eyebrow, 11.0, &t.primary).bold(),
+        ));
+        slide.add(Shape::text(
+            Frame::new(56.0, 92.0, 1160.0, 60.0),
+            Paragraph::new(title, 30.0, &t.text).bold(),
+        ));
+        if let Some(subtitle) = subtitle {
+            slide.add(Shape::text(
+                Frame::new(56.0, 146.0, 1160.0, 26.0),
+                Paragraph::new(subtitle, 13.0, &t.muted),
+            ));
+        }
+    }
+
+    /// Analyst note pinned to the top-right corner of a native slide
+    fn note(&self, slide: &mut PptxSlide, slide_id: &str) {
+        let Some(note) = self.override_text(slide_id, |o| &o.commentary) else {
+            return;
+        };
+        let t = &self.theme;
+        slide.add(Shape::card(
+            Frame::new(864.0, 24.0, 360.0, 120.0),
+            vec![
+                Paragraph::new(self.tr("label_analyst_note"), 9.0, &t.primary).bold(),
+                Paragraph::new(note, 11.0, &t.text),
+            ],
+            &t.surface,
+            &t.primary,
+        ));
+    }
+
+    fn kpi(&self, slide: &mut PptxSlide, frame: Frame, value: &str, label: &str) {
+        let t = &self.theme;
+        slide.add(Shape::card(
+            frame,
+            vec![
+                Paragraph::new(value, 30.0, &t.primary)
+                    .bold()
+                    .align(Align::Center),
+                Paragraph::new(label, 11.0, &t.muted).align(Align::Center),
+            ],
+            &t.surface,
+            &t.border,
+        ));
+    }
+
+    /// Row of KPI cards across the content area
+    fn kpi_row(&self, slide: &mut PptxSlide, y: f64, h: f64, items: &[(String, &str)]) {
+        let gap = 20.0;
+        let w = (1168.0 - gap * (items.len() as f64 - 1.0)) / items.len() as f64;
+        for (i, (value, label)) in items.iter().enumerate() {
+            let frame = Frame::new(56.0 + i as f64 * (w + gap), y, w, h);
+            self.kpi(slide, frame, value, label);
+        }
+    }
+
+    fn footer(&self, slide: &mut PptxSlide, number: usize) {
+        let t = &self.theme;
+        slide.add(Shape::text(
+            Frame::new(56.0, 676.0, 600.0, 24.0),
+            Paragraph::new("axur.com", 9.0, &t.muted),
+        ));
+        slide.add(Shape::text(
+            Frame::new(1124.0, 676.0, 100.0, 24.0),
+            Paragraph::new(number.to_string(), 9.0, &t.muted).align(Align::Right),
+        ));
+    }
+
+    fn column_chart(&self, kind: ChartKind, name: &str, items: &[(String, f64)]) -> Chart {
+        Chart::new(
+            kind,
+            items.iter().map(|(n, _)| n.clone()).collect(),
+            vec![ChartSeries {
+                name: name.to_string(),
+                values: items.iter().map(|(_, v)| *v).collect(),
+                color: self.theme.primary.clone(),
+            }],
+        )
+    }
+
+    // ========================
+    // SLIDE LAYOUTS
+    // ========================
+
+    fn cover(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let t = &self.theme;
+        slide.add(Shape::rect(Frame::new(0.0, 0.0, 12.0, 720.0), &t.primary));
+        slide.add(Shape::text(
+            Frame::new(96.0, 200.0, 1000.0, 36.0),
+            Paragraph::new(self.tr("pptx_cover_eyebrow"), 14.0, &t.primary).bold(),
+        ));
+        let title = self
+            .override_text("cover", |o| &o.title)
+            .unwrap_or(&data.company_name);
+        slide.add(Shape::text(
+            Frame::new(96.0, 240.0, 1080.0, 120.0),
+            Paragraph::new(title, 54.0, &t.text).bold(),
+        ));
+        let mut lines = vec![
+            Paragraph::new(
+                format!("{} — {}", data.start_date, data.end_date),
+                18.0,
+                &t.muted,
+            ),
+            Paragraph::new(format!("TLP:{}", data.tlp_level), 12.0, &t.muted).bold(),
+        ];
+        if let Some(subtitle) = self.override_text("cover", |o| &o.subtitle) {
+            lines.insert(0, Paragraph::new(subtitle, 18.0, &t.text));
+        }
+        slide.add(Shape::text_lines(
+            Frame::new(96.0, 380.0, 1000.0, 100.0),
+            lines,
+        ));
+        if let Some(partner) = &data.partner_name {
+            slide.add(Shape::text(
+                Frame::new(96.0, 620.0, 800.0, 30.0),
+                Paragraph::new(
+                    self.translations
+                        .format("pptx_partner", &[("name", partner)]),
+                    12.0,
+                    &t.muted,
+                ),
+            ));
+        }
+        true
+    }
+
+    fn metrics(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        self.header(
+            slide,
+            &self.tr("pptx_metrics_eyebrow"),
+            &self.tr("metrics_title"),
+        );
+        self.kpi_row(
+            slide,
+            176.0,
+            120.0,
+            &[
+                (data.total_tickets.to_string(), &self.tr("pptx_tickets")),
+                (data.total_threats.to_string(), &self.tr("pptx_threats")),
+                (data.total_incidents.to_string(), &self.tr("pptx_incidents")),
+                (
+                    format!("{:.0}", data.validation_hours),
+                    &self.tr("pptx_validation_hours"),
+                ),
+            ],
+        );
+
+        let mut threats: Vec<(String, f64)> = data
+            .threats_by_type
+            .iter()
+            .map(|t| (t.threat_type.clone(), t.count as f64))
+            .collect();
+        threats.truncate(8);
+        if !threats.is_empty() {
+            slide.add(Shape::chart(
+                Frame::new(56.0, 320.0, 1168.0, 340.0),
+                self.column_chart(ChartKind::Bar, &self.tr("pptx_detections"), &threats)
+                    .with_title(&self.tr("pptx_threats_by_type")),
+            ));
+        }
+        true
+    }
+
+    fn threats(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        if data.threats_by_type.is_empty() {
+            return false;
+        }
+        self.header(
+            slide,
+            &self.tr("pptx_threats_eyebrow"),
+            &self.tr("pptx_threats_title"),
+        );
+        let items: Vec<(String, f64)> = data
+            .threats_by_type
+            .iter()
+            .take(10)
+            .map(|t| (t.threat_type.clone(), t.count as f64))
+            .collect();
+        slide.add(Shape::chart(
+            Frame::new(56.0, 176.0, 1168.0, 480.0),
+            self.column_chart(ChartKind::Column, &self.tr("pptx_detections"), &items),
+        ));
+        true
+    }
+
+    fn takedowns(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let takedowns = self.tr("pptx_takedowns");
+        self.header(slide, &self.tr("pptx_takedowns_eyebrow"), &takedowns);
+        self.kpi_row(
+            slide,
+            176.0,
+            110.0,
+            &[
+                (
+                    format!("{:.1}%", data.takedown_success_rate),
+                    &self.tr("pptx_success_rate"),
+                ),
+                (
+                    data.takedown_median_time_to_notify.clone(),
+                    &self.tr("pptx_median_notify"),
+                ),
+                (
+                    data.takedown_median_uptime.clone(),
+                    &self.tr("pptx_median_uptime"),
+                ),
+            ],
+        );
+
+        let statuses = [
+            ("pptx_resolved", data.takedown_resolved),
+            ("pptx_pending", data.takedown_pending),
+            ("pptx_aborted", data.takedown_aborted),
+            ("pptx_unresolved", data.takedown_unresolved),
+        ];
+        let status_items: Vec<(String, f64)> = statuses
+            .iter()
+            .filter(|(_, v)| *v > 0)
+            .map(|(key, v)| (self.tr(key), *v as f64))
+            .collect();
+        if !status_items.is_empty() {
+            slide.add(Shape::chart(
+                Frame::new(56.0, 310.0, 560.0, 350.0),
+                self.column_chart(ChartKind::Doughnut, &takedowns, &status_items)
+                    .with_title(&self.tr("pptx_status"))
+                    .with_palette(PALETTE.iter().map(|c| c.to_string()).collect()),
+            ));
+        }
+
+        if !data.takedowns_by_type.is_empty() {
+            let by_type: Vec<(String, f64)> = data
+                .takedowns_by_type
+                .iter()
+                .take(6)
+                .map(|p| (p.name.clone(), p.value as f64))
+                .collect();
+            slide.add(Shape::chart(
+                Frame::new(664.0, 310.0, 560.0, 350.0),
+                self.column_chart(ChartKind::Bar, &takedowns, &by_type)
+                    .with_title(&self.tr("pptx_by_type")),
+            ));
+        }
+        true
+    }
+
+    fn credentials(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        if data.credentials_total == 0 {
+            return false;
+        }
+        let credentials = self.tr("pptx_credentials");
+        self.header(
+            slide,
+            &self.tr("pptx_credentials_eyebrow"),
+            &self.tr("pptx_credentials_title"),
+        );
+        self.kpi_row(
+            slide,
+            176.0,
+            110.0,
+            &[
+                (data.credentials_total.to_string(), &credentials),
+                (data.unique_hosts.to_string(), &self.tr("pptx_unique_hosts")),
+                (
+                    data.high_risk_users.to_string(),
+                    &self.tr("pptx_high_risk_users"),
+                ),
+            ],
+        );
+
+        let domains = &data.credential_leaks_summary.top_affected_domains;
+        let sources = if domains.is_empty() {
+            &data.top_services
+        } else {
+            domains
+        };
+        if !sources.is_empty() {
+            let mut rows = vec![vec![self.tr("pptx_domain"), credentials]];
+            rows.extend(
+                sources
+                    .iter()
+                    .take(6)
+                    .map(|p| vec![p.name.clone(), p.value.to_string()]),
+            );
+            let height = 40.0 * rows.len() as f64;
+            slide.add(Shape::table(
+                Frame::new(56.0, 310.0, 1168.0, height),
+                rows,
+                12.0,
+            ));
+        }
+        true
+    }
+
+    fn incidents(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        if data.incidents_by_type.is_empty() {
+            return false;
+        }
+        self.header(
+            slide,
+            &self.tr("pptx_incidents_eyebrow"),
+            &self.tr("pptx_incidents_title"),
+        );
+        let mut rows = vec![vec![
+            self.tr("pptx_type"),
+            self.tr("pptx_detections"),
+            self.tr("pptx_incidents"),
+            self.tr("pptx_rate"),
+        ]];
+        rows.extend(data.incidents_by_type.iter().take(10).map(|i| {
+            let rate = if i.detections > 0 {
+                i.incidents as f64 / i.detections as f64 * 100.0
+            } else {
+                0.0
+            };
+            vec![
+                i.incident_type.clone(),
+                i.detections.to_string(),
+                i.incidents.to_string(),
+                format!("{:.1}%", rate),
+            ]
+        }));
+        let height = (40.0 * rows.len() as f64).min(480.0);
+        slide.add(Shape::table(
+            Frame::new(56.0, 176.0, 1168.0, height),
+            rows,
+            12.0,
+        ));
+        true
+    }
+
+    fn roi(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let m = &data.roi_metrics;
+        self.header(
+            slide,
+            &self.tr("pptx_roi_eyebrow"),
+            &self.tr("pptx_roi_title"),
+        );
+        self.kpi_row(
+            slide,
+            176.0,
+            120.0,
+            &[
+                (
+                    format!("{:.0}", m.hours_saved_total),
+                    &self.tr("pptx_hours_saved"),
+                ),
+                (
+                    format!("{:.1}", m.analysts_equivalent_monthly),
+                    &self.tr("pptx_analysts_month"),
+                ),
+                (
+                    format!("{:.0}", m.person_days_saved),
+                    &self.tr("pptx_person_days"),
+                ),
+                (
+                    format!("{:.1}%", data.takedown_success_rate),
+                    &self.tr("pptx_takedown_success"),
+                ),
+            ],
+        );
+
+        let breakdown = [
+            ("pptx_validation", m.hours_saved_validation),
+            ("pptx_credentials", m.hours_saved_credentials),
+            ("pptx_takedowns", m.hours_saved_takedowns),
+            ("pptx_secrets", m.hours_saved_secrets),
+        ];
+        let items: Vec<(String, f64)> = breakdown
+            .iter()
+            .filter(|(_, v)| *v > 0.0)
+            .map(|(key, v)| (self.tr(key), (v * 10.0).round() / 10.0))
+            .collect();
+        if !items.is_empty() {
+            slide.add(Shape::chart(
+                Frame::new(56.0, 320.0, 1168.0, 340.0),
+                self.column_chart(ChartKind::Bar, &self.tr("pptx_hours"), &items)
+                    .with_title(&self.tr("pptx_hours_by_area")),
+            ));
+        }
+        true
+    }
+
+    fn heatmap(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let activity = &data.activity_heatmap;
+        if !activity.has_enough_data() {
+            return false;
+        }
+        let t = &self.theme;
+        self.header(
+            slide,
+            &self.tr("pptx_heatmap_eyebrow"),
+            &self.tr("heatmap_title"),
+        );
+
+        let weekdays = self.tr("pptx_weekdays");
+        let days: Vec<&str> = weekdays.split(',').map(str::trim).collect();
+        let day = |d: usize| days.get(d).copied().unwrap_or_default();
+        let max = activity
+            .grid
+            .iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let (left, top, cell, gap) = (120.0, 200.0, 42.0, 3.0);
+
+        for h in (0..24).step_by(3) {
+            slide.add(Shape::text(
+                Frame::new(left + h as f64 * (cell + gap), top - 26.0, cell, 22.0),
+                Paragraph::new(format!("{:02}", h), 9.0, &t.muted),
+            ));
+        }
+        for (d, row) in activity.grid.iter().enumerate() {
+            let y = top + d as f64 * (cell + gap);
+            slide.add(Shape::text(
+                Frame::new(56.0, y + 8.0, 60.0, 26.0),
+                Paragraph::new(day(d), 10.0, &t.muted).align(Align::Right),
+            ));
+            for (h, &count) in row.iter().enumerate() {
+                let x = left + h as f64 * (cell + gap);
+                let color = heat_color(count as f64 / max as f64, &t.primary, &t.surface);
+                slide.add(Shape::rect(Frame::new(x, y, cell, cell), &color));
+            }
+        }
+
+        let peak = match activity.peak() {
+            Some((d, h, count)) => self.translations.format(
+                "pptx_heatmap_peak",
+                &[
+                    ("day", day(d)),
+                    ("hour", &h.to_string()),
+                    ("count", &count.to_string()),
+                ],
+            ),
+            None => self.tr("pptx_no_data"),
+        };
+        slide.add(Shape::text_lines(
+            Frame::new(56.0, 530.0, 1168.0, 60.0),
+            vec![
+                Paragraph::new(peak, 14.0, &t.text).bold(),
+                Paragraph::new(
+                    self.translations.format(
+                        "pptx_heatmap_note",
+                        &[
+                            ("timezone", &activity.utc_label()),
+                            ("count", &activity.sample_size.to_string()),
+                        ],
+                    ),
+                    10.0,
+                    &t.muted,
+                ),
+            ],
+        ));
+        true
+    }
+
+    fn comparative(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let Some(cmp) = &data.comparison else {
+            return false;
+        };
+        self.header(
+            slide,
+            &self.tr("pptx_comparative_eyebrow"),
+            &self.translations.format(
+                "pptx_comparative_title",
+                &[("period", &cmp.label(&self.translations))],
+            ),
+        );
+        let (tickets, takedowns, credentials) = (
+            self.tr("pptx_tickets"),
+            self.tr("pptx_takedowns"),
+            self.tr("pptx_credentials"),
+        );
+        let (previous, current) = (
+            self.tr("pptx_previous_period"),
+            self.tr("pptx_current_period"),
+        );
+
+        let delta =
+            |current: u64, previous: u64| match crate::api::report::ComparisonData::delta_pct(
+                current, previous,
+            ) {
+                Some(d) => format!("{:+.0}%", d),
+                None => "—".to_string(),
+            };
+        let rows = vec![
+            vec![
+                self.tr("pptx_metric"),
+                previous.clone(),
+                current.clone(),
+                self.tr("pptx_change"),
+            ],
+            vec![
+                tickets.clone(),
+                cmp.prev_tickets.to_string(),
+                data.total_tickets.to_string(),
+                delta(data.total_tickets, cmp.prev_tickets),
+            ],
+            vec![
+                takedowns.clone(),
+                cmp.prev_takedowns.to_string(),
+                data.takedown_resolved.to_string(),
+                delta(data.takedown_resolved, cmp.prev_takedowns),
+            ],
+            vec![
+                credentials.clone(),
+                cmp.prev_credentials.to_string(),
+                data.credentials_total.to_string(),
+                delta(data.credentials_total, cmp.prev_credentials),
+            ],
+        ];
+        slide.add(Shape::table(
+            Frame::new(56.0, 190.0, 1168.0, 200.0),
+            rows,
+            14.0,
+        ));
+
+        let mut chart = Chart::new(
+            ChartKind::Column,
+            vec![tickets, takedowns, credentials],
+            vec![
+                ChartSeries {
+                    name: previous,
+                    values: vec![
+                        cmp.prev_tickets as f64,
+                        cmp.prev_takedowns as f64,
+                        cmp.prev_credentials as f64,
+                    ],
+                    color: self.theme.muted.clone(),
+                },
+                ChartSeries {
+                    name: current,
+                    values: vec![
+                        data.total_tickets as f64,
+                        data.takedown_resolved as f64,
+                        data.credentials_total as f64,
+                    ],
+                    color: self.theme.primary.clone(),
+                },
+            ],
+        );
+        chart.show_legend = true;
+        slide.add(Shape::chart(Frame::new(56.0, 410.0, 1168.0, 250.0), chart));
+        true
+    }
+
+    fn closing(&self, slide: &mut PptxSlide, data: &PocReportData) -> bool {
+        let t = &self.theme;
+        slide.add(Shape::rect(Frame::new(0.0, 0.0, 12.0, 720.0), &t.primary));
+        slide.add(Shape::text(
+            Frame::new(96.0, 260.0, 1080.0, 90.0),
+            Paragraph::new(
+                self.override_text("closing", |o| &o.title)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| self.tr("pptx_closing_title")),
+                54.0,
+                &t.text,
+            )
+            .bold(),
+        ));
+        let subtitle = match self.override_text("closing", |o| &o.subtitle) {
+            Some(subtitle) => subtitle.to_string(),
+            None => self
+                .translations
+                .format("pptx_closing_subtitle", &[("company", &data.company_name)]),
+        };
+        slide.add(Shape::text(
+            Frame::new(96.0, 360.0, 1080.0, 40.0),
+            Paragraph::new(subtitle, 18.0, &t.muted),
+        ));
+        slide.add(Shape::text(
+            Frame::new(96.0, 420.0, 600.0, 30.0),
+            Paragraph::new("axur.com", 14.0, &t.primary).bold(),
+        ));
+        true
+    }
+
+    /// Title and body text for slides without a native layout
+    fn fallback(&self, slide: &SlideOutput) -> PptxSlide {
+        let t = &self.theme;
+        let content = GoogleSlidesExportPlugin::parse_slide_html(slide);
+        let mut out = PptxSlide::new(&slide.id);
+
+        let title = if content.title.is_empty() {
+            slide.id.replace('_', " ")
+        } else {
+            content.title
+        };
+        self.header(&mut out, &slide.id.to_uppercase().replace('_', " "), &title);
+
+        let body: Vec<Paragraph> = content
+            .body
+            .iter()
+            .take(12)
+            .map(|line| Paragraph::new(format!("• {}", line), 14.0, &t.text))
+            .collect();
+        if !body.is_empty() {
+            out.add(Shape::text_lines(
+                Frame::new(56.0, 176.0, 1168.0, 480.0),
+                body,
+            ));
+        }
+        out
+    }
+}
+
+/// Blend from the empty-cell color to the brand color by intensity (0-1)
+fn heat_color(intensity: f64, full: &str, empty: &str) -> String {
+    if intensity < 0.05 {
+        return empty.to_string();
+    }
+    let channel = |hex: &str, i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0) as f64;
+    let base = "09090B";
+    let alpha = intensity.clamp(0.1, 1.0);
+    (0..3)
+        .map(|c| {
+            let i = c * 2;
+            let value = channel(base, i) + (channel(full, i) - channel(base, i)) * alpha;
+            format!("{:02X}", value.round() as u8)
+        })
+        .collect()
+}
+
+impl ExportPlugin for PptxExportPlugin {
+    fn id(&self) -> &'static str {
+        "builtin.export.pptx"
+    }
+
+    fn format(&self) -> &'static str {
+        "pptx"
+    }
+
+    fn export(&self, slides: &[SlideOutput]) -> Result<Vec<u8>, String> {
+        if slides.is_empty() {
+            return Err("No slides to export".to_string());
+        }
+
+        let mut deck = PptxPresentation::new(&self.title, self.theme.clone());
+        for (i, slide) in slides.iter().enumerate() {
+            deck.push(self.build_slide(slide, i + 1));
+        }
+
+        tracing::info!("Writing {} slides to PPTX", slides.len());
+        deck.to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    fn part(bytes: &[u8], name: &str) -> Option<String> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut file = archive.by_name(name).ok()?;
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        Some(content)
+    }
+
+    fn slide(id: &str) -> SlideOutput {
+        SlideOutput {
+            id: id.into(),
+            html: format!(
+                "<div class=\"bg-zinc-950\"><h2>{}</h2><p>Body text for the slide</p></div>",
+                id
+            ),
+        }
+    }
+
+    #[test]
+    fn test_plugin_metadata() {
+        let plugin = PptxExportPlugin::new();
+        assert_eq!(plugin.id(), "builtin.export.pptx");
+        assert_eq!(plugin.format(), "pptx");
+        assert!(plugin.export(&[]).is_err());
+    }
+
+    #[test]
+    fn test_native_slides_from_report_data() {
+        let data = PocReportData::demo();
+        let plugin = PptxExportPlugin::with_data(data.clone());
+        let bytes = plugin
+            .export(&[slide("cover"), slide("metrics"), slide("incidents")])
+            .unwrap();
+
+        let cover = part(&bytes, "ppt/slides/slide1.xml").unwrap();
+        assert!(cover.contains(&crate::pptx_writer::escape(&data.company_name)));
+        let content_types = part(&bytes, "[Content_Types].xml").unwrap();
+        assert!(content_types.contains("/ppt/slides/slide3.xml"));
+        // Metrics slide carries a native chart, incidents a native table
+        assert!(part(&bytes, "ppt/charts/chart1.xml").is_some());
+        assert!(part(&bytes, "ppt/slides/slide3.xml")
+            .unwrap()
+            .contains("<a:tbl>"));
+    }
+
+    #[test]
+    fn test_native_slides_use_language_and_overrides() {
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "builtin.metrics".to_string(),
+            SlideOverride {
+                title: Some("Quarter at a glance".into()),
+                subtitle: Some("Phishing drove most tickets".into()),
+                commentary: Some("Volume doubled after the campaign".into()),
+            },
+        );
+        let bytes = PptxExportPlugin::with_data(PocReportData::demo())
+            .with_translations(Translations::load("en").unwrap())
+            .with_slide_overrides(overrides)
+            .export(&[slide("metrics"), slide("threats")])
+            .unwrap();
+
+        let metrics = part(&bytes, "ppt/slides/slide1.xml").unwrap();
+        assert!(metrics.contains("Quarter at a glance"));
+        assert!(!metrics.contains("General Metrics"));
+        assert!(metrics.contains("Phishing drove most tickets"));
+        assert!(metrics.contains("Volume doubled after the campaign"));
+        assert!(metrics.contains("Validation hours"));
+
+        let threats = part(&bytes, "ppt/slides/slide2.xml").unwrap();
+        assert!(threats.contains("Threats Detected by Type"));
+        assert!(!threats.contains("Amenazas"));
+    }
+
+    #[test]
+    fn test_unknown_slide_falls_back_to_text() {
+        let bytes = PptxExportPlugin::new()
+            .export(&[slide("custom_section")])
+            .unwrap();
+        let xml = part(&bytes, "ppt/slides/slide1.xml").unwrap();
+        assert!(xml.contains("custom_section"));
+        assert!(xml.contains("Body text for the slide"));
+    }
+
+    #[test]
+    fn test_heat_color() {
+        assert_eq!(heat_color(0.0, "FF671F", "18181B"), "18181B");
+        assert_eq!(heat_color(1.0, "FF671F", "18181B"), "FF671F");
+    }
+}
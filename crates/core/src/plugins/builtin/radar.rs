@@ -44,17 +44,27 @@ impl SlidePlugin for RadarSlidePlugin {
         let title = t.get("radar_title");
 
         // ─── Overall risk assessment ───
-        let total_score: u32 = dimensions.iter().map(|d| d.score).sum();
-        let avg_score = total_score / dimensions.len().max(1) as u32;
-        let (risk_label, risk_color) = if avg_score > 75 {
-            ("CRÍTICO", "red")
-        } else if avg_score > 50 {
-            ("ALTO", "orange")
-        } else if avg_score > 25 {
-            ("MODERADO", "yellow")
+        // With a Risk Score V3 breakdown the badge shows the score and its band
+        let (risk_label, risk_color) = if data.risk_score.factors.is_empty() {
+            let total_score: u32 = dimensions.iter().map(|d| d.score).sum();
+            let avg_score = total_score / dimensions.len().max(1) as u32;
+            if avg_score > 75 {
+                ("CRÍTICO", "red")
+            } else if avg_score > 50 {
+                ("ALTO", "orange")
+            } else if avg_score > 25 {
+                ("MODERADO", "yellow")
+            } else {
+                ("BAJO", "green")
+            }
         } else {
-            ("BAJO", "green")
+            match data.risk_score.label.as_str() {
+                "Critical Risk" => ("CRÍTICO", "red"),
+                "Moderate Risk" => ("MODERADO", "yellow"),
+                _ => ("BAJO", "green"),
+            }
         };
+        let risk_trend = risk_trend_html(&data.risk_score);
 
         let html = format!(
             r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-12 mb-8 relative text-white overflow-hidden">
@@ -82,6 +92,7 @@ impl SlidePlugin for RadarSlidePlugin {
                             </div>
                             <span class="text-sm font-bold text-{risk_color}-400 bg-{risk_color}-500/10 px-3 py-1 rounded-full tracking-wider">{risk_label}</span>
                         </div>
+                        {risk_trend}
 
                         <!-- Dimension cards in 2-col grid -->
                         <div class="grid grid-cols-2 gap-2.5 flex-grow">
@@ -106,16 +117,11 @@ impl SlidePlugin for RadarSlidePlugin {
             svg = svg,
             risk_color = risk_color,
             risk_label = risk_label,
+            risk_trend = risk_trend,
             dimension_cards = generate_dimension_cards(&dimensions),
-            footer = footer_dark(9, &t.get("footer_text")),
+            footer = footer_dark(9, ctx),
         );
 
-        let html = html
-            .replace("#FF671F", "var(--color-primary)")
-            .replace("bg-orange-500", "bg-brand-primary")
-            .replace("text-orange-400", "text-brand-primary") // text-brand-primary is roughly orange-400/500
-            .replace("border-orange-500", "border-brand-primary");
-
         vec![SlideOutput {
             id: "radar".into(),
             html,
@@ -131,10 +137,17 @@ struct ThreatDimension {
     icon: &'static str,
 
     detail: String,
+    /// Inputs behind the score (card tooltip)
+    hint: String,
 }
 
-/// Calculate threat dimension scores from report data
+/// Calculate threat dimension scores from report data: the Risk Score V3
+/// factors when available, otherwise counts normalised per threat family
 fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatDimension> {
+    if !data.risk_score.factors.is_empty() {
+        return factor_dimensions(&data.risk_score.factors);
+    }
+
     // Phishing score based on threats
     let phishing_count = data
         .threats_by_type
@@ -189,6 +202,7 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "PH",
 
             detail: format!("{} detecciones", phishing_count),
+            hint: String::new(),
         },
         ThreatDimension {
             _id: "credentials",
@@ -197,6 +211,7 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "CR",
 
             detail: format!("{} expuestas", data.credentials_total),
+            hint: String::new(),
         },
         ThreatDimension {
             _id: "leaks",
@@ -205,6 +220,7 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "FL",
 
             detail: format!("{} secretos", data.secrets_total),
+            hint: String::new(),
         },
         ThreatDimension {
             _id: "social",
@@ -213,6 +229,7 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "RS",
 
             detail: format!("{} perfiles falsos", social_count),
+            hint: String::new(),
         },
         ThreatDimension {
             _id: "brand",
@@ -221,6 +238,7 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "MR",
 
             detail: format!("{} abusos", brand_count),
+            hint: String::new(),
         },
         ThreatDimension {
             _id: "exposure",
@@ -229,10 +247,73 @@ fn calculate_dimensions(data: &crate::api::report::PocReportData) -> Vec<ThreatD
             icon: "EX",
 
             detail: format!("{}% sin resolver", efficiency_score),
+            hint: String::new(),
         },
     ]
 }
 
+/// One dimension per risk factor; the card explains its share of the score
+fn factor_dimensions(factors: &[crate::api::risk::RiskFactor]) -> Vec<ThreatDimension> {
+    factors
+        .iter()
+        .map(|factor| {
+            let (id, label, icon) = match factor.key.as_str() {
+                "volume" => ("volume", "Volumen", "VO"),
+                "benchmark" => ("benchmark", "Mercado", "MK"),
+                "hygiene" => ("hygiene", "Higiene", "HI"),
+                "efficiency" => ("efficiency", "Eficiencia", "EF"),
+                "reputation" => ("reputation", "Reputación", "RE"),
+                _ => ("other", "Otro", "OT"),
+            };
+            ThreatDimension {
+                _id: id,
+                label,
+                score: (factor.score * 10.0).round().clamp(0.0, 100.0) as u32,
+                icon,
+                detail: format!(
+                    "+{:.1} pts (peso {:.0}%)",
+                    factor.points,
+                    factor.weight * 100.0
+                ),
+                hint: factor.detail.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Score trend across the history periods ("38 → 41 → 43", delta vs the
+/// previous period). Empty without history.
+fn risk_trend_html(risk: &crate::api::report::RiskScore) -> String {
+    let [.., previous, current] = risk.history.as_slice() else {
+        return String::new();
+    };
+    let delta = current - previous;
+    let (arrow, color) = if delta > 0.0 {
+        ("▲", "text-red-400")
+    } else if delta < 0.0 {
+        ("▼", "text-emerald-400")
+    } else {
+        ("=", "text-zinc-400")
+    };
+    let series = risk
+        .history
+        .iter()
+        .map(|score| format!("{:.0}", score))
+        .collect::<Vec<_>>()
+        .join(" → ");
+    format!(
+        r#"<div class="flex items-center justify-between bg-zinc-900/40 border border-zinc-800/50 rounded-xl px-4 py-2">
+                            <span class="text-xs text-zinc-400">Score {current:.0}/100 · Tendencia <span class="font-mono text-zinc-300">{series}</span></span>
+                            <span class="text-xs font-bold {color}">{arrow} {delta:+.0}</span>
+                        </div>"#,
+        current = current,
+        series = series,
+        color = color,
+        arrow = arrow,
+        delta = delta,
+    )
+}
+
 /// Normalize a count to a 0-100 score
 fn normalize_score(value: u64, max_expected: u64) -> u32 {
     ((value as f64 / max_expected as f64) * 100.0).min(100.0) as u32
@@ -295,7 +376,7 @@ fn generate_radar_svg(dimensions: &[ThreatDimension]) -> String {
         let label_color = if dim.score > 75 {
             "#EF4444"
         } else if dim.score > 50 {
-            "#FF671F"
+            "var(--color-primary)"
         } else {
             "#a1a1aa"
         };
@@ -337,7 +418,7 @@ fn generate_radar_svg(dimensions: &[ThreatDimension]) -> String {
         let dot_color = if dim.score > 75 {
             "#EF4444"
         } else if dim.score > 50 {
-            "#FF671F"
+            "var(--color-primary)"
         } else {
             "#22C55E"
         };
@@ -359,13 +440,13 @@ fn generate_radar_svg(dimensions: &[ThreatDimension]) -> String {
       </feMerge>
     </filter>
     <linearGradient id="polyGrad" x1="0" y1="0" x2="1" y2="1">
-       <stop offset="0%" stop-color="rgba(255, 103, 31, 0.35)"/>
+       <stop offset="0%" stop-color="rgba(var(--color-primary-rgb), 0.35)"/>
        <stop offset="100%" stop-color="rgba(239, 68, 68, 0.08)"/>
     </linearGradient>
   </defs>
   {circles}
   {axes}
-  <polygon points="{points}" fill="url(#polyGrad)" stroke="#FF671F" stroke-width="2" stroke-linejoin="round" style="filter: url(#glow)"/>
+  <polygon points="{points}" fill="url(#polyGrad)" stroke="var(--color-primary)" stroke-width="2" stroke-linejoin="round" style="filter: url(#glow)"/>
   {dots}
 </svg>"##,
         circles = circles,
@@ -381,13 +462,13 @@ fn generate_dimension_cards(dimensions: &[ThreatDimension]) -> String {
         let (border_color, text_color, bg_color, grad_from, grad_to) = if dim.score > 75 {
             ("border-red-500/30", "text-red-400", "bg-red-500/10", "#EF4444", "#F87171")
         } else if dim.score > 50 {
-            ("border-orange-500/30", "text-orange-400", "bg-orange-500/10", "#FF671F", "#FF8A4C")
+            ("border-orange-500/30", "text-orange-400", "bg-orange-500/10", "var(--color-primary)", "var(--color-primary-light)")
         } else {
             ("border-emerald-500/20", "text-emerald-400", "bg-emerald-500/10", "#22C55E", "#4ADE80")
         };
 
         format!(
-            r#"<div class="bg-zinc-900/40 p-3 rounded-xl border border-zinc-800/50 backdrop-blur-sm hover:{border_color} transition-all duration-300 hover:scale-[1.02] group/card">
+            r#"<div title="{hint}" class="bg-zinc-900/40 p-3 rounded-xl border border-zinc-800/50 backdrop-blur-sm hover:{border_color} transition-all duration-300 hover:scale-[1.02] group/card">
                 <div class="flex items-center justify-between mb-1.5">
                     <div class="flex items-center gap-2">
                         <span class="text-xs font-bold {text_color} {bg_color} w-7 h-7 flex items-center justify-center rounded-lg group-hover/card:scale-110 transition-transform">{icon}</span>
@@ -405,6 +486,7 @@ fn generate_dimension_cards(dimensions: &[ThreatDimension]) -> String {
             score = dim.score,
             pct = dim.score.min(100),
             detail = dim.detail,
+            hint = dim.hint,
             border_color = border_color,
             text_color = text_color,
             bg_color = bg_color,
@@ -425,6 +507,23 @@ mod tests {
         assert_eq!(normalize_score(0, 100), 0);
     }
 
+    #[test]
+    fn test_dimensions_follow_risk_factors() {
+        let mut data = crate::api::report::PocReportData::demo();
+        assert_eq!(calculate_dimensions(&data).len(), 6);
+
+        data.risk_score = crate::api::risk::calculate_risk_score(&Default::default());
+        data.risk_score.history = vec![30.0, 45.0, data.risk_score.current];
+        let dimensions = calculate_dimensions(&data);
+        assert_eq!(dimensions.len(), 5);
+        assert_eq!(dimensions[1].score, 50); // no benchmark data is neutral
+        assert_eq!(dimensions[1].hint, "no benchmark data");
+
+        let trend = risk_trend_html(&data.risk_score);
+        assert!(trend.contains("30 → 45 → 18"));
+        assert!(trend.contains("-27"));
+    }
+
     #[test]
     fn test_plugin_metadata() {
         let plugin = RadarSlidePlugin;
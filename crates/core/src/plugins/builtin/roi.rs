@@ -31,6 +31,26 @@ impl SlidePlugin for RoiSlidePlugin {
             format!("{:.0}%", metrics.analysts_equivalent_monthly * 100.0)
         };
 
+        // Period-over-period hours saved (only with comparison data)
+        let trend = match (metrics.hours_saved_delta_pct(), data.comparison.as_ref()) {
+            (Some(delta), Some(cmp)) => {
+                let (color, arrow) = if delta >= 0.0 {
+                    ("text-green-400", "↑")
+                } else {
+                    ("text-red-400", "↓")
+                };
+                format!(
+                    r#"<p class="text-xs text-zinc-400 mt-3">{hours} <span class="{color} font-bold">{arrow} {delta:.0}%</span> {label}</p>"#,
+                    hours = t.get("roi_hours_trend"),
+                    color = color,
+                    arrow = arrow,
+                    delta = delta.abs(),
+                    label = cmp.label(t),
+                )
+            }
+            _ => String::new(),
+        };
+
         // Premium Header
         let header = crate::plugins::builtin::theme::section_header_premium(
             "VALOR ESTRATÉGICO",
@@ -84,7 +104,7 @@ impl SlidePlugin for RoiSlidePlugin {
 
                             <!-- Right Column: The "Axur Way" (Value) -->
                             <div class="col-span-5 flex flex-col justify-center">
-                                <div class="glass-panel-premium p-8 h-full flex flex-col justify-center relative overflow-hidden ring-1 ring-orange-500/30 shadow-[0_0_40px_rgba(255,103,31,0.1)]">
+                                <div class="glass-panel-premium p-8 h-full flex flex-col justify-center relative overflow-hidden ring-1 ring-orange-500/30 shadow-[0_0_40px_rgba(var(--color-primary-rgb),0.1)]">
                                     <div class="absolute inset-0 bg-gradient-to-br from-orange-500/10 to-transparent"></div>
                                     <div class="absolute -top-10 -right-10 w-32 h-32 bg-orange-500/20 blur-3xl rounded-full"></div>
                                     
@@ -102,6 +122,7 @@ impl SlidePlugin for RoiSlidePlugin {
                                     <div class="bg-zinc-950/50 rounded-xl p-4 border border-zinc-700/50 backdrop-blur-md relative z-10 text-center">
                                         <p class="text-green-400 font-bold text-lg mb-1">{success_rate:.1}%</p>
                                         <p class="text-[10px] text-zinc-500 uppercase tracking-widest">Tasa de Éxito en Takedowns</p>
+                                        {trend}
                                     </div>
                                 </div>
                             </div>
@@ -113,7 +134,7 @@ impl SlidePlugin for RoiSlidePlugin {
                                 <h4 class="text-white font-bold text-lg mb-1">Maximice su Protección</h4>
                                 <p class="text-zinc-500 text-sm">Nuestro equipo de expertos está listo para escalar estos resultados.</p>
                             </div>
-                            <div class="bg-[#FF671F] text-white px-6 py-3 rounded-lg font-bold text-sm tracking-wide shadow-lg shadow-orange-900/20 group-hover/cta:shadow-orange-500/20 transition-all cursor-pointer">
+                            <div class="bg-brand-primary text-white px-6 py-3 rounded-lg font-bold text-sm tracking-wide shadow-lg shadow-orange-900/20 group-hover/cta:shadow-orange-500/20 transition-all cursor-pointer">
                                 AGENDAR REVISIÓN ESTRATÉGICA
                             </div>
                         </div>
@@ -128,7 +149,8 @@ impl SlidePlugin for RoiSlidePlugin {
             tickets = data.total_tickets,
             analysts = analysts_display,
             success_rate = data.takedown_success_rate,
-            footer = footer_dark(12, &t.get("footer_text")),
+            trend = trend,
+            footer = footer_dark(12, ctx),
         );
 
         vec![SlideOutput {
@@ -30,7 +30,7 @@ impl SlidePlugin for SolutionsSlidePlugin {
             sol_takedown = t.get("solution_takedown"),
             sol_brand = t.get("solution_brand_protection"),
             sol_intel = t.get("solution_threat_intel"),
-            footer = Self::footer_dark(3, &t.get("footer_text")),
+            footer = Self::footer_dark(3, ctx),
         );
 
         vec![SlideOutput {
@@ -41,10 +41,17 @@ impl SlidePlugin for SolutionsSlidePlugin {
 }
 
 impl SolutionsSlidePlugin {
-    fn footer_dark(page: u32, footer_text: &str) -> String {
+    fn footer_dark(page: u32, ctx: &PluginContext) -> String {
+        let brand = if ctx.config.custom_branding {
+            ctx.brand_logo("sm")
+        } else {
+            r#"<span class="text-orange-500 text-2xl -mr-1">///</span><span class="text-xl">AXUR</span>"#.to_string()
+        };
         format!(
-            r#"<footer class="absolute bottom-8 left-14 right-14 flex justify-between items-center"><div class="flex items-center font-black tracking-wider select-none text-white h-5"><span class="text-orange-500 text-2xl -mr-1">///</span><span class="text-xl">AXUR</span></div><div class="flex items-center text-xs text-zinc-400"><span>{}</span><span class="ml-4">{}</span></div></footer>"#,
-            footer_text, page
+            r#"<footer class="absolute bottom-8 left-14 right-14 flex justify-between items-center"><div class="flex items-center font-black tracking-wider select-none text-white h-5">{}</div><div class="flex items-center text-xs text-zinc-400"><span>{}</span><span class="ml-4">{}</span></div></footer>"#,
+            brand,
+            ctx.footer_text(),
+            page
         )
     }
 }
@@ -22,7 +22,7 @@ impl SlidePlugin for StyleShowcasePlugin {
         ctx.config.show_style_showcase
     }
 
-    fn generate_slides(&self, _ctx: &PluginContext) -> Vec<SlideOutput> {
+    fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let html = format!(
             r#"<div class="printable-slide aspect-[16/9] w-full flex flex-col p-14 text-white relative overflow-hidden">
                 <!-- Background -->
@@ -95,12 +95,12 @@ impl SlidePlugin for StyleShowcasePlugin {
             tag2 = theme::threat_tag("NORMAL", false),
             // Features
             feat1 = theme::feature_card(
-                r#"<svg class="w-8 h-8 text-[#FF671F]" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M13 10V3L4 14h7v7l9-11h-7z"></path></svg>"#,
+                r#"<svg class="w-8 h-8 text-brand-primary" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M13 10V3L4 14h7v7l9-11h-7z"></path></svg>"#,
                 "Feature Card",
                 "Demonstrates the standard feature card layout with icon circle and description."
             ),
             logo = theme::axur_logo_styled("xl"),
-            footer = helpers::footer_dark(1, "Confidential - Axur Design System"),
+            footer = helpers::footer_dark(1, ctx),
         );
         vec![SlideOutput {
             id: "style_showcase".into(),
@@ -168,7 +168,7 @@ impl SlidePlugin for TakedownsSlidePlugin {
                 )),
                 "gray"
             ),
-            footer = footer_dark(11, &t.get("footer_text")),
+            footer = footer_dark(11, ctx),
         );
 
         vec![SlideOutput {
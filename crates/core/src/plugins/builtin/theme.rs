@@ -3,6 +3,13 @@
 //! Design tokens, typography, and CSS based on Axur.com design system.
 //! Visual identity: "Cyber Noir Intelligence" — see `.agent/skills/axur-design-system/SKILL.md`
 //! Updated with actual colors from axur.com analysis.
+//!
+//! `ReportTheme` carries the per-report overrides (partner colors, fonts,
+//! logo, cover and footer) on top of these defaults.
+
+use crate::plugins::declarative::escape_html;
+use crate::pptx_writer::PptxTheme;
+use serde::{Deserialize, Serialize};
 
 /// Brand colors based on Axur.com analysis
 pub mod colors {
@@ -64,6 +71,229 @@ pub mod typography {
     pub const WEIGHT_EXTRABOLD: u16 = 800;
 }
 
+/// Report theme: colors, fonts, brand mark, cover image and footer text.
+///
+/// Slide plugins read it through `PluginContext::theme()`. Colors and fonts
+/// reach the slides as CSS variables (`--color-primary`, `--font-body`, ...)
+/// that the design system and the Tailwind `orange` palette resolve to. The
+/// logo, brand name and tagline replace the Axur ones only when
+/// `PluginConfig::custom_branding` is set (partner resale).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportTheme {
+    /// Primary accent (hex)
+    pub primary: String,
+    /// Lighter accent for gradients and secondary highlights (hex)
+    pub primary_light: String,
+    /// Panel surface (hex)
+    pub surface: String,
+    /// Slide background (hex); None keeps each slide's own background
+    pub background: Option<String>,
+    /// Heading/body text (hex)
+    pub text: String,
+    /// Secondary text (hex)
+    pub text_muted: String,
+    /// Heading font family (CSS `font-family` value)
+    pub font_display: String,
+    /// Body font family (CSS `font-family` value)
+    pub font_body: String,
+    /// Stylesheet loading the fonts (https URL, e.g. Google Fonts)
+    pub font_url: Option<String>,
+    /// Logo image (https URL or data URI) shown instead of a wordmark
+    pub logo_url: Option<String>,
+    /// Wordmark when there is no logo (defaults to the partner name)
+    pub brand_name: Option<String>,
+    /// Tagline next to the brand in footers
+    pub tagline: Option<String>,
+    /// Cover background image (https URL or data URI); None keeps the Axur cover
+    pub cover_image: Option<String>,
+    /// Footer text; None uses the translated `footer_text`
+    pub footer_text: Option<String>,
+}
+
+impl Default for ReportTheme {
+    /// Axur dark theme
+    fn default() -> Self {
+        Self {
+            primary: colors::ORANGE.to_string(),
+            primary_light: colors::ORANGE_LIGHT.to_string(),
+            surface: "#09090B".to_string(),
+            background: None,
+            text: colors::TEXT_WHITE.to_string(),
+            text_muted: colors::TEXT_MUTED.to_string(),
+            font_display: format!("{}, sans-serif", typography::FONT_DISPLAY),
+            font_body: format!("{}, sans-serif", typography::FONT_BODY),
+            font_url: None,
+            logo_url: None,
+            brand_name: None,
+            tagline: None,
+            cover_image: None,
+            footer_text: None,
+        }
+    }
+}
+
+impl ReportTheme {
+    /// Check colors and URLs before rendering with the theme
+    pub fn validate(&self) -> Result<(), String> {
+        let colors = [
+            ("primary", Some(&self.primary)),
+            ("primary_light", Some(&self.primary_light)),
+            ("surface", Some(&self.surface)),
+            ("background", self.background.as_ref()),
+            ("text", Some(&self.text)),
+            ("text_muted", Some(&self.text_muted)),
+        ];
+        for (name, color) in colors.iter().filter_map(|(n, c)| Some((n, (*c)?))) {
+            if hex_rgb(color).is_none() {
+                return Err(format!("Invalid {} color '{}'", name, color));
+            }
+        }
+        // Fonts go into CSS declarations, URLs into attributes and url("...")
+        let fonts = [&self.font_display, &self.font_body];
+        if fonts
+            .iter()
+            .any(|f| f.contains(['<', '>', '"', '{', '}', ';']))
+        {
+            return Err("Theme fonts cannot contain markup or CSS".to_string());
+        }
+        let urls = [&self.font_url, &self.logo_url, &self.cover_image];
+        for url in urls.iter().filter_map(|url| url.as_deref()) {
+            if url.contains(['<', '>', '"']) {
+                return Err("Theme URLs cannot contain markup".to_string());
+            }
+            // No local files or internal hosts: the PDF renderer loads them
+            let scheme = url.split_once(':').map(|(scheme, _)| scheme.to_lowercase());
+            if !matches!(scheme.as_deref(), Some("https") | Some("data")) {
+                return Err(format!("Theme URL '{}' must be https or a data URI", url));
+            }
+        }
+        // Text is escaped when rendered; keep it to a single short line
+        let texts = [&self.brand_name, &self.tagline, &self.footer_text];
+        if texts
+            .iter()
+            .filter_map(|text| text.as_deref())
+            .any(|text| text.chars().count() > 200 || text.contains(['\n', '\r']))
+        {
+            return Err(
+                "Theme brand, tagline and footer must be one line of up to 200 characters"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// CSS variables for the theme, appended after the design system
+    pub fn css(&self) -> String {
+        let rgb = |color: &str, fallback: (u8, u8, u8)| hex_rgb(color).unwrap_or(fallback);
+        let (r, g, b) = rgb(&self.primary, (255, 103, 31));
+        let (lr, lg, lb) = rgb(&self.primary_light, (255, 138, 76));
+        let (sr, sg, sb) = rgb(&self.surface, (9, 9, 11));
+
+        let mut css = String::new();
+        if let Some(url) = &self.font_url {
+            css.push_str(&format!("@import url(\"{}\");\n", url));
+        }
+        css.push_str(&format!(
+            r#":root {{
+            --color-primary: {primary};
+            --color-primary-rgb: {r}, {g}, {b};
+            --color-primary-channels: {r} {g} {b};
+            --color-primary-light: {primary_light};
+            --color-primary-light-channels: {lr} {lg} {lb};
+            --color-surface: {surface};
+            --color-surface-rgb: {sr}, {sg}, {sb};
+            --color-text: {text};
+            --color-text-muted: {text_muted};
+            --font-display: {font_display};
+            --font-body: {font_body};
+        }}"#,
+            primary = self.primary,
+            primary_light = self.primary_light,
+            surface = self.surface,
+            text = self.text,
+            text_muted = self.text_muted,
+            font_display = self.font_display,
+            font_body = self.font_body,
+        ));
+        if let Some(background) = &self.background {
+            css.push_str(&format!(
+                "\n        #report-content .printable-slide {{ background-color: {}; }}",
+                background
+            ));
+        }
+        css
+    }
+
+    /// White-label brand mark: the logo image, else the brand name (or the
+    /// partner name) as a wordmark, HTML-escaped. Sizes follow
+    /// `axur_logo_styled`.
+    pub fn brand_mark(&self, size: &str, partner_name: Option<&str>) -> String {
+        let (text_size, image_height) = match size {
+            "sm" => ("text-lg", "h-5"),
+            "lg" => ("text-3xl", "h-10"),
+            "xl" => ("text-4xl", "h-12"),
+            _ => ("text-xl", "h-6"),
+        };
+        let name = escape_html(
+            self.brand_name
+                .as_deref()
+                .or(partner_name)
+                .unwrap_or_default(),
+        );
+
+        match &self.logo_url {
+            Some(url) => format!(
+                r#"<img src="{url}" alt="{name}" class="{image_height} w-auto object-contain select-none"/>"#,
+                url = escape_html(url),
+                name = name,
+                image_height = image_height
+            ),
+            None => format!(
+                r#"<div class="flex items-center font-black tracking-wider select-none display-text">
+            <span class="{text_size}">{name}</span>
+        </div>"#,
+                text_size = text_size,
+                name = name
+            ),
+        }
+    }
+
+    /// Colors and font of the theme for native PPTX export
+    pub fn pptx_theme(&self) -> PptxTheme {
+        let hex = |color: &str| color.trim().trim_start_matches('#').to_uppercase();
+        let defaults = PptxTheme::default();
+        PptxTheme {
+            background: self
+                .background
+                .as_deref()
+                .map(hex)
+                .unwrap_or(defaults.background),
+            primary: hex(&self.primary),
+            text: hex(&self.text),
+            muted: hex(&self.text_muted),
+            font: self
+                .font_body
+                .split(',')
+                .next()
+                .map(|f| f.trim().trim_matches(['\'', '"']).to_string())
+                .filter(|f| !f.is_empty())
+                .unwrap_or(defaults.font.clone()),
+            ..defaults
+        }
+    }
+}
+
+/// Parse "#RRGGBB" (or "RRGGBB") into RGB
+pub fn hex_rgb(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
 /// CSS classes for brand styling - Updated Axur.com design system
 /// NOTE: Styles are now injected globally in `html.rs`. This constant is kept for compatibility but empty.
 pub const BRAND_CSS: &str = "";
@@ -432,9 +662,11 @@ pub fn comparison_card(
 /// Use for "Sugerencias de Acción" slides (Act 5: Need-Payoff).
 pub fn action_card(priority: &str, title: &str, description: &str, effort: &str) -> String {
     let priority_color = match priority.to_lowercase().as_str() {
-        "critical" | "crítico" => "bg-red-500/20 text-red-400 border-red-500/30",
-        "high" | "alto" => "bg-orange-500/20 text-orange-400 border-orange-500/30",
-        "medium" | "medio" => "bg-blue-500/20 text-blue-400 border-blue-500/30",
+        "critical" | "crítico" | "crítica" => "bg-red-500/20 text-red-400 border-red-500/30",
+        "high" | "alto" | "alta" => "bg-orange-500/20 text-orange-400 border-orange-500/30",
+        "medium" | "medio" | "media" | "média" => {
+            "bg-blue-500/20 text-blue-400 border-blue-500/30"
+        }
         _ => "bg-zinc-500/20 text-zinc-400 border-zinc-500/30",
     };
     format!(
@@ -482,3 +714,120 @@ pub fn timeline_entry(date: &str, title: &str, description: &str, severity: &str
         description = description,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::report::PocReportData;
+    use crate::i18n::Translations;
+    use crate::plugins::builtin::{ClosingSlidePlugin, CoverSlidePlugin};
+    use crate::plugins::{PluginConfig, PluginContext, SlidePlugin};
+
+    fn partner_theme() -> ReportTheme {
+        ReportTheme {
+            primary: "#1D4ED8".into(),
+            font_body: "'Roboto', sans-serif".into(),
+            logo_url: Some("https://partner.example/logo.png".into()),
+            tagline: Some("Managed Security".into()),
+            cover_image: Some("https://partner.example/cover.jpg".into()),
+            footer_text: Some("Partner SOC - Confidential".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_theme_css_and_validation() {
+        let theme = partner_theme();
+        assert!(theme.validate().is_ok());
+        let css = theme.css();
+        assert!(css.contains("--color-primary: #1D4ED8"));
+        assert!(css.contains("--color-primary-rgb: 29, 78, 216"));
+        assert!(css.contains("--color-primary-channels: 29 78 216"));
+        assert!(css.contains("--font-body: 'Roboto', sans-serif"));
+        assert!(!css.contains("printable-slide"));
+
+        let invalid = ReportTheme {
+            background: Some("navy".into()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let injected = ReportTheme {
+            logo_url: Some("x\" onerror=\"alert(1)".into()),
+            ..Default::default()
+        };
+        assert!(injected.validate().is_err());
+        for url in [
+            "file:///etc/passwd",
+            "http://169.254.169.254/latest",
+            "javascript:alert(1)",
+        ] {
+            let local = ReportTheme {
+                cover_image: Some(url.into()),
+                ..Default::default()
+            };
+            assert!(local.validate().is_err(), "{}", url);
+        }
+        let data_logo = ReportTheme {
+            logo_url: Some("data:image/png;base64,iVBORw0KGgo=".into()),
+            ..Default::default()
+        };
+        assert!(data_logo.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pptx_theme() {
+        let pptx = partner_theme().pptx_theme();
+        assert_eq!(pptx.primary, "1D4ED8");
+        assert_eq!(pptx.font, "Roboto");
+        assert_eq!(pptx.background, PptxTheme::default().background);
+    }
+
+    #[test]
+    fn test_white_label_slides() {
+        let data = PocReportData::demo();
+        let translations = Translations::load("es").unwrap();
+        let ctx = |custom_branding| PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig::default().with_branding(partner_theme(), custom_branding),
+        };
+
+        let branded = ctx(true);
+        let cover = &CoverSlidePlugin.generate_slides(&branded)[0].html;
+        assert!(cover.contains("https://partner.example/cover.jpg"));
+        assert!(cover.contains("https://partner.example/logo.png"));
+        let closing = &ClosingSlidePlugin.generate_slides(&branded)[0].html;
+        assert!(closing.contains("Partner SOC - Confidential"));
+        assert!(closing.contains("Managed Security"));
+        assert!(!closing.contains("Digital Experiences Made Safe"));
+
+        // Without custom branding the Axur brand stays; colors, cover and
+        // footer text still follow the theme
+        let axur = ctx(false);
+        let cover = &CoverSlidePlugin.generate_slides(&axur)[0].html;
+        assert!(cover.contains("https://partner.example/cover.jpg"));
+        assert!(!cover.contains("https://partner.example/logo.png"));
+        let closing = &ClosingSlidePlugin.generate_slides(&axur)[0].html;
+        assert!(closing.contains("Digital Experiences Made Safe"));
+        assert!(closing.contains("Partner SOC - Confidential"));
+
+        // Brand text is escaped, never rendered as markup
+        let injected = ReportTheme {
+            logo_url: None,
+            brand_name: Some("<iframe src=\"file:///etc/passwd\">".into()),
+            tagline: Some("<b>SOC</b>".into()),
+            footer_text: Some("<script>alert(1)</script>".into()),
+            ..partner_theme()
+        };
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig::default().with_branding(injected, true),
+        };
+        let closing = &ClosingSlidePlugin.generate_slides(&ctx)[0].html;
+        assert!(!closing.contains("<iframe") && !closing.contains("<script>"));
+        assert!(closing.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+}
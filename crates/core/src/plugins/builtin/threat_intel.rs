@@ -1,6 +1,7 @@
 //! Threat Intelligence Slide Plugin
 //!
-//! Shows dark web and threat hunting intelligence.
+//! Shows dark web and threat hunting intelligence, and which sources timed
+//! out (their counts are incomplete).
 
 use super::helpers::{footer_dark, format_number};
 use crate::plugins::{PluginContext, SlideOutput, SlidePlugin};
@@ -19,7 +20,8 @@ impl SlidePlugin for ThreatIntelSlidePlugin {
     }
 
     fn is_enabled(&self, ctx: &PluginContext) -> bool {
-        ctx.data.threat_intelligence.data_available
+        let ti = &ctx.data.threat_intelligence;
+        ti.data_available || !ti.timed_out_sources.is_empty()
     }
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
@@ -33,9 +35,22 @@ impl SlidePlugin for ThreatIntelSlidePlugin {
             .map(|s| format!(r#"<li class="text-zinc-300 text-sm">{}</li>"#, s))
             .collect();
 
+        let incomplete_html = if ti.timed_out_sources.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"<div class="mb-6 px-4 py-2 rounded-lg border border-amber-700 bg-amber-950/40 text-amber-300 text-sm">⚠ {}</div>"#,
+                t.format(
+                    "ti_incomplete",
+                    &[("sources", &ti.timed_out_sources.join(", "))]
+                )
+            )
+        };
+
         let html = format!(
-            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-zinc-950 text-white"><div class="flex-grow h-full overflow-hidden"><div class="h-full flex flex-col"><div class="mb-4"><span class="bg-red-800 px-4 py-1 text-sm font-semibold">THREAT INTEL</span></div><h2 class="text-4xl font-bold mb-8">{title}</h2><div class="grid grid-cols-2 gap-8"><div class="space-y-6"><div class="bg-zinc-900 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-red-400 mb-4">{lbl_dark}</h3><div class="grid grid-cols-2 gap-4"><div><p class="text-3xl font-bold">{dark_mentions}</p><p class="text-xs text-zinc-500">{lbl_mentions}</p></div><div><p class="text-3xl font-bold">{credentials}</p><p class="text-xs text-zinc-500">{lbl_creds}</p></div></div></div><div class="bg-zinc-900 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-orange-400 mb-4">{lbl_sources}</h3><ul class="list-disc list-inside">{sources}</ul></div></div><div class="bg-zinc-900/50 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-blue-400 mb-4">{lbl_quality}</h3><div class="space-y-4"><div class="flex justify-between"><span class="text-zinc-400">Stealer Logs</span><span class="font-bold text-red-400">{stealer_pct:.1}%</span></div><div class="flex justify-between"><span class="text-zinc-400">Plain Passwords</span><span class="font-bold text-orange-400">{plain_pct:.1}%</span></div><div class="flex justify-between"><span class="text-zinc-400">Combolists</span><span class="font-bold text-zinc-400">{combo}</span></div></div></div></div></div></div>{footer}</div></div>"#,
+            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-10 md:p-14 shadow-lg mb-8 relative bg-zinc-950 text-white"><div class="flex-grow h-full overflow-hidden"><div class="h-full flex flex-col"><div class="mb-4"><span class="bg-red-800 px-4 py-1 text-sm font-semibold">THREAT INTEL</span></div><h2 class="text-4xl font-bold mb-8">{title}</h2>{incomplete}<div class="grid grid-cols-2 gap-8"><div class="space-y-6"><div class="bg-zinc-900 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-red-400 mb-4">{lbl_dark}</h3><div class="grid grid-cols-2 gap-4"><div><p class="text-3xl font-bold">{dark_mentions}</p><p class="text-xs text-zinc-500">{lbl_mentions}</p></div><div><p class="text-3xl font-bold">{credentials}</p><p class="text-xs text-zinc-500">{lbl_creds}</p></div></div></div><div class="bg-zinc-900 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-orange-400 mb-4">{lbl_sources}</h3><ul class="list-disc list-inside">{sources}</ul></div></div><div class="bg-zinc-900/50 p-6 rounded-xl border border-zinc-800"><h3 class="text-lg font-semibold text-blue-400 mb-4">{lbl_quality}</h3><div class="space-y-4"><div class="flex justify-between"><span class="text-zinc-400">Stealer Logs</span><span class="font-bold text-red-400">{stealer_pct:.1}%</span></div><div class="flex justify-between"><span class="text-zinc-400">Plain Passwords</span><span class="font-bold text-orange-400">{plain_pct:.1}%</span></div><div class="flex justify-between"><span class="text-zinc-400">Combolists</span><span class="font-bold text-zinc-400">{combo}</span></div></div></div></div></div></div>{footer}</div></div>"#,
             title = t.get("ti_title"),
+            incomplete = incomplete_html,
             lbl_dark = t.get("ti_dark_web"),
             dark_mentions = ti.dark_web_mentions,
             lbl_mentions = t.get("ti_mentions"),
@@ -47,7 +62,7 @@ impl SlidePlugin for ThreatIntelSlidePlugin {
             stealer_pct = ti.stealer_log_percent,
             plain_pct = ti.plain_password_percent,
             combo = format_number(ti.combolist_count),
-            footer = footer_dark(16, &t.get("footer_text")),
+            footer = footer_dark(16, ctx),
         );
 
         vec![SlideOutput {
@@ -56,3 +71,40 @@ impl SlidePlugin for ThreatIntelSlidePlugin {
         }]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::report::{PocReportData, ThreatIntelligence};
+    use crate::i18n::Translations;
+    use crate::plugins::PluginConfig;
+
+    #[test]
+    fn test_timed_out_sources_are_shown() {
+        let mut data = PocReportData::demo();
+        data.threat_intelligence = ThreatIntelligence {
+            timed_out_sources: vec!["signal-lake".into(), "forum-message".into()],
+            ..Default::default()
+        };
+        let translations = Translations::load("en").unwrap();
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: "Acme",
+            config: PluginConfig::default(),
+        };
+
+        // Shown even when every search timed out
+        assert!(ThreatIntelSlidePlugin.is_enabled(&ctx));
+        let html = &ThreatIntelSlidePlugin.generate_slides(&ctx)[0].html;
+        assert!(html.contains("signal-lake, forum-message"));
+
+        let mut complete = data.clone();
+        complete.threat_intelligence.timed_out_sources.clear();
+        let ctx = PluginContext {
+            data: &complete,
+            ..ctx
+        };
+        assert!(!ThreatIntelSlidePlugin.is_enabled(&ctx));
+    }
+}
@@ -24,7 +24,6 @@ impl SlidePlugin for ThreatsSlidePlugin {
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let data = ctx.data;
-        let t = ctx.translations;
 
         let total_threats: u64 = data.threats_by_type.iter().map(|t| t.count).sum();
         let max_count = data
@@ -86,7 +85,7 @@ impl SlidePlugin for ThreatsSlidePlugin {
                         <div class="bg-zinc-900/50 p-8 rounded-xl border border-zinc-800 backdrop-blur-sm flex flex-col flex-grow justify-between">
                             <div>
                                 <h3 class="text-xl font-bold text-white mb-6 flex items-center gap-3">
-                                    <span class="text-[#FF671F] bg-[#FF671F]/10 p-2 rounded-lg">
+                                    <span class="text-brand-primary bg-orange-500/10 p-2 rounded-lg">
                                         <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 9v2m0 4h.01m-6.938 4h13.856c1.54 0 2.502-1.667 1.732-3L13.732 4c-.77-1.333-2.694-1.333-3.464 0L3.34 16c-.77 1.333.192 3 1.732 3z"></path></svg>
                                     </span>
                                     AMENAZAS CONFIRMADAS
@@ -103,8 +102,8 @@ impl SlidePlugin for ThreatsSlidePlugin {
                         </div>
 
                         <!-- Top Threat Mention -->
-                        <div class="p-6 rounded-xl border border-[#FF671F]/20 bg-[#FF671F]/5 flex-none">
-                            <h4 class="text-[#FF671F] uppercase text-xs font-bold tracking-wider mb-2">PRINCIPAL VECTOR</h4>
+                        <div class="p-6 rounded-xl border border-orange-500/20 bg-orange-500/5 flex-none">
+                            <h4 class="text-brand-primary uppercase text-xs font-bold tracking-wider mb-2">PRINCIPAL VECTOR</h4>
                             <div class="flex items-end gap-3">
                                 <span class="text-3xl font-bold text-white">{top_name}</span>
                                 <span class="text-xl text-zinc-500 mb-1">{top_count} incidentes</span>
@@ -140,15 +139,9 @@ impl SlidePlugin for ThreatsSlidePlugin {
             top_name = top_name,
             top_count = format_number(top_count),
             bars = bars_html,
-            footer = footer_dark(7, &t.get("footer_text")),
+            footer = footer_dark(7, ctx),
         );
 
-        let html = html
-            .replace("#FF671F", "var(--color-primary)")
-            .replace("bg-[#FF671F]", "bg-brand-primary")
-            .replace("text-[#FF671F]", "text-brand-primary")
-            .replace("border-[#FF671F]", "border-brand-primary");
-
         vec![SlideOutput {
             id: "threats".into(),
             html,
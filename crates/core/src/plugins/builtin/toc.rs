@@ -244,7 +244,7 @@ impl SlidePlugin for TocSlidePlugin {
             count = chapter_count,
             stats = stats_html,
             chapters = chapters_html,
-            footer = footer_dark(2, &t.get("footer_text")),
+            footer = footer_dark(2, ctx),
         );
 
         // Semantic replacements for static brand elements
@@ -256,8 +256,7 @@ impl SlidePlugin for TocSlidePlugin {
             .replace(
                 "from-orange-500 to-transparent",
                 "from-[var(--color-primary)] to-transparent",
-            ) // Underline gradient
-            .replace("bg-orange-500/40", "bg-[rgba(255,103,31,0.4)]"); // Footer dot
+            ); // Underline gradient
 
         vec![SlideOutput {
             id: "toc".into(),
@@ -28,7 +28,6 @@ impl SlidePlugin for VelocitySlidePlugin {
 
     fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
         let data = ctx.data;
-        let t = ctx.translations;
 
         // ─── Calculate distribution buckets ───
         // Try to compute from resolved_takedowns dates, fallback to simulated distribution
@@ -205,7 +204,7 @@ impl SlidePlugin for VelocitySlidePlugin {
             notify_time = data.takedown_median_time_to_notify,
             success_rate = data.takedown_success_rate,
             total_takedowns = total_takedowns,
-            footer = footer_dark(13, &t.get("footer_text")),
+            footer = footer_dark(13, ctx),
         );
 
         vec![SlideOutput {
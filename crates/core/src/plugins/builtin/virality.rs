@@ -137,7 +137,7 @@ impl SlidePlugin for ViralitySlidePlugin {
             insight_text = insight_text,
             insight_color = insight_color,
             insight_title = insight_title,
-            footer = footer_dark(12, &t.get("footer_text")),
+            footer = footer_dark(12, ctx),
         );
 
         vec![SlideOutput {
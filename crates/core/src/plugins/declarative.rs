@@ -0,0 +1,890 @@
+//! Declarative Slide Plugins
+//!
+//! Slides defined in JSON or TOML files and loaded at runtime, so
+//! customer-specific slides don't need a rebuild. A definition picks a
+//! layout and binds its text to report data and translations:
+//!
+//! - `{{credentials_total}}` - a `PocReportData` field (dotted paths and
+//!   array indexes work: `{{roi_metrics.hours_saved_total}}`, `{{top_paths.0.name}}`)
+//! - `{{credentials_total|number}}` - with a format: `number`, `decimal`, `percent`, `raw`
+//! - `{{t:footer_text}}` - a `Translations` key
+//!
+//! ```toml
+//! id = "custom.acme_credentials"
+//! name = "ACME Credential Summary"
+//! priority = 55
+//! enabled_when = ["credentials_total > 0", "unique_hosts >= 3"]
+//!
+//! [layout]
+//! type = "metrics"
+//! badge = "ACME"
+//! title = "{{t:credentials_title}}"
+//!
+//! [[layout.metrics]]
+//! value = "{{credentials_total}}"
+//! label = "Exposed credentials"
+//! highlight = true
+//! ```
+//!
+//! Installed definitions are registered by `PluginRegistry::with_builtins`
+//! next to the builtin slides.
+
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::builtin::helpers::{footer_dark, format_number, geometric_pattern};
+use super::builtin::theme::{section_header_premium, stat_card_glow, stat_card_hero};
+use super::{PluginContext, SlideOutput, SlidePlugin};
+use crate::api::report::PocReportData;
+use crate::i18n::Translations;
+
+/// Errors when loading a slide definition
+#[derive(Error, Debug)]
+pub enum DefinitionError {
+    #[error("Failed to read {0}: {1}")]
+    Io(String, std::io::Error),
+
+    #[error("Invalid JSON definition: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid TOML definition: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Unsupported definition file {0} (expected .json or .toml)")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid slide definition '{id}': {reason}")]
+    Invalid { id: String, reason: String },
+}
+
+/// A slide defined as data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlideDefinition {
+    /// Plugin id (e.g. "custom.acme_credentials"); the `builtin.` prefix is reserved
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Ordering, same scale as builtin plugins (higher = earlier)
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    /// Conditions that must all hold for the slide to render,
+    /// e.g. `"credentials_total > 0"`, `"comparison"`, `"!is_dynamic_window"`
+    #[serde(default)]
+    pub enabled_when: Vec<String>,
+    /// Page number shown in the footer (no footer when unset)
+    #[serde(default)]
+    pub page: Option<usize>,
+    /// Slide content
+    pub layout: SlideLayout,
+}
+
+fn default_priority() -> i32 {
+    50
+}
+
+/// Layouts available to declarative slides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlideLayout {
+    /// Section header and a row of stat cards
+    Metrics {
+        #[serde(default)]
+        badge: String,
+        title: String,
+        #[serde(default)]
+        subtitle: Option<String>,
+        metrics: Vec<MetricBinding>,
+    },
+    /// Section header and a ranked list from an array field
+    List {
+        #[serde(default)]
+        badge: String,
+        title: String,
+        #[serde(default)]
+        subtitle: Option<String>,
+        /// Path of an array field (e.g. "secret_types", "top_paths")
+        items: String,
+        /// Item field shown as the label
+        #[serde(default = "default_label_field")]
+        label_field: String,
+        /// Item field shown as the value
+        #[serde(default = "default_value_field")]
+        value_field: String,
+        #[serde(default = "default_list_limit")]
+        limit: usize,
+    },
+    /// Free-form slide body (trusted HTML) with placeholders
+    Html { body: String },
+}
+
+fn default_label_field() -> String {
+    "name".to_string()
+}
+
+fn default_value_field() -> String {
+    "value".to_string()
+}
+
+fn default_list_limit() -> usize {
+    8
+}
+
+/// One stat card of a metrics layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricBinding {
+    pub value: String,
+    pub label: String,
+    #[serde(default)]
+    pub sublabel: Option<String>,
+    /// Render as the hero card (use for one metric per slide)
+    #[serde(default)]
+    pub highlight: bool,
+}
+
+impl SlideDefinition {
+    /// Parse a JSON definition
+    pub fn from_json(source: &str) -> Result<Self, DefinitionError> {
+        Ok(serde_json::from_str(source)?)
+    }
+
+    /// Parse a TOML definition
+    pub fn from_toml(source: &str) -> Result<Self, DefinitionError> {
+        Ok(toml::from_str(source)?)
+    }
+
+    /// Read a `.json` or `.toml` definition file
+    pub fn from_file(path: &Path) -> Result<Self, DefinitionError> {
+        let display = path.display().to_string();
+        let source =
+            std::fs::read_to_string(path).map_err(|e| DefinitionError::Io(display.clone(), e))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&source),
+            Some("toml") => Self::from_toml(&source),
+            _ => Err(DefinitionError::UnsupportedFormat(display)),
+        }
+    }
+
+    /// Every template string of the definition
+    fn templates(&self) -> Vec<&str> {
+        let mut templates = Vec::new();
+        match &self.layout {
+            SlideLayout::Metrics {
+                badge,
+                title,
+                subtitle,
+                metrics,
+            } => {
+                templates.extend([badge.as_str(), title.as_str()]);
+                templates.extend(subtitle.as_deref());
+                for metric in metrics {
+                    templates.extend([metric.value.as_str(), metric.label.as_str()]);
+                    templates.extend(metric.sublabel.as_deref());
+                }
+            }
+            SlideLayout::List {
+                badge,
+                title,
+                subtitle,
+                ..
+            } => {
+                templates.extend([badge.as_str(), title.as_str()]);
+                templates.extend(subtitle.as_deref());
+            }
+            SlideLayout::Html { body } => templates.push(body),
+        }
+        templates
+    }
+}
+
+/// A slide plugin built from a `SlideDefinition`
+#[derive(Debug, Clone)]
+pub struct DeclarativeSlidePlugin {
+    definition: SlideDefinition,
+    conditions: Vec<Condition>,
+}
+
+impl DeclarativeSlidePlugin {
+    /// Validate a definition: id, conditions and placeholders are checked
+    /// against the `PocReportData` fields.
+    pub fn new(definition: SlideDefinition) -> Result<Self, DefinitionError> {
+        let invalid = |reason: String| DefinitionError::Invalid {
+            id: definition.id.clone(),
+            reason,
+        };
+
+        if definition.id.trim().is_empty() {
+            return Err(invalid("id is empty".into()));
+        }
+        if definition.id.starts_with("builtin.") {
+            return Err(invalid("the 'builtin.' prefix is reserved".into()));
+        }
+
+        let fields = report_fields();
+        let check_path = |path: &str| -> Result<(), DefinitionError> {
+            let root = path.split('.').next().unwrap_or_default();
+            if fields.contains(&root.to_string()) {
+                Ok(())
+            } else {
+                Err(invalid(format!("unknown report field '{}'", root)))
+            }
+        };
+
+        let conditions = definition
+            .enabled_when
+            .iter()
+            .map(|c| Condition::parse(c).map_err(&invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        for condition in &conditions {
+            check_path(&condition.path)?;
+        }
+
+        for template in definition.templates() {
+            for placeholder in placeholders(template).map_err(&invalid)? {
+                if let Placeholder::Field { path, .. } = placeholder {
+                    check_path(&path)?;
+                }
+            }
+        }
+        if let SlideLayout::List { items, .. } = &definition.layout {
+            check_path(items)?;
+        }
+
+        Ok(Self {
+            definition,
+            conditions,
+        })
+    }
+
+    /// Load and validate a definition file
+    pub fn from_file(path: &Path) -> Result<Self, DefinitionError> {
+        Self::new(SlideDefinition::from_file(path)?)
+    }
+
+    /// The underlying definition
+    pub fn definition(&self) -> &SlideDefinition {
+        &self.definition
+    }
+
+    fn render(&self, ctx: &PluginContext, data: &Value) -> String {
+        let fill = |template: &str| fill_template(template, data, ctx.translations);
+        let header = |badge: &str, title: &str, subtitle: &Option<String>| {
+            section_header_premium(
+                &fill(badge),
+                &fill(title),
+                subtitle.as_deref().map(&fill).as_deref(),
+            )
+        };
+
+        let body = match &self.definition.layout {
+            SlideLayout::Metrics {
+                badge,
+                title,
+                subtitle,
+                metrics,
+            } => {
+                let cards: String = metrics
+                    .iter()
+                    .map(|m| {
+                        let value = fill(&m.value);
+                        let label = fill(&m.label);
+                        if m.highlight {
+                            let sublabel = m.sublabel.as_deref().map(&fill);
+                            stat_card_hero(&value, &label, sublabel.as_deref())
+                        } else {
+                            stat_card_glow(&value, &label, false)
+                        }
+                    })
+                    .collect();
+                let columns = metrics.len().clamp(1, 4);
+                format!(
+                    r#"{header}
+                <div class="grid grid-cols-{columns} gap-8 flex-grow items-center relative z-10">{cards}</div>"#,
+                    header = header(badge, title, subtitle),
+                )
+            }
+            SlideLayout::List {
+                badge,
+                title,
+                subtitle,
+                items,
+                label_field,
+                value_field,
+                limit,
+            } => {
+                let rows: String = lookup(data, items)
+                    .and_then(Value::as_array)
+                    .map(|items| {
+                        items
+                            .iter()
+                            .take(*limit)
+                            .enumerate()
+                            .map(|(i, item)| {
+                                let label =
+                                    item.get(label_field).map(display_value).unwrap_or_default();
+                                let value =
+                                    item.get(value_field).map(display_value).unwrap_or_default();
+                                format!(
+                                    r#"<div class="flex items-center justify-between border-b border-zinc-800 py-3">
+                        <span class="flex items-center gap-4"><span class="font-mono text-orange-500 text-sm">{rank:02}</span><span class="text-zinc-200">{label}</span></span>
+                        <span class="font-mono text-white text-lg">{value}</span>
+                    </div>"#,
+                                    rank = i + 1,
+                                    label = escape_html(&label),
+                                    value = escape_html(&value),
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                format!(
+                    r#"{header}
+                <div class="glass-panel p-8 flex flex-col relative z-10">{rows}</div>"#,
+                    header = header(badge, title, subtitle),
+                )
+            }
+            SlideLayout::Html { body } => fill(body),
+        };
+
+        let footer = self
+            .definition
+            .page
+            .map(|page| footer_dark(page, ctx))
+            .unwrap_or_default();
+
+        format!(
+            r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full flex flex-col p-14 mb-8 relative bg-zinc-950 text-white overflow-hidden">
+                {pattern}
+                {body}
+                {footer}
+            </div></div>"#,
+            pattern = geometric_pattern(),
+        )
+    }
+}
+
+impl SlidePlugin for DeclarativeSlidePlugin {
+    fn id(&self) -> &str {
+        &self.definition.id
+    }
+
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.definition.priority
+    }
+
+    fn is_enabled(&self, ctx: &PluginContext) -> bool {
+        if !ctx.config.is_plugin_enabled(self.id()) {
+            return false;
+        }
+        if self.conditions.is_empty() {
+            return true;
+        }
+        let data = report_value(ctx.data);
+        self.conditions.iter().all(|c| c.holds(&data))
+    }
+
+    fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
+        let data = report_value(ctx.data);
+        vec![SlideOutput {
+            id: self.definition.id.clone(),
+            html: self.render(ctx, &data),
+        }]
+    }
+}
+
+/// Load every `.json` and `.toml` definition in a directory (sorted by file name)
+pub fn load_dir(dir: &Path) -> Result<Vec<DeclarativeSlidePlugin>, DefinitionError> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| DefinitionError::Io(dir.display().to_string(), e))?;
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("json") | Some("toml")
+            )
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| DeclarativeSlidePlugin::from_file(path))
+        .collect()
+}
+
+/// Declarative slides registered by `PluginRegistry::with_builtins`
+fn installed() -> &'static RwLock<Vec<DeclarativeSlidePlugin>> {
+    static INSTALLED: OnceLock<RwLock<Vec<DeclarativeSlidePlugin>>> = OnceLock::new();
+    INSTALLED.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Replace the declarative slides included in every builtin registry
+pub fn install(plugins: Vec<DeclarativeSlidePlugin>) {
+    if let Ok(mut installed) = installed().write() {
+        *installed = plugins;
+    }
+}
+
+/// Currently installed declarative slides
+pub fn installed_plugins() -> Vec<DeclarativeSlidePlugin> {
+    installed()
+        .read()
+        .map(|plugins| plugins.clone())
+        .unwrap_or_default()
+}
+
+// ========================
+// DATA BINDING
+// ========================
+
+/// Report data as JSON, the shape field paths refer to
+pub(crate) fn report_value(data: &PocReportData) -> Value {
+    serde_json::to_value(data).unwrap_or(Value::Null)
+}
+
+/// Top-level `PocReportData` field names
+pub(crate) fn report_fields() -> Vec<String> {
+    match report_value(&PocReportData::demo()) {
+        Value::Object(map) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve a dotted path ("roi_metrics.hours_saved_total", "top_paths.0.name")
+pub(crate) fn lookup<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(data, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Default text for a value
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_u64() {
+            Some(n) => format_number(n),
+            None => {
+                let f = n.as_f64().unwrap_or_default();
+                if f.fract() == 0.0 {
+                    format!("{:.0}", f)
+                } else {
+                    format!("{:.1}", f)
+                }
+            }
+        },
+        Value::Array(items) => format_number(items.len() as u64),
+        other => other.to_string(),
+    }
+}
+
+fn format_value(value: &Value, format: Option<&str>) -> String {
+    match (format, value.as_f64()) {
+        (Some("number"), Some(n)) => format_number(n.max(0.0).round() as u64),
+        (Some("decimal"), Some(n)) => format!("{:.1}", n),
+        (Some("percent"), Some(n)) => format!("{:.1}%", n),
+        (Some("raw"), _) => match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        },
+        _ => display_value(value),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Placeholder {
+    Field {
+        path: String,
+        format: Option<String>,
+    },
+    Translation(String),
+}
+
+impl Placeholder {
+    fn parse(inner: &str) -> Result<Self, String> {
+        let inner = inner.trim();
+        if let Some(key) = inner.strip_prefix("t:") {
+            return Ok(Placeholder::Translation(key.trim().to_string()));
+        }
+
+        let (path, format) = match inner.split_once('|') {
+            Some((path, format)) => (path.trim(), Some(format.trim().to_string())),
+            None => (inner, None),
+        };
+        if path.is_empty() {
+            return Err("empty placeholder".into());
+        }
+        if let Some(f) = &format {
+            if !matches!(f.as_str(), "number" | "decimal" | "percent" | "raw") {
+                return Err(format!("unknown format '{}'", f));
+            }
+        }
+        Ok(Placeholder::Field {
+            path: path.to_string(),
+            format,
+        })
+    }
+}
+
+/// Split a template into literal text and placeholders
+fn segments(template: &str) -> Result<Vec<(&str, Option<Placeholder>)>, String> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| format!("unclosed placeholder in '{}'", template))?;
+        let placeholder = Placeholder::parse(&rest[start + 2..start + end])?;
+        out.push((&rest[..start], Some(placeholder)));
+        rest = &rest[start + end + 2..];
+    }
+    out.push((rest, None));
+    Ok(out)
+}
+
+fn placeholders(template: &str) -> Result<Vec<Placeholder>, String> {
+    Ok(segments(template)?
+        .into_iter()
+        .filter_map(|(_, placeholder)| placeholder)
+        .collect())
+}
+
+/// Replace placeholders with escaped data values and translations.
+/// Literal template text is trusted and kept as-is.
+pub(crate) fn fill_template(template: &str, data: &Value, translations: &Translations) -> String {
+    let Ok(segments) = segments(template) else {
+        return template.to_string();
+    };
+
+    let mut out = String::with_capacity(template.len());
+    for (literal, placeholder) in segments {
+        out.push_str(literal);
+        let text = match placeholder {
+            Some(Placeholder::Field { path, format }) => lookup(data, &path)
+                .map(|v| format_value(v, format.as_deref()))
+                .unwrap_or_else(|| "-".to_string()),
+            Some(Placeholder::Translation(key)) => translations.get(&key),
+            None => continue,
+        };
+        out.push_str(&escape_html(&text));
+    }
+    out
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ========================
+// CONDITIONS
+// ========================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+/// `path`, `!path` or `path <op> literal`
+#[derive(Debug, Clone)]
+pub(crate) struct Condition {
+    pub(crate) path: String,
+    test: Test,
+}
+
+#[derive(Debug, Clone)]
+enum Test {
+    Truthy,
+    Falsy,
+    Compare(Op, Value),
+}
+
+impl Condition {
+    pub(crate) fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        const OPS: [(&str, Op); 6] = [
+            (">=", Op::Ge),
+            ("<=", Op::Le),
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            (">", Op::Gt),
+            ("<", Op::Lt),
+        ];
+
+        for (token, op) in OPS {
+            if let Some((path, literal)) = source.split_once(token) {
+                let path = path.trim();
+                let literal = literal.trim();
+                if path.is_empty() || literal.is_empty() {
+                    return Err(format!("incomplete condition '{}'", source));
+                }
+                let value = parse_literal(literal)
+                    .ok_or_else(|| format!("invalid value '{}' in '{}'", literal, source))?;
+                return Ok(Condition {
+                    path: path.to_string(),
+                    test: Test::Compare(op, value),
+                });
+            }
+        }
+
+        let (path, test) = match source.strip_prefix('!') {
+            Some(path) => (path.trim(), Test::Falsy),
+            None => (source, Test::Truthy),
+        };
+        if path.is_empty() || path.contains(char::is_whitespace) {
+            return Err(format!("invalid condition '{}'", source));
+        }
+        Ok(Condition {
+            path: path.to_string(),
+            test,
+        })
+    }
+
+    pub(crate) fn holds(&self, data: &Value) -> bool {
+        let value = lookup(data, &self.path).unwrap_or(&Value::Null);
+        match &self.test {
+            Test::Truthy => truthy(value),
+            Test::Falsy => !truthy(value),
+            Test::Compare(op, expected) => compare(value, *op, expected),
+        }
+    }
+}
+
+fn parse_literal(literal: &str) -> Option<Value> {
+    if let Ok(n) = literal.parse::<f64>() {
+        return serde_json::Number::from_f64(n).map(Value::Number);
+    }
+    match literal {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "null" => Some(Value::Null),
+        _ => {
+            let quoted = literal
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .or_else(|| {
+                    literal
+                        .strip_prefix('\'')
+                        .and_then(|s| s.strip_suffix('\''))
+                })?;
+            Some(Value::String(quoted.to_string()))
+        }
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().unwrap_or_default() != 0.0,
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+/// Numbers compare numerically, arrays and strings by length against a number
+fn compare(value: &Value, op: Op, expected: &Value) -> bool {
+    if let Some(rhs) = expected.as_f64() {
+        let lhs = match value {
+            Value::Number(n) => n.as_f64(),
+            Value::Array(items) => Some(items.len() as f64),
+            Value::String(s) => Some(s.chars().count() as f64),
+            _ => None,
+        };
+        let Some(lhs) = lhs else {
+            return false;
+        };
+        return match op {
+            Op::Gt => lhs > rhs,
+            Op::Ge => lhs >= rhs,
+            Op::Lt => lhs < rhs,
+            Op::Le => lhs <= rhs,
+            Op::Eq => lhs == rhs,
+            Op::Ne => lhs != rhs,
+        };
+    }
+
+    match op {
+        Op::Eq => value == expected,
+        Op::Ne => value != expected,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::PluginConfig;
+
+    const TOML_DEFINITION: &str = r#"
+id = "custom.acme_credentials"
+name = "ACME Credential Summary"
+priority = 55
+enabled_when = ["credentials_total > 0"]
+page = 12
+
+[layout]
+type = "metrics"
+badge = "ACME"
+title = "{{t:footer_text}}"
+
+[[layout.metrics]]
+value = "{{credentials_total}}"
+label = "Exposed credentials"
+highlight = true
+
+[[layout.metrics]]
+value = "{{roi_metrics.hours_saved_total|number}}h"
+label = "Hours saved"
+"#;
+
+    fn render(plugin: &DeclarativeSlidePlugin, data: &PocReportData) -> Option<String> {
+        let translations = Translations::load("en").unwrap();
+        let ctx = PluginContext {
+            data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig::default(),
+        };
+        plugin
+            .is_enabled(&ctx)
+            .then(|| plugin.generate_slides(&ctx).remove(0).html)
+    }
+
+    #[test]
+    fn test_toml_metrics_slide() {
+        let plugin =
+            DeclarativeSlidePlugin::new(SlideDefinition::from_toml(TOML_DEFINITION).unwrap())
+                .unwrap();
+        assert_eq!(plugin.id(), "custom.acme_credentials");
+        assert_eq!(plugin.priority(), 55);
+
+        let mut data = PocReportData::demo();
+        data.credentials_total = 1234;
+        data.roi_metrics.hours_saved_total = 1250.4;
+
+        let html = render(&plugin, &data).unwrap();
+        assert!(html.contains("1,234"));
+        assert!(html.contains("1,250h"));
+        assert!(html.contains("ACME"));
+
+        data.credentials_total = 0;
+        assert!(render(&plugin, &data).is_none());
+    }
+
+    #[test]
+    fn test_json_list_slide_escapes_values() {
+        let definition = SlideDefinition::from_json(
+            r#"{
+                "id": "custom.secret_types",
+                "name": "Secret Types",
+                "enabled_when": ["secret_types > 0"],
+                "layout": { "type": "list", "title": "Secrets", "items": "secret_types", "limit": 2 }
+            }"#,
+        )
+        .unwrap();
+        let plugin = DeclarativeSlidePlugin::new(definition).unwrap();
+
+        let mut data = PocReportData::demo();
+        data.secret_types = vec![
+            crate::api::report::NameValuePair {
+                name: "<script>".to_string(),
+                value: 7,
+            },
+            crate::api::report::NameValuePair {
+                name: "aws_key".to_string(),
+                value: 3,
+            },
+            crate::api::report::NameValuePair {
+                name: "slack_token".to_string(),
+                value: 1,
+            },
+        ];
+
+        let html = render(&plugin, &data).unwrap();
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("aws_key"));
+        assert!(!html.contains("slack_token"));
+
+        data.secret_types.clear();
+        assert!(render(&plugin, &data).is_none());
+    }
+
+    #[test]
+    fn test_invalid_definitions_are_rejected() {
+        let with_layout = |id: &str, condition: &str, body: &str| SlideDefinition {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            priority: 50,
+            enabled_when: vec![condition.to_string()],
+            page: None,
+            layout: SlideLayout::Html {
+                body: body.to_string(),
+            },
+        };
+
+        assert!(DeclarativeSlidePlugin::new(with_layout("custom.ok", "total_tickets", "")).is_ok());
+        assert!(
+            DeclarativeSlidePlugin::new(with_layout("builtin.cover", "total_tickets", "")).is_err()
+        );
+        assert!(
+            DeclarativeSlidePlugin::new(with_layout("custom.x", "no_such_field > 1", "")).is_err()
+        );
+        assert!(
+            DeclarativeSlidePlugin::new(with_layout("custom.x", "total_tickets >", "")).is_err()
+        );
+        assert!(
+            DeclarativeSlidePlugin::new(with_layout("custom.x", "total_tickets", "{{oops"))
+                .is_err()
+        );
+        assert!(DeclarativeSlidePlugin::new(with_layout(
+            "custom.x",
+            "total_tickets",
+            "{{total_tickets|money}}"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_conditions() {
+        let data = serde_json::json!({
+            "count": 5,
+            "name": "acme",
+            "items": [1, 2],
+            "flag": false,
+            "nested": { "rate": 92.5 }
+        });
+        let holds = |c: &str| Condition::parse(c).unwrap().holds(&data);
+
+        assert!(holds("count > 0"));
+        assert!(holds("count >= 5"));
+        assert!(!holds("count < 5"));
+        assert!(holds("nested.rate > 90"));
+        assert!(holds("items == 2"));
+        assert!(holds("name == \"acme\""));
+        assert!(holds("name != 'other'"));
+        assert!(holds("!flag"));
+        assert!(!holds("missing"));
+    }
+}
@@ -31,10 +31,14 @@ mod registry;
 mod traits;
 
 pub mod builtin;
+pub mod declarative;
 
 pub use traits::{
     CloudExportOutput, CloudExportPlugin, DataPlugin, ExportPlugin, PluginConfig, PluginContext,
-    SlideOutput, SlidePlugin, ThemeMode,
+    SlideOutput, SlideOverride, SlidePlugin, ThemeMode,
 };
 
+pub use builtin::annotations::{HighlightedTicket, ReportAnnotations};
+pub use builtin::theme::ReportTheme;
+pub use declarative::{DeclarativeSlidePlugin, SlideDefinition};
 pub use registry::{PluginRegistry, RegistryStats};
@@ -2,8 +2,10 @@
 //!
 //! Manages registration and execution of plugins.
 
+use super::declarative::escape_html;
 use super::traits::{
-    CloudExportPlugin, DataPlugin, ExportPlugin, PluginContext, SlideOutput, SlidePlugin,
+    CloudExportPlugin, DataPlugin, ExportPlugin, PluginConfig, PluginContext, SlideOutput,
+    SlideOverride, SlidePlugin,
 };
 use crate::api::report::PocReportData;
 
@@ -41,6 +43,7 @@ impl PluginRegistry {
         registry.register_slide(Box::new(IntroSlidePlugin));
         registry.register_slide(Box::new(SolutionsSlidePlugin));
         registry.register_slide(Box::new(TocSlidePlugin));
+        registry.register_slide(Box::new(AnalystSummarySlidePlugin));
         registry.register_slide(Box::new(PocDataSlidePlugin));
         registry.register_slide(Box::new(MetricsSlidePlugin));
         registry.register_slide(Box::new(ComparativeSlidePlugin)); // NEW: Comparative analysis
@@ -62,8 +65,18 @@ impl PluginRegistry {
         registry.register_slide(Box::new(TakedownExamplesSlidePlugin));
         registry.register_slide(Box::new(PocExamplesSlidePlugin));
         registry.register_slide(Box::new(InsightsSlidePlugin)); // NEW: Insights & Recommendations
+        registry.register_slide(Box::new(AnalystRecommendationsSlidePlugin));
         registry.register_slide(Box::new(StyleShowcasePlugin)); // NEW: Style Showcase
         registry.register_slide(Box::new(ClosingSlidePlugin));
+        // Runtime-defined slides (JSON/TOML), see `declarative::install`
+        for plugin in super::declarative::installed_plugins() {
+            registry.register_slide(Box::new(plugin));
+        }
+        registry.register_data(Box::new(DeepAnalyticsDataPlugin));
+        registry.register_data(Box::new(RiskScoreDataPlugin));
+        registry.register_data(Box::new(OperationalMetricsDataPlugin));
+        registry.register_export(Box::new(PdfExportPlugin::new()));
+        registry.register_export(Box::new(PptxExportPlugin::new()));
         registry
     }
 
@@ -127,12 +140,53 @@ impl PluginRegistry {
         }
     }
 
-    /// Generate all slides from registered plugins
+    /// Run the data plugins not disabled in `config`
+    pub fn transform_data_with_config(&self, data: &mut PocReportData, config: &PluginConfig) {
+        for plugin in &self.data_plugins {
+            if config.is_plugin_enabled(plugin.id()) {
+                plugin.transform_with_config(data, config);
+            }
+        }
+    }
+
+    /// Generate all slides from registered plugins, in `config.plugin_order`
+    /// when set (priority order otherwise)
     pub fn generate_slides(&self, ctx: &PluginContext) -> Vec<SlideOutput> {
-        self.slide_plugins
+        let order = &ctx.config.plugin_order;
+        let mut plugins: Vec<&dyn SlidePlugin> = self
+            .slide_plugins
             .iter()
+            .map(|p| p.as_ref())
             .filter(|p| p.is_enabled(ctx))
-            .flat_map(|p| p.generate_slides(ctx))
+            .collect();
+        if !order.is_empty() {
+            // Stable sort: unlisted plugins keep their priority order at the end
+            plugins.sort_by_key(|p| {
+                order
+                    .iter()
+                    .position(|id| id == p.id())
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        plugins
+            .into_iter()
+            .flat_map(|p| {
+                let mut slides = p.generate_slides(ctx);
+                let slide_override = ctx
+                    .config
+                    .slide_overrides
+                    .get(p.id())
+                    .filter(|o| !o.is_empty());
+                if let Some(slide_override) = slide_override {
+                    let note_label = ctx.translations.get("label_analyst_note");
+                    for (i, slide) in slides.iter_mut().enumerate() {
+                        // The note is pinned once, on the plugin's first slide
+                        let note = slide_override.commentary.as_deref().filter(|_| i == 0);
+                        slide.html = apply_override(&slide.html, slide_override, note, &note_label);
+                    }
+                }
+                slides
+            })
             .collect()
     }
 
@@ -147,6 +201,95 @@ impl PluginRegistry {
     }
 }
 
+/// Position of the first `<h1>`/`<h2>` of a slide: (tag, open start,
+/// content start, close start)
+fn first_heading(html: &str) -> Option<(&'static str, usize, usize, usize)> {
+    ["h1", "h2"]
+        .into_iter()
+        .filter_map(|tag| {
+            let pattern = format!("<{}", tag);
+            let open = html.match_indices(&pattern).find(|(i, m)| {
+                html[i + m.len()..].starts_with(|c: char| c == '>' || c.is_whitespace())
+            })?;
+            let content = open.0 + html[open.0..].find('>')? + 1;
+            let close = content + html[content..].find(&format!("</{}>", tag))?;
+            Some((tag, open.0, content, close))
+        })
+        .min_by_key(|(_, open, _, _)| *open)
+}
+
+/// Apply a slide override to rendered slide HTML: the first heading gets the
+/// new title and the subtitle is inserted after it (or both go at the top of
+/// the slide when it has no heading); the note is pinned to the slide corner.
+fn apply_override(
+    html: &str,
+    slide_override: &SlideOverride,
+    note: Option<&str>,
+    note_label: &str,
+) -> String {
+    let text = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(escape_html)
+    };
+    let title = text(&slide_override.title);
+    let subtitle = text(&slide_override.subtitle).map(|s| {
+        format!(
+            r#"<p class="slide-subtitle text-zinc-400 text-lg mt-3 max-w-3xl relative z-10">{}</p>"#,
+            s
+        )
+    });
+    let mut html = html.to_string();
+
+    match first_heading(&html) {
+        Some((tag, _, content, close)) => {
+            let after_close = close + tag.len() + 3;
+            if let Some(subtitle) = &subtitle {
+                html.insert_str(after_close, subtitle);
+            }
+            if let Some(title) = &title {
+                html.replace_range(content..close, title);
+            }
+        }
+        None if title.is_some() || subtitle.is_some() => {
+            let header = format!(
+                r#"<div class="slide-override-header mb-6 relative z-10">{}{}</div>"#,
+                title
+                    .map(|t| format!(
+                        r#"<h2 class="section-header text-4xl leading-tight display-text">{}</h2>"#,
+                        t
+                    ))
+                    .unwrap_or_default(),
+                subtitle.unwrap_or_default()
+            );
+            insert_in_slide(&mut html, &header);
+        }
+        None => {}
+    }
+
+    if let Some(note) = note.map(str::trim).filter(|n| !n.is_empty()) {
+        let aside = format!(
+            r#"<aside class="slide-commentary absolute top-6 right-6 z-40 max-w-sm rounded-lg border border-orange-500/40 bg-zinc-950/90 p-4 shadow-lg"><p class="label-text text-brand-primary mb-1">{}</p><p class="text-sm text-zinc-200 leading-relaxed whitespace-pre-line">{}</p></aside>"#,
+            escape_html(note_label),
+            escape_html(note)
+        );
+        insert_in_slide(&mut html, &aside);
+    }
+    html
+}
+
+/// Insert markup as the first child of the `printable-slide` element
+/// (or at the start of the HTML for slides without one)
+fn insert_in_slide(html: &mut String, markup: &str) {
+    let at = html
+        .find("printable-slide")
+        .and_then(|i| html[i..].find('>').map(|end| i + end + 1))
+        .unwrap_or(0);
+    html.insert_str(at, markup);
+}
+
 /// Statistics about registered plugins
 #[derive(Debug)]
 pub struct RegistryStats {
@@ -172,11 +315,100 @@ mod tests {
     #[test]
     fn test_registry_with_builtins() {
         let registry = PluginRegistry::with_builtins();
-        // 27 builtin slide plugins registered (incl. StyleShowcase + KillChain + Velocity)
-        assert_eq!(registry.slide_plugins().len(), 27);
+        // 29 builtin slide plugins registered (incl. StyleShowcase + KillChain + Velocity
+        // and the two analyst annotation slides)
+        assert_eq!(registry.slide_plugins().len(), 29);
         // Verify ordering by priority (StyleShowcase=999 should be first, cover=100 second)
         assert_eq!(registry.slide_plugins()[0].id(), "builtin.style_showcase");
         assert_eq!(registry.slide_plugins()[1].id(), "builtin.cover");
-        assert_eq!(registry.slide_plugins()[26].id(), "builtin.closing");
+        assert_eq!(registry.slide_plugins()[28].id(), "builtin.closing");
+        assert!(registry.export_plugin("pdf").is_some());
+        assert!(registry.export_plugin("pptx").is_some());
+        // Enrichment data plugins, ROI last since it reads the final counts
+        assert_eq!(registry.data_plugins().len(), 3);
+        assert_eq!(
+            registry.data_plugins()[2].id(),
+            "builtin.data.operational_metrics"
+        );
+    }
+
+    #[test]
+    fn test_plugin_order() {
+        let registry = PluginRegistry::with_builtins();
+        let data = PocReportData::demo();
+        let translations = crate::i18n::Translations::load("es").unwrap();
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig {
+                plugin_order: vec!["builtin.closing".into(), "builtin.metrics".into()],
+                ..Default::default()
+            },
+        };
+
+        let slides = registry.generate_slides(&ctx);
+        let closing = registry
+            .slide_plugins()
+            .iter()
+            .find(|p| p.id() == "builtin.closing")
+            .unwrap()
+            .generate_slides(&ctx);
+        assert_eq!(slides[0].id, closing[0].id);
+        assert!(slides.len() > 2);
+    }
+
+    #[test]
+    fn test_slide_overrides() {
+        let registry = PluginRegistry::with_builtins();
+        let data = PocReportData::demo();
+        let translations = crate::i18n::Translations::load("en").unwrap();
+        let mut slide_overrides = std::collections::HashMap::new();
+        slide_overrides.insert(
+            "builtin.metrics".to_string(),
+            SlideOverride {
+                title: Some("Q1 <Overview>".into()),
+                subtitle: Some("Shared with the board".into()),
+                commentary: Some("Phishing volume doubled after the campaign.".into()),
+            },
+        );
+        let ctx = PluginContext {
+            data: &data,
+            translations: &translations,
+            tenant_name: &data.company_name,
+            config: PluginConfig {
+                slide_overrides,
+                ..Default::default()
+            },
+        };
+
+        let slides = registry.generate_slides(&ctx);
+        let metrics = slides.iter().find(|s| s.id == "metrics").unwrap();
+        assert!(metrics.html.contains("Q1 &lt;Overview&gt;</h"));
+        assert!(metrics.html.contains("Shared with the board"));
+        assert!(metrics.html.contains("ANALYST NOTE"));
+        assert!(metrics.html.contains("Phishing volume doubled"));
+        assert_eq!(
+            slides
+                .iter()
+                .filter(|s| s.html.contains("slide-commentary"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_override_without_heading() {
+        let html = r#"<div class="relative group"><div class="printable-slide p-10"><p>Body</p></div></div>"#;
+        let slide_override = SlideOverride {
+            title: Some("Custom".into()),
+            ..Default::default()
+        };
+        let out = apply_override(html, &slide_override, Some("Note"), "NOTE");
+        assert!(out.starts_with(
+            r#"<div class="relative group"><div class="printable-slide p-10"><aside"#
+        ));
+        assert!(out.contains(r#"display-text">Custom</h2>"#));
+        assert!(out.find("slide-commentary") < out.find("slide-override-header"));
     }
 }
@@ -3,8 +3,15 @@
 //! This module defines the interfaces for extending report functionality
 //! without modifying core code.
 
+use crate::api::insights::InsightRulePack;
 use crate::api::report::PocReportData;
+use crate::api::risk::RiskModel;
 use crate::i18n::Translations;
+use crate::plugins::builtin::annotations::ReportAnnotations;
+use crate::plugins::builtin::theme::{self, ReportTheme};
+use crate::plugins::declarative::escape_html;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Output from a slide plugin
 #[derive(Debug, Clone)]
@@ -27,8 +34,38 @@ pub struct PluginContext<'a> {
     pub config: PluginConfig,
 }
 
+impl PluginContext<'_> {
+    /// Report theme (colors, fonts, brand, cover and footer)
+    pub fn theme(&self) -> &ReportTheme {
+        &self.config.branding
+    }
+
+    /// Brand mark for slide headers: the theme brand when custom branding
+    /// is enabled, the Axur logo otherwise
+    pub fn brand_logo(&self, size: &str) -> String {
+        if self.config.custom_branding {
+            self.theme()
+                .brand_mark(size, self.data.partner_name.as_deref())
+        } else {
+            theme::axur_logo_styled(size)
+        }
+    }
+
+    /// Footer text for slide HTML (escaped): the theme override, else the
+    /// translated default (which presets can override too)
+    pub fn footer_text(&self) -> String {
+        let text = self
+            .theme()
+            .footer_text
+            .clone()
+            .unwrap_or_else(|| self.translations.get("footer_text"));
+        escape_html(&text)
+    }
+}
+
 /// Theme mode for report generation
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ThemeMode {
     /// Dark theme (default) - Axur.com style
     #[default]
@@ -46,16 +83,53 @@ pub struct PluginConfig {
     pub is_poc: bool,
     /// Show compliance/regulatory slides
     pub show_compliance: bool,
-    /// Custom branding enabled
+    /// Custom branding enabled: the theme logo/brand name replace Axur's
     pub custom_branding: bool,
     /// Theme mode (dark/light/auto)
     pub theme: ThemeMode,
     /// List of plugin IDs to disable
     pub disabled_plugins: Vec<String>,
+    /// Colors, fonts, logo, cover image and footer text
+    pub branding: ReportTheme,
     /// Custom CSS to inject
     pub custom_css: Option<String>,
     /// Show style showcase (debug/verification only)
     pub show_style_showcase: bool,
+    /// Risk score model for this tenant/industry (default model when None)
+    pub risk_model: Option<RiskModel>,
+    /// Insight rules for this tenant/industry (default pack when None)
+    pub insight_rules: Option<InsightRulePack>,
+    /// Slide plugin IDs in display order; plugins not listed keep their
+    /// priority order after the listed ones
+    pub plugin_order: Vec<String>,
+    /// Translation overrides (key -> text) for customer terminology
+    pub terminology: HashMap<String, String>,
+    /// Per-plugin slide overrides (plugin ID -> title, subtitle, commentary)
+    pub slide_overrides: HashMap<String, SlideOverride>,
+    /// Analyst executive summary, highlighted tickets and recommendations
+    pub annotations: ReportAnnotations,
+}
+
+/// Analyst overrides for the slides of one plugin, applied by
+/// `PluginRegistry::generate_slides`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlideOverride {
+    /// Replaces the slide title (its first heading)
+    pub title: Option<String>,
+    /// Shown under the title
+    pub subtitle: Option<String>,
+    /// Free-text analyst note pinned on the slide
+    pub commentary: Option<String>,
+}
+
+impl SlideOverride {
+    /// True when the override changes nothing
+    pub fn is_empty(&self) -> bool {
+        [&self.title, &self.subtitle, &self.commentary]
+            .iter()
+            .all(|text| text.as_deref().unwrap_or_default().trim().is_empty())
+    }
 }
 
 impl PluginConfig {
@@ -76,6 +150,53 @@ impl PluginConfig {
         self
     }
 
+    /// Set the report theme; `custom_branding` swaps the Axur brand for
+    /// the theme's (white-label)
+    pub fn with_branding(mut self, branding: ReportTheme, custom_branding: bool) -> Self {
+        self.branding = branding;
+        self.custom_branding = custom_branding;
+        self
+    }
+
+    /// Set an explicit slide order (when given, replacing a preset order)
+    /// and the per-plugin slide overrides
+    pub fn with_slides(
+        mut self,
+        order: Option<Vec<String>>,
+        overrides: HashMap<String, SlideOverride>,
+    ) -> Self {
+        if let Some(order) = order {
+            self.plugin_order = order;
+        }
+        self.slide_overrides = overrides;
+        self
+    }
+
+    /// Set the analyst annotations. Callouts become the commentary of the
+    /// plugin's slide override unless it already has one, so call this after
+    /// `with_slides`.
+    pub fn with_annotations(mut self, annotations: ReportAnnotations) -> Self {
+        for (plugin_id, note) in &annotations.callouts {
+            if note.trim().is_empty() {
+                continue;
+            }
+            let slide_override = self.slide_overrides.entry(plugin_id.clone()).or_default();
+            if slide_override.commentary.is_none() {
+                slide_override.commentary = Some(note.clone());
+            }
+        }
+        self.annotations = annotations;
+        self
+    }
+
+    /// Theme CSS followed by the custom CSS, for the report document
+    pub fn document_css(&self) -> String {
+        match &self.custom_css {
+            Some(custom) => format!("{}\n{}", self.branding.css(), custom),
+            None => self.branding.css(),
+        }
+    }
+
     /// Check if a plugin is enabled
     pub fn is_plugin_enabled(&self, plugin_id: &str) -> bool {
         !self.disabled_plugins.contains(&plugin_id.to_string())
@@ -85,10 +206,10 @@ impl PluginConfig {
 /// Plugin that generates one or more slides for the report
 pub trait SlidePlugin: Send + Sync {
     /// Unique identifier for this plugin (e.g., "builtin.metrics")
-    fn id(&self) -> &'static str;
+    fn id(&self) -> &str;
 
     /// Human-readable name
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Generate slides based on report data
     /// Returns empty vec if no slides should be generated
@@ -121,6 +242,11 @@ pub trait DataPlugin: Send + Sync {
     /// Transform/enrich the report data
     fn transform(&self, data: &mut PocReportData);
 
+    /// Transform with the report configuration (defaults to `transform`)
+    fn transform_with_config(&self, data: &mut PocReportData, _config: &PluginConfig) {
+        self.transform(data);
+    }
+
     /// Priority (higher = runs first)
     fn priority(&self) -> i32 {
         0
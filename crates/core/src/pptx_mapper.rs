@@ -216,9 +216,115 @@ pub fn map_placeholders(data: &PocReportData) -> HashMap<String, String> {
         format_number(data.threat_intelligence.paid_ads_detected),
     );
 
+    // ============================================================
+    // EDITOR LIBRARY (keys offered by the slide editor)
+    // ============================================================
+    map.insert(
+        "report_date_range".into(),
+        format!("{} - {}", data.start_date, data.end_date),
+    );
+    map.insert("total_incidents".into(), format_number(data.total_threats));
+    map.insert(
+        "total_takedowns".into(),
+        format_number(data.takedown_resolved),
+    );
+    map.insert(
+        "avg_takedown_time".into(),
+        data.deep_analytics
+            .avg_takedown_time_hours
+            .map(|h| format!("{:.1}h", h))
+            .unwrap_or_else(|| data.takedown_median_uptime.clone()),
+    );
+    map.insert(
+        "risk_score".into(),
+        format!("{:.0}", data.risk_score.current),
+    );
+    map.insert(
+        "risk_score_gauge".into(),
+        format!("{:.0}", data.risk_score.current),
+    );
+
+    // Short keys used by the builtin editor templates
+    map.insert("date".into(), data.end_date.clone());
+    map.insert("signals".into(), format_number(data.total_tickets));
+    map.insert("incidents".into(), format_number(data.total_threats));
+    map.insert(
+        "threats".into(),
+        format_number(data.total_threats.saturating_sub(data.takedown_resolved)),
+    );
+    map.insert("credentials".into(), format_number(data.credentials_total));
+    map.insert("takedowns".into(), format_number(data.takedown_resolved));
+    map.insert("code_leaks".into(), format_number(data.secrets_total));
+    map.insert("resolved".into(), format_number(data.takedown_resolved));
+    map.insert("pending".into(), format_number(data.takedown_pending));
+    map.insert("aborted".into(), format_number(data.takedown_aborted));
+    map.insert(
+        "success_rate".into(),
+        format!("{:.1}%", data.takedown_success_rate),
+    );
+    map.insert("risk_label".into(), data.risk_score.label.clone());
+
+    // Chart and table keys are "label: value" lines, see `parse_series`
+    let threat_lines = series_lines(
+        data.threats_by_type
+            .iter()
+            .map(|t| (t.threat_type.as_str(), t.count)),
+    );
+    map.insert("incidents_chart".into(), threat_lines.clone());
+    map.insert("threat_distribution_pie".into(), threat_lines.clone());
+    map.insert("top_threats_table".into(), threat_lines);
+    map.insert(
+        "takedowns_table".into(),
+        series_lines([
+            ("Resolved", data.takedown_resolved),
+            ("Pending", data.takedown_pending),
+            ("Aborted", data.takedown_aborted),
+            ("Unresolved", data.takedown_unresolved),
+        ]),
+    );
+
+    let mut countries: Vec<(&str, u64)> = Vec::new();
+    for takedown in data
+        .resolved_takedowns
+        .iter()
+        .filter(|t| !t.country.is_empty())
+    {
+        match countries.iter_mut().find(|(c, _)| *c == takedown.country) {
+            Some((_, count)) => *count += 1,
+            None => countries.push((takedown.country.as_str(), 1)),
+        }
+    }
+    countries.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    map.insert("geospatial_map".into(), series_lines(countries));
+
     map
 }
 
+fn series_lines<'a>(items: impl IntoIterator<Item = (&'a str, u64)>) -> String {
+    items
+        .into_iter()
+        .map(|(label, value)| format!("{}: {}", label, format_number(value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a "label: value" list (one pair per line) into chart data.
+/// Lines whose value is not numeric are skipped.
+pub fn parse_series(value: &str) -> Vec<(String, f64)> {
+    value
+        .lines()
+        .filter_map(|line| {
+            let (label, number) = line.rsplit_once(':')?;
+            let number: String = number
+                .trim()
+                .chars()
+                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+                .collect();
+            Some((label.trim().to_string(), number.parse().ok()?))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +336,27 @@ mod tests {
         assert_eq!(format_number(1234), "1,234");
         assert_eq!(format_number(1234567), "1,234,567");
     }
+
+    #[test]
+    fn test_editor_keys_and_series() {
+        let data = PocReportData::demo();
+        let map = map_placeholders(&data);
+
+        for key in crate::editor::get_all_placeholders() {
+            if !matches!(
+                key.key.as_str(),
+                "evidence_gallery" | "ai_executive_summary" | "campaign_summary"
+            ) {
+                assert!(map.contains_key(&key.key), "missing {}", key.key);
+            }
+        }
+
+        let series = parse_series(&map["incidents_chart"]);
+        assert_eq!(series.len(), data.threats_by_type.len());
+        assert_eq!(series[0].1, data.threats_by_type[0].count as f64);
+        assert_eq!(
+            parse_series("Phishing: 1,234\nnot a pair\nLeaks: 5.5%"),
+            vec![("Phishing".to_string(), 1234.0), ("Leaks".to_string(), 5.5)]
+        );
+    }
 }
@@ -0,0 +1,1058 @@
+//! Native PPTX Writer
+//!
+//! Builds a complete Office Open XML presentation from scratch: one blank
+//! master/layout with the Axur theme, and slides made of native text boxes,
+//! shapes, tables, pictures and charts. Charts carry literal data (no
+//! embedded workbook), so they render and restyle in PowerPoint without any
+//! external template.
+//!
+//! Coordinates use a 1280x720 pixel grid (16:9), converted to EMUs at 96 DPI.
+
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Slide width in EMUs (13.333in, 16:9)
+pub const SLIDE_WIDTH_EMU: i64 = 12_192_000;
+/// Slide height in EMUs (7.5in, 16:9)
+pub const SLIDE_HEIGHT_EMU: i64 = 6_858_000;
+
+const NS_A: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+const NS_R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const NS_P: &str = "http://schemas.openxmlformats.org/presentationml/2006/main";
+const NS_C: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+const REL_BASE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Convert a position on the 1280x720 design grid to EMUs
+pub fn px(value: f64) -> i64 {
+    (value * 9525.0).round() as i64
+}
+
+/// Colors and font used for the whole deck (hex without '#')
+#[derive(Debug, Clone)]
+pub struct PptxTheme {
+    pub background: String,
+    pub surface: String,
+    pub border: String,
+    pub primary: String,
+    pub text: String,
+    pub muted: String,
+    pub font: String,
+}
+
+impl Default for PptxTheme {
+    /// Axur dark theme
+    fn default() -> Self {
+        Self {
+            background: "09090B".into(),
+            surface: "18181B".into(),
+            border: "27272A".into(),
+            primary: "FF671F".into(),
+            text: "FFFFFF".into(),
+            muted: "A1A1AA".into(),
+            font: "Inter".into(),
+        }
+    }
+}
+
+/// Position and size on the 1280x720 grid
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl Frame {
+    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Self { x, y, w, h }
+    }
+
+    fn xfrm(&self, prefix: &str) -> String {
+        format!(
+            r#"<{p}:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></{p}:xfrm>"#,
+            px(self.x),
+            px(self.y),
+            px(self.w),
+            px(self.h),
+            p = prefix
+        )
+    }
+}
+
+/// Horizontal text alignment
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    fn as_attr(&self) -> &'static str {
+        match self {
+            Align::Left => "l",
+            Align::Center => "ctr",
+            Align::Right => "r",
+        }
+    }
+}
+
+/// A single-run paragraph
+#[derive(Debug, Clone)]
+pub struct Paragraph {
+    pub text: String,
+    /// Font size in points
+    pub size: f64,
+    pub bold: bool,
+    pub color: String,
+    pub align: Align,
+}
+
+impl Paragraph {
+    pub fn new(text: impl Into<String>, size: f64, color: &str) -> Self {
+        Self {
+            text: text.into(),
+            size,
+            bold: false,
+            color: color.to_string(),
+            align: Align::Left,
+        }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+/// Native shapes supported by the writer
+#[derive(Debug, Clone)]
+pub enum Shape {
+    /// Text box, optionally filled (rounded card when `rounded`)
+    Text {
+        frame: Frame,
+        paragraphs: Vec<Paragraph>,
+        fill: Option<String>,
+        border: Option<String>,
+        rounded: bool,
+        /// Vertically center the text
+        center: bool,
+    },
+    /// Plain filled rectangle (bars, accents, heatmap cells)
+    Rect { frame: Frame, fill: String },
+    /// Native table; first row is the header
+    Table {
+        frame: Frame,
+        rows: Vec<Vec<String>>,
+        /// Relative column widths (defaults to equal)
+        col_weights: Vec<f64>,
+        font_size: f64,
+    },
+    /// Native chart with literal data
+    Chart { frame: Frame, chart: Chart },
+    /// PNG or JPEG picture
+    Picture {
+        frame: Frame,
+        bytes: Vec<u8>,
+        /// "png" or "jpeg"
+        extension: &'static str,
+    },
+}
+
+impl Shape {
+    /// Unfilled text box with one paragraph
+    pub fn text(frame: Frame, paragraph: Paragraph) -> Self {
+        Shape::Text {
+            frame,
+            paragraphs: vec![paragraph],
+            fill: None,
+            border: None,
+            rounded: false,
+            center: false,
+        }
+    }
+
+    /// Unfilled text box with several paragraphs
+    pub fn text_lines(frame: Frame, paragraphs: Vec<Paragraph>) -> Self {
+        Shape::Text {
+            frame,
+            paragraphs,
+            fill: None,
+            border: None,
+            rounded: false,
+            center: false,
+        }
+    }
+
+    /// Rounded, filled card with vertically centered text
+    pub fn card(frame: Frame, paragraphs: Vec<Paragraph>, fill: &str, border: &str) -> Self {
+        Shape::Text {
+            frame,
+            paragraphs,
+            fill: Some(fill.to_string()),
+            border: Some(border.to_string()),
+            rounded: true,
+            center: true,
+        }
+    }
+
+    pub fn rect(frame: Frame, fill: &str) -> Self {
+        Shape::Rect {
+            frame,
+            fill: fill.to_string(),
+        }
+    }
+
+    pub fn table(frame: Frame, rows: Vec<Vec<String>>, font_size: f64) -> Self {
+        Shape::Table {
+            frame,
+            rows,
+            col_weights: Vec::new(),
+            font_size,
+        }
+    }
+
+    pub fn chart(frame: Frame, chart: Chart) -> Self {
+        Shape::Chart { frame, chart }
+    }
+
+    /// Picture from raw bytes; None when the format is not PNG or JPEG
+    pub fn picture(frame: Frame, bytes: Vec<u8>) -> Option<Self> {
+        let extension = if bytes.starts_with(b"\x89PNG") {
+            "png"
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            "jpeg"
+        } else {
+            return None;
+        };
+        Some(Shape::Picture {
+            frame,
+            bytes,
+            extension,
+        })
+    }
+}
+
+/// Chart types supported natively
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartKind {
+    /// Horizontal bars
+    Bar,
+    /// Vertical columns
+    Column,
+    Line,
+    Doughnut,
+}
+
+/// One data series (values aligned with the chart categories)
+#[derive(Debug, Clone)]
+pub struct ChartSeries {
+    pub name: String,
+    pub values: Vec<f64>,
+    /// Series color (hex); doughnuts color each point from the palette instead
+    pub color: String,
+}
+
+/// Chart definition with literal data
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub title: Option<String>,
+    pub categories: Vec<String>,
+    pub series: Vec<ChartSeries>,
+    /// Point colors for doughnut charts
+    pub palette: Vec<String>,
+    pub show_values: bool,
+    pub show_legend: bool,
+}
+
+impl Chart {
+    pub fn new(kind: ChartKind, categories: Vec<String>, series: Vec<ChartSeries>) -> Self {
+        Self {
+            kind,
+            title: None,
+            categories,
+            series,
+            palette: Vec::new(),
+            show_values: true,
+            show_legend: kind == ChartKind::Doughnut,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn with_palette(mut self, palette: Vec<String>) -> Self {
+        self.palette = palette;
+        self
+    }
+}
+
+/// A slide: optional background override plus shapes in z-order
+#[derive(Debug, Clone, Default)]
+pub struct PptxSlide {
+    pub background: Option<String>,
+    pub shapes: Vec<Shape>,
+    /// Speaker notes are not written; kept for callers that track provenance
+    pub source_id: String,
+}
+
+impl PptxSlide {
+    pub fn new(source_id: &str) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, shape: Shape) -> &mut Self {
+        self.shapes.push(shape);
+        self
+    }
+}
+
+/// Presentation builder
+#[derive(Debug, Clone, Default)]
+pub struct PptxPresentation {
+    pub title: String,
+    pub theme: PptxTheme,
+    pub slides: Vec<PptxSlide>,
+}
+
+impl PptxPresentation {
+    pub fn new(title: &str, theme: PptxTheme) -> Self {
+        Self {
+            title: title.to_string(),
+            theme,
+            slides: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, slide: PptxSlide) {
+        self.slides.push(slide);
+    }
+
+    /// Serialize the presentation to .pptx bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut buffer = Vec::new();
+        let mut zip = ZipWriter::new(Cursor::new(&mut buffer));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mut write = |name: &str, content: &[u8]| -> Result<(), String> {
+            zip.start_file(name, options)
+                .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+            zip.write_all(content)
+                .map_err(|e| format!("Failed to write {}: {}", name, e))
+        };
+
+        let mut chart_count = 0;
+        let mut media_count = 0;
+        let mut slide_parts = Vec::new();
+        let mut chart_parts = Vec::new();
+        let mut media_parts: Vec<(String, Vec<u8>)> = Vec::new();
+
+        for (i, slide) in self.slides.iter().enumerate() {
+            let mut rels = vec![(
+                "rId1".to_string(),
+                format!("{}/slideLayout", REL_BASE),
+                "../slideLayouts/slideLayout1.xml".to_string(),
+            )];
+            let mut shapes_xml = String::new();
+
+            for (shape_idx, shape) in slide.shapes.iter().enumerate() {
+                let id = shape_idx + 2;
+                match shape {
+                    Shape::Chart { frame, chart } => {
+                        chart_count += 1;
+                        let rid = format!("rId{}", rels.len() + 1);
+                        rels.push((
+                            rid.clone(),
+                            format!("{}/chart", REL_BASE),
+                            format!("../charts/chart{}.xml", chart_count),
+                        ));
+                        chart_parts.push((chart_count, chart_xml(chart, &self.theme)));
+                        shapes_xml.push_str(&chart_frame_xml(id, frame, &rid));
+                    }
+                    Shape::Picture {
+                        frame,
+                        bytes,
+                        extension,
+                    } => {
+                        media_count += 1;
+                        let file = format!("image{}.{}", media_count, extension);
+                        let rid = format!("rId{}", rels.len() + 1);
+                        rels.push((
+                            rid.clone(),
+                            format!("{}/image", REL_BASE),
+                            format!("../media/{}", file),
+                        ));
+                        media_parts.push((file, bytes.clone()));
+                        shapes_xml.push_str(&picture_xml(id, frame, &rid));
+                    }
+                    other => shapes_xml.push_str(&shape_xml(id, other, &self.theme)),
+                }
+            }
+
+            slide_parts.push((i + 1, slide_xml(slide, &shapes_xml), rels_xml(&rels)));
+        }
+
+        write(
+            "[Content_Types].xml",
+            self.content_types(chart_count).as_bytes(),
+        )?;
+        write("_rels/.rels", ROOT_RELS.as_bytes())?;
+        write("docProps/core.xml", self.core_props().as_bytes())?;
+        write(
+            "docProps/app.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"><Application>Axur Tool</Application><Slides>{}</Slides></Properties>"#,
+                self.slides.len()
+            )
+            .as_bytes(),
+        )?;
+        write("ppt/presentation.xml", self.presentation_xml().as_bytes())?;
+        write(
+            "ppt/_rels/presentation.xml.rels",
+            self.presentation_rels().as_bytes(),
+        )?;
+        write(
+            "ppt/presProps.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentationPr xmlns:a="{NS_A}" xmlns:r="{NS_R}" xmlns:p="{NS_P}"/>"#
+            )
+            .as_bytes(),
+        )?;
+        write(
+            "ppt/viewProps.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:viewPr xmlns:a="{NS_A}" xmlns:r="{NS_R}" xmlns:p="{NS_P}"/>"#
+            )
+            .as_bytes(),
+        )?;
+        write(
+            "ppt/tableStyles.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:tblStyleLst xmlns:a="{NS_A}" def="{{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}}"/>"#
+            )
+            .as_bytes(),
+        )?;
+        write("ppt/theme/theme1.xml", theme_xml(&self.theme).as_bytes())?;
+        write(
+            "ppt/slideMasters/slideMaster1.xml",
+            master_xml(&self.theme).as_bytes(),
+        )?;
+        write(
+            "ppt/slideMasters/_rels/slideMaster1.xml.rels",
+            rels_xml(&[
+                (
+                    "rId1".into(),
+                    format!("{}/slideLayout", REL_BASE),
+                    "../slideLayouts/slideLayout1.xml".into(),
+                ),
+                (
+                    "rId2".into(),
+                    format!("{}/theme", REL_BASE),
+                    "../theme/theme1.xml".into(),
+                ),
+            ])
+            .as_bytes(),
+        )?;
+        write("ppt/slideLayouts/slideLayout1.xml", LAYOUT_XML.as_bytes())?;
+        write(
+            "ppt/slideLayouts/_rels/slideLayout1.xml.rels",
+            rels_xml(&[(
+                "rId1".into(),
+                format!("{}/slideMaster", REL_BASE),
+                "../slideMasters/slideMaster1.xml".into(),
+            )])
+            .as_bytes(),
+        )?;
+
+        for (n, xml, rels) in &slide_parts {
+            write(&format!("ppt/slides/slide{}.xml", n), xml.as_bytes())?;
+            write(
+                &format!("ppt/slides/_rels/slide{}.xml.rels", n),
+                rels.as_bytes(),
+            )?;
+        }
+        for (n, xml) in &chart_parts {
+            write(&format!("ppt/charts/chart{}.xml", n), xml.as_bytes())?;
+        }
+        for (file, bytes) in &media_parts {
+            write(&format!("ppt/media/{}", file), bytes)?;
+        }
+
+        zip.finish()
+            .map_err(|e| format!("Failed to finish PPTX: {}", e))?;
+        Ok(buffer)
+    }
+
+    fn content_types(&self, chart_count: usize) -> String {
+        let mut overrides = String::new();
+        for n in 1..=self.slides.len() {
+            overrides.push_str(&format!(
+                r#"<Override PartName="/ppt/slides/slide{}.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml"/>"#,
+                n
+            ));
+        }
+        for n in 1..=chart_count {
+            overrides.push_str(&format!(
+                r#"<Override PartName="/ppt/charts/chart{}.xml" ContentType="application/vnd.openxmlformats-officedocument.drawingml.chart+xml"/>"#,
+                n
+            ));
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Default Extension="jpeg" ContentType="image/jpeg"/><Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/><Override PartName="/ppt/presProps.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presProps+xml"/><Override PartName="/ppt/viewProps.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.viewProps+xml"/><Override PartName="/ppt/tableStyles.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.tableStyles+xml"/><Override PartName="/ppt/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/><Override PartName="/ppt/slideMasters/slideMaster1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml"/><Override PartName="/ppt/slideLayouts/slideLayout1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/><Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>{}</Types>"#,
+            overrides
+        )
+    }
+
+    fn core_props(&self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:creator>Axur</dc:creator><dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created></cp:coreProperties>"#,
+            escape(&self.title),
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+        )
+    }
+
+    fn presentation_xml(&self) -> String {
+        let slide_ids: String = (0..self.slides.len())
+            .map(|i| format!(r#"<p:sldId id="{}" r:id="rId{}"/>"#, 256 + i, i + 6))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentation xmlns:a="{NS_A}" xmlns:r="{NS_R}" xmlns:p="{NS_P}" saveSubsetFonts="1"><p:sldMasterIdLst><p:sldMasterId id="2147483648" r:id="rId1"/></p:sldMasterIdLst><p:sldIdLst>{slide_ids}</p:sldIdLst><p:sldSz cx="{SLIDE_WIDTH_EMU}" cy="{SLIDE_HEIGHT_EMU}"/><p:notesSz cx="6858000" cy="9144000"/></p:presentation>"#
+        )
+    }
+
+    fn presentation_rels(&self) -> String {
+        let mut rels = vec![
+            (
+                "rId1".to_string(),
+                format!("{}/slideMaster", REL_BASE),
+                "slideMasters/slideMaster1.xml".to_string(),
+            ),
+            (
+                "rId2".into(),
+                format!("{}/theme", REL_BASE),
+                "theme/theme1.xml".into(),
+            ),
+            (
+                "rId3".into(),
+                format!("{}/presProps", REL_BASE),
+                "presProps.xml".into(),
+            ),
+            (
+                "rId4".into(),
+                format!("{}/viewProps", REL_BASE),
+                "viewProps.xml".into(),
+            ),
+            (
+                "rId5".into(),
+                format!("{}/tableStyles", REL_BASE),
+                "tableStyles.xml".into(),
+            ),
+        ];
+        for n in 1..=self.slides.len() {
+            rels.push((
+                format!("rId{}", n + 5),
+                format!("{}/slide", REL_BASE),
+                format!("slides/slide{}.xml", n),
+            ));
+        }
+        rels_xml(&rels)
+    }
+}
+
+// ========================
+// PART TEMPLATES
+// ========================
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="ppt/presentation.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/></Relationships>"#;
+
+const EMPTY_TREE: &str = r#"<p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="0" cy="0"/><a:chOff x="0" y="0"/><a:chExt cx="0" cy="0"/></a:xfrm></p:grpSpPr>"#;
+
+const LAYOUT_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sldLayout xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" type="blank" preserve="1"><p:cSld name="Blank"><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="0" cy="0"/><a:chOff x="0" y="0"/><a:chExt cx="0" cy="0"/></a:xfrm></p:grpSpPr></p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sldLayout>"#;
+
+fn rels_xml(rels: &[(String, String, String)]) -> String {
+    let body: String = rels
+        .iter()
+        .map(|(id, kind, target)| {
+            format!(
+                r#"<Relationship Id="{}" Type="{}" Target="{}"/>"#,
+                id, kind, target
+            )
+        })
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{}</Relationships>"#,
+        body
+    )
+}
+
+fn master_xml(theme: &PptxTheme) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sldMaster xmlns:a="{NS_A}" xmlns:r="{NS_R}" xmlns:p="{NS_P}"><p:cSld><p:bg><p:bgPr><a:solidFill><a:srgbClr val="{bg}"/></a:solidFill><a:effectLst/></p:bgPr></p:bg><p:spTree>{EMPTY_TREE}</p:spTree></p:cSld><p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink"/><p:sldLayoutIdLst><p:sldLayoutId id="2147483649" r:id="rId1"/></p:sldLayoutIdLst><p:txStyles><p:titleStyle><a:lvl1pPr><a:defRPr sz="3200"/></a:lvl1pPr></p:titleStyle><p:bodyStyle><a:lvl1pPr><a:defRPr sz="1800"/></a:lvl1pPr></p:bodyStyle><p:otherStyle><a:lvl1pPr><a:defRPr sz="1800"/></a:lvl1pPr></p:otherStyle></p:txStyles></p:sldMaster>"#,
+        bg = theme.background
+    )
+}
+
+fn theme_xml(theme: &PptxTheme) -> String {
+    let fill_styles = r#"<a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill><a:solidFill><a:schemeClr val="phClr"/></a:solidFill>"#;
+    let line_styles = r#"<a:ln w="6350"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln><a:ln w="12700"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln><a:ln w="19050"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>"#;
+    let effect_styles = r#"<a:effectStyle><a:effectLst/></a:effectStyle><a:effectStyle><a:effectLst/></a:effectStyle><a:effectStyle><a:effectLst/></a:effectStyle>"#;
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:theme xmlns:a="{NS_A}" name="Axur"><a:themeElements><a:clrScheme name="Axur"><a:dk1><a:srgbClr val="{bg}"/></a:dk1><a:lt1><a:srgbClr val="{text}"/></a:lt1><a:dk2><a:srgbClr val="{surface}"/></a:dk2><a:lt2><a:srgbClr val="{muted}"/></a:lt2><a:accent1><a:srgbClr val="{primary}"/></a:accent1><a:accent2><a:srgbClr val="7C3AED"/></a:accent2><a:accent3><a:srgbClr val="0EA5E9"/></a:accent3><a:accent4><a:srgbClr val="22C55E"/></a:accent4><a:accent5><a:srgbClr val="EAB308"/></a:accent5><a:accent6><a:srgbClr val="EF4444"/></a:accent6><a:hlink><a:srgbClr val="{primary}"/></a:hlink><a:folHlink><a:srgbClr val="{muted}"/></a:folHlink></a:clrScheme><a:fontScheme name="Axur"><a:majorFont><a:latin typeface="{font}"/><a:ea typeface=""/><a:cs typeface=""/></a:majorFont><a:minorFont><a:latin typeface="{font}"/><a:ea typeface=""/><a:cs typeface=""/></a:minorFont></a:fontScheme><a:fmtScheme name="Axur"><a:fillStyleLst>{fill_styles}</a:fillStyleLst><a:lnStyleLst>{line_styles}</a:lnStyleLst><a:effectStyleLst>{effect_styles}</a:effectStyleLst><a:bgFillStyleLst>{fill_styles}</a:bgFillStyleLst></a:fmtScheme></a:themeElements></a:theme>"#,
+        bg = theme.background,
+        text = theme.text,
+        surface = theme.surface,
+        muted = theme.muted,
+        primary = theme.primary,
+        font = escape(&theme.font),
+    )
+}
+
+fn slide_xml(slide: &PptxSlide, shapes: &str) -> String {
+    let bg = slide
+        .background
+        .as_ref()
+        .map(|c| {
+            format!(
+                r#"<p:bg><p:bgPr><a:solidFill><a:srgbClr val="{}"/></a:solidFill><a:effectLst/></p:bgPr></p:bg>"#,
+                c
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sld xmlns:a="{NS_A}" xmlns:r="{NS_R}" xmlns:p="{NS_P}"><p:cSld>{bg}<p:spTree>{EMPTY_TREE}{shapes}</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>"#
+    )
+}
+
+// ========================
+// SHAPES
+// ========================
+
+fn solid_fill(color: &str) -> String {
+    format!(r#"<a:solidFill><a:srgbClr val="{}"/></a:solidFill>"#, color)
+}
+
+fn run_xml(text: &str, size: f64, bold: bool, color: &str, font: &str) -> String {
+    format!(
+        r#"<a:r><a:rPr lang="es-ES" sz="{}" b="{}" dirty="0">{}<a:latin typeface="{}"/></a:rPr><a:t>{}</a:t></a:r>"#,
+        (size * 100.0).round() as i64,
+        if bold { 1 } else { 0 },
+        solid_fill(color),
+        escape(font),
+        escape(text)
+    )
+}
+
+fn paragraphs_xml(paragraphs: &[Paragraph], font: &str) -> String {
+    if paragraphs.is_empty() {
+        return r#"<a:p><a:endParaRPr lang="es-ES"/></a:p>"#.to_string();
+    }
+    paragraphs
+        .iter()
+        .map(|p| {
+            format!(
+                r#"<a:p><a:pPr algn="{}"/>{}</a:p>"#,
+                p.align.as_attr(),
+                run_xml(&p.text, p.size, p.bold, &p.color, font)
+            )
+        })
+        .collect()
+}
+
+fn shape_xml(id: usize, shape: &Shape, theme: &PptxTheme) -> String {
+    match shape {
+        Shape::Text {
+            frame,
+            paragraphs,
+            fill,
+            border,
+            rounded,
+            center,
+        } => {
+            let geometry = if *rounded { "roundRect" } else { "rect" };
+            let adjust = if *rounded {
+                r#"<a:gd name="adj" fmla="val 8000"/>"#
+            } else {
+                ""
+            };
+            let fill_xml = fill
+                .as_deref()
+                .map(solid_fill)
+                .unwrap_or_else(|| "<a:noFill/>".to_string());
+            let line_xml = match border {
+                Some(color) => format!(r#"<a:ln w="9525">{}</a:ln>"#, solid_fill(color)),
+                None => "<a:ln><a:noFill/></a:ln>".to_string(),
+            };
+            format!(
+                r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="TextBox {id}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr>{xfrm}<a:prstGeom prst="{geometry}"><a:avLst>{adjust}</a:avLst></a:prstGeom>{fill_xml}{line_xml}</p:spPr><p:txBody><a:bodyPr wrap="square" lIns="91440" tIns="45720" rIns="91440" bIns="45720" anchor="{anchor}"><a:normAutofit/></a:bodyPr><a:lstStyle/>{paragraphs}</p:txBody></p:sp>"#,
+                xfrm = frame.xfrm("a"),
+                anchor = if *center { "ctr" } else { "t" },
+                paragraphs = paragraphs_xml(paragraphs, &theme.font),
+            )
+        }
+        Shape::Rect { frame, fill } => format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="Rectangle {id}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr>{xfrm}<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>{fill}<a:ln><a:noFill/></a:ln></p:spPr></p:sp>"#,
+            xfrm = frame.xfrm("a"),
+            fill = solid_fill(fill),
+        ),
+        Shape::Table {
+            frame,
+            rows,
+            col_weights,
+            font_size,
+        } => table_xml(id, frame, rows, col_weights, *font_size, theme),
+        // Charts and pictures need relationships and are written by the package writer
+        Shape::Chart { .. } | Shape::Picture { .. } => String::new(),
+    }
+}
+
+fn table_xml(
+    id: usize,
+    frame: &Frame,
+    rows: &[Vec<String>],
+    col_weights: &[f64],
+    font_size: f64,
+    theme: &PptxTheme,
+) -> String {
+    let cols = rows.iter().map(|r| r.len()).max().unwrap_or(0).max(1);
+    let weights: Vec<f64> = if col_weights.len() == cols {
+        col_weights.to_vec()
+    } else {
+        vec![1.0; cols]
+    };
+    let total_weight: f64 = weights.iter().sum();
+    let width = px(frame.w);
+    let grid: String = weights
+        .iter()
+        .map(|w| {
+            format!(
+                r#"<a:gridCol w="{}"/>"#,
+                (width as f64 * w / total_weight).round() as i64
+            )
+        })
+        .collect();
+
+    let row_height = px(frame.h) / rows.len().max(1) as i64;
+    let border = format!(r#"<a:lnB w="6350">{}</a:lnB>"#, solid_fill(&theme.border));
+    let mut rows_xml = String::new();
+    for (r, row) in rows.iter().enumerate() {
+        let header = r == 0;
+        let (fill, color) = if header {
+            (&theme.surface, &theme.primary)
+        } else {
+            (&theme.background, &theme.text)
+        };
+        rows_xml.push_str(&format!(r#"<a:tr h="{}">"#, row_height));
+        for c in 0..cols {
+            let text = row.get(c).map(String::as_str).unwrap_or("");
+            let align = if c > 0 { "r" } else { "l" };
+            rows_xml.push_str(&format!(
+                r#"<a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:pPr algn="{}"/>{}</a:p></a:txBody><a:tcPr marL="91440" marR="91440" marT="45720" marB="45720" anchor="ctr">{}{}</a:tcPr></a:tc>"#,
+                align,
+                run_xml(text, font_size, header, color, &theme.font),
+                border,
+                solid_fill(fill)
+            ));
+        }
+        rows_xml.push_str("</a:tr>");
+    }
+
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{id}" name="Table {id}"/><p:cNvGraphicFramePr><a:graphicFrameLocks noGrp="1"/></p:cNvGraphicFramePr><p:nvPr/></p:nvGraphicFramePr>{xfrm}<a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblPr firstRow="1" bandRow="1"/><a:tblGrid>{grid}</a:tblGrid>{rows_xml}</a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#,
+        xfrm = frame.xfrm("p"),
+    )
+}
+
+fn chart_frame_xml(id: usize, frame: &Frame, rid: &str) -> String {
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{id}" name="Chart {id}"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr>{xfrm}<a:graphic><a:graphicData uri="{NS_C}"><c:chart xmlns:c="{NS_C}" r:id="{rid}"/></a:graphicData></a:graphic></p:graphicFrame>"#,
+        xfrm = frame.xfrm("p"),
+    )
+}
+
+fn picture_xml(id: usize, frame: &Frame, rid: &str) -> String {
+    format!(
+        r#"<p:pic><p:nvPicPr><p:cNvPr id="{id}" name="Picture {id}"/><p:cNvPicPr><a:picLocks noChangeAspect="1"/></p:cNvPicPr><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{rid}"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr>{xfrm}<a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic>"#,
+        xfrm = frame.xfrm("a"),
+    )
+}
+
+// ========================
+// CHARTS
+// ========================
+
+fn text_props(size: f64, color: &str) -> String {
+    format!(
+        r#"<c:txPr><a:bodyPr/><a:lstStyle/><a:p><a:pPr><a:defRPr sz="{}">{}</a:defRPr></a:pPr><a:endParaRPr lang="es-ES"/></a:p></c:txPr>"#,
+        (size * 100.0).round() as i64,
+        solid_fill(color)
+    )
+}
+
+fn literal_categories(categories: &[String]) -> String {
+    let points: String = categories
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!(r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, escape(c)))
+        .collect();
+    format!(
+        r#"<c:cat><c:strLit><c:ptCount val="{}"/>{}</c:strLit></c:cat>"#,
+        categories.len(),
+        points
+    )
+}
+
+fn literal_values(values: &[f64]) -> String {
+    let points: String = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| format!(r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, v))
+        .collect();
+    format!(
+        r#"<c:val><c:numLit><c:formatCode>General</c:formatCode><c:ptCount val="{}"/>{}</c:numLit></c:val>"#,
+        values.len(),
+        points
+    )
+}
+
+fn data_labels(show: bool, theme: &PptxTheme) -> String {
+    if !show {
+        return r#"<c:dLbls><c:delete val="1"/></c:dLbls>"#.to_string();
+    }
+    format!(
+        r#"<c:dLbls>{}<c:showLegendKey val="0"/><c:showVal val="1"/><c:showCatName val="0"/><c:showSerName val="0"/><c:showPercent val="0"/><c:showBubbleSize val="0"/></c:dLbls>"#,
+        text_props(10.0, &theme.text)
+    )
+}
+
+fn axes_xml(cat_pos: &str, val_pos: &str, theme: &PptxTheme) -> String {
+    let axis_line = format!(
+        r#"<c:spPr><a:ln w="6350">{}</a:ln></c:spPr>"#,
+        solid_fill(&theme.border)
+    );
+    format!(
+        r#"<c:catAx><c:axId val="500000001"/><c:scaling><c:orientation val="{orientation}"/></c:scaling><c:delete val="0"/><c:axPos val="{cat_pos}"/><c:numFmt formatCode="General" sourceLinked="0"/><c:majorTickMark val="none"/><c:minorTickMark val="none"/><c:tickLblPos val="nextTo"/>{axis_line}{labels}<c:crossAx val="500000002"/><c:crosses val="autoZero"/><c:auto val="1"/><c:lblAlgn val="ctr"/><c:lblOffset val="100"/><c:noMultiLvlLbl val="0"/></c:catAx><c:valAx><c:axId val="500000002"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:delete val="0"/><c:axPos val="{val_pos}"/><c:majorGridlines><c:spPr><a:ln w="6350">{grid}</a:ln></c:spPr></c:majorGridlines><c:numFmt formatCode="General" sourceLinked="0"/><c:majorTickMark val="none"/><c:minorTickMark val="none"/><c:tickLblPos val="nextTo"/><c:spPr><a:ln><a:noFill/></a:ln></c:spPr>{labels}<c:crossAx val="500000001"/><c:crosses val="autoZero"/><c:crossBetween val="between"/></c:valAx>"#,
+        // Horizontal bars read top-down in category order
+        orientation = if cat_pos == "l" { "maxMin" } else { "minMax" },
+        labels = text_props(10.0, &theme.muted),
+        grid = solid_fill(&theme.border),
+    )
+}
+
+/// Chart part XML (`ppt/charts/chartN.xml`) for a chart definition
+pub fn chart_xml(chart: &Chart, theme: &PptxTheme) -> String {
+    let categories = literal_categories(&chart.categories);
+    let mut series_xml = String::new();
+
+    for (i, series) in chart.series.iter().enumerate() {
+        let header = format!(
+            r#"<c:idx val="{i}"/><c:order val="{i}"/><c:tx><c:v>{}</c:v></c:tx>"#,
+            escape(&series.name)
+        );
+        let values = literal_values(&series.values);
+        let labels = data_labels(chart.show_values, theme);
+
+        let ser = match chart.kind {
+            ChartKind::Bar | ChartKind::Column => format!(
+                r#"<c:ser>{header}<c:spPr>{fill}</c:spPr><c:invertIfNegative val="0"/>{labels}{categories}{values}</c:ser>"#,
+                fill = solid_fill(&series.color),
+            ),
+            ChartKind::Line => format!(
+                r#"<c:ser>{header}<c:spPr><a:ln w="28575" cap="rnd">{fill}<a:round/></a:ln></c:spPr><c:marker><c:symbol val="circle"/><c:size val="6"/><c:spPr>{fill}</c:spPr></c:marker>{labels}{categories}{values}<c:smooth val="0"/></c:ser>"#,
+                fill = solid_fill(&series.color),
+            ),
+            ChartKind::Doughnut => {
+                let points: String = (0..chart.categories.len())
+                    .map(|p| {
+                        let color = chart
+                            .palette
+                            .get(p % chart.palette.len().max(1))
+                            .cloned()
+                            .unwrap_or_else(|| series.color.clone());
+                        format!(
+                            r#"<c:dPt><c:idx val="{p}"/><c:bubble3D val="0"/><c:spPr>{}<a:ln w="19050">{}</a:ln></c:spPr></c:dPt>"#,
+                            solid_fill(&color),
+                            solid_fill(&theme.background)
+                        )
+                    })
+                    .collect();
+                format!(r#"<c:ser>{header}{points}{labels}{categories}{values}</c:ser>"#)
+            }
+        };
+        series_xml.push_str(&ser);
+    }
+
+    let plot = match chart.kind {
+        ChartKind::Bar | ChartKind::Column => format!(
+            r#"<c:barChart><c:barDir val="{dir}"/><c:grouping val="clustered"/><c:varyColors val="0"/>{series_xml}<c:gapWidth val="60"/><c:axId val="500000001"/><c:axId val="500000002"/></c:barChart>{axes}"#,
+            dir = if chart.kind == ChartKind::Bar {
+                "bar"
+            } else {
+                "col"
+            },
+            axes = if chart.kind == ChartKind::Bar {
+                axes_xml("l", "b", theme)
+            } else {
+                axes_xml("b", "l", theme)
+            },
+        ),
+        ChartKind::Line => format!(
+            r#"<c:lineChart><c:grouping val="standard"/><c:varyColors val="0"/>{series_xml}<c:marker val="1"/><c:axId val="500000001"/><c:axId val="500000002"/></c:lineChart>{axes}"#,
+            axes = axes_xml("b", "l", theme),
+        ),
+        ChartKind::Doughnut => format!(
+            r#"<c:doughnutChart><c:varyColors val="1"/>{series_xml}<c:firstSliceAng val="0"/><c:holeSize val="60"/></c:doughnutChart>"#
+        ),
+    };
+
+    let title = match &chart.title {
+        Some(title) => format!(
+            r#"<c:title><c:tx><c:rich><a:bodyPr/><a:lstStyle/><a:p><a:pPr><a:defRPr sz="1400" b="1"/></a:pPr>{}</a:p></c:rich></c:tx><c:overlay val="0"/></c:title><c:autoTitleDeleted val="0"/>"#,
+            run_xml(title, 14.0, true, &theme.text, &theme.font)
+        ),
+        None => r#"<c:autoTitleDeleted val="1"/>"#.to_string(),
+    };
+    let legend = if chart.show_legend {
+        format!(
+            r#"<c:legend><c:legendPos val="r"/><c:overlay val="0"/>{}</c:legend>"#,
+            text_props(11.0, &theme.muted)
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<c:chartSpace xmlns:c="{NS_C}" xmlns:a="{NS_A}" xmlns:r="{NS_R}"><c:roundedCorners val="0"/><c:chart>{title}<c:plotArea><c:layout/>{plot}<c:spPr><a:noFill/><a:ln><a:noFill/></a:ln></c:spPr></c:plotArea>{legend}<c:plotVisOnly val="1"/><c:dispBlanksAs val="gap"/></c:chart><c:spPr><a:noFill/><a:ln><a:noFill/></a:ln></c:spPr>{txpr}</c:chartSpace>"#,
+        txpr = text_props(11.0, &theme.muted),
+    )
+}
+
+/// Escape text for XML content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters are invalid in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn read_part(bytes: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut file = archive.by_name(name).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn test_package_contains_required_parts() {
+        let theme = PptxTheme::default();
+        let mut deck = PptxPresentation::new("Acme & Co", theme.clone());
+        let mut slide = PptxSlide::new("cover");
+        slide.add(Shape::text(
+            Frame::new(80.0, 80.0, 600.0, 80.0),
+            Paragraph::new("Acme <Report>", 40.0, &theme.text).bold(),
+        ));
+        slide.add(Shape::table(
+            Frame::new(80.0, 200.0, 600.0, 120.0),
+            vec![
+                vec!["Tipo".into(), "Total".into()],
+                vec!["phishing".into(), "10".into()],
+            ],
+            12.0,
+        ));
+        slide.add(Shape::chart(
+            Frame::new(700.0, 200.0, 500.0, 300.0),
+            Chart::new(
+                ChartKind::Bar,
+                vec!["a".into(), "b".into()],
+                vec![ChartSeries {
+                    name: "Tickets".into(),
+                    values: vec![1.0, 2.0],
+                    color: theme.primary.clone(),
+                }],
+            ),
+        ));
+        deck.push(slide);
+
+        let bytes = deck.to_bytes().unwrap();
+        let archive = zip::ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        for part in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "ppt/presentation.xml",
+            "ppt/slideMasters/slideMaster1.xml",
+            "ppt/slideLayouts/slideLayout1.xml",
+            "ppt/theme/theme1.xml",
+            "ppt/slides/slide1.xml",
+            "ppt/slides/_rels/slide1.xml.rels",
+            "ppt/charts/chart1.xml",
+        ] {
+            assert!(names.contains(&part), "missing {}", part);
+        }
+
+        let slide = read_part(&bytes, "ppt/slides/slide1.xml");
+        assert!(slide.contains("Acme &lt;Report&gt;"));
+        assert!(slide.contains("<a:tbl>"));
+        assert!(read_part(&bytes, "[Content_Types].xml").contains("/ppt/charts/chart1.xml"));
+        assert!(read_part(&bytes, "ppt/slides/_rels/slide1.xml.rels").contains("chart1.xml"));
+        assert!(read_part(&bytes, "docProps/core.xml").contains("Acme &amp; Co"));
+    }
+
+    #[test]
+    fn test_picture_format_detection() {
+        let frame = Frame::new(0.0, 0.0, 10.0, 10.0);
+        assert!(Shape::picture(frame, b"\x89PNG\r\n".to_vec()).is_some());
+        assert!(Shape::picture(frame, vec![0xFF, 0xD8, 0xFF]).is_some());
+        assert!(Shape::picture(frame, b"GIF89a".to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a & <b> \"c\""), "a &amp; &lt;b&gt; &quot;c&quot;");
+        assert_eq!(escape("x\u{1}y"), "xy");
+    }
+}
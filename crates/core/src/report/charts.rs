@@ -0,0 +1,709 @@
+//! Data-bound charts
+//!
+//! Registry of named data series drawn from `PocReportData`, which editor
+//! `Element::Chart`s bind through `data_source`, plus the two chart
+//! renderers: inline SVG for HTML reports and native chart XML for PPTX.
+
+use serde::Serialize;
+
+use crate::api::report::{NameValuePair, PocReportData};
+use crate::editor::{ChartStyle, ChartType};
+use crate::pptx_writer::{self, Chart, ChartKind, ChartSeries};
+
+/// Chart data: (category label, value)
+pub type SeriesPoints = Vec<(String, f64)>;
+
+/// A named data series chart elements can bind to
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesDefinition {
+    /// Key used as `data_source`
+    pub key: &'static str,
+    /// Display name for the editor
+    pub name: &'static str,
+    /// Chart type used when a placeholder shows the series
+    pub default_chart: ChartType,
+    #[serde(skip)]
+    extract: fn(&PocReportData) -> SeriesPoints,
+}
+
+impl SeriesDefinition {
+    /// Extract the series from report data
+    pub fn points(&self, data: &PocReportData) -> SeriesPoints {
+        (self.extract)(data)
+    }
+}
+
+fn pairs(items: &[NameValuePair]) -> SeriesPoints {
+    items
+        .iter()
+        .map(|item| (item.name.clone(), item.value as f64))
+        .collect()
+}
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const SERIES: &[SeriesDefinition] = &[
+    SeriesDefinition {
+        key: "threats_by_type",
+        name: "Threats by type",
+        default_chart: ChartType::Bar,
+        extract: |d| {
+            d.threats_by_type
+                .iter()
+                .map(|t| (t.threat_type.clone(), t.count as f64))
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "incidents_by_type",
+        name: "Incidents by type",
+        default_chart: ChartType::Bar,
+        extract: |d| {
+            d.incidents_by_type
+                .iter()
+                .map(|t| (t.incident_type.clone(), t.incidents as f64))
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "detections_by_type",
+        name: "Detections by type",
+        default_chart: ChartType::Bar,
+        extract: |d| {
+            d.incidents_by_type
+                .iter()
+                .map(|t| (t.incident_type.clone(), t.detections as f64))
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "malware_breakdown",
+        name: "Malware families",
+        default_chart: ChartType::Donut,
+        extract: |d| pairs(&d.malware_breakdown),
+    },
+    SeriesDefinition {
+        key: "top_services",
+        name: "Top affected services",
+        default_chart: ChartType::Bar,
+        extract: |d| pairs(&d.top_services),
+    },
+    SeriesDefinition {
+        key: "credential_sources",
+        name: "Credential leak sources",
+        default_chart: ChartType::Pie,
+        extract: |d| pairs(&d.credential_leaks_summary.sources),
+    },
+    SeriesDefinition {
+        key: "affected_domains",
+        name: "Top affected domains",
+        default_chart: ChartType::Bar,
+        extract: |d| pairs(&d.credential_leaks_summary.top_affected_domains),
+    },
+    SeriesDefinition {
+        key: "secret_types",
+        name: "Secret types",
+        default_chart: ChartType::Donut,
+        extract: |d| pairs(&d.secret_types),
+    },
+    SeriesDefinition {
+        key: "code_leak_platforms",
+        name: "Code leak platforms",
+        default_chart: ChartType::Pie,
+        extract: |d| pairs(&d.platform_breakdown),
+    },
+    SeriesDefinition {
+        key: "takedowns_by_type",
+        name: "Takedowns by type",
+        default_chart: ChartType::Bar,
+        extract: |d| pairs(&d.takedowns_by_type),
+    },
+    SeriesDefinition {
+        key: "takedown_status",
+        name: "Takedown status",
+        default_chart: ChartType::Donut,
+        extract: |d| {
+            vec![
+                ("Resolved".to_string(), d.takedown_resolved as f64),
+                ("Pending".to_string(), d.takedown_pending as f64),
+                ("Aborted".to_string(), d.takedown_aborted as f64),
+                ("Unresolved".to_string(), d.takedown_unresolved as f64),
+            ]
+        },
+    },
+    SeriesDefinition {
+        key: "takedown_countries",
+        name: "Takedowns by country",
+        default_chart: ChartType::Bar,
+        extract: |d| {
+            let mut countries: SeriesPoints = Vec::new();
+            for takedown in d
+                .resolved_takedowns
+                .iter()
+                .filter(|t| !t.country.is_empty())
+            {
+                match countries.iter_mut().find(|(c, _)| *c == takedown.country) {
+                    Some((_, count)) => *count += 1.0,
+                    None => countries.push((takedown.country.clone(), 1.0)),
+                }
+            }
+            countries.sort_by(|a, b| b.1.total_cmp(&a.1));
+            countries
+        },
+    },
+    SeriesDefinition {
+        key: "activity_by_weekday",
+        name: "Activity by weekday",
+        default_chart: ChartType::Bar,
+        extract: |d| {
+            WEEKDAYS
+                .iter()
+                .zip(d.activity_heatmap.grid.iter())
+                .map(|(day, hours)| (day.to_string(), hours.iter().sum::<u32>() as f64))
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "activity_by_hour",
+        name: "Activity by hour",
+        default_chart: ChartType::Area,
+        extract: |d| {
+            (0..24)
+                .map(|hour| {
+                    let total: u32 = d.activity_heatmap.grid.iter().map(|day| day[hour]).sum();
+                    (format!("{:02}h", hour), total as f64)
+                })
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "risk_history",
+        name: "Risk score history",
+        default_chart: ChartType::Line,
+        extract: |d| {
+            let labels = &d.risk_score.history_labels;
+            d.risk_score
+                .history
+                .iter()
+                .enumerate()
+                .map(|(i, score)| {
+                    let label = labels.get(i).cloned();
+                    (label.unwrap_or_else(|| format!("#{}", i + 1)), *score)
+                })
+                .collect()
+        },
+    },
+    SeriesDefinition {
+        key: "risk_score",
+        name: "Risk score",
+        default_chart: ChartType::Gauge,
+        extract: |d| vec![(d.risk_score.label.clone(), d.risk_score.current)],
+    },
+];
+
+/// Editor library keys that show a registered series with their own chart type
+const ALIASES: &[(&str, &str, ChartType)] = &[
+    ("incidents_chart", "threats_by_type", ChartType::Bar),
+    ("threat_distribution_pie", "threats_by_type", ChartType::Pie),
+    ("top_threats_table", "threats_by_type", ChartType::Bar),
+    ("takedowns_table", "takedown_status", ChartType::Bar),
+    ("geospatial_map", "takedown_countries", ChartType::Bar),
+    ("risk_score_gauge", "risk_score", ChartType::Gauge),
+];
+
+/// All registered series
+pub fn series_definitions() -> &'static [SeriesDefinition] {
+    SERIES
+}
+
+/// Series and chart type for a series key or editor library alias
+pub fn chart_for_key(key: &str) -> Option<(&'static SeriesDefinition, ChartType)> {
+    let (key, chart) = match ALIASES.iter().find(|(alias, _, _)| *alias == key) {
+        Some((_, key, chart)) => (*key, Some(chart.clone())),
+        None => (key, None),
+    };
+    let series = SERIES.iter().find(|s| s.key == key)?;
+    Some((
+        series,
+        chart.unwrap_or_else(|| series.default_chart.clone()),
+    ))
+}
+
+/// Resolve a series key (or alias) against report data
+pub fn resolve_series(key: &str, data: &PocReportData) -> Option<SeriesPoints> {
+    chart_for_key(key).map(|(series, _)| series.points(data))
+}
+
+// ============================================================
+// SVG
+// ============================================================
+
+const SVG_WIDTH: f64 = 400.0;
+const SVG_HEIGHT: f64 = 225.0;
+const GRID_COLOR: &str = "#3F3F46";
+const TRACK_COLOR: &str = "#27272A";
+
+fn color_at(colors: &[String], index: usize) -> String {
+    if colors.is_empty() {
+        "#FF671F".to_string()
+    } else {
+        pptx_writer::escape(&colors[index % colors.len()])
+    }
+}
+
+/// Value label without trailing ".0" for counts
+pub(crate) fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+/// Render a chart as a self-contained inline SVG (scales to its container,
+/// text uses `currentColor`)
+pub fn chart_svg(chart_type: &ChartType, points: &[(String, f64)], style: &ChartStyle) -> String {
+    let top = if style.title.is_some() { 28.0 } else { 8.0 };
+    let title = style
+        .title
+        .as_deref()
+        .map(|t| {
+            format!(
+                r#"<text x="{}" y="16" text-anchor="middle" font-size="14" font-weight="600" fill="currentColor">{}</text>"#,
+                SVG_WIDTH / 2.0,
+                pptx_writer::escape(t)
+            )
+        })
+        .unwrap_or_default();
+
+    let body = if points.is_empty() {
+        String::new()
+    } else {
+        match chart_type {
+            ChartType::Bar => bars_svg(points, style, top),
+            ChartType::Line => line_svg(points, style, top, false),
+            ChartType::Area => line_svg(points, style, top, true),
+            ChartType::Pie => pie_svg(points, style, top, false),
+            ChartType::Donut => pie_svg(points, style, top, true),
+            ChartType::Gauge => gauge_svg(points, style, top),
+        }
+    };
+
+    format!(
+        r#"<svg viewBox="0 0 {} {}" width="100%" height="100%" preserveAspectRatio="xMidYMid meet" font-family="inherit">{}{}</svg>"#,
+        SVG_WIDTH, SVG_HEIGHT, title, body
+    )
+}
+
+fn max_value(points: &[(String, f64)]) -> f64 {
+    points
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(0.0_f64, f64::max)
+        .max(1.0)
+}
+
+fn bars_svg(points: &[(String, f64)], style: &ChartStyle, top: f64) -> String {
+    let esc = pptx_writer::escape;
+    let (left, right, bottom) = (10.0, SVG_WIDTH - 10.0, SVG_HEIGHT - 22.0);
+    let max = max_value(points);
+    let slot = (right - left) / points.len() as f64;
+    let bar_width = slot * 0.65;
+    let plot_height = bottom - top - 16.0;
+
+    let mut svg = format!(
+        r#"<line x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}" stroke="{GRID_COLOR}" stroke-width="1"/>"#
+    );
+    for (i, (label, value)) in points.iter().enumerate() {
+        let height = value / max * plot_height;
+        let x = left + slot * i as f64 + (slot - bar_width) / 2.0;
+        let center = x + bar_width / 2.0;
+        svg.push_str(&format!(
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" rx="3" fill="{}"/>"#,
+            x,
+            bottom - height,
+            bar_width,
+            height,
+            color_at(&style.colors, i)
+        ));
+        if style.show_labels {
+            svg.push_str(&format!(
+                r#"<text x="{:.2}" y="{:.2}" text-anchor="middle" font-size="11" font-weight="600" fill="currentColor">{}</text>"#,
+                center,
+                bottom - height - 4.0,
+                format_value(*value)
+            ));
+        }
+        svg.push_str(&format!(
+            r#"<text x="{:.2}" y="{:.2}" text-anchor="middle" font-size="10" fill="currentColor" fill-opacity="0.7">{}</text>"#,
+            center,
+            bottom + 14.0,
+            esc(&truncate(label, (slot / 6.0) as usize))
+        ));
+    }
+    svg
+}
+
+fn line_svg(points: &[(String, f64)], style: &ChartStyle, top: f64, area: bool) -> String {
+    let esc = pptx_writer::escape;
+    let (left, right, bottom) = (16.0, SVG_WIDTH - 16.0, SVG_HEIGHT - 22.0);
+    let max = max_value(points);
+    let step = (right - left) / (points.len().max(2) - 1) as f64;
+    let plot_height = bottom - top - 16.0;
+    let color = color_at(&style.colors, 0);
+
+    let coords: Vec<(f64, f64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, v))| (left + step * i as f64, bottom - v / max * plot_height))
+        .collect();
+    let path: Vec<String> = coords
+        .iter()
+        .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+        .collect();
+
+    let mut svg = format!(
+        r#"<line x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}" stroke="{GRID_COLOR}" stroke-width="1"/>"#
+    );
+    if area {
+        svg.push_str(&format!(
+            r#"<polygon points="{left},{bottom} {} {:.2},{bottom}" fill="{color}" fill-opacity="0.25"/>"#,
+            path.join(" "),
+            coords.last().map(|(x, _)| *x).unwrap_or(left)
+        ));
+    }
+    svg.push_str(&format!(
+        r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="2.5" stroke-linejoin="round"/>"#,
+        path.join(" ")
+    ));
+
+    // Keep category labels readable on long series (e.g. 24 hours)
+    let label_every = points.len().div_ceil(12);
+    for (i, ((label, value), (x, y))) in points.iter().zip(&coords).enumerate() {
+        svg.push_str(&format!(
+            r#"<circle cx="{:.2}" cy="{:.2}" r="3" fill="{color}"/>"#,
+            x, y
+        ));
+        if style.show_labels && points.len() <= 12 {
+            svg.push_str(&format!(
+                r#"<text x="{:.2}" y="{:.2}" text-anchor="middle" font-size="10" font-weight="600" fill="currentColor">{}</text>"#,
+                x,
+                y - 7.0,
+                format_value(*value)
+            ));
+        }
+        if i % label_every == 0 {
+            svg.push_str(&format!(
+                r#"<text x="{:.2}" y="{:.2}" text-anchor="middle" font-size="10" fill="currentColor" fill-opacity="0.7">{}</text>"#,
+                x,
+                bottom + 14.0,
+                esc(&truncate(label, 10))
+            ));
+        }
+    }
+    svg
+}
+
+fn pie_svg(points: &[(String, f64)], style: &ChartStyle, top: f64, donut: bool) -> String {
+    let esc = pptx_writer::escape;
+    let radius = ((SVG_HEIGHT - top - 8.0) / 2.0).min(95.0);
+    let (cx, cy) = (
+        if style.show_legend {
+            radius + 16.0
+        } else {
+            SVG_WIDTH / 2.0
+        },
+        top + (SVG_HEIGHT - top) / 2.0,
+    );
+    // Slices are dashes of one thick circle stroke, starting at 12 o'clock
+    let (ring, width) = if donut {
+        (radius * 0.75, radius * 0.5)
+    } else {
+        (radius / 2.0, radius)
+    };
+    let total: f64 = points.iter().map(|(_, v)| v).sum::<f64>().max(f64::EPSILON);
+
+    let mut svg = String::new();
+    let mut offset = 0.0;
+    for (i, (_, value)) in points.iter().enumerate() {
+        let share = value / total * 100.0;
+        svg.push_str(&format!(
+            r#"<circle cx="{cx:.2}" cy="{cy:.2}" r="{ring:.2}" fill="none" stroke="{}" stroke-width="{width:.2}" pathLength="100" stroke-dasharray="{share:.3} {:.3}" stroke-dashoffset="{:.3}" transform="rotate(-90 {cx:.2} {cy:.2})"/>"#,
+            color_at(&style.colors, i),
+            100.0 - share,
+            -offset
+        ));
+        offset += share;
+    }
+    if donut && style.show_labels {
+        svg.push_str(&format!(
+            r#"<text x="{cx:.2}" y="{:.2}" text-anchor="middle" font-size="20" font-weight="700" fill="currentColor">{}</text>"#,
+            cy + 7.0,
+            format_value(total)
+        ));
+    }
+
+    if style.show_legend {
+        let x = cx + radius + 24.0;
+        let row = 18.0;
+        let start = cy - (points.len().min(9) as f64 * row) / 2.0 + row / 2.0;
+        for (i, (label, value)) in points.iter().take(9).enumerate() {
+            let y = start + row * i as f64;
+            let text = if style.show_labels {
+                format!(
+                    "{} · {} ({:.0}%)",
+                    truncate(label, 18),
+                    format_value(*value),
+                    value / total * 100.0
+                )
+            } else {
+                truncate(label, 24)
+            };
+            svg.push_str(&format!(
+                r#"<circle cx="{:.2}" cy="{:.2}" r="5" fill="{}"/><text x="{:.2}" y="{:.2}" font-size="11" fill="currentColor">{}</text>"#,
+                x,
+                y,
+                color_at(&style.colors, i),
+                x + 11.0,
+                y + 4.0,
+                esc(&text)
+            ));
+        }
+    }
+    svg
+}
+
+/// Half-circle gauge of the first point's value on a 0-100 scale
+fn gauge_svg(points: &[(String, f64)], style: &ChartStyle, top: f64) -> String {
+    let esc = pptx_writer::escape;
+    let (label, value) = &points[0];
+    let radius = (SVG_HEIGHT - top - 40.0).clamp(40.0, SVG_WIDTH / 2.0 - 30.0);
+    let (cx, cy) = (SVG_WIDTH / 2.0, top + radius + 8.0);
+    let arc = format!(
+        "M {:.2} {:.2} A {r:.2} {r:.2} 0 0 1 {:.2} {:.2}",
+        cx - radius,
+        cy,
+        cx + radius,
+        cy,
+        r = radius
+    );
+    let share = value.clamp(0.0, 100.0);
+
+    let mut svg = format!(
+        r#"<path d="{arc}" fill="none" stroke="{TRACK_COLOR}" stroke-width="22" stroke-linecap="round"/><path d="{arc}" fill="none" stroke="{}" stroke-width="22" stroke-linecap="round" pathLength="100" stroke-dasharray="{share:.2} 100"/>"#,
+        color_at(&style.colors, 0)
+    );
+    svg.push_str(&format!(
+        r#"<text x="{cx:.2}" y="{:.2}" text-anchor="middle" font-size="40" font-weight="800" fill="currentColor">{}</text>"#,
+        cy - 6.0,
+        format_value(*value)
+    ));
+    if style.show_labels && !label.is_empty() {
+        svg.push_str(&format!(
+            r#"<text x="{cx:.2}" y="{:.2}" text-anchor="middle" font-size="12" fill="currentColor" fill-opacity="0.7">{}</text>"#,
+            cy + 20.0,
+            esc(label)
+        ));
+    }
+    svg
+}
+
+fn truncate(label: &str, max_chars: usize) -> String {
+    let max_chars = max_chars.max(3);
+    if label.chars().count() <= max_chars {
+        label.to_string()
+    } else {
+        let mut short: String = label.chars().take(max_chars - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+// ============================================================
+// PPTX
+// ============================================================
+
+/// Build a native PPTX chart (literal data, editable in PowerPoint).
+/// Gauges become a two-slice doughnut (value and remainder to 100).
+pub fn native_chart(
+    chart_type: &ChartType,
+    points: &[(String, f64)],
+    style: &ChartStyle,
+) -> Option<Chart> {
+    if points.is_empty() {
+        return None;
+    }
+    let palette: Vec<String> = style.colors.iter().filter_map(|c| hex_color(c)).collect();
+    let primary = palette
+        .first()
+        .cloned()
+        .unwrap_or_else(|| pptx_writer::PptxTheme::default().primary);
+
+    let (kind, categories, values, palette) = match chart_type {
+        ChartType::Gauge => {
+            let (label, value) = &points[0];
+            let value = value.clamp(0.0, 100.0);
+            (
+                ChartKind::Doughnut,
+                vec![label.clone(), String::new()],
+                vec![value, 100.0 - value],
+                vec![
+                    primary.clone(),
+                    TRACK_COLOR.trim_start_matches('#').to_string(),
+                ],
+            )
+        }
+        _ => {
+            let kind = match chart_type {
+                ChartType::Line | ChartType::Area => ChartKind::Line,
+                ChartType::Pie | ChartType::Donut => ChartKind::Doughnut,
+                _ => ChartKind::Column,
+            };
+            let (categories, values) = points.iter().cloned().unzip();
+            (kind, categories, values, palette)
+        }
+    };
+
+    let mut chart = Chart::new(
+        kind,
+        categories,
+        vec![ChartSeries {
+            name: style.title.clone().unwrap_or_default(),
+            values,
+            color: primary,
+        }],
+    )
+    .with_palette(palette);
+    if let Some(title) = &style.title {
+        chart = chart.with_title(title);
+    }
+    let gauge = matches!(chart_type, ChartType::Gauge);
+    chart.show_legend = style.show_legend && kind == ChartKind::Doughnut && !gauge;
+    chart.show_values = style.show_labels && !gauge;
+    Some(chart)
+}
+
+/// CSS color (#RGB, #RRGGBB[AA], rgb()/rgba(), white/black) as PPTX hex.
+/// Transparent and unsupported values give None.
+pub(crate) fn hex_color(css: &str) -> Option<String> {
+    let css = css.trim();
+    if let Some(hex) = css.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => Some(hex.chars().flat_map(|c| [c, c]).collect::<String>()),
+            6 | 8 => Some(hex[..6].to_string()),
+            _ => None,
+        }
+        .map(|hex| hex.to_uppercase());
+    }
+    if let Some(args) = css
+        .strip_prefix("rgba(")
+        .or_else(|| css.strip_prefix("rgb("))
+    {
+        let parts: Vec<&str> = args
+            .trim_end_matches(')')
+            .split(',')
+            .map(str::trim)
+            .collect();
+        if parts.get(3).and_then(|a| a.parse::<f64>().ok()) == Some(0.0) {
+            return None;
+        }
+        let rgb: Vec<u8> = parts
+            .iter()
+            .take(3)
+            .filter_map(|p| p.parse::<f64>().ok())
+            .map(|v| v.clamp(0.0, 255.0).round() as u8)
+            .collect();
+        return (rgb.len() == 3).then(|| format!("{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2]));
+    }
+    match css.to_ascii_lowercase().as_str() {
+        "white" => Some("FFFFFF".into()),
+        "black" => Some("000000".into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_registry() {
+        let data = PocReportData::demo();
+
+        let mut keys: Vec<_> = series_definitions().iter().map(|s| s.key).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(
+            keys.len(),
+            series_definitions().len(),
+            "duplicate series key"
+        );
+        for (alias, key, _) in ALIASES {
+            assert!(chart_for_key(alias).is_some(), "alias {} -> {}", alias, key);
+        }
+
+        let threats = resolve_series("threats_by_type", &data).unwrap();
+        assert_eq!(threats.len(), data.threats_by_type.len());
+        assert_eq!(
+            resolve_series("risk_history", &data).unwrap().len(),
+            data.risk_score.history.len()
+        );
+        assert_eq!(resolve_series("activity_by_hour", &data).unwrap().len(), 24);
+        assert!(matches!(
+            chart_for_key("threat_distribution_pie"),
+            Some((series, ChartType::Pie)) if series.key == "threats_by_type"
+        ));
+        assert!(resolve_series("unknown", &data).is_none());
+    }
+
+    #[test]
+    fn test_chart_svg_types() {
+        let points = vec![
+            ("Phishing <web>".to_string(), 40.0),
+            ("Fake app".to_string(), 10.0),
+        ];
+        let style = ChartStyle::default();
+
+        let bar = chart_svg(&ChartType::Bar, &points, &style);
+        assert!(bar.starts_with("<svg"));
+        assert_eq!(bar.matches("<rect").count(), 2);
+        assert!(bar.contains("Phishing &lt;web&gt;"));
+
+        let donut = chart_svg(&ChartType::Donut, &points, &style);
+        assert!(donut.contains(r#"stroke-dasharray="80.000 20.000""#));
+        assert!(donut.contains(r#"stroke-dashoffset="-80.000""#));
+
+        assert!(chart_svg(&ChartType::Line, &points, &style).contains("<polyline"));
+        assert!(chart_svg(&ChartType::Area, &points, &style).contains("<polygon"));
+        assert!(
+            chart_svg(&ChartType::Gauge, &[("Risk".into(), 72.0)], &style)
+                .contains(r#"stroke-dasharray="72.00 100""#)
+        );
+    }
+
+    #[test]
+    fn test_native_chart() {
+        let style = ChartStyle::default();
+        assert!(native_chart(&ChartType::Bar, &[], &style).is_none());
+
+        let gauge = native_chart(&ChartType::Gauge, &[("Risk".into(), 72.0)], &style).unwrap();
+        assert_eq!(gauge.kind, ChartKind::Doughnut);
+        assert_eq!(gauge.series[0].values, vec![72.0, 28.0]);
+
+        let pie = native_chart(&ChartType::Pie, &[("A".into(), 1.0)], &style).unwrap();
+        assert_eq!(pie.kind, ChartKind::Doughnut);
+        assert_eq!(pie.palette[0], "6366F1");
+    }
+
+    #[test]
+    fn test_hex_color() {
+        assert_eq!(hex_color("#fff").as_deref(), Some("FFFFFF"));
+        assert_eq!(hex_color("#FF671F80").as_deref(), Some("FF671F"));
+        assert_eq!(hex_color("rgb(255, 103, 31)").as_deref(), Some("FF671F"));
+        assert_eq!(hex_color("rgba(0,0,0,0)"), None);
+        assert_eq!(hex_color("transparent"), None);
+    }
+}
@@ -30,7 +30,7 @@
 use super::OfflineAssets;
 use crate::api::report::{DeepAnalyticsData, PocReport, PocReportData, ResolvedTakedown};
 use crate::i18n::{Dictionary, Translations};
-use crate::plugins::{PluginConfig, PluginContext, PluginRegistry};
+use crate::plugins::{PluginConfig, PluginContext, PluginRegistry, SlideOutput};
 use chrono::{DateTime, Datelike, Timelike};
 use std::collections::HashMap;
 
@@ -221,11 +221,88 @@ pub fn generate_report_with_plugins(
     offline_assets: Option<&OfflineAssets>,
     config: Option<PluginConfig>,
 ) -> String {
-    // Create registry with all builtin plugins
-    let registry = PluginRegistry::with_builtins();
+    generate_report_with_registry(
+        &PluginRegistry::with_builtins(),
+        data,
+        translations,
+        offline_assets,
+        config,
+    )
+}
+
+/// Same as `generate_report_with_plugins`, with a caller-provided registry
+/// (e.g. builtins plus custom data or slide plugins)
+pub fn generate_report_with_registry(
+    registry: &PluginRegistry,
+    data: &PocReportData,
+    translations: &Translations,
+    offline_assets: Option<&OfflineAssets>,
+    config: Option<PluginConfig>,
+) -> String {
+    // Theme variables and custom CSS
+    let custom_css = config.as_ref().map(|c| c.document_css());
+    let slides = generate_plugin_slides_with(registry, data, translations, config);
+    render_slides_document_with_css(
+        &slides,
+        &data.company_name,
+        offline_assets,
+        custom_css.as_deref(),
+    )
+}
+
+/// Run the builtin data plugins and slide plugins and return the slides
+/// (no HTML shell). Used by export plugins that need the individual slides.
+pub fn generate_plugin_slides(
+    data: &PocReportData,
+    translations: &Translations,
+    config: Option<PluginConfig>,
+) -> Vec<SlideOutput> {
+    generate_plugin_slides_with(&PluginRegistry::with_builtins(), data, translations, config)
+}
 
+/// Run a registry's data plugins, then its slide plugins
+pub fn generate_plugin_slides_with(
+    registry: &PluginRegistry,
+    data: &PocReportData,
+    translations: &Translations,
+    config: Option<PluginConfig>,
+) -> Vec<SlideOutput> {
     // Build the plugin context with provided config or default
     let plugin_config = config.unwrap_or_default();
+
+    // Enrich a copy of the data before any slide reads it
+    let mut data = data.clone();
+    registry.transform_data_with_config(&mut data, &plugin_config);
+
+    render_plugin_slides_with(registry, &data, translations, plugin_config)
+}
+
+/// Run only the builtin slide plugins, on data that already went through
+/// `enrich_report_data` with the same config (e.g. PPTX export, whose native
+/// charts need the enriched data as well)
+pub fn render_enriched_slides(
+    data: &PocReportData,
+    translations: &Translations,
+    config: PluginConfig,
+) -> Vec<SlideOutput> {
+    render_plugin_slides_with(&PluginRegistry::with_builtins(), data, translations, config)
+}
+
+fn render_plugin_slides_with(
+    registry: &PluginRegistry,
+    data: &PocReportData,
+    translations: &Translations,
+    plugin_config: PluginConfig,
+) -> Vec<SlideOutput> {
+    // Customer terminology replaces the matching translation keys
+    let overridden;
+    let translations = if plugin_config.terminology.is_empty() {
+        translations
+    } else {
+        overridden = translations.with_overrides(&plugin_config.terminology);
+        &overridden
+    };
+
     let ctx = PluginContext {
         data,
         translations,
@@ -234,7 +311,34 @@ pub fn generate_report_with_plugins(
     };
 
     // Generate all slides from plugins
-    let slides = registry.generate_slides(&ctx);
+    registry.generate_slides(&ctx)
+}
+
+/// Run the builtin data plugins on report data, for consumers that do not
+/// go through the slide pipeline (legacy HTML, PPTX export, comparisons)
+pub fn enrich_report_data(data: &PocReportData, config: &PluginConfig) -> PocReportData {
+    let mut data = data.clone();
+    PluginRegistry::with_builtins().transform_data_with_config(&mut data, config);
+    data
+}
+
+/// Wrap slide outputs in the full report document (Tailwind, Chart.js, print CSS)
+pub fn render_slides_document(
+    slides: &[SlideOutput],
+    company_name: &str,
+    offline_assets: Option<&OfflineAssets>,
+) -> String {
+    render_slides_document_with_css(slides, company_name, offline_assets, None)
+}
+
+/// Same as `render_slides_document`, with CSS appended after the design
+/// system (e.g. `PluginConfig::document_css`: theme variables, custom CSS)
+pub fn render_slides_document_with_css(
+    slides: &[SlideOutput],
+    company_name: &str,
+    offline_assets: Option<&OfflineAssets>,
+    custom_css: Option<&str>,
+) -> String {
     let all_slides_html: String = slides
         .iter()
         .map(|s| s.html.as_str())
@@ -276,18 +380,22 @@ pub fn generate_report_with_plugins(
     <title>Reporte {company}</title>
     {font_links}
     {tailwind}
-    <script>tailwind.config={{theme:{{extend:{{fontFamily:{{sans:['{font_family}']}}}}}}}};</script>
+    <script>tailwind.config={{theme:{{extend:{{fontFamily:{{sans:['{font_family}']}},colors:{{orange:{{300:'rgb(var(--color-primary-light-channels) / <alpha-value>)',400:'rgb(var(--color-primary-light-channels) / <alpha-value>)',500:'rgb(var(--color-primary-channels) / <alpha-value>)',600:'rgb(var(--color-primary-channels) / <alpha-value>)'}}}}}}}}}};</script>
     <style>
         @media print {{
             @page {{ size: 16in 9in landscape; margin: 0; }}
             body {{ background-color: #fff !important; -webkit-print-color-adjust: exact; print-color-adjust: exact; width: 16in; height: 9in; }}
             .no-print {{ display: none !important; }}
+            #report-content {{ padding: 0 !important; }}
             .printable-slide {{ width: 16in; height: 9in; box-sizing: border-box; aspect-ratio: 16/9 !important; padding: 0.75in !important; box-shadow: none !important; border-radius: 0 !important; break-inside: avoid; break-after: page; page-break-after: always; margin: 0 !important; }}
         }}
         .printable-slide {{ aspect-ratio: 16/9; }}
         
         {css_styles}
     </style>
+    <style>
+        {custom_css}
+    </style>
     {chart}
 </head>
 <body class="bg-zinc-950 text-zinc-200 axur-gradient-bg">
@@ -298,7 +406,7 @@ pub fn generate_report_with_plugins(
     {lang_scripts}
 </body>
 </html>"#,
-        company = data.company_name,
+        company = company_name,
         slides = all_slides_html,
         font_links = font_links,
         tailwind = tailwind_script,
@@ -307,6 +415,7 @@ pub fn generate_report_with_plugins(
         lang_selector = lang_selector_ui,
         lang_scripts = lang_scripts,
         css_styles = axur_design_system_css(),
+        custom_css = custom_css.unwrap_or_default(),
     )
 }
 
@@ -317,18 +426,28 @@ fn axur_design_system_css() -> &'static str {
         :root {
             --color-primary: #FF671F;
             --color-primary-rgb: 255, 103, 31;
+            --color-primary-channels: 255 103 31;
+            --color-primary-light: #FF8A4C;
+            --color-primary-light-channels: 255 138 76;
             --color-surface: #09090b;
             --color-surface-rgb: 9, 9, 11;
+            --color-text: #FFFFFF;
+            --color-text-muted: #A1A1AA;
+            --font-display: 'Inter', sans-serif;
+            --font-body: 'Inter', sans-serif;
         }
 
+        body { font-family: var(--font-body); }
+        .display-text { font-family: var(--font-display); }
+
         .text-brand-primary { color: var(--color-primary); }
         .bg-brand-primary { background-color: var(--color-primary); }
         .border-brand-primary { border-color: var(--color-primary); }
-        .shadow-brand-glow { box-shadow: 0 0 15px rgba(255, 103, 31, 0.4); }
+        .shadow-brand-glow { box-shadow: 0 0 15px rgba(var(--color-primary-rgb), 0.4); }
 
         .axur-gradient-bg {
             background: 
-                radial-gradient(circle at 15% 50%, rgba(255, 103, 31, 0.08) 0%, transparent 25%),
+                radial-gradient(circle at 15% 50%, rgba(var(--color-primary-rgb), 0.08) 0%, transparent 25%),
                 radial-gradient(circle at 85% 30%, rgba(76, 29, 149, 0.15) 0%, transparent 25%),
                 linear-gradient(180deg, #050505 0%, #000000 100%);
             position: relative;
@@ -368,7 +487,7 @@ fn axur_design_system_css() -> &'static str {
         }
 
         .text-glow {
-            text-shadow: 0 0 20px rgba(255, 103, 31, 0.5);
+            text-shadow: 0 0 20px rgba(var(--color-primary-rgb), 0.5);
         }
 
         .display-text {
@@ -387,15 +506,15 @@ fn axur_design_system_css() -> &'static str {
         
         /* Pill Badge */
         .pill-badge {
-            background: linear-gradient(135deg, #FF671F 0%, #FF8A4C 100%);
+            background: linear-gradient(135deg, var(--color-primary) 0%, var(--color-primary-light) 100%);
             padding: 0.5rem 1.5rem;
             border-radius: 200px;
             font-weight: 600;
             display: inline-flex;
             align-items: center;
             justify-content: center;
-            box-shadow: 0 0 15px rgba(255, 103, 31, 0.3);
-            font-family: 'Inter', sans-serif;
+            box-shadow: 0 0 15px rgba(var(--color-primary-rgb), 0.3);
+            font-family: var(--font-body);
             color: white;
         }
 
@@ -405,7 +524,7 @@ fn axur_design_system_css() -> &'static str {
             padding: 0.5rem 1.5rem;
             border-radius: 200px;
             font-weight: 500;
-            color: #A1A1AA; /* text-muted */
+            color: var(--color-text-muted);
         }
 
         /* Section Components */
@@ -413,12 +532,12 @@ fn axur_design_system_css() -> &'static str {
             font-weight: 800;
             text-transform: uppercase;
             letter-spacing: 0.05em;
-            color: #FFFFFF;
-            font-family: 'Inter', sans-serif;
+            color: var(--color-text);
+            font-family: var(--font-display);
         }
 
         .section-badge {
-            background: #FF671F;
+            background: var(--color-primary);
             padding: 0.5rem 1.25rem;
             font-size: 0.75rem;
             font-weight: 700;
@@ -438,7 +557,7 @@ fn axur_design_system_css() -> &'static str {
 
         .h-bar-fill {
             height: 100%;
-            background: linear-gradient(90deg, #FF671F 0%, #FF8A4C 100%);
+            background: linear-gradient(90deg, var(--color-primary) 0%, var(--color-primary-light) 100%);
             border-radius: 4px;
             transition: width 0.5s ease;
         }
@@ -468,15 +587,15 @@ fn axur_design_system_css() -> &'static str {
         }
 
         .threat-tag-orange {
-            background: rgba(255, 103, 31, 0.15);
-            color: #FF671F;
+            background: rgba(var(--color-primary-rgb), 0.15);
+            color: var(--color-primary);
         }
 
         /* Logo */
         .axur-logo {
             font-weight: 900;
             letter-spacing: 0.1em;
-            font-family: 'Inter', sans-serif;
+            font-family: var(--font-display);
         }
 
         /* ============================================ */
@@ -491,8 +610,8 @@ fn axur_design_system_css() -> &'static str {
         }
 
         @keyframes pulse-orange {
-            0%, 100% { box-shadow: 0 0 20px rgba(255, 103, 31, 0.15), 0 0 60px rgba(255, 103, 31, 0.05); }
-            50% { box-shadow: 0 0 30px rgba(255, 103, 31, 0.3), 0 0 80px rgba(255, 103, 31, 0.1); }
+            0%, 100% { box-shadow: 0 0 20px rgba(var(--color-primary-rgb), 0.15), 0 0 60px rgba(var(--color-primary-rgb), 0.05); }
+            50% { box-shadow: 0 0 30px rgba(var(--color-primary-rgb), 0.3), 0 0 80px rgba(var(--color-primary-rgb), 0.1); }
         }
 
         @keyframes gradient-shift {
@@ -512,8 +631,8 @@ fn axur_design_system_css() -> &'static str {
         }
 
         @keyframes glow-pulse {
-            0%, 100% { text-shadow: 0 0 20px rgba(255, 103, 31, 0.4), 0 0 40px rgba(255, 103, 31, 0.1); }
-            50% { text-shadow: 0 0 30px rgba(255, 103, 31, 0.6), 0 0 60px rgba(255, 103, 31, 0.2); }
+            0%, 100% { text-shadow: 0 0 20px rgba(var(--color-primary-rgb), 0.4), 0 0 40px rgba(var(--color-primary-rgb), 0.1); }
+            50% { text-shadow: 0 0 30px rgba(var(--color-primary-rgb), 0.6), 0 0 60px rgba(var(--color-primary-rgb), 0.2); }
         }
 
         @keyframes line-expand {
@@ -535,7 +654,7 @@ fn axur_design_system_css() -> &'static str {
             letter-spacing: -0.04em;
             color: white;
             position: relative;
-            text-shadow: 0 0 30px rgba(255, 103, 31, 0.4), 0 0 60px rgba(255, 103, 31, 0.15);
+            text-shadow: 0 0 30px rgba(var(--color-primary-rgb), 0.4), 0 0 60px rgba(var(--color-primary-rgb), 0.15);
             animation: glow-pulse 3s ease-in-out infinite;
         }
 
@@ -546,7 +665,7 @@ fn axur_design_system_css() -> &'static str {
             letter-spacing: -0.05em;
             color: white;
             position: relative;
-            text-shadow: 0 0 40px rgba(255, 103, 31, 0.5), 0 0 80px rgba(255, 103, 31, 0.2);
+            text-shadow: 0 0 40px rgba(var(--color-primary-rgb), 0.5), 0 0 80px rgba(var(--color-primary-rgb), 0.2);
             animation: glow-pulse 3s ease-in-out infinite;
         }
 
@@ -588,7 +707,7 @@ fn axur_design_system_css() -> &'static str {
 
         .accent-line {
             height: 3px;
-            background: linear-gradient(90deg, #FF671F 0%, #FF8A4C 40%, transparent 100%);
+            background: linear-gradient(90deg, var(--color-primary) 0%, var(--color-primary-light) 40%, transparent 100%);
             border-radius: 2px;
             margin-top: 12px;
             margin-bottom: 8px;
@@ -597,7 +716,7 @@ fn axur_design_system_css() -> &'static str {
 
         .accent-line-thin {
             height: 1px;
-            background: linear-gradient(90deg, rgba(255,103,31,0.6) 0%, rgba(255,103,31,0.1) 60%, transparent 100%);
+            background: linear-gradient(90deg, rgba(var(--color-primary-rgb),0.6) 0%, rgba(var(--color-primary-rgb),0.1) 60%, transparent 100%);
             margin-top: 8px;
         }
 
@@ -617,11 +736,11 @@ fn axur_design_system_css() -> &'static str {
         }
 
         .glass-panel-premium:hover {
-            border-color: rgba(255, 103, 31, 0.15);
+            border-color: rgba(var(--color-primary-rgb), 0.15);
             box-shadow:
                 0 8px 32px rgba(0, 0, 0, 0.3),
                 0 2px 8px rgba(0, 0, 0, 0.2),
-                0 0 30px rgba(255, 103, 31, 0.08),
+                0 0 30px rgba(var(--color-primary-rgb), 0.08),
                 inset 0 1px 0 rgba(255, 255, 255, 0.06);
             transform: translateY(-2px);
         }
@@ -676,9 +795,9 @@ fn axur_design_system_css() -> &'static str {
             display: flex;
             align-items: center;
             justify-content: center;
-            background: rgba(255, 103, 31, 0.1);
-            border: 1px solid rgba(255, 103, 31, 0.2);
-            color: #FF671F;
+            background: rgba(var(--color-primary-rgb), 0.1);
+            border: 1px solid rgba(var(--color-primary-rgb), 0.2);
+            color: var(--color-primary);
             flex-shrink: 0;
         }
 
@@ -728,8 +847,8 @@ fn axur_design_system_css() -> &'static str {
         /* --- NEXT CHAPTER TEASER (Zeigarnik) --- */
 
         .next-chapter {
-            background: rgba(255, 103, 31, 0.06);
-            border: 1px solid rgba(255, 103, 31, 0.15);
+            background: rgba(var(--color-primary-rgb), 0.06);
+            border: 1px solid rgba(var(--color-primary-rgb), 0.15);
             backdrop-filter: blur(12px);
             padding: 12px 20px;
             border-radius: 12px;
@@ -738,8 +857,8 @@ fn axur_design_system_css() -> &'static str {
         }
 
         .next-chapter:hover {
-            background: rgba(255, 103, 31, 0.12);
-            border-color: rgba(255, 103, 31, 0.3);
+            background: rgba(var(--color-primary-rgb), 0.12);
+            border-color: rgba(var(--color-primary-rgb), 0.3);
             transform: translateY(-2px);
         }
 
@@ -1381,19 +1500,15 @@ fn render_data_exposure_slide(data: &PocReportData, dict: &Box<dyn Dictionary>)
         let count = data.critical_credentials.len();
         let examples: String = data.critical_credentials.iter().take(3).map(|c| {
             let user = c.user.as_deref().unwrap_or("unknown");
+            // Already masked by the redaction policy when the data was fetched
             let pass = c.password.as_deref().unwrap_or("***");
-            let masked_pass = if pass.len() > 4 {
-                format!("{}...{}", &pass[..2], &pass[pass.len()-2..])
-            } else {
-                "***".to_string()
-            };
-            
+
             format!(
                 r#"<div class="font-mono text-xs text-red-200 bg-red-950/40 px-2 py-1.5 rounded border border-red-500/20 flex justify-between items-center mb-1">
                     <span class="truncate pr-2 max-w-[120px]">{}</span>
                     <span class="text-red-400 font-bold">{}</span>
                 </div>"#,
-                user, masked_pass
+                user, pass
             )
         }).collect::<Vec<_>>().join("");
 
@@ -1504,14 +1619,9 @@ fn render_infostealer_slide(data: &PocReportData, dict: &Box<dyn Dictionary>) ->
         let count = data.critical_credentials.len();
         let examples: String = data.critical_credentials.iter().take(3).map(|c| {
             let user = c.user.as_deref().unwrap_or("unknown");
+            // Already masked by the redaction policy when the data was fetched
             let pass = c.password.as_deref().unwrap_or("***");
-            // Simple mask: show first 2 chars, mask rest, show last 2 if long enough
-            let masked_pass = if pass.len() > 4 {
-                format!("{}...{}", &pass[..2], &pass[pass.len()-2..])
-            } else {
-                "***".to_string()
-            };
-            
+
             format!(
                 r#"<div class="font-mono text-xs md:text-sm text-red-200 bg-red-950/40 px-3 py-2 rounded border border-red-500/20 flex justify-between items-center">
                     <span class="truncate pr-4">{}</span>
@@ -1520,7 +1630,7 @@ fn render_infostealer_slide(data: &PocReportData, dict: &Box<dyn Dictionary>) ->
                         <span class="text-red-400 font-bold">{}</span>
                     </div>
                 </div>"#,
-                user, masked_pass
+                user, pass
             )
         }).collect::<Vec<_>>().join("");
 
@@ -4123,6 +4233,7 @@ mod tests {
             start_date: "2023-01-01".to_string(),
             end_date: "2023-01-31".to_string(),
             is_dynamic_window: false,
+            enrichment_inputs: None,
             partner_name: None,
             tlp_level: "AMBER".to_string(),
 
@@ -4407,6 +4518,7 @@ mod tests {
             ],
             // Comparison data for comparative analysis
             comparison: None,
+            activity_heatmap: Default::default(),
         };
 
         let html = generate_full_report_html(&data, None, None, &dict);
@@ -1,5 +1,6 @@
 //! Report generation module
 
+pub mod charts;
 pub mod html;
 pub mod language_switcher;
 pub mod template_renderer;
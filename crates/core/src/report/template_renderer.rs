@@ -1,9 +1,30 @@
-//! Template Renderer (Simplified)
+//! Template Renderer
 //!
-//! Resolves placeholders in user templates with real report data
+//! Renders editor templates (`PresentationTemplate`) into report HTML and
+//! native PPTX with real data. Placeholders, `{{key}}` text and charts are
+//! resolved against the report through `pptx_mapper::map_placeholders`.
 
 use std::collections::HashMap;
 
+use base64::Engine;
+
+use super::charts::{
+    chart_for_key, chart_svg, format_value, hex_color, native_chart, resolve_series, SeriesPoints,
+};
+use super::html::render_slides_document;
+use super::OfflineAssets;
+use crate::api::report::PocReportData;
+use crate::editor::{
+    get_placeholder, resolve_canvas_slide, Background, ChartStyle, ChartType, Element,
+    GradientDirection, ImageSource, PlaceholderCategory, Position, PresentationTemplate, ShapeType,
+    Size, TextAlign, CANVAS_HEIGHT, CANVAS_WIDTH,
+};
+use crate::plugins::SlideOutput;
+use crate::pptx_mapper::{map_placeholders, parse_series};
+use crate::pptx_writer::{
+    self, Align, Frame, Paragraph, PptxPresentation, PptxSlide, PptxTheme, Shape,
+};
+
 /// Resolve all placeholders in a template JSON string
 pub fn resolve_placeholders(template_json: &str, values: &HashMap<String, String>) -> String {
     let mut result = template_json.to_string();
@@ -136,6 +157,518 @@ pub fn get_mock_placeholder_values() -> HashMap<String, String> {
     values
 }
 
+// ============================================================
+// TEMPLATE RENDERING
+// ============================================================
+
+/// Report values an element can bind to
+struct Bindings<'a> {
+    data: &'a PocReportData,
+    values: HashMap<String, String>,
+}
+
+impl<'a> Bindings<'a> {
+    fn new(data: &'a PocReportData) -> Self {
+        Self {
+            data,
+            values: map_placeholders(data),
+        }
+    }
+
+    /// Chart points for a data source: a registered series, else a
+    /// "label: value" list or a single number among the placeholder values
+    fn points(&self, key: &str) -> SeriesPoints {
+        if let Some(points) = resolve_series(key, self.data) {
+            return points;
+        }
+        let Some(value) = self.values.get(key) else {
+            return Vec::new();
+        };
+        let points = parse_series(value);
+        if !points.is_empty() {
+            return points;
+        }
+        value
+            .replace(',', "")
+            .trim_end_matches(['%', 'h'])
+            .trim()
+            .parse()
+            .map(|number| vec![(String::new(), number)])
+            .unwrap_or_default()
+    }
+}
+
+/// How a placeholder is drawn: tables for the table/geo library entries,
+/// charts for registered series, text otherwise
+enum PlaceholderView {
+    Value,
+    Chart(ChartType),
+    Table,
+}
+
+fn placeholder_view(key: &str) -> PlaceholderView {
+    if matches!(
+        get_placeholder(key).map(|p| p.category),
+        Some(PlaceholderCategory::Tables | PlaceholderCategory::Geospatial)
+    ) {
+        return PlaceholderView::Table;
+    }
+    match chart_for_key(key) {
+        Some((_, chart_type)) => PlaceholderView::Chart(chart_type),
+        None => PlaceholderView::Value,
+    }
+}
+
+/// Visible slides in presentation order, with `canvas_json` converted to elements
+pub fn template_slides(template: &PresentationTemplate) -> Vec<crate::editor::SlideDefinition> {
+    let mut slides: Vec<_> = template
+        .slides
+        .iter()
+        .filter(|slide| slide.visible)
+        .map(resolve_canvas_slide)
+        .collect();
+    slides.sort_by_key(|slide| slide.order);
+    slides
+}
+
+/// Render each visible template slide to report HTML.
+/// `data` should already be enriched (see `enrich_report_data`).
+pub fn render_template_slides(
+    template: &PresentationTemplate,
+    data: &PocReportData,
+) -> Vec<SlideOutput> {
+    let bindings = Bindings::new(data);
+    template_slides(template)
+        .iter()
+        .map(|slide| SlideOutput {
+            id: format!("template.{}", slide.id),
+            html: slide_html(slide, &bindings),
+        })
+        .collect()
+}
+
+/// Render a template to a complete report document
+pub fn render_template_report(
+    template: &PresentationTemplate,
+    data: &PocReportData,
+    offline_assets: Option<&OfflineAssets>,
+) -> String {
+    render_slides_document(
+        &render_template_slides(template, data),
+        &data.company_name,
+        offline_assets,
+    )
+}
+
+/// Canvas pixels as container width units, so text scales with the slide
+fn cqw(px: f64) -> String {
+    format!("{:.3}cqw", px / CANVAS_WIDTH * 100.0)
+}
+
+fn box_style(position: &Position, size: &Size) -> String {
+    format!(
+        "position: absolute; left: {:.3}%; top: {:.3}%; width: {:.3}%; height: {:.3}%;",
+        position.x, position.y, size.width, size.height
+    )
+}
+
+/// Font size for a single value filling a box of the given height
+fn value_font_px(size: &Size) -> f64 {
+    (size.height / 100.0 * CANVAS_HEIGHT * 0.45).clamp(12.0, 72.0)
+}
+
+fn background_css(background: &Background) -> String {
+    let esc = pptx_writer::escape;
+    match background {
+        Background::Solid { color } => format!("background: {};", esc(color)),
+        Background::Gradient {
+            start_color,
+            end_color,
+            direction,
+        } => {
+            let direction = match direction {
+                GradientDirection::ToRight => "to right",
+                GradientDirection::ToBottom => "to bottom",
+                GradientDirection::ToBottomRight => "to bottom right",
+                GradientDirection::ToBottomLeft => "to bottom left",
+            };
+            format!(
+                "background: linear-gradient({}, {}, {});",
+                direction,
+                esc(start_color),
+                esc(end_color)
+            )
+        }
+        Background::Image { url, .. } => {
+            format!("background: #000 url('{}') center / cover;", esc(url))
+        }
+    }
+}
+
+fn slide_html(slide: &crate::editor::SlideDefinition, bindings: &Bindings) -> String {
+    let elements: String = slide
+        .elements
+        .iter()
+        .map(|element| element_html(element, bindings))
+        .collect();
+    format!(
+        r#"<div class="relative group"><div class="printable-slide aspect-[16/9] w-full mb-8 relative text-white overflow-hidden" style="container-type: inline-size; {background}">
+                {elements}
+            </div></div>"#,
+        background = background_css(&slide.background),
+        elements = elements
+    )
+}
+
+fn element_html(element: &Element, bindings: &Bindings) -> String {
+    let esc = pptx_writer::escape;
+    match element {
+        Element::Text {
+            content,
+            style,
+            position,
+            size,
+            ..
+        } => {
+            let align = match style.text_align {
+                TextAlign::Left => "left",
+                TextAlign::Center => "center",
+                TextAlign::Right => "right",
+                TextAlign::Justify => "justify",
+            };
+            format!(
+                r#"<div style="{} color: {}; font-family: {}, sans-serif; font-size: {}; font-weight: {}; text-align: {}; line-height: {}; white-space: pre-wrap; overflow-wrap: break-word;">{}</div>"#,
+                box_style(position, size),
+                esc(&style.color),
+                esc(&style.font_family),
+                cqw(style.font_size as f64),
+                esc(&style.font_weight),
+                align,
+                style.line_height,
+                esc(&resolve_placeholders(content, &bindings.values))
+            )
+        }
+        Element::Placeholder {
+            key,
+            position,
+            size,
+            ..
+        } => {
+            let inner = match placeholder_view(key) {
+                PlaceholderView::Value => {
+                    let Some(value) = bindings.values.get(key) else {
+                        return String::new();
+                    };
+                    format!(
+                        r#"<div style="display: flex; align-items: center; justify-content: center; height: 100%; font-size: {}; font-weight: 700; text-align: center;">{}</div>"#,
+                        cqw(value_font_px(size)),
+                        esc(value)
+                    )
+                }
+                PlaceholderView::Chart(chart_type) => {
+                    chart_svg(&chart_type, &bindings.points(key), &ChartStyle::default())
+                }
+                PlaceholderView::Table => table_html(&bindings.points(key)),
+            };
+            format!(
+                r#"<div style="{}">{}</div>"#,
+                box_style(position, size),
+                inner
+            )
+        }
+        Element::Chart {
+            chart_type,
+            data_source,
+            style,
+            position,
+            size,
+            ..
+        } => {
+            format!(
+                r#"<div style="{}">{}</div>"#,
+                box_style(position, size),
+                chart_svg(chart_type, &bindings.points(data_source), style)
+            )
+        }
+        Element::Image {
+            src,
+            position,
+            size,
+            ..
+        } => {
+            let src = match src {
+                ImageSource::Url { url } => url.clone(),
+                ImageSource::Base64 { data, mime_type } => {
+                    format!("data:{};base64,{}", mime_type, data)
+                }
+                // Uploaded assets are resolved by the editor, not available here
+                ImageSource::Asset { .. } => return String::new(),
+            };
+            format!(
+                r#"<img src="{}" style="{} object-fit: contain;" alt="">"#,
+                esc(&src),
+                box_style(position, size)
+            )
+        }
+        Element::Shape {
+            shape_type,
+            style,
+            position,
+            size,
+            ..
+        } => {
+            let stroke = format!(
+                "{} solid {}",
+                cqw(style.stroke_width),
+                esc(&style.stroke_color)
+            );
+            let shape = match shape_type {
+                ShapeType::Rectangle => format!("border: {};", stroke),
+                ShapeType::RoundedRect => format!(
+                    "border: {}; border-radius: {};",
+                    stroke,
+                    cqw(style.border_radius)
+                ),
+                ShapeType::Circle | ShapeType::Ellipse => {
+                    format!("border: {}; border-radius: 50%;", stroke)
+                }
+                ShapeType::Triangle => "clip-path: polygon(50% 0, 100% 100%, 0 100%);".to_string(),
+                ShapeType::Arrow => {
+                    "clip-path: polygon(0 35%, 70% 35%, 70% 0, 100% 50%, 70% 100%, 70% 65%, 0 65%);"
+                        .to_string()
+                }
+                ShapeType::Line => format!("border-top: {}; height: 0;", stroke),
+            };
+            let fill = match shape_type {
+                ShapeType::Line => "transparent".to_string(),
+                _ => esc(&style.fill_color),
+            };
+            format!(
+                r#"<div style="{} background: {}; opacity: {}; {}"></div>"#,
+                box_style(position, size),
+                fill,
+                style.opacity,
+                shape
+            )
+        }
+    }
+}
+
+fn table_html(rows: &[(String, f64)]) -> String {
+    let esc = pptx_writer::escape;
+    let rows: String = rows
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                r#"<tr style="border-bottom: 1px solid #334155;"><td style="padding: 0.4em 0.6em;">{}</td><td style="padding: 0.4em 0.6em; text-align: right; font-weight: 700;">{}</td></tr>"#,
+                esc(label),
+                format_value(*value)
+            )
+        })
+        .collect();
+    format!(
+        r#"<table style="width: 100%; border-collapse: collapse; font-size: {};">{}</table>"#,
+        cqw(14.0),
+        rows
+    )
+}
+
+// ============================================================
+// PPTX
+// ============================================================
+
+/// Render a template to a native PPTX deck: text, placeholders and charts
+/// become editable text boxes, tables and charts
+pub fn render_template_pptx(
+    template: &PresentationTemplate,
+    data: &PocReportData,
+) -> Result<Vec<u8>, String> {
+    let bindings = Bindings::new(data);
+    let mut deck = PptxPresentation::new(
+        &format!("{} - {}", data.company_name, template.name),
+        PptxTheme::default(),
+    );
+
+    for slide in template_slides(template) {
+        let mut out = PptxSlide::new(&slide.id.to_string());
+        match &slide.background {
+            Background::Solid { color } => out.background = hex_color(color),
+            Background::Gradient { start_color, .. } => out.background = hex_color(start_color),
+            Background::Image { url, .. } => {
+                if let Some(picture) = decode_data_url(url).and_then(|bytes| {
+                    Shape::picture(Frame::new(0.0, 0.0, CANVAS_WIDTH, CANVAS_HEIGHT), bytes)
+                }) {
+                    out.add(picture);
+                }
+            }
+        }
+        for element in &slide.elements {
+            if let Some(shape) = element_shape(element, &bindings) {
+                out.add(shape);
+            }
+        }
+        deck.push(out);
+    }
+
+    deck.to_bytes()
+}
+
+fn frame(position: &Position, size: &Size) -> Frame {
+    Frame::new(
+        position.x / 100.0 * CANVAS_WIDTH,
+        position.y / 100.0 * CANVAS_HEIGHT,
+        size.width / 100.0 * CANVAS_WIDTH,
+        size.height / 100.0 * CANVAS_HEIGHT,
+    )
+}
+
+/// Canvas pixels to points
+fn pt(px: f64) -> f64 {
+    px * 0.75
+}
+
+fn decode_data_url(url: &str) -> Option<Vec<u8>> {
+    let (_, data) = url.strip_prefix("data:")?.split_once(";base64,")?;
+    base64::engine::general_purpose::STANDARD.decode(data).ok()
+}
+
+fn element_shape(element: &Element, bindings: &Bindings) -> Option<Shape> {
+    match element {
+        Element::Text {
+            content,
+            style,
+            position,
+            size,
+            ..
+        } => {
+            let color = hex_color(&style.color).unwrap_or_else(|| "FFFFFF".into());
+            let bold = style.font_weight == "bold"
+                || style.font_weight.parse::<u32>().unwrap_or(400) >= 600;
+            let align = match style.text_align {
+                TextAlign::Center => Align::Center,
+                TextAlign::Right => Align::Right,
+                TextAlign::Left | TextAlign::Justify => Align::Left,
+            };
+            let paragraphs = resolve_placeholders(content, &bindings.values)
+                .lines()
+                .map(|line| {
+                    let paragraph =
+                        Paragraph::new(line, pt(style.font_size as f64), &color).align(align);
+                    if bold {
+                        paragraph.bold()
+                    } else {
+                        paragraph
+                    }
+                })
+                .collect();
+            Some(Shape::text_lines(frame(position, size), paragraphs))
+        }
+        Element::Placeholder {
+            key,
+            position,
+            size,
+            ..
+        } => {
+            let frame = frame(position, size);
+            match placeholder_view(key) {
+                PlaceholderView::Value => Some(Shape::Text {
+                    frame,
+                    paragraphs: vec![Paragraph::new(
+                        bindings.values.get(key)?.as_str(),
+                        pt(value_font_px(size)),
+                        "FFFFFF",
+                    )
+                    .bold()
+                    .align(Align::Center)],
+                    fill: None,
+                    border: None,
+                    rounded: false,
+                    center: true,
+                }),
+                PlaceholderView::Chart(chart_type) => {
+                    native_chart(&chart_type, &bindings.points(key), &ChartStyle::default())
+                        .map(|chart| Shape::chart(frame, chart))
+                }
+                PlaceholderView::Table => {
+                    let points = bindings.points(key);
+                    if points.is_empty() {
+                        return None;
+                    }
+                    let header = get_placeholder(key)
+                        .map(|p| p.display_name)
+                        .unwrap_or_default();
+                    let mut rows = vec![vec![header, String::new()]];
+                    rows.extend(
+                        points
+                            .iter()
+                            .map(|(label, value)| vec![label.clone(), format_value(*value)]),
+                    );
+                    Some(Shape::table(frame, rows, 11.0))
+                }
+            }
+        }
+        Element::Chart {
+            chart_type,
+            data_source,
+            style,
+            position,
+            size,
+            ..
+        } => native_chart(chart_type, &bindings.points(data_source), style)
+            .map(|chart| Shape::chart(frame(position, size), chart)),
+        Element::Image {
+            src: ImageSource::Base64 { data, .. },
+            position,
+            size,
+            ..
+        } => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .ok()?;
+            Shape::picture(frame(position, size), bytes)
+        }
+        // Remote and uploaded images are not fetched while rendering
+        Element::Image { .. } => None,
+        Element::Shape {
+            shape_type,
+            style,
+            position,
+            size,
+            ..
+        } => {
+            let frame = frame(position, size);
+            let fill = hex_color(&style.fill_color);
+            let stroke = hex_color(&style.stroke_color).filter(|_| style.stroke_width > 0.0);
+            match shape_type {
+                ShapeType::Line => Some(Shape::rect(
+                    Frame::new(
+                        frame.x,
+                        frame.y,
+                        frame.w.max(1.0),
+                        style.stroke_width.max(1.0),
+                    ),
+                    &stroke?,
+                )),
+                ShapeType::Rectangle if stroke.is_none() => Some(Shape::rect(frame, &fill?)),
+                // Other shapes are approximated by (rounded) boxes
+                _ => {
+                    if fill.is_none() && stroke.is_none() {
+                        return None;
+                    }
+                    Some(Shape::Text {
+                        frame,
+                        paragraphs: Vec::new(),
+                        fill,
+                        border: stroke,
+                        rounded: !matches!(shape_type, ShapeType::Rectangle | ShapeType::Triangle),
+                        center: false,
+                    })
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +684,97 @@ mod tests {
         assert!(result.contains("Test Corp"));
         assert!(result.contains("100"));
     }
+
+    fn template() -> PresentationTemplate {
+        use crate::editor::SlideDefinition;
+        use uuid::Uuid;
+
+        let mut visible = SlideDefinition {
+            order: 1,
+            ..Default::default()
+        };
+        visible.elements = vec![
+            Element::Text {
+                id: Uuid::new_v4(),
+                content: "Report for {{company_name}}".to_string(),
+                style: Default::default(),
+                position: Position { x: 5.0, y: 5.0 },
+                size: Size {
+                    width: 90.0,
+                    height: 10.0,
+                },
+                locked: false,
+            },
+            Element::Placeholder {
+                id: Uuid::new_v4(),
+                key: "total_incidents".to_string(),
+                position: Position { x: 5.0, y: 20.0 },
+                size: Size {
+                    width: 30.0,
+                    height: 20.0,
+                },
+                locked: false,
+            },
+            Element::Chart {
+                id: Uuid::new_v4(),
+                chart_type: ChartType::Bar,
+                data_source: "threats_by_type".to_string(),
+                style: ChartStyle::default(),
+                position: Position { x: 40.0, y: 20.0 },
+                size: Size {
+                    width: 55.0,
+                    height: 60.0,
+                },
+                locked: false,
+            },
+        ];
+        let hidden = SlideDefinition {
+            visible: false,
+            elements: vec![Element::Placeholder {
+                id: Uuid::new_v4(),
+                key: "company_name".to_string(),
+                position: Position::default(),
+                size: Size::default(),
+                locked: false,
+            }],
+            ..Default::default()
+        };
+
+        PresentationTemplate {
+            slides: vec![visible, hidden],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_template_slides_with_real_data() {
+        let data = PocReportData::demo();
+        let slides = render_template_slides(&template(), &data);
+
+        assert_eq!(slides.len(), 1);
+        let html = &slides[0].html;
+        assert!(html.contains(&format!("Report for {}", data.company_name)));
+        assert!(!html.contains("{{company_name}}"));
+        assert!(html.contains(&map_placeholders(&data)["total_incidents"]));
+        assert!(html.contains(&data.threats_by_type[0].threat_type));
+    }
+
+    #[test]
+    fn test_render_template_pptx() {
+        use std::io::Read;
+
+        let data = PocReportData::demo();
+        let bytes = render_template_pptx(&template(), &data).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut slide = String::new();
+        archive
+            .by_name("ppt/slides/slide1.xml")
+            .unwrap()
+            .read_to_string(&mut slide)
+            .unwrap();
+        assert!(slide.contains(&pptx_writer::escape(&data.company_name)));
+        assert!(slide.contains("<c:chart"));
+        assert!(archive.by_name("ppt/slides/slide2.xml").is_err());
+    }
 }
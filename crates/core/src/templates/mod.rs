@@ -1,144 +1,288 @@
-//! Industry Templates for Report Generation
+//! Report Presets for Report Generation
 //!
-//! Pre-configured slide sets and styling for different industry verticals.
-//! Each template defines which plugins to show, color schemes, and terminology.
+//! A preset bundles the report settings of a vertical or a customer: which
+//! slide plugins to show and in which order, theme, accent color,
+//! terminology overrides, default language, risk model and insight rules.
+//! The builtin
+//! presets cover the usual industries; users can store their own (see the
+//! backend `/api/presets` routes) and they are applied through `PluginConfig`.
 
-use crate::plugins::ThemeMode;
+use crate::api::insights::InsightRulePack;
+use crate::api::risk::RiskModel;
+use crate::plugins::builtin::theme::hex_rgb;
+use crate::plugins::{PluginConfig, PluginRegistry, ThemeMode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-/// Industry-specific report template
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum IndustryTemplate {
-    /// General purpose template (default)
-    #[default]
-    General,
-    /// Financial services: credentials, BINs, fraud focus
-    Fintech,
-    /// Retail/E-commerce: brand abuse, phishing, counterfeits
-    Retail,
-    /// Healthcare: data leaks, compliance focus
-    Healthcare,
+/// Stored report preset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportPreset {
+    /// Unique identifier ("fintech", or a UUID for user presets)
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Description for UI tooltip
+    pub description: String,
+    /// Slide plugins to show, in order. Empty keeps every plugin in its
+    /// default order.
+    pub plugins: Vec<String>,
+    /// Theme mode
+    pub theme: ThemeMode,
+    /// Primary accent color (hex); None keeps the Axur orange
+    pub accent_color: Option<String>,
+    /// Translation key -> replacement text (e.g. "Clientes" -> "Ciudadanos")
+    pub terminology: HashMap<String, String>,
+    /// Default report language ("es", "en", "pt-br")
+    pub language: Option<String>,
+    /// Risk score model for the vertical
+    pub risk_model: Option<RiskModel>,
+    /// Insight rule pack for the vertical or customer
+    pub insight_rules: Option<InsightRulePack>,
 }
 
-impl IndustryTemplate {
-    /// Parse from string (case-insensitive)
-    pub fn from_str(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "fintech" | "finance" | "banking" => Self::Fintech,
-            "retail" | "ecommerce" | "e-commerce" => Self::Retail,
-            "healthcare" | "health" | "medical" => Self::Healthcare,
-            _ => Self::General,
+impl ReportPreset {
+    /// Check the preset before storing it
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Preset name is required".into());
+        }
+        if let Some(color) = &self.accent_color {
+            if hex_rgb(color).is_none() {
+                return Err(format!("Invalid accent color '{}'", color));
+            }
+        }
+        if let Some(language) = &self.language {
+            if !matches!(language.as_str(), "es" | "en" | "pt" | "pt-br") {
+                return Err(format!("Unsupported language '{}'", language));
+            }
         }
+        if let Some(rules) = &self.insight_rules {
+            rules.validate()?;
+        }
+        Ok(())
     }
 
-    /// Human-readable name
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            Self::General => "General",
-            Self::Fintech => "🏦 Fintech",
-            Self::Retail => "🛒 Retail",
-            Self::Healthcare => "🏥 Healthcare",
+    /// Apply the preset on top of a request configuration: plugins outside
+    /// the preset are disabled (as are those the request disables), and the
+    /// preset theme, order, colors, terminology, risk model and insight rules
+    /// are set. A risk model or rule pack already in `config` wins over the
+    /// preset one.
+    pub fn apply(&self, mut config: PluginConfig) -> PluginConfig {
+        if !self.plugins.is_empty() {
+            let registry = PluginRegistry::with_builtins();
+            for plugin in registry.slide_plugins() {
+                let id = plugin.id().to_string();
+                if !self.plugins.contains(&id) && !config.disabled_plugins.contains(&id) {
+                    config.disabled_plugins.push(id);
+                }
+            }
+            config.plugin_order = self.plugins.clone();
         }
+
+        config.theme = self.theme.clone();
+        if let Some(color) = &self.accent_color {
+            if hex_rgb(color).is_some() {
+                config.branding.primary = color.clone();
+                config.branding.primary_light = color.clone();
+            }
+        }
+        for (key, text) in &self.terminology {
+            config
+                .terminology
+                .entry(key.clone())
+                .or_insert_with(|| text.clone());
+        }
+        if config.risk_model.is_none() {
+            config.risk_model = self.risk_model.clone();
+        }
+        if config.insight_rules.is_none() {
+            config.insight_rules = self.insight_rules.clone();
+        }
+        config
     }
+}
 
-    /// Default plugins to ENABLE for this template
-    /// (all others will be disabled)
-    pub fn enabled_plugins(&self) -> Vec<&'static str> {
-        match self {
-            Self::General => vec![
+fn preset(
+    id: &str,
+    name: &str,
+    description: &str,
+    plugins: &[&str],
+    theme: ThemeMode,
+    accent_color: &str,
+) -> ReportPreset {
+    ReportPreset {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        plugins: plugins.iter().map(|p| p.to_string()).collect(),
+        theme,
+        accent_color: Some(accent_color.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Fintech: every leaked credential is an account takeover risk, so the
+/// credential insight fires (and turns critical) much earlier
+fn fintech_insight_rules() -> InsightRulePack {
+    let mut pack = InsightRulePack {
+        id: "fintech".to_string(),
+        name: "Fintech".to_string(),
+        ..Default::default()
+    };
+    if let Some(rule) = pack
+        .rules
+        .iter_mut()
+        .find(|r| r.id == "credential_exposure")
+    {
+        rule.when = vec!["credentials_total > 20".to_string()];
+        for escalation in &mut rule.escalations {
+            escalation.when = vec!["credentials_total > 100".to_string()];
+        }
+    }
+    pack
+}
+
+/// Presets shipped with the tool
+pub fn builtin_presets() -> Vec<ReportPreset> {
+    vec![
+        preset(
+            "general",
+            "General",
+            "Standard report with all sections",
+            &[],
+            ThemeMode::Dark,
+            "#FF671F", // Axur orange
+        ),
+        ReportPreset {
+            insight_rules: Some(fintech_insight_rules()),
+            ..preset(
+                "fintech",
+                "🏦 Fintech",
+                "Focus on credentials, BINs, and fraud indicators",
+                &[
+                    "builtin.cover",
+                    "builtin.toc",
+                    "builtin.metrics",
+                    "builtin.threats",
+                    "builtin.credentials", // Credentials are critical
+                    "builtin.data_exposure",
+                    "builtin.takedowns",
+                    "builtin.radar",
+                    "builtin.roi",
+                    "builtin.insights",
+                    "builtin.closing",
+                ],
+                ThemeMode::Dark,
+                "#10B981", // Emerald green (trust/money)
+            )
+        },
+        preset(
+            "retail",
+            "🛒 Retail",
+            "Emphasis on brand abuse, phishing, and counterfeits",
+            &[
                 "builtin.cover",
                 "builtin.toc",
                 "builtin.metrics",
                 "builtin.threats",
-                "builtin.takedowns",
+                "builtin.takedowns", // Brand abuse focus
+                "builtin.takedown_examples",
+                "builtin.poc_examples", // Screenshots important
+                "builtin.geospatial",   // Track attack origins
                 "builtin.roi",
-                "builtin.exposure",
-                "builtin.geospatial",
-                "builtin.evidence",
+                "builtin.closing",
             ],
-            Self::Fintech => vec![
+            ThemeMode::Dark,
+            "#8B5CF6", // Purple (brand)
+        ),
+        preset(
+            "healthcare",
+            "🏥 Healthcare",
+            "Prioritizes data exposure and compliance metrics",
+            &[
                 "builtin.cover",
                 "builtin.toc",
                 "builtin.metrics",
+                "builtin.data_exposure", // PHI leaks critical
+                "builtin.credentials",
                 "builtin.threats",
-                "builtin.exposure", // Credentials are critical
                 "builtin.takedowns",
                 "builtin.roi",
-                // Omit: geospatial (less relevant)
+                "builtin.closing",
+                // Omit: examples (privacy concerns)
             ],
-            Self::Retail => vec![
+            ThemeMode::Light, // Professional/clinical look
+            "#0EA5E9",        // Blue (medical)
+        ),
+        preset(
+            "government",
+            "🏛️ Government",
+            "Impersonation of public services, leaks and citizen-facing fraud",
+            &[
                 "builtin.cover",
                 "builtin.toc",
                 "builtin.metrics",
                 "builtin.threats",
-                "builtin.takedowns",  // Brand abuse focus
-                "builtin.evidence",   // Screenshots important
-                "builtin.geospatial", // Track attack origins
-                "builtin.roi",
+                "builtin.data_exposure",
+                "builtin.credentials",
+                "builtin.takedowns",
+                "builtin.insights",
+                "builtin.closing",
             ],
-            Self::Healthcare => vec![
+            ThemeMode::Light,
+            "#1D4ED8", // Institutional blue
+        ),
+        preset(
+            "telecom",
+            "📡 Telecom",
+            "Fake apps, SIM-swap phishing and subscriber credential leaks",
+            &[
                 "builtin.cover",
                 "builtin.toc",
                 "builtin.metrics",
-                "builtin.exposure", // PHI leaks critical
                 "builtin.threats",
+                "builtin.heatmap",
+                "builtin.credentials",
                 "builtin.takedowns",
+                "builtin.velocity",
                 "builtin.roi",
-                // Omit: evidence (privacy concerns)
+                "builtin.closing",
             ],
-        }
-    }
-
-    /// Plugins to DISABLE for this template
-    pub fn disabled_plugins(&self) -> Vec<String> {
-        let all_plugins = vec![
-            "builtin.cover",
-            "builtin.toc",
-            "builtin.metrics",
-            "builtin.threats",
-            "builtin.takedowns",
-            "builtin.roi",
-            "builtin.exposure",
-            "builtin.geospatial",
-            "builtin.evidence",
-        ];
-
-        let enabled = self.enabled_plugins();
-        all_plugins
-            .into_iter()
-            .filter(|p| !enabled.contains(p))
-            .map(|s| s.to_string())
-            .collect()
-    }
-
-    /// Preferred theme mode for this template
-    pub fn theme(&self) -> ThemeMode {
-        match self {
-            Self::Healthcare => ThemeMode::Light, // Professional/clinical look
-            _ => ThemeMode::Dark,                 // Axur brand default
-        }
-    }
-
-    /// Primary accent color (hex)
-    pub fn accent_color(&self) -> &'static str {
-        match self {
-            Self::General => "#FF671F",    // Axur orange
-            Self::Fintech => "#10B981",    // Emerald green (trust/money)
-            Self::Retail => "#8B5CF6",     // Purple (brand)
-            Self::Healthcare => "#0EA5E9", // Blue (medical)
-        }
-    }
+            ThemeMode::Dark,
+            "#E11D48", // Rose
+        ),
+        preset(
+            "education",
+            "🎓 Education",
+            "Student and staff credentials, phishing and code leaks",
+            &[
+                "builtin.cover",
+                "builtin.toc",
+                "builtin.metrics",
+                "builtin.credentials",
+                "builtin.data_exposure",
+                "builtin.threats",
+                "builtin.takedowns",
+                "builtin.closing",
+            ],
+            ThemeMode::Light,
+            "#F59E0B", // Amber
+        ),
+    ]
+}
 
-    /// Description for UI tooltip
-    pub fn description(&self) -> &'static str {
-        match self {
-            Self::General => "Standard report with all sections",
-            Self::Fintech => "Focus on credentials, BINs, and fraud indicators",
-            Self::Retail => "Emphasis on brand abuse, phishing, and counterfeits",
-            Self::Healthcare => "Prioritizes data exposure and compliance metrics",
-        }
-    }
+/// Builtin preset by id or common alias (case-insensitive)
+pub fn builtin_preset(id: &str) -> Option<ReportPreset> {
+    let id = match id.to_lowercase().as_str() {
+        "finance" | "banking" => "fintech".to_string(),
+        "ecommerce" | "e-commerce" => "retail".to_string(),
+        "health" | "medical" => "healthcare".to_string(),
+        "public" | "public-sector" => "government".to_string(),
+        "telco" | "telecommunications" => "telecom".to_string(),
+        other => other.to_string(),
+    };
+    builtin_presets().into_iter().find(|p| p.id == id)
 }
 
 #[cfg(test)]
@@ -146,26 +290,64 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_from_str() {
-        assert_eq!(
-            IndustryTemplate::from_str("fintech"),
-            IndustryTemplate::Fintech
-        );
-        assert_eq!(
-            IndustryTemplate::from_str("RETAIL"),
-            IndustryTemplate::Retail
-        );
-        assert_eq!(
-            IndustryTemplate::from_str("unknown"),
-            IndustryTemplate::General
-        );
+    fn test_builtin_preset_lookup() {
+        assert_eq!(builtin_preset("fintech").unwrap().id, "fintech");
+        assert_eq!(builtin_preset("RETAIL").unwrap().id, "retail");
+        assert_eq!(builtin_preset("banking").unwrap().id, "fintech");
+        assert!(builtin_preset("unknown").is_none());
+
+        for preset in builtin_presets() {
+            assert!(preset.validate().is_ok(), "{}", preset.id);
+        }
+    }
+
+    #[test]
+    fn test_apply_preset() {
+        let mut fintech = builtin_preset("fintech").unwrap();
+        fintech
+            .terminology
+            .insert("footer_text".into(), "Banco X".into());
+
+        let config = fintech.apply(PluginConfig::default());
+        assert!(config
+            .disabled_plugins
+            .contains(&"builtin.geospatial".to_string()));
+        assert!(!config
+            .disabled_plugins
+            .contains(&"builtin.credentials".to_string()));
+        assert_eq!(config.plugin_order, fintech.plugins);
+        assert_eq!(config.branding.primary, "#10B981");
+        assert!(config
+            .branding
+            .css()
+            .contains("--color-primary-rgb: 16, 185, 129"));
+        assert_eq!(config.terminology["footer_text"], "Banco X");
+        assert_eq!(config.insight_rules.unwrap().id, "fintech");
+
+        // "General" keeps every plugin and the default order
+        let config = builtin_preset("general")
+            .unwrap()
+            .apply(PluginConfig::default());
+        assert!(config.disabled_plugins.is_empty());
+        assert!(config.plugin_order.is_empty());
     }
 
     #[test]
-    fn test_disabled_plugins() {
-        let fintech = IndustryTemplate::Fintech;
-        let disabled = fintech.disabled_plugins();
-        assert!(disabled.contains(&"builtin.geospatial".to_string()));
-        assert!(!disabled.contains(&"builtin.exposure".to_string()));
+    fn test_validate() {
+        let mut preset = ReportPreset {
+            name: "Gov".into(),
+            accent_color: Some("blue".into()),
+            ..Default::default()
+        };
+        assert!(preset.validate().is_err());
+        preset.accent_color = Some("#1d4ed8".into());
+        assert!(preset.validate().is_ok());
+        preset.language = Some("fr".into());
+        assert!(preset.validate().is_err());
+        preset.language = None;
+        let mut rules = InsightRulePack::default();
+        rules.rules[0].when = vec!["unknown_metric > 1".into()];
+        preset.insight_rules = Some(rules);
+        assert!(preset.validate().is_err());
     }
 }
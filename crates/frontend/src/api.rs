@@ -115,6 +115,11 @@ pub struct GenerateReportResponse {
     pub error_code: Option<String>,
     /// User-friendly error message
     pub error_message: Option<String>,
+    /// Stored data snapshot id (re-render without re-fetching)
+    pub snapshot_id: Option<String>,
+    /// Threat Hunting sources whose searches timed out (counts incomplete)
+    #[serde(default)]
+    pub timed_out_sources: Vec<String>,
 }
 
 // ========================
@@ -406,6 +411,10 @@ pub enum ThreatHuntingStreamEvent {
         source: String,
         count: u64,
     },
+    DomainTimedOut {
+        domain: String,
+        source: String,
+    },
     Finished {
         total_count: u64,
         signal_lake_count: u64,
@@ -465,6 +474,24 @@ pub enum ReportStreamEvent {
     StageComplete {
         stage: String,
     },
+    ThreatHunting {
+        source: String,
+        status: String,
+        #[serde(default)]
+        step: Option<String>,
+        /// Poll attempt (polling)
+        #[serde(default)]
+        attempt: Option<u32>,
+        #[serde(default)]
+        max_attempts: Option<u32>,
+        /// Results fetched (completed)
+        #[serde(default)]
+        results: Option<u64>,
+    },
+    Warning {
+        code: String,
+        message: String,
+    },
     Finished {
         html: String,
         company_name: Option<String>,
@@ -487,6 +514,8 @@ pub fn get_report_stream_url(
     template_id: Option<&str>,
     use_plugins: bool,
     mock: bool, // NEW
+    plugin_theme: Option<&str>,
+    disabled_slides: &[String],
 ) -> String {
     let mut url = format!(
         "{}/api/reports/generate-stream?tenant_id={}&from_date={}&to_date={}&language={}&include_threat_intel={}&use_plugins={}&mock={}",
@@ -510,6 +539,17 @@ pub fn get_report_stream_url(
         url.push_str(&format!("&template_id={}", urlencoding_encode(tid)));
     }
 
+    if let Some(theme) = plugin_theme {
+        url.push_str(&format!("&plugin_theme={}", urlencoding_encode(theme)));
+    }
+
+    if !disabled_slides.is_empty() {
+        url.push_str(&format!(
+            "&disabled_slides={}",
+            urlencoding_encode(&disabled_slides.join(","))
+        ));
+    }
+
     url
 }
 
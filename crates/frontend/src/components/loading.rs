@@ -20,7 +20,12 @@ pub fn FullScreenLoader() -> impl IntoView {
 
 /// Enhanced Report Loader with progress steps and rotating messages
 #[component]
-pub fn ReportLoader(#[prop(optional)] include_threat_intel: bool) -> impl IntoView {
+pub fn ReportLoader(
+    #[prop(optional)] include_threat_intel: bool,
+    /// Live status reported by the server (stage, Threat Hunting searches)
+    #[prop(optional)]
+    status: Option<ReadSignal<Option<String>>>,
+) -> impl IntoView {
     // Current step index (simulated progress)
     let current_step = create_rw_signal(0u8);
     let message_index = create_rw_signal(0usize);
@@ -171,6 +176,11 @@ pub fn ReportLoader(#[prop(optional)] include_threat_intel: bool) -> impl IntoVi
                     ></div>
                 </div>
 
+                // Live status from the server
+                {move || status.and_then(|s| s.get()).map(|text| view! {
+                    <p class="mt-4 text-center text-xs text-zinc-400 font-mono truncate">{text}</p>
+                })}
+
                 // Threat Intel badge if enabled
                 {if include_threat_intel {
                     view! {
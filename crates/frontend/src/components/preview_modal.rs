@@ -26,6 +26,8 @@ pub struct StreamingProgress {
     pub chatter_count: u64,
     pub credential_count: u64,
     pub error_message: Option<String>,
+    /// Searches that timed out ("domain (source)"), counts incomplete
+    pub timed_out: Vec<String>,
     pub is_finished: bool,
 }
 
@@ -130,6 +132,7 @@ pub fn ThreatHuntingPreviewModal(
                                 // Finished state - show results
                                 let data = preview.get();
                                 let error = progress.error_message.clone();
+                                let timed_out = progress.timed_out.clone();
 
                                 view! {
                                     <div>
@@ -140,6 +143,15 @@ pub fn ThreatHuntingPreviewModal(
                                             </div>
                                         })}
 
+                                        // Searches still running after their last poll
+                                        {(!timed_out.is_empty()).then(|| view! {
+                                            <div class="bg-amber-900/20 backdrop-blur-md border border-amber-500/20 rounded-xl p-4 mb-4">
+                                                <p class="text-amber-300 text-sm">
+                                                    "Búsquedas sin terminar (resultados incompletos): " {timed_out.join(", ")}
+                                                </p>
+                                            </div>
+                                        })}
+
                                         <div class="grid grid-cols-2 gap-4 mb-6">
                                             <div class="bg-surface-elevated/40 backdrop-blur-md rounded-xl p-4 border border-white/5 group hover:border-white/10 transition-colors">
                                                 <span class="text-zinc-400 text-sm font-mono tracking-wider">"Signal-Lake"</span>
@@ -45,7 +45,7 @@ pub fn QueueStatusPanel(
                         attempts++;
                         
                         try {{
-                            const resp = await fetch('/api/queue/status/' + jobId);
+                            const resp = await fetch('/api/queue/status/' + jobId, {{ credentials: 'include' }});
                             const data = await resp.json();
                             
                             // Update DOM elements
@@ -66,7 +66,7 @@ pub fn QueueStatusPanel(
                                     statusEl.textContent = data.status;
                                 }}
                                 
-                                if (data.status === 'completed' || data.status === 'failed') {{
+                                if (data.status === 'completed' || data.status === 'failed' || data.status === 'cancelled') {{
                                     panel.classList.add('queue-done');
                                     window.dispatchEvent(new CustomEvent('queue-job-done', {{
                                         detail: {{ jobId, status: data.status, result: data.result }}
@@ -36,6 +36,30 @@ impl AppError {
     }
 }
 
+/// Status line of a Threat Hunting search progress event
+fn th_status_text(
+    source: &str,
+    status: &str,
+    attempt: Option<u32>,
+    max_attempts: Option<u32>,
+    results: Option<u64>,
+) -> String {
+    let detail = match status {
+        "started" => "búsqueda iniciada".to_string(),
+        "cached" => "resultados en caché".to_string(),
+        "polling" => format!(
+            "esperando resultados ({}/{})",
+            attempt.unwrap_or_default(),
+            max_attempts.unwrap_or_default()
+        ),
+        "completed" => format!("{} resultados", results.unwrap_or_default()),
+        "timed_out" => "tiempo agotado, resultados incompletos".to_string(),
+        "failed" => "no se pudo iniciar".to_string(),
+        other => other.to_string(),
+    };
+    format!("Threat Hunting {}: {}", source, detail)
+}
+
 /// Helper to create a slide toggle checkbox
 fn slide_toggle(
     plugin_id: &'static str,
@@ -116,6 +140,9 @@ pub fn DashboardPage() -> impl IntoView {
     let generating = create_rw_signal(false);
     let error = create_rw_signal(Option::<AppError>::None);
     let report_html = create_rw_signal(Option::<String>::None);
+    // Live progress and non-fatal warnings of the report stream
+    let report_status = create_rw_signal(Option::<String>::None);
+    let report_warnings = create_rw_signal(Vec::<String>::new());
 
     // Preview modal state
     let show_preview_modal = create_rw_signal(false);
@@ -196,6 +223,7 @@ pub fn DashboardPage() -> impl IntoView {
                     chatter_count: 0,
                     credential_count: 0,
                     error_message: None,
+                    timed_out: Vec::new(),
                     is_finished: false,
                 });
 
@@ -266,6 +294,14 @@ pub fn DashboardPage() -> impl IntoView {
                                                 }
                                             });
                                         }
+                                        api::ThreatHuntingStreamEvent::DomainTimedOut {
+                                            domain,
+                                            source,
+                                        } => {
+                                            sp.update(|s| {
+                                                s.timed_out.push(format!("{} ({})", domain, source))
+                                            });
+                                        }
                                         api::ThreatHuntingStreamEvent::Finished {
                                             total_count,
                                             signal_lake_count,
@@ -339,6 +375,8 @@ pub fn DashboardPage() -> impl IntoView {
             generating.set(true);
             error.set(None);
             report_html.set(None);
+            report_status.set(None);
+            report_warnings.set(Vec::new());
             preview_confirmed.set(false);
 
             let tenant = selected_tenant.get();
@@ -364,6 +402,8 @@ pub fn DashboardPage() -> impl IntoView {
                 template_id.as_deref(),
                 use_plugins.get(),
                 use_mock_data.get(),
+                Some(plugin_theme.get().as_str()),
+                &disabled_slides.get(),
             );
 
             // Use JavaScript EventSource for SSE
@@ -384,6 +424,8 @@ pub fn DashboardPage() -> impl IntoView {
                     let ti = threat_intel;
                     let es_clone = event_source.clone();
                     let app_state = state.clone();
+                    let status = report_status;
+                    let warnings = report_warnings;
 
                     // Message handler
                     let onmessage = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
@@ -391,7 +433,27 @@ pub fn DashboardPage() -> impl IntoView {
                             match serde_json::from_str::<api::ReportStreamEvent>(&data) {
                                 Ok(evt) => match evt {
                                     api::ReportStreamEvent::StageProgress { message, .. } => {
-                                        leptos::logging::log!("Report progress: {}", message);
+                                        status.set(Some(message));
+                                    }
+                                    api::ReportStreamEvent::ThreatHunting {
+                                        source,
+                                        status: search_status,
+                                        attempt,
+                                        max_attempts,
+                                        results,
+                                        ..
+                                    } => {
+                                        status.set(Some(th_status_text(
+                                            &source,
+                                            &search_status,
+                                            attempt,
+                                            max_attempts,
+                                            results,
+                                        )));
+                                    }
+                                    api::ReportStreamEvent::Warning { code, message } => {
+                                        warnings
+                                            .update(|w| w.push(format!("[{}] {}", code, message)));
                                     }
                                     api::ReportStreamEvent::Finished { html, .. } => {
                                         report.set(Some(html));
@@ -467,7 +529,10 @@ pub fn DashboardPage() -> impl IntoView {
     view! {
         // Loading overlay with enhanced progress
         <Show when=move || generating.get()>
-            <ReportLoader include_threat_intel=include_threat_intel.get() />
+            <ReportLoader
+                include_threat_intel=include_threat_intel.get()
+                status=report_status.read_only()
+            />
         </Show>
 
         // Threat Hunting Preview Modal
@@ -514,6 +579,18 @@ pub fn DashboardPage() -> impl IntoView {
                     }
                 })}
 
+                // Non-fatal problems of the last report (e.g. incomplete Threat Hunting results)
+                {move || {
+                    let warnings = report_warnings.get();
+                    (!warnings.is_empty()).then(|| view! {
+                        <div class="mb-6 bg-amber-900/20 border border-amber-500/20 rounded-xl p-4 space-y-1">
+                            {warnings.into_iter().map(|w| view! {
+                                <p class="text-amber-300 text-sm">"⚠ " {w}</p>
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    })
+                }}
+
                 <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
                     // Form
                     // NEW: Extracted Dynamic Form Component
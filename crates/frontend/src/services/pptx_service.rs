@@ -18,6 +18,9 @@ pub struct ImportResult {
     pub success: bool,
     pub slides: Vec<String>, // URLs
     pub message: Option<String>,
+    /// Editable template parsed by the backend (editor `PresentationTemplate`)
+    #[serde(default)]
+    pub template: Option<serde_json::Value>,
 }
 
 pub struct PptxService;
@@ -29,6 +32,10 @@ impl PptxService {
         form_data
             .append_with_blob("file", &file)
             .map_err(|e| format!("{:?}", e))?;
+        // Thumbnails are only rendered on request
+        form_data
+            .append_with_str("previews", "true")
+            .map_err(|e| format!("{:?}", e))?;
 
         let resp = Request::post("/api/import/pptx")
             .body(form_data)